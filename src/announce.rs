@@ -0,0 +1,124 @@
+//! Announce module.
+//!
+//! Mirrors changes in the user interface to a linear text channel, so that
+//! users of screen readers get meaningful feedback from spatial layouts.
+
+use std::fmt;
+use std::io::{self, Write};
+
+use libc;
+
+/// Represents where announcements are written.
+pub enum Sink {
+    /// Standard error, one announcement per line.
+    ///
+    /// *Standard error is usually the terminal the interface is drawn on, so
+    /// nothing is written to it while it is a terminal. Redirect it, such as
+    /// with `2>announcements.log`, to use this sink.*
+    Stderr,
+    /// A writer such as a pipe or file, one announcement per line.
+    Writer(Box<dyn Write + Send>),
+    /// OSC 777 notifications sent to the terminal.
+    ///
+    /// *Only [announcements](struct.Announcer.html#method.announce) are
+    /// sent, since a notification for every focus or selection change would
+    /// flood the desktop.*
+    Osc,
+}
+
+impl fmt::Debug for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Sink::Stderr => f.write_str("Stderr"),
+            Sink::Writer(_) => f.write_str("Writer(..)"),
+            Sink::Osc => f.write_str("Osc"),
+        }
+    }
+}
+
+/// Writes focus changes, selection changes and announcements to a sink.
+///
+/// Repeated reports of the same focus or selection are suppressed, so widgets
+/// can report their state every frame.
+#[derive(Debug)]
+pub struct Announcer {
+    sink: Sink,
+    /// Whether the sink is standard error drawn on as the terminal.
+    muted: bool,
+    focus: Option<String>,
+    selection: Option<String>,
+}
+
+impl Announcer {
+    /// Creates a new announcer writing to the given sink.
+    pub fn new(sink: Sink) -> Announcer {
+        let muted = match sink {
+            Sink::Stderr => unsafe { libc::isatty(libc::STDERR_FILENO) == 1 },
+            _ => false,
+        };
+        if muted {
+            warn!("standard error is a terminal, so announcements are dropped");
+        }
+        Announcer {
+            sink,
+            muted,
+            focus: None,
+            selection: None,
+        }
+    }
+
+    /// Creates a new announcer writing to standard error, which must be
+    /// redirected away from the terminal.
+    pub fn stderr() -> Announcer {
+        Announcer::new(Sink::Stderr)
+    }
+
+    /// Creates a new announcer writing to the given writer.
+    pub fn to_writer<W: Write + Send + 'static>(writer: W) -> Announcer {
+        Announcer::new(Sink::Writer(Box::new(writer)))
+    }
+
+    /// Reports that the focus moved to the named element.
+    pub fn focus_changed(&mut self, name: &str) {
+        if self.focus.as_deref() != Some(name) {
+            self.focus = Some(name.to_owned());
+            self.write(&format!("focus: {}", name), false);
+        }
+    }
+
+    /// Reports that the selection changed to the described item.
+    pub fn selection_changed(&mut self, desc: &str) {
+        if self.selection.as_deref() != Some(desc) {
+            self.selection = Some(desc.to_owned());
+            self.write(&format!("selected: {}", desc), false);
+        }
+    }
+
+    /// Announces a message.
+    pub fn announce(&mut self, msg: &str) {
+        self.write(msg, true);
+    }
+
+    /// Writes a message, or an announcement if `notify` is set.
+    fn write(&mut self, msg: &str, notify: bool) {
+        if self.muted {
+            return;
+        }
+        let result = match self.sink {
+            Sink::Stderr => writeln!(io::stderr(), "{}", msg),
+            Sink::Writer(ref mut writer) => {
+                writeln!(writer, "{}", msg).and_then(|_| writer.flush())
+            }
+            Sink::Osc if !notify => return,
+            Sink::Osc => {
+                let msg: String = msg.chars().filter(|c| !c.is_control()).collect();
+                let mut stdout = io::stdout();
+                write!(stdout, "\x1B]777;notify;termui;{}\x1B\\", msg).and_then(|_| stdout.flush())
+            }
+        };
+
+        if let Err(err) = result {
+            warn!("could not write announcement: {}", err);
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::VecDeque;
+use std::env;
+use std::io::{self, ErrorKind, Read, Write};
+use std::mem;
+use std::time::{Duration, Instant};
+
+use libc;
+
+use backend::{terminal_size, AnsiScreen, Backend};
+use caps::{self, Capabilities, ColorChoice, Features, Profile};
+use error::{Error, Result};
+use event::{Decoder, Event};
+use style::{ColorCaps, Style};
+use text::AmbiguousWidth;
+use window::CursorStyle;
+
+/// How long a partial escape sequence waits for the rest of its bytes before
+/// being taken as it is, so a lone escape is the escape key.
+const ESCAPE_DELAY: Duration = Duration::from_millis(25);
+
+/// A backend drawing to the terminal with ANSI escape sequences, without
+/// curses.
+///
+/// The terminal is put in non-canonical mode with termios and switched to the
+/// alternate screen, with SGR mouse reporting and bracketed paste when the
+/// profile allows them. Input is decoded by a [`Decoder`], so it understands
+/// the keys and mouse reports of xterm compatible terminals.
+///
+/// ```no_run
+/// use termui::backend::AnsiBackend;
+/// use termui::Window;
+///
+/// let backend = AnsiBackend::new().unwrap();
+/// let mut win = Window::with_backend(backend);
+/// win.print(0, 0, "Hello");
+/// win.refresh();
+/// ```
+///
+/// *Colors are guessed from `TERM` and `COLORTERM`, with 256 colors for
+/// terminals named like `xterm-256color`, and follow the
+/// [`ColorChoice`](../caps/enum.ColorChoice.html) detected from the
+/// environment. Requires the `ansi` feature, and
+/// is only available on Unix.*
+///
+/// [`Decoder`]: ../event/struct.Decoder.html
+#[derive(Debug)]
+pub struct AnsiBackend {
+    termios: libc::termios,
+    decoder: Decoder,
+    events: VecDeque<Event>,
+    last_input: Instant,
+    screen: AnsiScreen,
+    capabilities: Capabilities,
+    suspended: bool,
+}
+
+impl AnsiBackend {
+    /// Takes over the terminal on standard input and output.
+    pub fn new() -> io::Result<AnsiBackend> {
+        let mut termios = unsafe { mem::zeroed::<libc::termios>() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let (rows, cols) = terminal_size().unwrap_or((24, 80));
+        let backend = AnsiBackend {
+            termios,
+            decoder: Decoder::new(),
+            events: VecDeque::new(),
+            last_input: Instant::now(),
+            screen: AnsiScreen::new(rows, cols),
+            capabilities: {
+                let color_choice = ColorChoice::detect();
+                Capabilities::guess(guess_colors(color_choice), Profile::detect(), color_choice)
+            },
+            suspended: false,
+        };
+        backend.enter_modes()?;
+        Ok(backend)
+    }
+
+    /// Puts the terminal in the modes used by the backend.
+    fn enter_modes(&self) -> io::Result<()> {
+        let mut raw = self.termios;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+        raw.c_iflag &= !(libc::IXON | libc::ICRNL);
+        raw.c_cc[libc::VMIN] = 0;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut modes = String::from("\x1B[?1049h\x1B[?25l");
+        if self.capabilities.supports(Features::Mouse) {
+            modes.push_str("\x1B[?1002h\x1B[?1006h");
+        }
+        if self.capabilities.supports(Features::BracketedPaste) {
+            modes.push_str("\x1B[?2004h");
+        }
+        write_stdout(&modes)
+    }
+
+    /// Restores the terminal as it was before the backend.
+    fn leave_modes(&self) -> io::Result<()> {
+        let mut modes = String::from("\x1B[0m");
+        if self.capabilities.supports(Features::CursorShape) {
+            modes.push_str("\x1B[0 q");
+        }
+        if self.capabilities.supports(Features::BracketedPaste) {
+            modes.push_str("\x1B[?2004l");
+        }
+        if self.capabilities.supports(Features::Mouse) {
+            modes.push_str("\x1B[?1006l\x1B[?1002l");
+        }
+        modes.push_str("\x1B[?25h\x1B[?1049l");
+        write_stdout(&modes)?;
+
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Reads the input waiting on the terminal, decoding it into events.
+    fn receive(&mut self) {
+        let mut buf = [0; 4096];
+        let mut received = false;
+        loop {
+            match io::stdin().read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let events = self.decoder.push_bytes(&buf[..n]);
+                    self.events.extend(events);
+                    received = true;
+                }
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+
+        if received {
+            self.last_input = Instant::now();
+        } else if !self.decoder.pending_bytes().is_empty()
+            && self.last_input.elapsed() >= ESCAPE_DELAY
+        {
+            let events = self.decoder.flush();
+            self.events.extend(events);
+        }
+    }
+}
+
+impl Drop for AnsiBackend {
+    fn drop(&mut self) {
+        if !self.suspended {
+            let _ = self.leave_modes();
+        }
+    }
+}
+
+impl Backend for AnsiBackend {
+    fn size(&self) -> (usize, usize) {
+        self.screen.size()
+    }
+
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    fn erase(&self) {
+        self.screen.erase();
+    }
+
+    fn print(&self, row: usize, col: usize, text: &str, style: Style) {
+        self.screen.print(row, col, text, style, &self.capabilities);
+    }
+
+    fn print_here(&self, text: &str, style: Style) {
+        let (row, col) = self.cursor();
+        self.print(row, col, text, style);
+    }
+
+    fn insert_char(&self, row: usize, col: usize, ch: char) {
+        self.screen.insert_char(row, col, ch, &self.capabilities);
+    }
+
+    fn delete_char(&self, row: usize, col: usize) {
+        self.screen.delete_char(row, col, &self.capabilities);
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.screen.cursor()
+    }
+
+    fn move_cursor(&self, row: usize, col: usize) {
+        self.screen.move_cursor(row, col);
+    }
+
+    fn set_ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        self.screen.set_ambiguous_width(ambiguous);
+    }
+
+    fn present(&self, cursor: CursorStyle) {
+        let frame = match self.screen.take_frame(cursor, &self.capabilities) {
+            Some(frame) => frame,
+            None => return,
+        };
+        let result = if self.capabilities.supports(Features::SyncUpdate) {
+            write_stdout(&format!("\x1B[?2026h{}\x1B[?2026l", frame))
+        } else {
+            write_stdout(&frame)
+        };
+        if let Err(err) = result {
+            warn!("could not draw to the terminal: {}", err);
+        }
+    }
+
+    fn read_event(&mut self) -> Option<Event> {
+        if self.suspended {
+            return None;
+        }
+
+        if let Some(size) = terminal_size() {
+            if size != self.size() {
+                self.screen.resize(size.0, size.1);
+                self.capabilities.cell_size = caps::cell_size();
+                self.screen.replay(&self.capabilities);
+                return Some(Event::Resize);
+            }
+        }
+        if self.events.is_empty() {
+            self.receive();
+        }
+        self.events.pop_front()
+    }
+
+    fn suspend(&mut self) -> Result<()> {
+        if self.suspended {
+            return Ok(());
+        }
+        self.leave_modes()
+            .map_err(|err| Error::Io("restoring the terminal", err.kind()))?;
+        self.suspended = true;
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        if !self.suspended {
+            return Ok(());
+        }
+        self.enter_modes()
+            .map_err(|err| Error::Io("entering raw mode", err.kind()))?;
+        self.suspended = false;
+        if let Some((rows, cols)) = terminal_size() {
+            self.screen.resize(rows, cols);
+        }
+        self.screen.replay(&self.capabilities);
+        self.present(CursorStyle::Hidden);
+        Ok(())
+    }
+}
+
+/// Guesses the colors of the terminal from the environment, with at least
+/// the basic colors when they are forced on.
+fn guess_colors(color_choice: ColorChoice) -> ColorCaps {
+    let term = env::var("TERM").unwrap_or_default();
+    let truecolor = match env::var("COLORTERM") {
+        Ok(ref value) => value == "truecolor" || value == "24bit",
+        Err(_) => false,
+    };
+    let colors = if term.contains("256color") || truecolor {
+        256
+    } else if (term.is_empty() || term == "dumb") && color_choice != ColorChoice::Always {
+        0
+    } else {
+        8
+    };
+
+    ColorCaps {
+        has_colors: colors > 0,
+        can_change_color: false,
+        colors,
+        pairs: colors * colors,
+        truecolor,
+        default_colors: colors > 0,
+    }
+}
+
+/// Writes to standard output, flushing it.
+fn write_stdout(text: &str) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    stdout.write_all(text.as_bytes())?;
+    stdout.flush()
+}
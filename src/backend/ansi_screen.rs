@@ -0,0 +1,167 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Write;
+use std::mem;
+
+use libc;
+
+use backend::Buffer;
+use caps::{Capabilities, Features};
+use style::Style;
+use text::AmbiguousWidth;
+use window::CursorStyle;
+
+/// A screen drawn with ANSI escape sequences, keeping what was drawn and
+/// queuing the sequences until the frame is taken.
+///
+/// *Shared by the backends that write escape sequences themselves, rather
+/// than through curses.*
+#[derive(Debug)]
+pub(crate) struct AnsiScreen {
+    screen: RefCell<Buffer>,
+    out: RefCell<String>,
+    shown_cursor: Cell<Option<CursorStyle>>,
+    ambiguous: Cell<AmbiguousWidth>,
+}
+
+impl AnsiScreen {
+    pub(crate) fn new(rows: usize, cols: usize) -> AnsiScreen {
+        AnsiScreen {
+            screen: RefCell::new(Buffer::new(rows, cols)),
+            out: RefCell::new(String::new()),
+            shown_cursor: Cell::new(None),
+            ambiguous: Cell::new(AmbiguousWidth::Narrow),
+        }
+    }
+
+    pub(crate) fn set_ambiguous_width(&self, ambiguous: AmbiguousWidth) {
+        self.ambiguous.set(ambiguous);
+    }
+
+    pub(crate) fn size(&self) -> (usize, usize) {
+        self.screen.borrow().size()
+    }
+
+    pub(crate) fn resize(&self, rows: usize, cols: usize) {
+        self.screen.borrow_mut().resize(rows, cols);
+    }
+
+    pub(crate) fn erase(&self) {
+        self.screen.borrow_mut().clear(Style::default());
+        self.out.borrow_mut().push_str("\x1B[0m\x1B[2J");
+    }
+
+    pub(crate) fn print(
+        &self,
+        row: usize,
+        col: usize,
+        text: &str,
+        style: Style,
+        caps: &Capabilities,
+    ) {
+        self.screen
+            .borrow_mut()
+            .put(row, col, text, style, self.ambiguous.get());
+        let mut out = self.out.borrow_mut();
+        let _ = write!(
+            out,
+            "\x1B[{};{}H{}{}\x1B[0m",
+            row + 1,
+            col + 1,
+            style.sgr(caps.colors),
+            text
+        );
+    }
+
+    pub(crate) fn insert_char(&self, row: usize, col: usize, ch: char, caps: &Capabilities) {
+        self.screen
+            .borrow_mut()
+            .insert_char(row, col, ch, Style::default(), self.ambiguous.get());
+        self.draw_row(row, caps);
+    }
+
+    pub(crate) fn delete_char(&self, row: usize, col: usize, caps: &Capabilities) {
+        self.screen.borrow_mut().delete_char(row, col);
+        self.draw_row(row, caps);
+    }
+
+    pub(crate) fn cursor(&self) -> (usize, usize) {
+        self.screen.borrow().cursor()
+    }
+
+    pub(crate) fn move_cursor(&self, row: usize, col: usize) {
+        self.screen.borrow_mut().move_cursor(row, col);
+    }
+
+    /// Queues the whole screen to be drawn again, cursor included.
+    pub(crate) fn replay(&self, caps: &Capabilities) {
+        let (rows, _) = self.size();
+        self.out.borrow_mut().push_str("\x1B[0m\x1B[2J");
+        for row in 0..rows {
+            self.draw_row(row, caps);
+        }
+        self.shown_cursor.set(None);
+    }
+
+    /// Queues a row to be drawn as it is on the screen.
+    fn draw_row(&self, row: usize, caps: &Capabilities) {
+        let screen = self.screen.borrow();
+        let (_, cols) = screen.size();
+        let mut out = self.out.borrow_mut();
+        let _ = write!(out, "\x1B[{};1H", row + 1);
+        let mut current = None;
+        for col in 0..cols {
+            let cell = match screen.get(row, col) {
+                Some(cell) if !cell.is_continuation() => cell,
+                _ => continue,
+            };
+            if current != Some(cell.style) {
+                out.push_str(&cell.style.sgr(caps.colors));
+                current = Some(cell.style);
+            }
+            out.push_str(&cell.symbol);
+        }
+        out.push_str("\x1B[0m");
+    }
+
+    /// Takes what was queued so far, leaving the cursor as it is.
+    pub(crate) fn take_output(&self) -> String {
+        mem::take(&mut *self.out.borrow_mut())
+    }
+
+    /// Takes what was queued since the last frame, ending with the cursor
+    /// shown as given, or `None` if nothing changed.
+    pub(crate) fn take_frame(&self, cursor: CursorStyle, caps: &Capabilities) -> Option<String> {
+        let mut out = self.out.borrow_mut();
+        // The cursor is left alone while nothing moved it.
+        if out.is_empty() && self.shown_cursor.get() == Some(cursor) {
+            return None;
+        }
+        self.shown_cursor.set(Some(cursor));
+        match cursor.position() {
+            Some((row, col)) => {
+                let _ = write!(out, "\x1B[{};{}H", row + 1, col + 1);
+                if let (true, Some(code)) =
+                    (caps.supports(Features::CursorShape), cursor.shape_code())
+                {
+                    let _ = write!(out, "\x1B[{} q", code);
+                }
+                out.push_str("\x1B[?25h");
+            }
+            None => out.push_str("\x1B[?25l"),
+        }
+        Some(mem::take(&mut *out))
+    }
+}
+
+/// Gets the size of the terminal on standard output in rows and columns.
+pub(crate) fn terminal_size() -> Option<(usize, usize)> {
+    let mut size = unsafe { mem::zeroed::<libc::winsize>() };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+        && size.ws_row > 0
+        && size.ws_col > 0
+    {
+        Some((size.ws_row as usize, size.ws_col as usize))
+    } else {
+        None
+    }
+}
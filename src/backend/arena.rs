@@ -0,0 +1,75 @@
+use std::ops::Range;
+
+use style::Style;
+
+/// A run of changed cells, as its position, the range of its text in the
+/// arena and its style.
+pub(crate) type Run = (usize, usize, Range<usize>, Style);
+
+/// A bump arena for the transient data of a flush, reset before each one.
+///
+/// It holds the runs of changed cells found by diffing the buffers, and the
+/// text of those runs and of encoded messages. Text is appended to one buffer
+/// and handed out as ranges, so once the arena has grown to fit a frame,
+/// flushing the next one allocates nothing.
+///
+/// *Layout results and hit-test regions don't go through the arena, since
+/// they outlive the flush: layouts cache their split of the last area, and
+/// hit maps keep their memory from one frame to the next.*
+#[derive(Debug, Default)]
+pub(crate) struct Arena {
+    text: String,
+    runs: Vec<Run>,
+}
+
+impl Arena {
+    /// Creates an empty arena.
+    pub(crate) fn new() -> Arena {
+        Arena::default()
+    }
+
+    /// Empties the arena, keeping its memory for the next frame.
+    pub(crate) fn reset(&mut self) {
+        self.text.clear();
+        self.runs.clear();
+    }
+
+    /// Copies text into the arena, giving its range.
+    pub(crate) fn alloc_str(&mut self, text: &str) -> Range<usize> {
+        let start = self.text.len();
+        self.text.push_str(text);
+        start..self.text.len()
+    }
+
+    /// Copies characters into the arena, giving the range of their text.
+    pub(crate) fn alloc_chars<I: IntoIterator<Item = char>>(&mut self, chars: I) -> Range<usize> {
+        let start = self.text.len();
+        self.text.extend(chars);
+        start..self.text.len()
+    }
+
+    /// Gets the text in a range given by the arena.
+    pub(crate) fn get(&self, range: Range<usize>) -> &str {
+        &self.text[range]
+    }
+
+    /// Adds a run, copying its text into the arena.
+    pub(crate) fn push_run(&mut self, row: usize, col: usize, text: &str, style: Style) {
+        let range = self.alloc_str(text);
+        self.runs.push((row, col, range, style));
+    }
+
+    /// Extends the text of the last run, which must be the last text
+    /// allocated.
+    pub(crate) fn extend_run(&mut self, text: &str) {
+        self.text.push_str(text);
+        if let Some(run) = self.runs.last_mut() {
+            run.2.end = self.text.len();
+        }
+    }
+
+    /// Gets the runs added since the last reset.
+    pub(crate) fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+}
@@ -0,0 +1,429 @@
+use std::mem;
+
+use layout::Rect;
+use style::Style;
+use text::{self, AmbiguousWidth};
+
+use super::Arena;
+
+/// A cell of a buffer, holding the text drawn in it and its style.
+///
+/// *The cell after a wide character is empty, and is drawn by the wide
+/// character.*
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Cell {
+    pub(crate) symbol: String,
+    pub(crate) style: Style,
+}
+
+impl Cell {
+    fn blank() -> Cell {
+        Cell {
+            symbol: " ".to_owned(),
+            style: Style::default(),
+        }
+    }
+
+    fn continuation(style: Style) -> Cell {
+        Cell {
+            symbol: String::new(),
+            style,
+        }
+    }
+
+    /// Checks whether the cell is covered by the wide character before it.
+    pub(crate) fn is_continuation(&self) -> bool {
+        self.symbol.is_empty()
+    }
+}
+
+/// A grid of cells with a drawing cursor.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Buffer {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Cell>,
+    cursor: (usize, usize),
+}
+
+impl Buffer {
+    /// Creates a blank buffer of the given size.
+    pub(crate) fn new(rows: usize, cols: usize) -> Buffer {
+        Buffer {
+            rows,
+            cols,
+            cells: vec![Cell::blank(); rows * cols],
+            cursor: (0, 0),
+        }
+    }
+
+    /// Gets the size of the buffer in rows and columns.
+    pub(crate) fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Resizes the buffer, keeping the cells that still fit.
+    pub(crate) fn resize(&mut self, rows: usize, cols: usize) {
+        let old = mem::replace(self, Buffer::new(rows, cols));
+        for row in 0..rows.min(old.rows) {
+            for col in 0..cols.min(old.cols) {
+                self.cells[row * cols + col] = old.cells[row * old.cols + col].clone();
+            }
+            // A wide character may have lost its second half.
+            if cols < old.cols && cols > 0 && old.cells[row * old.cols + cols].is_continuation() {
+                self.cells[row * cols + cols - 1] = Cell::blank();
+            }
+        }
+        self.move_cursor(old.cursor.0, old.cursor.1);
+    }
+
+    /// Blanks every cell in the given style and moves the cursor to the top
+    /// left.
+    pub(crate) fn clear(&mut self, style: Style) {
+        for cell in &mut self.cells {
+            *cell = Cell {
+                symbol: " ".to_owned(),
+                style,
+            };
+        }
+        self.cursor = (0, 0);
+    }
+
+    /// Gets the cell at the given position.
+    pub(crate) fn get(&self, row: usize, col: usize) -> Option<&Cell> {
+        if row < self.rows && col < self.cols {
+            Some(&self.cells[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    /// Gets the text of a row, without trailing whitespace.
+    pub(crate) fn line(&self, row: usize) -> String {
+        if row >= self.rows {
+            return String::new();
+        }
+        let line: String = self.row(row).iter().map(|cell| &*cell.symbol).collect();
+        line.trim_end().to_owned()
+    }
+
+    /// Gets the position of the cursor.
+    pub(crate) fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Moves the cursor, if the position is inside the buffer.
+    pub(crate) fn move_cursor(&mut self, row: usize, col: usize) {
+        if row < self.rows && col < self.cols {
+            self.cursor = (row, col);
+        }
+    }
+
+    /// Draws text at the given position, moving the cursor past it.
+    ///
+    /// *Text past the end of the row is cut off, and zero width characters
+    /// are joined to the character before them.*
+    pub(crate) fn put(
+        &mut self,
+        row: usize,
+        col: usize,
+        text: &str,
+        style: Style,
+        ambiguous: AmbiguousWidth,
+    ) {
+        if row >= self.rows || col >= self.cols {
+            return;
+        }
+
+        let mut col = col;
+        for ch in text.chars() {
+            let width = text::char_width(ch, ambiguous);
+            if width == 0 {
+                if ch.is_control() || ch == text::SOFT_HYPHEN {
+                    continue;
+                }
+                // Combining characters join the previous cell.
+                if let Some(prev) = (0..col)
+                    .rev()
+                    .find(|&c| !self.row(row)[c].is_continuation())
+                {
+                    self.row_mut(row)[prev].symbol.push(ch);
+                }
+                continue;
+            }
+            if col + width > self.cols {
+                break;
+            }
+
+            self.split_wide(row, col);
+            if width == 2 {
+                self.split_wide(row, col + 1);
+            }
+            let cells = self.row_mut(row);
+            cells[col] = Cell {
+                symbol: ch.to_string(),
+                style,
+            };
+            if width == 2 {
+                cells[col + 1] = Cell::continuation(style);
+            }
+            col += width;
+        }
+        self.cursor = (row, col.min(self.cols - 1));
+    }
+
+    /// Inserts a character at the given position, shifting the rest of the row
+    /// right and losing the cells pushed off its end.
+    pub(crate) fn insert_char(
+        &mut self,
+        row: usize,
+        col: usize,
+        ch: char,
+        style: Style,
+        ambiguous: AmbiguousWidth,
+    ) {
+        let width = text::char_width(ch, ambiguous);
+        if row >= self.rows || col + width > self.cols || width == 0 {
+            return;
+        }
+
+        self.split_wide(row, col);
+        let cols = self.cols;
+        let cells = self.row_mut(row);
+        for _ in 0..width {
+            cells[col..].rotate_right(1);
+        }
+        cells[col] = Cell {
+            symbol: ch.to_string(),
+            style,
+        };
+        if width == 2 {
+            cells[col + 1] = Cell::continuation(style);
+        }
+        // A wide character may have lost its second half off the end.
+        if cells[cols - 1]
+            .symbol
+            .chars()
+            .any(|ch| text::char_width(ch, ambiguous) == 2)
+        {
+            cells[cols - 1] = Cell::blank();
+        }
+    }
+
+    /// Deletes the character at the given position, shifting the rest of the
+    /// row left.
+    pub(crate) fn delete_char(&mut self, row: usize, col: usize) {
+        if row >= self.rows || col >= self.cols {
+            return;
+        }
+
+        let cells = self.row_mut(row);
+        let mut col = col;
+        while col > 0 && cells[col].is_continuation() {
+            col -= 1;
+        }
+        let mut width = 1;
+        while col + width < cells.len() && cells[col + width].is_continuation() {
+            width += 1;
+        }
+        for _ in 0..width {
+            cells[col..].rotate_left(1);
+            *cells.last_mut().expect("rows are not empty") = Cell::blank();
+        }
+    }
+
+    /// Adds the runs of cells in an area that differ from another buffer of
+    /// the same size to the arena, as positions, their text and style.
+    pub(crate) fn diff(&self, other: &Buffer, area: Rect, arena: &mut Arena) -> usize {
+        let mut changed = 0;
+        for row in area.y..area.bottom().min(self.rows) {
+            let mut end = None;
+            for col in area.x..area.right().min(self.cols) {
+                let cell = &self.cells[row * self.cols + col];
+                if *cell == other.cells[row * self.cols + col] || cell.is_continuation() {
+                    if !cell.is_continuation() {
+                        end = None;
+                    }
+                    continue;
+                }
+
+                match arena.runs().last() {
+                    Some(&(_, _, _, style)) if end == Some(col) && style == cell.style => {
+                        arena.extend_run(&cell.symbol);
+                    }
+                    _ => arena.push_run(row, col, &cell.symbol, cell.style),
+                }
+                end = Some(col + self.width_at(row, col));
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Copies the cells in an area from another buffer of the same size.
+    pub(crate) fn copy_area(&mut self, other: &Buffer, area: Rect) {
+        for row in area.y..area.bottom().min(self.rows) {
+            let start = row * self.cols + area.x.min(self.cols);
+            let end = row * self.cols + area.right().min(self.cols);
+            self.cells[start..end].clone_from_slice(&other.cells[start..end]);
+        }
+    }
+
+    /// Copies the cells in an area into a buffer of its own, cut down to fit
+    /// within this one.
+    pub(crate) fn crop(&self, area: Rect) -> Buffer {
+        let (x, y) = (area.x.min(self.cols), area.y.min(self.rows));
+        let cols = area.right().min(self.cols) - x;
+        let rows = area.bottom().min(self.rows) - y;
+        let mut part = Buffer::new(rows, cols);
+        for row in 0..rows {
+            let start = (y + row) * self.cols + x;
+            part.row_mut(row)
+                .clone_from_slice(&self.cells[start..start + cols]);
+        }
+        part
+    }
+
+    /// Copies the cells of a buffer made by [`crop`](#method.crop) back in,
+    /// with its top left at the given position.
+    pub(crate) fn paste(&mut self, part: &Buffer, row: usize, col: usize) {
+        let cols = part.cols.min(self.cols.saturating_sub(col));
+        for i in 0..part.rows.min(self.rows.saturating_sub(row)) {
+            let start = (row + i) * self.cols + col;
+            self.cells[start..start + cols].clone_from_slice(&part.row(i)[..cols]);
+        }
+    }
+
+    /// Gets the number of columns covered by the cell at the given position.
+    fn width_at(&self, row: usize, col: usize) -> usize {
+        let cells = self.row(row);
+        1 + cells[col + 1..]
+            .iter()
+            .take_while(|cell| cell.is_continuation())
+            .count()
+    }
+
+    /// Blanks the wide character covering the given position, if it would be
+    /// split by drawing over it.
+    fn split_wide(&mut self, row: usize, col: usize) {
+        let cells = self.row_mut(row);
+        if cells[col].is_continuation() {
+            let mut lead = col;
+            while lead > 0 && cells[lead].is_continuation() {
+                lead -= 1;
+            }
+            for cell in &mut cells[lead..col] {
+                *cell = Cell::blank();
+            }
+        }
+        let mut end = col + 1;
+        while end < cells.len() && cells[end].is_continuation() {
+            cells[end] = Cell::blank();
+            end += 1;
+        }
+    }
+
+    fn row(&self, row: usize) -> &[Cell] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut [Cell] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arena, Buffer};
+    use layout::Rect;
+    use style::{Color, Style};
+    use text::AmbiguousWidth;
+
+    fn diff(buffer: &Buffer, front: &Buffer, area: Rect) -> (usize, Vec<(usize, usize, String)>) {
+        let mut arena = Arena::new();
+        let changed = buffer.diff(front, area, &mut arena);
+        let runs = arena
+            .runs()
+            .iter()
+            .map(|&(row, col, ref text, _)| (row, col, arena.get(text.clone()).to_owned()))
+            .collect();
+        (changed, runs)
+    }
+
+    #[test]
+    fn diff_gives_changed_runs() {
+        let front = Buffer::new(2, 8);
+        let mut buffer = front.clone();
+        buffer.put(0, 0, "ab", Style::default(), AmbiguousWidth::Narrow);
+        buffer.put(0, 4, "c", Style::default(), AmbiguousWidth::Narrow);
+        buffer.put(
+            1,
+            1,
+            "de",
+            Style::new().fg(Color::Red),
+            AmbiguousWidth::Narrow,
+        );
+        buffer.put(1, 3, "f", Style::default(), AmbiguousWidth::Narrow);
+        let (changed, runs) = diff(&buffer, &front, Rect::new(0, 0, 8, 2));
+        assert_eq!(changed, 6);
+        assert_eq!(
+            runs,
+            vec![
+                (0, 0, "ab".to_owned()),
+                (0, 4, "c".to_owned()),
+                (1, 1, "de".to_owned()),
+                (1, 3, "f".to_owned()),
+            ]
+        );
+
+        // Only the area asked for is compared.
+        let (changed, runs) = diff(&buffer, &front, Rect::new(1, 0, 3, 1));
+        assert_eq!(changed, 1);
+        assert_eq!(runs, vec![(0, 1, "b".to_owned())]);
+
+        let mut copy = front.clone();
+        copy.copy_area(&buffer, Rect::new(0, 0, 8, 2));
+        assert_eq!(diff(&buffer, &copy, Rect::new(0, 0, 8, 2)).0, 0);
+    }
+
+    #[test]
+    fn diff_skips_wide_continuations() {
+        let front = Buffer::new(1, 8);
+        let mut buffer = front.clone();
+        buffer.put(0, 0, "漢b", Style::default(), AmbiguousWidth::Narrow);
+        let (changed, runs) = diff(&buffer, &front, Rect::new(0, 0, 8, 1));
+        assert_eq!(changed, 2);
+        assert_eq!(runs, vec![(0, 0, "漢b".to_owned())]);
+        assert_eq!(buffer.line(0), "漢b");
+    }
+
+    #[test]
+    fn put_lays_out_ambiguous_width() {
+        let mut narrow = Buffer::new(1, 8);
+        narrow.put(0, 0, "①Z", Style::default(), AmbiguousWidth::Narrow);
+        assert_eq!(narrow.get(0, 1).unwrap().symbol, "Z");
+        assert_eq!(narrow.cursor(), (0, 2));
+
+        let mut wide = Buffer::new(1, 8);
+        wide.put(0, 0, "①Z", Style::default(), AmbiguousWidth::Wide);
+        assert!(wide.get(0, 1).unwrap().is_continuation());
+        assert_eq!(wide.get(0, 2).unwrap().symbol, "Z");
+        assert_eq!(wide.cursor(), (0, 3));
+    }
+
+    #[test]
+    fn drawing_over_half_of_wide_character_blanks_it() {
+        let mut buffer = Buffer::new(1, 6);
+        buffer.put(0, 0, "漢字", Style::default(), AmbiguousWidth::Narrow);
+        buffer.put(0, 1, "a", Style::default(), AmbiguousWidth::Narrow);
+        assert_eq!(buffer.line(0), " a字");
+        buffer.put(0, 2, "b", Style::default(), AmbiguousWidth::Narrow);
+        assert_eq!(buffer.line(0), " ab");
+
+        let mut buffer = Buffer::new(1, 4);
+        buffer.put(0, 0, "a漢字", Style::default(), AmbiguousWidth::Narrow);
+        assert_eq!(buffer.line(0), "a漢");
+        buffer.resize(1, 2);
+        assert_eq!(buffer.line(0), "a");
+    }
+}
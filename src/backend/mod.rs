@@ -0,0 +1,120 @@
+//! Backend module.
+//!
+//! A [`Window`](../window/struct.Window.html) draws through a backend, which
+//! is curses by default. The [`TestBackend`](struct.TestBackend.html) draws
+//! into memory instead, so applications can be tested without a terminal.
+//!
+//! With the `ansi` feature, the `AnsiBackend` draws to the terminal with
+//! escape sequences and termios, for where curses isn't available.
+
+#[cfg(all(unix, feature = "ansi"))]
+mod ansi;
+#[cfg(unix)]
+mod ansi_screen;
+mod arena;
+mod buffer;
+mod test;
+
+#[cfg(all(unix, feature = "ansi"))]
+pub use self::ansi::AnsiBackend;
+pub use self::test::TestBackend;
+
+#[cfg(unix)]
+pub(crate) use self::ansi_screen::{terminal_size, AnsiScreen};
+pub(crate) use self::arena::Arena;
+pub(crate) use self::buffer::Buffer;
+#[doc(no_inline)]
+pub use window::CursesBackend;
+
+use std::rc::Rc;
+
+use caps::Capabilities;
+use error::Result;
+use event::Event;
+use style::Style;
+use text::AmbiguousWidth;
+use window::CursorStyle;
+
+/// Represents something a window can draw to and read input from.
+///
+/// Drawing is buffered until [`present`](#tymethod.present) is called.
+/// Positions are given as rows and columns, and text given to the backend has
+/// already been encoded for the terminal.
+pub trait Backend {
+    /// Gets the size of the screen in rows and columns.
+    fn size(&self) -> (usize, usize);
+
+    /// Gets the capabilities of the terminal.
+    fn capabilities(&self) -> &Capabilities;
+
+    /// Clears the screen.
+    fn erase(&self);
+
+    /// Draws text at the given position in the given style.
+    fn print(&self, row: usize, col: usize, text: &str, style: Style);
+
+    /// Draws text at the cursor position in the given style, advancing the
+    /// cursor past it.
+    fn print_here(&self, text: &str, style: Style);
+
+    /// Inserts a character at the given position, shifting the rest of the row
+    /// right.
+    fn insert_char(&self, row: usize, col: usize, ch: char);
+
+    /// Deletes the character at the given position, shifting the rest of the
+    /// row left.
+    fn delete_char(&self, row: usize, col: usize);
+
+    /// Gets the position of the drawing cursor.
+    fn cursor(&self) -> (usize, usize);
+
+    /// Moves the drawing cursor.
+    fn move_cursor(&self, row: usize, col: usize);
+
+    /// Queues an encoded graphic to be drawn at the given position when the
+    /// screen is presented.
+    ///
+    /// *Backends without graphics ignore it.*
+    fn draw_graphic(&self, row: usize, col: usize, payload: Rc<str>) {
+        let _ = (row, col, payload);
+    }
+
+    /// Presents everything drawn since the last present, showing the cursor
+    /// in the given style.
+    fn present(&self, cursor: CursorStyle);
+
+    /// Sets how East Asian ambiguous-width characters are measured, kept the
+    /// same as the window so text is laid out alike.
+    ///
+    /// *Backends without a grid of their own ignore it.*
+    fn set_ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        let _ = ambiguous;
+    }
+
+    /// Reads the next input event, if one is waiting.
+    fn read_event(&mut self) -> Option<Event>;
+
+    /// Leaves the terminal temporarily, restoring it as it was before the
+    /// backend, such as to run another program.
+    ///
+    /// *Backends without a terminal do nothing.*
+    fn suspend(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Takes over the terminal again after a suspend, redrawing the screen.
+    ///
+    /// *Backends without a terminal do nothing.*
+    fn resume(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Checks whether the backend draws single line box characters that the
+    /// terminal can't encode, such as with the line drawing characters of
+    /// curses.
+    ///
+    /// *When it doesn't, they are printed as `?` in ASCII fallback mode.*
+    fn line_drawing(&self) -> bool {
+        false
+    }
+}
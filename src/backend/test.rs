@@ -0,0 +1,248 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use backend::{Backend, Buffer};
+use caps::Capabilities;
+use event::Event;
+use style::Style;
+use text::AmbiguousWidth;
+use window::CursorStyle;
+
+/// A backend drawing into memory, for testing applications.
+///
+/// Drawing goes into a back buffer, which is copied to the screen inspected
+/// by tests when the window is refreshed. Input is taken from events pushed
+/// with [`push_event`](#method.push_event).
+///
+/// ```
+/// use termui::backend::TestBackend;
+/// use termui::window::Window;
+///
+/// let mut window = Window::with_backend(TestBackend::new(2, 10));
+/// window.print(0, 2, "hello");
+/// window.refresh();
+/// assert_eq!(window.backend().line(0), "  hello");
+/// ```
+#[derive(Debug)]
+pub struct TestBackend {
+    buffer: RefCell<Buffer>,
+    screen: RefCell<Buffer>,
+    shown_cursor: Cell<CursorStyle>,
+    graphics: RefCell<Vec<(usize, usize, Rc<str>)>>,
+    presented_graphics: RefCell<Vec<(usize, usize, Rc<str>)>>,
+    frames: Cell<usize>,
+    events: VecDeque<Event>,
+    capabilities: Capabilities,
+    ambiguous: AmbiguousWidth,
+}
+
+impl TestBackend {
+    /// Creates a blank test backend of the given size.
+    pub fn new(rows: usize, cols: usize) -> TestBackend {
+        TestBackend {
+            buffer: RefCell::new(Buffer::new(rows, cols)),
+            screen: RefCell::new(Buffer::new(rows, cols)),
+            shown_cursor: Cell::new(CursorStyle::Hidden),
+            graphics: RefCell::new(Vec::new()),
+            presented_graphics: RefCell::new(Vec::new()),
+            frames: Cell::new(0),
+            events: VecDeque::new(),
+            capabilities: Capabilities::none(),
+            ambiguous: AmbiguousWidth::Narrow,
+        }
+    }
+
+    /// Queues an event to be read as terminal input.
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push_back(event);
+    }
+
+    /// Resizes the screen, clearing it and queueing a resize event.
+    pub fn resize(&mut self, rows: usize, cols: usize) {
+        *self.buffer.borrow_mut() = Buffer::new(rows, cols);
+        *self.screen.borrow_mut() = Buffer::new(rows, cols);
+        self.events.push_back(Event::Resize);
+    }
+
+    /// Gets the capabilities reported to the window, to adjust them for a
+    /// test.
+    pub fn capabilities_mut(&mut self) -> &mut Capabilities {
+        &mut self.capabilities
+    }
+
+    /// Gets the presented character at the given position and its style.
+    ///
+    /// *The cell after a wide character holds `'\0'`.*
+    pub fn cell(&self, row: usize, col: usize) -> Option<(char, Style)> {
+        self.screen
+            .borrow()
+            .get(row, col)
+            .map(|cell| (cell.symbol.chars().next().unwrap_or('\0'), cell.style))
+    }
+
+    /// Gets the presented text of a row, without trailing whitespace.
+    pub fn line(&self, row: usize) -> String {
+        self.screen.borrow().line(row)
+    }
+
+    /// Gets the presented text of the screen, one line per row.
+    pub fn contents(&self) -> String {
+        let (rows, _) = self.size();
+        let lines: Vec<String> = (0..rows).map(|row| self.line(row)).collect();
+        lines.join("\n")
+    }
+
+    /// Gets the cursor shown by the last present.
+    pub fn shown_cursor(&self) -> CursorStyle {
+        self.shown_cursor.get()
+    }
+
+    /// Gets the graphics drawn by the last present.
+    pub fn graphics(&self) -> Vec<(usize, usize, Rc<str>)> {
+        self.presented_graphics.borrow().clone()
+    }
+
+    /// Gets the number of times the screen has been presented.
+    pub fn frames(&self) -> usize {
+        self.frames.get()
+    }
+}
+
+impl Backend for TestBackend {
+    fn size(&self) -> (usize, usize) {
+        self.buffer.borrow().size()
+    }
+
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    fn erase(&self) {
+        self.buffer.borrow_mut().clear(Style::default());
+    }
+
+    fn print(&self, row: usize, col: usize, text: &str, style: Style) {
+        self.buffer
+            .borrow_mut()
+            .put(row, col, text, style, self.ambiguous);
+    }
+
+    fn print_here(&self, text: &str, style: Style) {
+        let mut buffer = self.buffer.borrow_mut();
+        let (row, col) = buffer.cursor();
+        buffer.put(row, col, text, style, self.ambiguous);
+    }
+
+    fn insert_char(&self, row: usize, col: usize, ch: char) {
+        self.buffer
+            .borrow_mut()
+            .insert_char(row, col, ch, Style::default(), self.ambiguous);
+    }
+
+    fn delete_char(&self, row: usize, col: usize) {
+        self.buffer.borrow_mut().delete_char(row, col);
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.buffer.borrow().cursor()
+    }
+
+    fn move_cursor(&self, row: usize, col: usize) {
+        self.buffer.borrow_mut().move_cursor(row, col);
+    }
+
+    fn draw_graphic(&self, row: usize, col: usize, payload: Rc<str>) {
+        self.graphics.borrow_mut().push((row, col, payload));
+    }
+
+    fn set_ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        self.ambiguous = ambiguous;
+    }
+
+    fn present(&self, cursor: CursorStyle) {
+        self.screen.borrow_mut().clone_from(&self.buffer.borrow());
+        *self.presented_graphics.borrow_mut() = self.graphics.replace(Vec::new());
+        self.shown_cursor.set(cursor);
+        self.frames.set(self.frames.get() + 1);
+    }
+
+    fn read_event(&mut self) -> Option<Event> {
+        self.events.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestBackend;
+    use layout::Rect;
+    use text::AmbiguousWidth;
+    use widget::{ProgressBar, Widget};
+    use window::Window;
+
+    #[test]
+    fn refresh_sends_changed_cells() {
+        let win = Window::with_backend(TestBackend::new(2, 10));
+        win.print(0, 0, "hello");
+        win.print(1, 2, "world");
+        win.refresh();
+        assert_eq!(win.cells_sent(), 10);
+        assert_eq!(win.backend().contents(), "hello\n  world");
+
+        win.print(0, 0, "jello");
+        win.refresh();
+        assert_eq!(win.cells_sent(), 11);
+        assert_eq!(win.backend().line(0), "jello");
+        assert_eq!(win.backend().frames(), 2);
+
+        // Redrawing the same content after erasing sends nothing.
+        win.erase();
+        win.print(0, 0, "jello");
+        win.print(1, 2, "world");
+        win.refresh();
+        assert_eq!(win.cells_sent(), 11);
+        assert_eq!(win.backend().contents(), "jello\n  world");
+
+        win.erase();
+        win.refresh();
+        assert_eq!(win.backend().contents(), "\n");
+    }
+
+    #[test]
+    fn resize_redraws_everything() {
+        let mut win = Window::with_backend(TestBackend::new(1, 10));
+        win.print(0, 0, "hello");
+        win.refresh();
+        win.backend_mut().resize(2, 8);
+        win.print(1, 0, "world");
+        win.refresh();
+        assert_eq!(win.backend().contents(), "hello\nworld");
+    }
+
+    #[test]
+    fn backend_follows_ambiguous_width() {
+        let mut win = Window::with_backend(TestBackend::new(1, 10));
+        win.print(0, 0, "①Z");
+        win.refresh();
+        assert_eq!(win.backend().cell(0, 1).map(|cell| cell.0), Some('Z'));
+
+        win.set_ambiguous_width(AmbiguousWidth::Wide);
+        win.erase();
+        win.print(0, 0, "①Z");
+        win.refresh();
+        assert_eq!(win.backend().cell(0, 1).map(|cell| cell.0), Some('\0'));
+        assert_eq!(win.backend().cell(0, 2).map(|cell| cell.0), Some('Z'));
+        assert_eq!(win.backend().line(0), "①Z");
+    }
+
+    #[test]
+    fn renders_widget() {
+        let mut win = Window::with_backend(TestBackend::new(2, 20));
+        win.set_ascii_mode(true);
+        let mut bar = ProgressBar::new(4);
+        bar.advance(2);
+        bar.render(Rect::new(0, 1, 20, 1), &win);
+        win.refresh();
+        assert_eq!(win.backend().contents(), "\n[######-------]  50%");
+    }
+}
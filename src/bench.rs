@@ -0,0 +1,164 @@
+//! Bench module.
+//!
+//! Replays a recording of events against an application drawn on a
+//! [`TestBackend`](../backend/struct.TestBackend.html), measuring how long
+//! it takes to handle the events and draw the frames, so the performance of
+//! an interface can be tracked over time.
+
+use std::fmt;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use backend::TestBackend;
+use event::{Event, ReplaySource};
+use window::{ControlFlow, Frame, Window};
+
+/// What was measured while replaying a recording with a
+/// [`Bench`](struct.Bench.html).
+///
+/// *Times are summed over the whole replay.*
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Report {
+    /// The number of recorded events handled.
+    pub events: usize,
+    /// The number of frames drawn.
+    pub frames: usize,
+    /// The number of cells that changed between frames, and were sent to the
+    /// backend.
+    pub cells: usize,
+    /// The time spent handling events, ticks included.
+    pub update: Duration,
+    /// The time spent drawing frames into the window.
+    pub draw: Duration,
+    /// The time spent comparing frames and sending the changes to the
+    /// backend.
+    pub flush: Duration,
+}
+
+impl Report {
+    /// Gets the time spent in every phase.
+    pub fn total(&self) -> Duration {
+        self.update + self.draw + self.flush
+    }
+
+    /// Gets the average time spent on a frame, zero if none were drawn.
+    pub fn per_frame(&self) -> Duration {
+        match self.frames {
+            0 => Duration::ZERO,
+            frames => self.total() / frames as u32,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} events, {} frames, {} cells changed",
+            self.events, self.frames, self.cells
+        )?;
+        writeln!(f, "update: {:?}", self.update)?;
+        writeln!(f, "draw:   {:?}", self.draw)?;
+        writeln!(f, "flush:  {:?}", self.flush)?;
+        write!(
+            f,
+            "total:  {:?} ({:?} per frame)",
+            self.total(),
+            self.per_frame()
+        )
+    }
+}
+
+/// Replays recordings against an application as fast as it can, for
+/// benchmarking.
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use termui::bench::Bench;
+/// use termui::event::{Event, Key};
+/// use termui::window::ControlFlow;
+///
+/// let recording = File::open("session.rec").unwrap();
+/// let report = Bench::new(24, 80).run(recording, |frame, ev| {
+///     match ev {
+///         Some(Event::Key { key: Key::Char('q'), .. }) => return ControlFlow::Break,
+///         Some(_) => {}
+///         None => frame.window().print(0, 0, "hello"),
+///     }
+///     ControlFlow::Continue
+/// });
+/// println!("{}", report);
+/// ```
+///
+/// The application is the same closure given to
+/// [`Window::run`](../window/struct.Window.html#method.run). After each
+/// recorded event it is given an
+/// [`Event::Tick`](../event/enum.Event.html#variant.Tick), then `None` to
+/// draw the frame, which is refreshed, so every event is followed by a frame.
+/// Frames are told the time between them as recorded, not as replayed.
+///
+/// *Recordings are made with a
+/// [`Recorder`](../event/struct.Recorder.html), or
+/// [`Window::start_recording`](../window/struct.Window.html#method.start_recording).*
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Bench {
+    rows: usize,
+    cols: usize,
+}
+
+impl Bench {
+    /// Creates a bench drawing on a test backend of the given size.
+    pub fn new(rows: usize, cols: usize) -> Bench {
+        Bench { rows, cols }
+    }
+
+    /// Replays the recording read from the reader against the application,
+    /// until the recording ends or the application breaks.
+    pub fn run<R, F>(&self, recording: R, mut f: F) -> Report
+    where
+        R: Read,
+        F: FnMut(&mut Frame<'_, TestBackend>, Option<Event>) -> ControlFlow,
+    {
+        let mut win = Window::with_backend(TestBackend::new(self.rows, self.cols));
+        let mut replay = ReplaySource::new(recording);
+        let mut report = Report::default();
+        let mut last = Duration::ZERO;
+        let mut count = 0;
+
+        while let Some((at, event)) = replay.read_next() {
+            report.events += 1;
+            let delta = at.saturating_sub(last);
+            last = at;
+
+            let start = Instant::now();
+            let flow = win.run_frame(&mut f, count, delta, Some(event));
+            let flow = if flow == ControlFlow::Continue {
+                win.run_frame(&mut f, count, delta, Some(Event::Tick))
+            } else {
+                flow
+            };
+            report.update += start.elapsed();
+            if flow == ControlFlow::Break {
+                break;
+            }
+
+            let start = Instant::now();
+            win.erase();
+            let flow = win.run_frame(&mut f, count, delta, None);
+            report.draw += start.elapsed();
+            if flow == ControlFlow::Break {
+                break;
+            }
+
+            let start = Instant::now();
+            win.refresh();
+            report.flush += start.elapsed();
+            report.frames += 1;
+            count += 1;
+        }
+
+        report.cells = win.cells_sent();
+        report
+    }
+}
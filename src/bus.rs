@@ -0,0 +1,207 @@
+//! Bus module.
+//!
+//! Lets loosely coupled parts of an interface talk through typed topics, such
+//! as a list publishing its selection for a status bar to show, without
+//! either holding a reference to the other.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+
+/// Names a topic of a [`Bus`](struct.Bus.html), with the type of the messages
+/// published on it.
+///
+/// ```
+/// use termui::bus::Topic;
+///
+/// const SELECTED: Topic<usize> = Topic::new("selected");
+/// ```
+///
+/// *Topics with the same name but different message types are kept apart.*
+pub struct Topic<T> {
+    name: &'static str,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Topic<T> {
+    /// Creates a topic with the given name.
+    pub const fn new(name: &'static str) -> Topic<T> {
+        Topic {
+            name,
+            marker: PhantomData,
+        }
+    }
+
+    /// Gets the name of the topic.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for Topic<T> {
+    fn clone(&self) -> Topic<T> {
+        *self
+    }
+}
+
+impl<T> Copy for Topic<T> {}
+
+impl<T> fmt::Debug for Topic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Topic").field(&self.name).finish()
+    }
+}
+
+type Queue<T> = RefCell<VecDeque<T>>;
+
+/// The latest message of a topic and the queues of its subscribers.
+#[derive(Default)]
+struct Channel {
+    latest: Option<Box<dyn Any>>,
+    queues: Vec<Box<dyn Any>>,
+}
+
+/// A bus of typed topics that anything holding a clone of it can publish
+/// messages on, or read them from.
+///
+/// ```
+/// use termui::bus::{Bus, Topic};
+///
+/// const SELECTED: Topic<usize> = Topic::new("selected");
+///
+/// let bus = Bus::new();
+/// let changes = bus.subscribe(&SELECTED);
+///
+/// // In the list.
+/// bus.publish(&SELECTED, 3);
+///
+/// // In the status bar, as it renders.
+/// assert_eq!(bus.latest(&SELECTED), Some(3));
+/// assert_eq!(changes.poll(), Some(3));
+/// ```
+///
+/// Each topic keeps its latest message, for parts that only show the
+/// current value when they render. A [subscription](#method.subscribe)
+/// queues every message published after it was made, for parts that react
+/// to each one.
+///
+/// *Clones share the same topics. The bus is meant for the thread running
+/// the interface, other threads can send events through an
+/// [`EventSender`](../window/struct.EventSender.html) instead.*
+#[derive(Clone, Default)]
+pub struct Bus {
+    channels: Rc<RefCell<HashMap<(&'static str, TypeId), Channel>>>,
+}
+
+impl Bus {
+    /// Creates a bus without any topics.
+    pub fn new() -> Bus {
+        Bus::default()
+    }
+
+    /// Publishes a message on a topic, keeping it as the latest and queueing
+    /// it for every subscription.
+    pub fn publish<T: Clone + 'static>(&self, topic: &Topic<T>, message: T) {
+        let mut channels = self.channels.borrow_mut();
+        let channel = channels.entry((topic.name, TypeId::of::<T>())).or_default();
+        channel.queues.retain(|queue| {
+            let queue = queue
+                .downcast_ref::<Weak<Queue<T>>>()
+                .and_then(Weak::upgrade);
+            match queue {
+                Some(queue) => {
+                    queue.borrow_mut().push_back(message.clone());
+                    true
+                }
+                // The subscription was dropped.
+                None => false,
+            }
+        });
+        channel.latest = Some(Box::new(message));
+    }
+
+    /// Gets the latest message published on a topic.
+    pub fn latest<T: Clone + 'static>(&self, topic: &Topic<T>) -> Option<T> {
+        self.channels
+            .borrow()
+            .get(&(topic.name, TypeId::of::<T>()))?
+            .latest
+            .as_ref()?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    /// Forgets the latest message published on a topic.
+    pub fn clear<T: 'static>(&self, topic: &Topic<T>) {
+        if let Some(channel) = self
+            .channels
+            .borrow_mut()
+            .get_mut(&(topic.name, TypeId::of::<T>()))
+        {
+            channel.latest = None;
+        }
+    }
+
+    /// Subscribes to a topic, queueing the messages published on it from now
+    /// on until the subscription is dropped.
+    pub fn subscribe<T: 'static>(&self, topic: &Topic<T>) -> Subscription<T> {
+        let queue = Rc::new(RefCell::new(VecDeque::new()));
+        self.channels
+            .borrow_mut()
+            .entry((topic.name, TypeId::of::<T>()))
+            .or_default()
+            .queues
+            .push(Box::new(Rc::downgrade(&queue)));
+        Subscription { queue }
+    }
+}
+
+impl fmt::Debug for Bus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let channels = self.channels.borrow();
+        let mut topics: Vec<_> = channels.keys().map(|&(name, _)| name).collect();
+        topics.sort_unstable();
+        f.debug_struct("Bus").field("topics", &topics).finish()
+    }
+}
+
+/// The messages published on a topic since subscribing, given by
+/// [`Bus::subscribe`](struct.Bus.html#method.subscribe).
+///
+/// *Dropping the subscription unsubscribes.*
+pub struct Subscription<T> {
+    queue: Rc<Queue<T>>,
+}
+
+impl<T> Subscription<T> {
+    /// Takes the oldest message not yet taken.
+    pub fn poll(&self) -> Option<T> {
+        self.queue.borrow_mut().pop_front()
+    }
+
+    /// Takes every message not yet taken, oldest first.
+    pub fn drain(&self) -> Vec<T> {
+        self.queue.borrow_mut().drain(..).collect()
+    }
+
+    /// Gets the number of messages not yet taken.
+    pub fn len(&self) -> usize {
+        self.queue.borrow().len()
+    }
+
+    /// Checks whether every message has been taken.
+    pub fn is_empty(&self) -> bool {
+        self.queue.borrow().is_empty()
+    }
+}
+
+impl<T> fmt::Debug for Subscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("pending", &self.len())
+            .finish()
+    }
+}
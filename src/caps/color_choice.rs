@@ -0,0 +1,52 @@
+use std::env;
+
+use super::Features;
+
+/// Represents whether colors are used, as chosen by the user through the
+/// environment or by the application.
+///
+/// *Colors being turned off makes windows
+/// [monochrome](../window/struct.Window.html#method.set_monochrome) by
+/// default, so what they told apart still shows.*
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum ColorChoice {
+    /// Colors are used as the terminal and profile allow.
+    #[default]
+    Auto,
+    /// Colors are used even if the profile would leave them out, such as
+    /// with `TERM` unset.
+    ///
+    /// *Colors the terminal description lacks still can't be drawn through
+    /// curses.*
+    Always,
+    /// Colors are never used.
+    Never,
+}
+
+impl ColorChoice {
+    /// Detects the choice from the environment.
+    ///
+    /// `CLICOLOR_FORCE` set to anything but `0` turns colors on. Otherwise
+    /// `NO_COLOR` set to anything but an empty string, or `CLICOLOR` set to
+    /// `0`, turns them off.
+    pub fn detect() -> ColorChoice {
+        let var = |name| env::var_os(name).filter(|value| !value.is_empty());
+        if var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+            ColorChoice::Always
+        } else if var("NO_COLOR").is_some() || var("CLICOLOR").is_some_and(|value| value == "0") {
+            ColorChoice::Never
+        } else {
+            ColorChoice::Auto
+        }
+    }
+
+    /// Gets the features allowed once the choice is applied to those of a
+    /// profile.
+    pub(crate) fn apply(self, features: Features) -> Features {
+        match self {
+            ColorChoice::Auto => features,
+            ColorChoice::Always => features | Features::Color,
+            ColorChoice::Never => features - Features::Color - Features::Truecolor,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Capabilities module.
+
+mod color_choice;
+mod probe;
+mod profile;
+mod quirks;
+
+pub use self::color_choice::ColorChoice;
+pub use self::profile::{Features, Profile};
+pub use self::quirks::{Quirk, Quirks};
+
+pub(crate) use self::probe::Probe;
+
+use std::env;
+
+use style::ColorCaps;
+
+/// Represents the capabilities of the terminal.
+///
+/// Initially guessed from curses and the environment, and replaced with the
+/// terminal's own answers when probed with
+/// [`Builder::probe_capabilities`](../window/struct.Builder.html#method.probe_capabilities).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Capabilities {
+    /// The color capabilities.
+    pub colors: ColorCaps,
+    /// The name of the terminal, from XTGETTCAP or `TERM`.
+    pub terminal_name: Option<String>,
+    /// The number of colors the terminal reported through XTGETTCAP.
+    ///
+    /// *Curses only knows of the colors in `colors`, which is what pairs and
+    /// palette changes are limited to, so this may be more.*
+    pub reported_colors: Option<usize>,
+    /// The attributes reported in the primary device attributes (DA1).
+    pub device_attributes: Vec<u32>,
+    /// Whether the terminal supports sixel graphics.
+    pub sixel: bool,
+    /// The size of a cell in pixels as width and height, from the terminal
+    /// size or a `CSI 16 t` query.
+    pub cell_size: Option<(usize, usize)>,
+    /// Whether the terminal answered a cursor position report (DSR).
+    pub cursor_report: bool,
+    /// Whether the terminal answered a query for the progressive keyboard
+    /// protocol of kitty.
+    pub keyboard_protocol: bool,
+    /// Whether the capabilities were probed from the terminal.
+    pub probed: bool,
+    /// The degradation profile in use.
+    pub profile: Profile,
+    /// Whether colors are used, as chosen by the user or application.
+    pub color_choice: ColorChoice,
+    /// The optional features allowed by the profile, color choice and
+    /// quirks.
+    pub features: Features,
+    /// The names of the quirks applied.
+    pub quirks: Vec<String>,
+}
+
+impl Capabilities {
+    /// Guesses the capabilities from the environment, restricted to the
+    /// features of the profile and the color choice.
+    pub(crate) fn guess(
+        mut colors: ColorCaps,
+        profile: Profile,
+        color_choice: ColorChoice,
+    ) -> Capabilities {
+        let features = color_choice.apply(profile.features());
+        if !features.contains(Features::Color) {
+            colors = ColorCaps {
+                has_colors: false,
+                can_change_color: false,
+                colors: 0,
+                pairs: 0,
+                truecolor: false,
+                default_colors: false,
+            };
+        }
+        colors.truecolor &= features.contains(Features::Truecolor);
+
+        Capabilities {
+            colors,
+            terminal_name: env::var("TERM").ok().filter(|term| !term.is_empty()),
+            reported_colors: None,
+            device_attributes: Vec::new(),
+            sixel: false,
+            cell_size: cell_size(),
+            cursor_report: false,
+            keyboard_protocol: false,
+            probed: false,
+            profile,
+            color_choice,
+            features,
+            quirks: Vec::new(),
+        }
+    }
+
+    /// Gives the capabilities of a dumb terminal, without consulting the
+    /// environment.
+    pub(crate) fn none() -> Capabilities {
+        Capabilities {
+            colors: ColorCaps {
+                has_colors: false,
+                can_change_color: false,
+                colors: 0,
+                pairs: 0,
+                truecolor: false,
+                default_colors: false,
+            },
+            terminal_name: None,
+            reported_colors: None,
+            device_attributes: Vec::new(),
+            sixel: false,
+            cell_size: None,
+            cursor_report: false,
+            keyboard_protocol: false,
+            probed: false,
+            profile: Profile::Dumb,
+            color_choice: ColorChoice::Auto,
+            features: Profile::Dumb.features(),
+            quirks: Vec::new(),
+        }
+    }
+
+    /// Applies the feature adjustments of quirks, only turning on what the
+    /// profile allows if it was chosen explicitly.
+    pub(crate) fn apply_quirks(&mut self, quirks: &[&Quirk], explicit_profile: bool) {
+        let allowed = if explicit_profile {
+            self.profile.features()
+        } else {
+            Features::all()
+        };
+        for quirk in quirks {
+            self.features = (self.features | (quirk.enable & allowed)) - quirk.disable;
+            self.quirks.push(quirk.name.clone());
+        }
+        // The user's choice wins over what quirks turn on.
+        if self.color_choice == ColorChoice::Never {
+            self.features = self.color_choice.apply(self.features);
+        }
+
+        if !self.features.contains(Features::Color) {
+            self.colors.has_colors = false;
+        }
+        self.colors.truecolor = self.colors.has_colors
+            && self.features.contains(Features::Truecolor)
+            && (self.colors.truecolor
+                || quirks
+                    .iter()
+                    .any(|q| q.enable.contains(Features::Truecolor)));
+    }
+}
+
+/// Gets the size of a cell in pixels from the terminal size, if the terminal
+/// reports it.
+#[cfg(unix)]
+pub(crate) fn cell_size() -> Option<(usize, usize)> {
+    use libc;
+
+    let mut size = unsafe { ::std::mem::zeroed::<libc::winsize>() };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } != 0
+        || size.ws_col == 0
+        || size.ws_row == 0
+    {
+        return None;
+    }
+
+    let width = usize::from(size.ws_xpixel / size.ws_col);
+    let height = usize::from(size.ws_ypixel / size.ws_row);
+    if width > 0 && height > 0 {
+        Some((width, height))
+    } else {
+        None
+    }
+}
+
+/// Gets the size of a cell in pixels from the terminal size, if the terminal
+/// reports it.
+#[cfg(not(unix))]
+pub(crate) fn cell_size() -> Option<(usize, usize)> {
+    None
+}
+
+impl Capabilities {
+    /// Checks whether a feature is allowed by the profile.
+    pub fn supports(&self, feature: Features) -> bool {
+        self.features.contains(feature)
+    }
+}
@@ -0,0 +1,191 @@
+use curses::Input;
+
+use super::{Capabilities, Features};
+
+/// The capabilities requested with XTGETTCAP.
+const TERMCAPS: &[&str] = &["TN", "colors", "RGB"];
+
+/// Parses the terminal's answers to capability queries out of the input
+/// stream.
+///
+/// A cursor position report is requested last, since terminals answer queries
+/// in order, receiving it means all supported queries have been answered.
+#[derive(Debug, Default)]
+pub(crate) struct Probe {
+    seq: Vec<Input>,
+    size: (usize, usize),
+    done: bool,
+}
+
+impl Probe {
+    /// Creates a probe for a terminal of the given rows and columns, used to
+    /// get the size of a cell from the size of its text area.
+    pub(crate) fn new(size: (usize, usize)) -> Probe {
+        Probe {
+            size,
+            ..Probe::default()
+        }
+    }
+
+    /// Gets the queries to send to the terminal.
+    pub(crate) fn queries() -> String {
+        let mut queries = String::new();
+        for cap in TERMCAPS {
+            queries.push_str("\x1BP+q");
+            queries.push_str(&hex_encode(cap));
+            queries.push_str("\x1B\\");
+        }
+        queries.push_str("\x1B[16t");
+        queries.push_str("\x1B[14t");
+        queries.push_str("\x1B[?u");
+        queries.push_str("\x1B[c");
+        queries.push_str("\x1B[6n");
+        queries
+    }
+
+    /// Checks whether all queries have been answered.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Feeds an input into the probe, updating the capabilities with any
+    /// complete answer.
+    ///
+    /// Gives back the inputs that were not part of an answer.
+    pub(crate) fn feed(&mut self, input: Input, caps: &mut Capabilities) -> Vec<Input> {
+        let byte = match input {
+            Input::Character(ch) if (ch as u32) < 0x80 => ch as u8,
+            input => {
+                let mut rest: Vec<Input> = self.seq.drain(..).collect();
+                rest.push(input);
+                return rest;
+            }
+        };
+
+        if self.seq.is_empty() && byte != 0x1B {
+            return vec![input];
+        }
+        self.seq.push(input);
+
+        let bytes: Vec<u8> = self
+            .seq
+            .iter()
+            .map(|input| match *input {
+                Input::Character(ch) => ch as u8,
+                _ => 0,
+            })
+            .collect();
+
+        match bytes.get(1) {
+            None => Vec::new(),
+            Some(b'[') if bytes.len() > 2 && (0x40..=0x7E).contains(&byte) => {
+                if self.parse_csi(&bytes[2..], caps) {
+                    self.seq.clear();
+                    Vec::new()
+                } else {
+                    self.seq.drain(..).collect()
+                }
+            }
+            Some(b'[') => Vec::new(),
+            Some(b'P') if bytes.ends_with(b"\x1B\\") && bytes.len() > 3 => {
+                self.parse_dcs(&bytes[2..bytes.len() - 2], caps);
+                self.seq.clear();
+                Vec::new()
+            }
+            Some(b'P') => Vec::new(),
+            Some(_) => self.seq.drain(..).collect(),
+        }
+    }
+
+    /// Parses the body of a CSI sequence, including the final byte.
+    fn parse_csi(&mut self, body: &[u8], caps: &mut Capabilities) -> bool {
+        let (params, fin) = body.split_at(body.len() - 1);
+        match (fin[0], params.first()) {
+            (b'c', Some(b'?')) => {
+                caps.device_attributes = parse_params(&params[1..]);
+                caps.sixel = caps.device_attributes.contains(&4);
+                true
+            }
+            (b'u', Some(b'?')) => {
+                caps.keyboard_protocol = true;
+                true
+            }
+            (b't', _) => match *parse_params(params) {
+                [6, height, width] if height > 0 && width > 0 => {
+                    caps.cell_size = Some((width as usize, height as usize));
+                    true
+                }
+                // The text area, answered after the cell size, so only used
+                // by terminals that don't report the cell size.
+                [4, height, width] => {
+                    let (rows, cols) = self.size;
+                    if caps.cell_size.is_none() && rows > 0 && cols > 0 {
+                        let size = (width as usize / cols, height as usize / rows);
+                        if size.0 > 0 && size.1 > 0 {
+                            caps.cell_size = Some(size);
+                        }
+                    }
+                    true
+                }
+                _ => false,
+            },
+            (b'R', _) if parse_params(params).len() == 2 => {
+                caps.cursor_report = true;
+                self.done = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Parses the body of a DCS sequence, excluding the string terminator.
+    fn parse_dcs(&mut self, body: &[u8], caps: &mut Capabilities) {
+        let body = String::from_utf8_lossy(body);
+        if !body.starts_with("1+r") {
+            return;
+        }
+
+        for pair in body[3..].split(';') {
+            let mut parts = pair.splitn(2, '=');
+            let name = parts.next().and_then(hex_decode);
+            let value = parts.next().and_then(hex_decode);
+
+            match (name.as_deref(), value) {
+                (Some("TN"), Some(value)) => caps.terminal_name = Some(value),
+                (Some("colors"), Some(value)) => {
+                    if let Ok(colors) = value.parse() {
+                        // Curses calls stay within the colors curses knows.
+                        caps.reported_colors = Some(colors);
+                        caps.colors.truecolor |=
+                            colors >= 1 << 24 && caps.supports(Features::Truecolor);
+                    }
+                }
+                (Some("RGB"), _) => caps.colors.truecolor = caps.supports(Features::Truecolor),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn parse_params(params: &[u8]) -> Vec<u32> {
+    String::from_utf8_lossy(params)
+        .split(';')
+        .filter_map(|param| param.parse().ok())
+        .collect()
+}
+
+fn hex_encode(s: &str) -> String {
+    s.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_decode(s: &str) -> Option<String> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let bytes: Option<Vec<u8>> = (0..s.len())
+        .step_by(2)
+        .map(|i| s.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+        .collect();
+    bytes.and_then(|bytes| String::from_utf8(bytes).ok())
+}
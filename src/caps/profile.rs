@@ -0,0 +1,105 @@
+#![allow(non_upper_case_globals)]
+
+use std::env;
+
+bitflags! {
+    /// Represents optional terminal features gated by a degradation profile.
+    pub struct Features: u16 {
+        /// No optional features.
+        const None = 0;
+
+        /// Colors.
+        const Color = 1 << 0;
+        /// 24-bit colors.
+        const Truecolor = 1 << 1;
+        /// Mouse reporting.
+        const Mouse = 1 << 2;
+        /// Palette redefinition with OSC 4.
+        const Palette = 1 << 3;
+        /// Cursor shapes with DECSCUSR.
+        const CursorShape = 1 << 4;
+        /// Clipboard access with OSC 52.
+        const Osc52 = 1 << 5;
+        /// Synchronized updates.
+        const SyncUpdate = 1 << 6;
+        /// Capability queries answered through the input stream.
+        const Queries = 1 << 7;
+        /// Bracketed paste.
+        const BracketedPaste = 1 << 8;
+        /// Styled and colored underlines with `SGR 4:x` and `SGR 58`.
+        const StyledUnderline = 1 << 9;
+        /// Setting the window title with OSC 2.
+        const Title = 1 << 10;
+        /// Hyperlinks with OSC 8.
+        const Hyperlinks = 1 << 11;
+    }
+}
+
+impl Features {
+    /// Gets a feature from its name, such as `"cursor-shape"`.
+    pub fn from_name(name: &str) -> Option<Features> {
+        let feature = match name {
+            "color" => Features::Color,
+            "truecolor" => Features::Truecolor,
+            "mouse" => Features::Mouse,
+            "palette" => Features::Palette,
+            "cursor-shape" => Features::CursorShape,
+            "osc52" => Features::Osc52,
+            "sync-update" => Features::SyncUpdate,
+            "queries" => Features::Queries,
+            "bracketed-paste" => Features::BracketedPaste,
+            "styled-underline" => Features::StyledUnderline,
+            "title" => Features::Title,
+            "hyperlinks" => Features::Hyperlinks,
+            _ => return None,
+        };
+        Some(feature)
+    }
+}
+
+/// Represents a degradation profile, selecting which optional features may be
+/// used.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Profile {
+    /// All features, for modern terminal emulators.
+    Modern,
+    /// Features common to xterm compatible terminals.
+    Xterm,
+    /// No optional features beyond cursor addressing, for serial consoles.
+    Vt100,
+    /// No optional features, for terminals that can't address the cursor.
+    Dumb,
+}
+
+impl Profile {
+    /// Detects the profile from the `TERM` environment variable.
+    pub fn detect() -> Profile {
+        let term = env::var("TERM").unwrap_or_default();
+        if term.is_empty() || term == "dumb" {
+            Profile::Dumb
+        } else if term.starts_with("vt") {
+            Profile::Vt100
+        } else if term == "linux" {
+            Profile::Xterm
+        } else {
+            Profile::Modern
+        }
+    }
+
+    /// Gets the features allowed by the profile.
+    pub fn features(self) -> Features {
+        match self {
+            Profile::Modern => Features::all(),
+            Profile::Xterm => {
+                Features::Color | Features::Mouse | Features::Palette | Features::CursorShape
+            }
+            Profile::Vt100 | Profile::Dumb => Features::None,
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Profile {
+        Profile::detect()
+    }
+}
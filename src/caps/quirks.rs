@@ -0,0 +1,185 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use event::Event;
+
+use super::Features;
+
+/// The quirks shipped with the crate.
+const BUILTIN: &str = include_str!("quirks.txt");
+
+/// Represents the adjustments for a terminal that doesn't behave the way its
+/// profile suggests.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Quirk {
+    /// The section header identifying the terminal, such as `TERM=rxvt*`.
+    pub name: String,
+    /// The features supported regardless of the profile detected, though not
+    /// beyond a profile chosen explicitly.
+    pub enable: Features,
+    /// The features not supported despite the profile.
+    pub disable: Features,
+    /// The key sequences sent by the terminal that curses doesn't know about.
+    pub keys: Vec<(Vec<u8>, Event)>,
+}
+
+impl Quirk {
+    /// Checks whether the quirk applies to the current terminal.
+    pub fn matches(&self) -> bool {
+        let mut parts = self.name.splitn(2, '=');
+        let value = match parts.next().map(env::var) {
+            Some(Ok(value)) => value,
+            _ => return false,
+        };
+        match parts.next() {
+            Some(pattern) if pattern.ends_with('*') => {
+                value.starts_with(&pattern[..pattern.len() - 1])
+            }
+            Some(pattern) => value == pattern,
+            None => true,
+        }
+    }
+}
+
+/// A database of terminal quirks.
+///
+/// Quirks are written as data, one section per terminal:
+///
+/// ```text
+/// # rxvt has its own sequences for the arrow keys with ctrl.
+/// [TERM=rxvt*]
+/// key \EOa = ctrl+up
+/// key \EOb = ctrl+down
+///
+/// # tmux only passes queries through when configured to.
+/// [TMUX]
+/// disable = queries
+/// ```
+///
+/// The header names an environment variable and a pattern its value must
+/// match, where a trailing `*` matches any suffix, or just a variable that
+/// must be set. Features are named as in
+/// [`Features::from_name`](struct.Features.html#method.from_name), and key
+/// sequences use the terminfo escapes `\E` and `^X`.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Quirks {
+    quirks: Vec<Quirk>,
+}
+
+impl Quirks {
+    /// Creates an empty quirks database.
+    pub fn new() -> Quirks {
+        Quirks::default()
+    }
+
+    /// Gets the quirks shipped with the crate.
+    pub fn builtin() -> Quirks {
+        Quirks::parse(BUILTIN)
+    }
+
+    /// Parses a quirks database.
+    ///
+    /// *Invalid lines are logged and skipped.*
+    pub fn parse(text: &str) -> Quirks {
+        let mut quirks: Vec<Quirk> = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                quirks.push(Quirk {
+                    name: line[1..line.len() - 1].trim().to_owned(),
+                    enable: Features::None,
+                    disable: Features::None,
+                    keys: Vec::new(),
+                });
+                continue;
+            }
+
+            let parsed = match quirks.last_mut() {
+                Some(quirk) => parse_line(quirk, line),
+                None => false,
+            };
+            if !parsed {
+                warn!("invalid quirk at line {}: {}", number + 1, line);
+            }
+        }
+        Quirks { quirks }
+    }
+
+    /// Loads a quirks database from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Quirks> {
+        fs::read_to_string(path).map(|text| Quirks::parse(&text))
+    }
+
+    /// Adds the quirks of another database, applied after these.
+    pub fn extend(&mut self, other: Quirks) {
+        self.quirks.extend(other.quirks);
+    }
+
+    /// Gets all the quirks.
+    pub fn quirks(&self) -> &[Quirk] {
+        &self.quirks
+    }
+
+    /// Gets the quirks that apply to the current terminal.
+    pub fn matching(&self) -> Vec<&Quirk> {
+        self.quirks.iter().filter(|quirk| quirk.matches()).collect()
+    }
+}
+
+fn parse_line(quirk: &mut Quirk, line: &str) -> bool {
+    let mut parts = line.splitn(2, '=');
+    let (left, right) = match (parts.next(), parts.next()) {
+        (Some(left), Some(right)) => (left.trim(), right.trim()),
+        _ => return false,
+    };
+
+    if let Some(sequence) = left.strip_prefix("key ") {
+        return match (parse_sequence(sequence.trim()), Event::parse_key(right)) {
+            (Some(sequence), Some(event)) => {
+                quirk.keys.push((sequence, event));
+                true
+            }
+            _ => false,
+        };
+    }
+
+    let features = right
+        .split_whitespace()
+        .map(Features::from_name)
+        .collect::<Option<Vec<_>>>()
+        .map(|features| features.into_iter().fold(Features::None, |acc, f| acc | f));
+    match (left, features) {
+        ("enable", Some(features)) => quirk.enable |= features,
+        ("disable", Some(features)) => quirk.disable |= features,
+        _ => return false,
+    }
+    true
+}
+
+/// Parses a key sequence with the terminfo escapes `\E`, `^X` and `\\`.
+fn parse_sequence(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut chars = s.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'\\' => match chars.next()? {
+                b'E' | b'e' => bytes.push(0x1B),
+                b's' => bytes.push(b' '),
+                other => bytes.push(other),
+            },
+            b'^' => bytes.push(chars.next()?.to_ascii_uppercase() ^ 0x40),
+            other => bytes.push(other),
+        }
+    }
+    if bytes.is_empty() {
+        None
+    } else {
+        Some(bytes)
+    }
+}
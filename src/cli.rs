@@ -0,0 +1,193 @@
+//! Cli module.
+//!
+//! Parses the terminal related command-line flags every termui application
+//! can share, so users find the same `--color`, `--ascii` and `--no-mouse`
+//! wherever they go.
+//!
+//! *Requires the `cli` feature.*
+
+use std::env;
+use std::error;
+use std::fmt;
+use std::path::PathBuf;
+
+use caps::{ColorChoice, Profile};
+use window::Builder;
+
+/// The help text for the flags, to add to the application's own.
+pub const HELP: &str = "\
+Terminal options:
+    --color <auto|always|never>  Whether to use colors
+    --force-color                Use colors even if the terminal seems not to
+    --no-color                   Never use colors
+    --ascii                      Draw with ASCII characters only
+    --mouse                      Use the mouse if the terminal supports it
+    --no-mouse                   Never use the mouse
+    --theme <path>               Load the theme from the given file
+    --profile <modern|xterm|vt100|dumb>
+                                 Limit the terminal features used
+";
+
+/// Represents an error in the terminal flags.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum CliError {
+    /// A flag was given without its value.
+    MissingValue(&'static str),
+    /// A flag was given a value it doesn't take.
+    InvalidValue(&'static str, String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CliError::MissingValue(flag) => write!(f, "missing value for {}", flag),
+            CliError::InvalidValue(flag, ref value) => {
+                write!(f, "invalid value for {}: {}", flag, value)
+            }
+        }
+    }
+}
+
+impl error::Error for CliError {}
+
+/// The terminal related options given on the command line.
+///
+/// ```
+/// use termui::cli::TerminalOptions;
+///
+/// let (options, rest) =
+///     TerminalOptions::parse(vec!["--no-mouse", "notes.txt", "--color=never"]).unwrap();
+/// assert_eq!(options.mouse, Some(false));
+/// assert_eq!(rest, vec!["notes.txt".to_owned()]);
+/// ```
+///
+/// Options left as `None` keep the defaults of the
+/// [`Builder`](../window/struct.Builder.html) they are
+/// [applied](#method.apply) to, such as the colors chosen through
+/// `NO_COLOR`.
+///
+/// *Applications with a parser of their own can hand it each argument
+/// through [`parse_arg`](#method.parse_arg).*
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct TerminalOptions {
+    /// Whether to use colors, from `--color`, `--force-color` or
+    /// `--no-color`.
+    pub color: Option<ColorChoice>,
+    /// Whether to draw with ASCII characters only, from `--ascii`.
+    pub ascii: bool,
+    /// Whether to use the mouse, from `--mouse` or `--no-mouse`.
+    pub mouse: Option<bool>,
+    /// The theme file to load, from `--theme`.
+    ///
+    /// *Loading it is up to the application.*
+    pub theme: Option<PathBuf>,
+    /// The degradation profile to use, from `--profile`.
+    pub profile: Option<Profile>,
+}
+
+impl TerminalOptions {
+    /// Creates options with nothing given.
+    pub fn new() -> TerminalOptions {
+        TerminalOptions::default()
+    }
+
+    /// Parses the arguments of the process, leaving out the program name.
+    ///
+    /// Gives back the arguments that aren't terminal flags, in order.
+    pub fn from_args() -> Result<(TerminalOptions, Vec<String>), CliError> {
+        TerminalOptions::parse(env::args().skip(1))
+    }
+
+    /// Parses the given arguments, giving back those that aren't terminal
+    /// flags, in order.
+    ///
+    /// *Arguments after `--` are all given back, along with the `--`.*
+    pub fn parse<I, S>(args: I) -> Result<(TerminalOptions, Vec<String>), CliError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut options = TerminalOptions::new();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                rest.push(arg);
+                rest.extend(args);
+                break;
+            }
+            if !options.parse_arg(&arg, || args.next())? {
+                rest.push(arg);
+            }
+        }
+        Ok((options, rest))
+    }
+
+    /// Parses a single argument, taking the value of a flag from `next` if
+    /// not given after an `=`.
+    ///
+    /// Returns whether the argument was a terminal flag, so a parser can
+    /// handle it otherwise.
+    pub fn parse_arg<F>(&mut self, arg: &str, next: F) -> Result<bool, CliError>
+    where
+        F: FnOnce() -> Option<String>,
+    {
+        let (flag, inline) = match arg.find('=') {
+            Some(i) if arg.starts_with("--") => (&arg[..i], Some(arg[i + 1..].to_owned())),
+            _ => (arg, None),
+        };
+        let value = |flag| inline.or_else(next).ok_or(CliError::MissingValue(flag));
+
+        match flag {
+            "--color" => {
+                let value = value("--color")?;
+                self.color = Some(match &*value {
+                    "auto" => ColorChoice::Auto,
+                    "always" => ColorChoice::Always,
+                    "never" => ColorChoice::Never,
+                    _ => return Err(CliError::InvalidValue("--color", value)),
+                });
+            }
+            "--force-color" => self.color = Some(ColorChoice::Always),
+            "--no-color" => self.color = Some(ColorChoice::Never),
+            "--ascii" => self.ascii = true,
+            "--mouse" => self.mouse = Some(true),
+            "--no-mouse" => self.mouse = Some(false),
+            "--theme" => self.theme = Some(value("--theme")?.into()),
+            "--profile" => {
+                let value = value("--profile")?;
+                self.profile = Some(match &*value {
+                    "modern" => Profile::Modern,
+                    "xterm" => Profile::Xterm,
+                    "vt100" => Profile::Vt100,
+                    "dumb" => Profile::Dumb,
+                    _ => return Err(CliError::InvalidValue("--profile", value)),
+                });
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Applies the options given to a builder.
+    pub fn apply(&self, mut builder: Builder) -> Builder {
+        if let Some(color) = self.color {
+            builder = builder.color_choice(color);
+        }
+        if self.ascii {
+            builder = builder.ascii_mode(true);
+        }
+        if let Some(mouse) = self.mouse {
+            builder = builder.mouse(mouse);
+        }
+        if let Some(profile) = self.profile {
+            builder = builder.profile(profile);
+        }
+        builder
+    }
+
+    /// Creates a builder with the options given applied.
+    pub fn builder(&self) -> Builder {
+        self.apply(Builder::new())
+    }
+}
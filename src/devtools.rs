@@ -0,0 +1,289 @@
+//! Devtools module.
+//!
+//! Provides tools for developing applications, such as stepping back through
+//! the states an application went through to see how it got there.
+//!
+//! *Requires the `devtools` feature.*
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use backend::{Backend, TestBackend};
+use event::{Event, Key, Recorder};
+use style::{Attr, Style};
+use window::Window;
+
+/// The number of states moved by Page Up and Page Down.
+const PAGE_STEP: usize = 10;
+
+/// Keeps the states an application goes through, each with the event that
+/// led to it, so the developer can step back and forth through them while
+/// the application draws the state being looked at.
+///
+/// ```no_run
+/// use termui::devtools::TimeTravel;
+/// use termui::Window;
+///
+/// # fn update(count: &mut u32, _: &termui::Event) { *count += 1 }
+/// # fn draw(count: &u32, win: &Window) {}
+/// let mut win = Window::new();
+/// let mut count = 0;
+/// let mut debugger = TimeTravel::new(1000);
+/// while let Some(event) = win.wait_event(None) {
+///     if !debugger.handle_event(&event) {
+///         update(&mut count, &event);
+///         debugger.record(&event, &count);
+///     }
+///     draw(debugger.viewed().unwrap_or(&count), &win);
+///     debugger.render(&win);
+///     win.refresh();
+/// }
+/// ```
+///
+/// F12, or the [key](#method.set_toggle_key) set instead, starts travelling
+/// back from the latest state. While travelling, the key, mouse and paste
+/// events are used by the debugger rather than the application: Left and
+/// Right step through the states, Page Up and Page Down step ten at a time,
+/// Home and End go to the oldest and latest, and Escape or F12 goes back to
+/// the present. A line at the bottom of the window shows where in the
+/// history the state is.
+///
+/// The events kept can be [written out](#method.write_recording) as a
+/// recording to play back with a
+/// [`ReplaySource`](../event/struct.ReplaySource.html), and any state can
+/// be [drawn](#method.snapshot) into a
+/// [`TestBackend`](../backend/struct.TestBackend.html) to keep as text.
+///
+/// *Only the latest states up to the capacity are kept. States recorded while
+/// travelling are kept too, leaving the state being looked at in place.*
+#[derive(Debug)]
+pub struct TimeTravel<S> {
+    history: VecDeque<(Duration, Event, S)>,
+    capacity: usize,
+    start: Instant,
+    cursor: Option<usize>,
+    toggle_key: Key,
+    style: Style,
+}
+
+impl<S: Clone> TimeTravel<S> {
+    /// Creates a debugger keeping up to the given number of states.
+    pub fn new(capacity: usize) -> TimeTravel<S> {
+        TimeTravel {
+            history: VecDeque::new(),
+            capacity,
+            start: Instant::now(),
+            cursor: None,
+            toggle_key: Key::F12,
+            style: Style::new().attr(Attr::Reverse),
+        }
+    }
+
+    /// Keeps the state reached after handling an event.
+    pub fn record(&mut self, event: &Event, state: &S) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.history.len() == self.capacity {
+            self.history.pop_front();
+            if let Some(ref mut cursor) = self.cursor {
+                *cursor = cursor.saturating_sub(1);
+            }
+        }
+        let elapsed = self.start.elapsed();
+        self.history
+            .push_back((elapsed, event.clone(), state.clone()));
+    }
+
+    /// Gets the number of states kept.
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Checks whether no states are kept.
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+
+    /// Removes every state kept, going back to the present.
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.cursor = None;
+    }
+
+    /// Checks whether a past state is being looked at.
+    pub fn is_travelling(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    /// Starts looking at the latest state, if any are kept.
+    pub fn travel(&mut self) {
+        if !self.history.is_empty() {
+            self.cursor = Some(self.history.len() - 1);
+        }
+    }
+
+    /// Goes back to the present, so the application draws its own state.
+    pub fn resume(&mut self) {
+        self.cursor = None;
+    }
+
+    /// Gets the index of the state being looked at, counted from the oldest
+    /// kept.
+    pub fn position(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Looks at the state at the given index, counted from the oldest kept.
+    ///
+    /// *Indices past the latest state look at the latest.*
+    pub fn jump_to(&mut self, index: usize) {
+        if !self.history.is_empty() {
+            self.cursor = Some(index.min(self.history.len() - 1));
+        }
+    }
+
+    /// Steps back to the state before the one looked at, starting to travel
+    /// if needed.
+    pub fn step_back(&mut self) {
+        match self.cursor {
+            Some(cursor) => self.jump_to(cursor.saturating_sub(1)),
+            None => self.travel(),
+        }
+    }
+
+    /// Steps forward to the state after the one looked at.
+    pub fn step_forward(&mut self) {
+        if let Some(cursor) = self.cursor {
+            self.jump_to(cursor + 1);
+        }
+    }
+
+    /// Gets the state being looked at, or `None` in the present.
+    pub fn viewed(&self) -> Option<&S> {
+        self.history.get(self.cursor?).map(|(_, _, state)| state)
+    }
+
+    /// Gets the event that led to the state being looked at.
+    pub fn viewed_event(&self) -> Option<&Event> {
+        self.history.get(self.cursor?).map(|(_, event, _)| event)
+    }
+
+    /// Gets the state at the given index, counted from the oldest kept.
+    pub fn state(&self, index: usize) -> Option<&S> {
+        self.history.get(index).map(|(_, _, state)| state)
+    }
+
+    /// Gets the key starting and stopping travelling.
+    pub fn toggle_key(&self) -> Key {
+        self.toggle_key
+    }
+
+    /// Sets the key starting and stopping travelling, F12 by default.
+    pub fn set_toggle_key(&mut self, key: Key) {
+        self.toggle_key = key;
+    }
+
+    /// Gets the style of the line showing where in the history the state is.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style of the line showing where in the history the state is,
+    /// reversed by default.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Handles an event, giving whether the debugger used it, in which case
+    /// the application should not.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let key = match *event {
+            Event::Key { key, .. } => key,
+            Event::Mouse { .. } | Event::Paste(_) => return self.is_travelling(),
+            _ => return false,
+        };
+        if event.is_key_release() {
+            return self.is_travelling() || key == self.toggle_key;
+        }
+        let cursor = match self.cursor {
+            Some(cursor) => cursor,
+            None if key == self.toggle_key => {
+                self.travel();
+                return true;
+            }
+            None => return false,
+        };
+        match key {
+            key if key == self.toggle_key => self.resume(),
+            Key::Escape => self.resume(),
+            Key::Left | Key::Up => self.step_back(),
+            Key::Right | Key::Down => self.step_forward(),
+            Key::PageUp => self.jump_to(cursor.saturating_sub(PAGE_STEP)),
+            Key::PageDown => self.jump_to(cursor + PAGE_STEP),
+            Key::Home => self.jump_to(0),
+            Key::End => self.jump_to(usize::MAX),
+            _ => {}
+        }
+        true
+    }
+
+    /// Draws the line showing where in the history the state is over the
+    /// bottom of the window, while travelling.
+    pub fn render<B: Backend>(&self, win: &Window<B>) {
+        let (cursor, &(elapsed, ref event, _)) = match self.cursor {
+            Some(cursor) => match self.history.get(cursor) {
+                Some(entry) => (cursor, entry),
+                None => return,
+            },
+            None => return,
+        };
+        let (rows, cols) = win.get_size();
+        if rows == 0 || cols == 0 {
+            return;
+        }
+        let line = format!(
+            " {}/{} at {:.2}s after {} | Left/Right to step, Esc to resume ",
+            cursor + 1,
+            self.history.len(),
+            elapsed.as_secs_f64(),
+            event.describe(),
+        );
+        let line = ::text::truncate(&line, cols, win.ambiguous_width());
+        let pad = cols - win.display_width(line);
+        win.print_styled(
+            rows - 1,
+            0,
+            format!("{}{}", line, " ".repeat(pad)),
+            self.style,
+        );
+    }
+
+    /// Writes the events kept as a recording, with the times they
+    /// happened, to play back with a
+    /// [`ReplaySource`](../event/struct.ReplaySource.html).
+    ///
+    /// *The recording starts from the oldest event kept.*
+    pub fn write_recording<W: Write + 'static>(&self, writer: W) -> io::Result<()> {
+        let mut recorder = Recorder::new(writer)?;
+        let first = self.history.front().map_or(Duration::ZERO, |entry| entry.0);
+        for &(elapsed, ref event, _) in &self.history {
+            recorder.record_at(event, elapsed - first)?;
+        }
+        Ok(())
+    }
+
+    /// Draws the state at the given index into a test window of the given
+    /// size, giving what was drawn as lines of text.
+    pub fn snapshot<F>(&self, index: usize, rows: usize, cols: usize, draw: F) -> Option<String>
+    where
+        F: FnOnce(&S, &Window<TestBackend>),
+    {
+        let state = self.state(index)?;
+        let win = Window::with_backend(TestBackend::new(rows, cols));
+        draw(state, &win);
+        win.refresh();
+        Some(win.backend().contents())
+    }
+}
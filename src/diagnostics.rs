@@ -0,0 +1,189 @@
+//! Diagnostics module.
+//!
+//! Provides tools for users to report problems with their terminal, such as
+//! keys that are not recognised.
+
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use curses::{self, Input};
+use event::Event;
+use ffi;
+use window::Window;
+
+/// How long to wait for the rest of an escape sequence, matching `ESCDELAY`.
+const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(25);
+
+/// How long to wait between polls when there is no input.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A key press recorded by [`input_probe`](fn.input_probe.html).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct InputRecord {
+    /// The raw bytes sent by the terminal.
+    pub bytes: Vec<u8>,
+    /// The events the bytes were decoded into.
+    pub events: Vec<Event>,
+    /// The time from the first byte arriving to the event being decoded,
+    /// leaving out the wait for more bytes after the last one.
+    pub latency: Duration,
+}
+
+impl fmt::Display for InputRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.bytes {
+            write!(f, "{:02x} ", byte)?;
+        }
+        write!(f, "({:?}) => ", String::from_utf8_lossy(&self.bytes))?;
+        if self.events.is_empty() {
+            write!(f, "nothing")?;
+        }
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", event.describe())?;
+        }
+        write!(f, " in {:.1}ms", self.latency.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Runs an interactive probe, showing how each key press is decoded until
+/// `Ctrl-C` is pressed.
+///
+/// Gives the records of every key pressed, which are useful to include when
+/// reporting keys that are not recognised.
+///
+/// # Panics
+///
+/// Panics if curses could not be initialized.
+pub fn input_probe() -> Vec<InputRecord> {
+    let mut window = Window::builder().quit_signals(true).build();
+    let mut records = Vec::new();
+
+    raw_keypad(&window);
+    loop {
+        draw(&window, &records);
+
+        let (bytes, arrival) = match read_raw(&window) {
+            Some(raw) => raw,
+            None => {
+                // Only take queued events, so no input is read with keys
+                // translated.
+                window.sync_shared();
+                if window.pending_events() > 0 {
+                    if let Some(Event::Quit(_)) = window.poll_event() {
+                        break;
+                    }
+                }
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        };
+
+        // Decoding is timed on its own, since the sequence ended before the
+        // wait for more bytes timed out.
+        let decoding = Instant::now();
+        let events = decode(&mut window, &bytes);
+        records.push(InputRecord {
+            bytes,
+            events,
+            latency: arrival + decoding.elapsed(),
+        });
+    }
+
+    records
+}
+
+/// Decodes the raw bytes of a key press the same way as
+/// [`poll_event`](../window/struct.Window.html#method.poll_event).
+fn decode(window: &mut Window, bytes: &[u8]) -> Vec<Event> {
+    // Curses does not translate keys pushed back byte by byte, so look the
+    // sequence up directly.
+    let code = match CString::new(bytes) {
+        Ok(ref definition) if bytes.len() > 1 => unsafe { ffi::key_defined(definition.as_ptr()) },
+        _ => 0,
+    };
+    if code > 0 {
+        window.input().ungetch(&Input::Unknown(code));
+    } else {
+        for &byte in bytes.iter().rev() {
+            window.input().ungetch(&Input::Unknown(i32::from(byte)));
+        }
+    }
+
+    window.input().keypad(true);
+    let mut events = Vec::new();
+    while let Some(event) = window.read_event() {
+        events.push(event);
+    }
+    raw_keypad(window);
+    events
+}
+
+/// Turns off key translation, but keeps the terminal sending the same
+/// sequences as with it on.
+fn raw_keypad(window: &Window) {
+    window.input().keypad(false);
+
+    let transmit = unsafe { ffi::tigetstr(b"smkx\0".as_ptr() as *const _) };
+    if !transmit.is_null() && transmit as isize != -1 {
+        let transmit = unsafe { CStr::from_ptr(transmit) };
+        print!("{}", transmit.to_string_lossy());
+        io::stdout().flush().expect("could not flush stdout");
+    }
+}
+
+/// Reads the raw bytes of the next key press, with the time from the first
+/// byte arriving to the last.
+fn read_raw(window: &Window) -> Option<(Vec<u8>, Duration)> {
+    let mut bytes = Vec::new();
+    let mut start = None;
+    let mut last = Instant::now();
+    let mut deadline = Instant::now();
+    loop {
+        match window.input().getch() {
+            Some(Input::Character(ch)) => {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+            }
+            Some(Input::Unknown(code)) => bytes.push(code as u8),
+            Some(Input::KeyResize) => {
+                curses::resize_term(0, 0);
+                continue;
+            }
+            Some(_) => continue,
+            None if start.is_none() || Instant::now() >= deadline => break,
+            None => {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        }
+
+        last = Instant::now();
+        start = start.or(Some(last));
+        deadline = last + SEQUENCE_TIMEOUT;
+    }
+
+    start.map(|start| (bytes, last - start))
+}
+
+fn draw(window: &Window, records: &[InputRecord]) {
+    let (rows, _) = window.get_size();
+
+    window.erase();
+    window.print(
+        0,
+        0,
+        "Press keys to see how they are decoded, Ctrl-C to exit.",
+    );
+    let shown = rows.saturating_sub(2);
+    let skip = records.len().saturating_sub(shown);
+    for (row, record) in records.iter().skip(skip).enumerate() {
+        window.print(row + 2, 0, record.to_string());
+    }
+    window.refresh();
+}
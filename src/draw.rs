@@ -0,0 +1,114 @@
+//! Drawing module.
+//!
+//! Lines and boxes drawn into a [`Window`](../window/struct.Window.html),
+//! with Unicode box drawing characters or ASCII.
+
+use backend::Backend;
+use curses;
+use layout::Rect;
+use window::Window;
+
+/// The characters a line or box is drawn with.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub enum BorderStyle {
+    /// Drawn with `+`, `-`, and `|`.
+    Ascii,
+    /// Drawn with single lines, such as `┌─┐`.
+    #[default]
+    Single,
+    /// Drawn with double lines, such as `╔═╗`.
+    Double,
+    /// Drawn with single lines and rounded corners, such as `╭─╮`.
+    Rounded,
+}
+
+/// The characters of a border, as horizontal, vertical, then the top left,
+/// top right, bottom left, and bottom right corners.
+type Glyphs = (char, char, [char; 4]);
+
+impl BorderStyle {
+    fn glyphs(self) -> Glyphs {
+        match self {
+            BorderStyle::Ascii => ('-', '|', ['+', '+', '+', '+']),
+            BorderStyle::Single => ('─', '│', ['┌', '┐', '└', '┘']),
+            BorderStyle::Double => ('═', '║', ['╔', '╗', '╚', '╝']),
+            BorderStyle::Rounded => ('─', '│', ['╭', '╮', '╰', '╯']),
+        }
+    }
+}
+
+/// Gets the curses alternate character set equivalent of a line drawing
+/// character.
+pub(crate) fn acs(ch: char) -> Option<curses::chtype> {
+    let acs = match ch {
+        '─' => curses::ACS_HLINE(),
+        '│' => curses::ACS_VLINE(),
+        '┌' => curses::ACS_ULCORNER(),
+        '┐' => curses::ACS_URCORNER(),
+        '└' => curses::ACS_LLCORNER(),
+        '┘' => curses::ACS_LRCORNER(),
+        '├' => curses::ACS_LTEE(),
+        '┤' => curses::ACS_RTEE(),
+        '┬' => curses::ACS_TTEE(),
+        '┴' => curses::ACS_BTEE(),
+        '┼' => curses::ACS_PLUS(),
+        _ => return None,
+    };
+    Some(acs)
+}
+
+impl<B: Backend> Window<B> {
+    /// Draws a horizontal line of the given length, starting at the given
+    /// position.
+    pub fn hline(&self, row: usize, col: usize, len: usize, border: BorderStyle) {
+        let (horizontal, _, _) = self.glyphs(border);
+        self.print(row, col, horizontal.to_string().repeat(len));
+    }
+
+    /// Draws a vertical line of the given length, starting at the given
+    /// position.
+    pub fn vline(&self, row: usize, col: usize, len: usize, border: BorderStyle) {
+        let (_, vertical, _) = self.glyphs(border);
+        for row in row..row + len {
+            self.printch(row, col, vertical);
+        }
+    }
+
+    /// Draws a box around the edge of the given region.
+    ///
+    /// In [ASCII fallback mode](../window/struct.Window.html#method.ascii_mode)
+    /// the box is drawn with the line drawing characters of curses instead,
+    /// which are single lines, or ASCII where the terminal has none.
+    pub fn draw_box(&self, rect: Rect, border: BorderStyle) {
+        let (width, height) = (rect.width, rect.height);
+        if width < 2 || height < 2 {
+            return;
+        }
+
+        let (horizontal, vertical, corners) = self.glyphs(border);
+        let edge = horizontal.to_string().repeat(width - 2);
+        self.print(
+            rect.y,
+            rect.x,
+            format!("{}{}{}", corners[0], edge, corners[1]),
+        );
+        for row in rect.y + 1..rect.bottom() - 1 {
+            self.printch(row, rect.x, vertical);
+            self.printch(row, rect.right() - 1, vertical);
+        }
+        self.print(
+            rect.bottom() - 1,
+            rect.x,
+            format!("{}{}{}", corners[2], edge, corners[3]),
+        );
+    }
+
+    fn glyphs(&self, border: BorderStyle) -> Glyphs {
+        match border {
+            BorderStyle::Ascii => border.glyphs(),
+            _ if !self.ascii_mode() => border.glyphs(),
+            _ if self.backend().line_drawing() => BorderStyle::Single.glyphs(),
+            _ => BorderStyle::Ascii.glyphs(),
+        }
+    }
+}
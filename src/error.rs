@@ -0,0 +1,35 @@
+//! Error module.
+
+use std::{error, fmt, io, result};
+
+/// Represents an error in a window operation.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Error {
+    /// The terminal does not support the operation.
+    Unsupported(&'static str),
+    /// A value was outside of the range supported by the terminal.
+    OutOfRange(&'static str),
+    /// A curses call failed.
+    Curses(&'static str),
+    /// Reading from or writing to the terminal failed.
+    Io(&'static str, io::ErrorKind),
+    /// Another window is already active, curses only supports one at a time.
+    AlreadyActive,
+}
+
+/// A specialized `Result` type for window operations.
+pub type Result<T> = result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Unsupported(what) => write!(f, "unsupported by terminal: {}", what),
+            Error::OutOfRange(what) => write!(f, "out of range: {}", what),
+            Error::Curses(func) => write!(f, "curses call failed: {}", func),
+            Error::Io(what, kind) => write!(f, "{} failed: {}", what, io::Error::from(kind)),
+            Error::AlreadyActive => write!(f, "a window is already active"),
+        }
+    }
+}
+
+impl error::Error for Error {}
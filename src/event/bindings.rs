@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use event::{Event, Key, Modifier};
+
+/// Maps key chords, a key with modifiers, to actions defined by the
+/// application.
+///
+/// ```no_run
+/// use termui::event::{Bindings, Key, Modifier, Resolved};
+/// use termui::Window;
+///
+/// #[derive(Copy, Clone)]
+/// enum Action {
+///     Save,
+///     Quit,
+/// }
+///
+/// let mut bindings = Bindings::new();
+/// bindings.bind(Key::Char('s'), Modifier::Ctrl, Action::Save);
+/// bindings.bind_str("ctrl+q", Action::Quit);
+///
+/// let mut win = Window::new();
+/// while let Some(resolved) = win.poll_action(&bindings) {
+///     match resolved {
+///         Resolved::Action(Action::Save) => {}
+///         Resolved::Action(Action::Quit) => break,
+///         Resolved::Event(_) => {}
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bindings<A> {
+    chords: HashMap<(Key, Modifier), A>,
+}
+
+/// Represents an event resolved through [`Bindings`](struct.Bindings.html).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Resolved<A> {
+    /// The action bound to a key event.
+    Action(A),
+    /// An event without a bound action.
+    Event(Event),
+}
+
+impl<A> Bindings<A> {
+    /// Creates an empty set of bindings.
+    pub fn new() -> Bindings<A> {
+        Bindings {
+            chords: HashMap::new(),
+        }
+    }
+
+    /// Binds a chord to an action, giving the action it was bound to before.
+    pub fn bind(&mut self, key: Key, modifier: Modifier, action: A) -> Option<A> {
+        self.chords.insert((key, modifier), action)
+    }
+
+    /// Binds a chord described as text, such as `ctrl+shift+left`, to an
+    /// action.
+    ///
+    /// *Gives `false` without binding anything when the description can't be
+    /// parsed.*
+    pub fn bind_str(&mut self, chord: &str, action: A) -> bool {
+        match Event::parse_key(chord) {
+            Some(Event::Key { key, modifier, .. }) => {
+                self.bind(key, modifier, action);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the binding of a chord, giving the action it was bound to.
+    pub fn unbind(&mut self, key: Key, modifier: Modifier) -> Option<A> {
+        self.chords.remove(&(key, modifier))
+    }
+
+    /// Gets the action bound to a chord.
+    pub fn get(&self, key: Key, modifier: Modifier) -> Option<&A> {
+        self.chords.get(&(key, modifier))
+    }
+
+    /// Gets the action bound to a key event, repeated or not.
+    ///
+    /// *Key releases have no action.*
+    pub fn action(&self, event: &Event) -> Option<&A> {
+        match *event {
+            Event::Key { key, modifier, .. } if !event.is_key_release() => self.get(key, modifier),
+            _ => None,
+        }
+    }
+}
+
+impl<A: Clone> Bindings<A> {
+    /// Resolves an event into its bound action, or gives the event back when
+    /// nothing is bound to it.
+    pub fn resolve(&self, event: Event) -> Resolved<A> {
+        match self.action(&event) {
+            Some(action) => Resolved::Action(action.clone()),
+            None => Resolved::Event(event),
+        }
+    }
+}
+
+impl<A> Default for Bindings<A> {
+    fn default() -> Bindings<A> {
+        Bindings::new()
+    }
+}
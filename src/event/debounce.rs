@@ -0,0 +1,203 @@
+use std::time::{Duration, Instant};
+
+/// Holds back a value until no newer one has come for a delay, such as
+/// running a search only once typing pauses.
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+///
+/// use termui::event::Debounce;
+/// use termui::widget::{TextInput, Widget};
+/// use termui::Window;
+///
+/// # fn search(_: &str) {}
+/// let mut win = Window::new();
+/// let mut input = TextInput::new();
+/// let mut query = Debounce::new(Duration::from_millis(150));
+/// loop {
+///     if let Some(ev) = win.wait_event(query.timeout(Instant::now())) {
+///         if <TextInput as Widget>::handle_event(&mut input, &ev) {
+///             query.push(input.value().to_owned());
+///         }
+///     }
+///     if let Some(text) = query.poll(Instant::now()) {
+///         search(&text);
+///     }
+/// }
+/// ```
+///
+/// *The [timeout](#method.timeout) gives how long to wait for the value, so
+/// the loop wakes in time to take it.*
+#[derive(Clone, Debug)]
+pub struct Debounce<T> {
+    delay: Duration,
+    pending: Option<(T, Instant)>,
+}
+
+impl<T> Debounce<T> {
+    /// Creates a debounce holding values back for the given delay.
+    pub fn new(delay: Duration) -> Debounce<T> {
+        Debounce {
+            delay,
+            pending: None,
+        }
+    }
+
+    /// Gets the delay values are held back for.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Sets the delay values are held back for.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// Holds back a value, replacing any held and starting the delay again.
+    pub fn push(&mut self, value: T) {
+        self.push_at(value, Instant::now());
+    }
+
+    /// Holds back a value as given at the given time.
+    pub fn push_at(&mut self, value: T, now: Instant) {
+        self.pending = Some((value, now + self.delay));
+    }
+
+    /// Takes the value held, once the delay has passed by the given time.
+    pub fn poll(&mut self, now: Instant) -> Option<T> {
+        match self.pending {
+            Some((_, due)) if now >= due => self.pending.take().map(|(value, _)| value),
+            _ => None,
+        }
+    }
+
+    /// Takes the value held straight away, such as when Enter is pressed.
+    pub fn flush(&mut self) -> Option<T> {
+        self.pending.take().map(|(value, _)| value)
+    }
+
+    /// Drops the value held.
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+
+    /// Checks whether a value is held.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Gets how long from the given time until the value held is due, or
+    /// `None` if none is held.
+    pub fn timeout(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .as_ref()
+            .map(|&(_, due)| due.saturating_duration_since(now))
+    }
+}
+
+/// Lets a value through at most once per interval, such as laying out again
+/// while the window is being resized.
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+///
+/// use termui::event::Throttle;
+/// use termui::{Event, Window};
+///
+/// # fn layout(_: (usize, usize)) {}
+/// let mut win = Window::new();
+/// let mut resize = Throttle::new(Duration::from_millis(100));
+/// loop {
+///     let size = match win.wait_event(resize.timeout(Instant::now())) {
+///         Some(Event::Resize) => resize.push(win.get_size()),
+///         _ => resize.poll(Instant::now()),
+///     };
+///     if let Some(size) = size {
+///         layout(size);
+///     }
+/// }
+/// ```
+///
+/// The first value is let through at once. Values coming sooner than the
+/// interval after it are held, the latest replacing the others, and let
+/// through once the interval has passed.
+#[derive(Clone, Debug)]
+pub struct Throttle<T> {
+    interval: Duration,
+    last: Option<Instant>,
+    pending: Option<T>,
+}
+
+impl<T> Throttle<T> {
+    /// Creates a throttle letting a value through at most once per interval.
+    pub fn new(interval: Duration) -> Throttle<T> {
+        Throttle {
+            interval,
+            last: None,
+            pending: None,
+        }
+    }
+
+    /// Gets the shortest time between values let through.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Sets the shortest time between values let through.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Gives a value, getting it back if it can be let through now, or
+    /// holding it until it can.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.push_at(value, Instant::now())
+    }
+
+    /// Gives a value at the given time, getting it back if it can be let
+    /// through then.
+    pub fn push_at(&mut self, value: T, now: Instant) -> Option<T> {
+        if self.is_ready(now) {
+            self.last = Some(now);
+            self.pending = None;
+            Some(value)
+        } else {
+            self.pending = Some(value);
+            None
+        }
+    }
+
+    /// Takes the value held, once it can be let through by the given time.
+    pub fn poll(&mut self, now: Instant) -> Option<T> {
+        if self.pending.is_some() && self.is_ready(now) {
+            self.last = Some(now);
+            self.pending.take()
+        } else {
+            None
+        }
+    }
+
+    /// Drops the value held.
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+
+    /// Checks whether a value is held.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Gets how long from the given time until the value held can be let
+    /// through, or `None` if none is held.
+    pub fn timeout(&self, now: Instant) -> Option<Duration> {
+        self.pending.as_ref()?;
+        Some(self.last.map_or(Duration::ZERO, |last| {
+            (last + self.interval).saturating_duration_since(now)
+        }))
+    }
+
+    fn is_ready(&self, now: Instant) -> bool {
+        self.last
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval)
+    }
+}
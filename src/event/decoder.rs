@@ -0,0 +1,1281 @@
+use std::collections::HashMap;
+use std::{mem, str};
+
+use curses::{self, Input};
+use event::{Event, Key, KeyEventKind, Keymap, Modifier, MouseButton, MouseEvent};
+
+/// The longest partial sequence kept waiting for more bytes.
+const MAX_PENDING: usize = 256;
+
+/// The sequence a terminal sends before pasted text, in bracketed paste mode.
+pub(crate) const PASTE_START: &[u8] = b"\x1B[200~";
+/// The sequence a terminal sends after pasted text, in bracketed paste mode.
+pub(crate) const PASTE_END: &[u8] = b"\x1B[201~";
+
+/// The result of parsing the start of a byte sequence.
+enum Parsed {
+    /// Events decoded from the given number of bytes.
+    Events(Vec<Event>, usize),
+    /// The bytes are the start of a longer sequence.
+    Incomplete,
+}
+
+/// Decodes terminal input into events.
+///
+/// Input can be given either as curses inputs, as read by a
+/// [`Window`](../window/struct.Window.html), or as the raw bytes sent by the
+/// terminal.
+///
+/// *Curses key codes without a standard meaning, such as keys with
+/// modifiers, are decoded through the registered key codes.*
+#[derive(Clone, Debug, Default)]
+pub struct Decoder {
+    keymap: Keymap,
+    last_mouse_button: Option<MouseButton>,
+    cell_size: Option<(usize, usize)>,
+    pending: Vec<u8>,
+    paste_codes: Option<(i32, i32)>,
+    paste: Option<String>,
+    raw_escapes: bool,
+    reassemble_utf8: bool,
+    utf8: Vec<u8>,
+    escape: bool,
+}
+
+impl Decoder {
+    /// Creates a new decoder, without any registered key codes.
+    pub fn new() -> Decoder {
+        Decoder::default()
+    }
+
+    /// Registers the event given for a curses key code, replacing any
+    /// existing mapping.
+    pub fn register_keycode(&mut self, code: i32, event: Event) {
+        self.keymap.insert(code, event);
+    }
+
+    /// Gets the keymap decoding curses key codes without a standard meaning.
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Gets the keymap decoding curses key codes without a standard meaning,
+    /// to add or remove mappings.
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        &mut self.keymap
+    }
+
+    /// Sets the size of a cell in pixels when mouse positions are reported in
+    /// pixels, or `None` when they are reported in cells.
+    pub fn set_pixel_mouse(&mut self, cell_size: Option<(usize, usize)>) {
+        self.cell_size = cell_size;
+    }
+
+    /// Sets the curses key codes given for the start and end of a bracketed
+    /// paste.
+    pub(crate) fn set_paste_codes(&mut self, start: i32, end: i32) {
+        self.paste_codes = Some((start, end));
+    }
+
+    /// Sets whether escape sequences curses doesn't know, which it gives as
+    /// characters, are decoded from their bytes.
+    pub(crate) fn set_raw_escapes(&mut self, raw_escapes: bool) {
+        self.raw_escapes = raw_escapes;
+    }
+
+    /// Sets whether characters curses gives for single bytes, when the locale
+    /// isn't UTF-8, are reassembled into the UTF-8 characters they encode.
+    ///
+    /// *Bytes that aren't valid UTF-8 are taken as Latin-1 characters.*
+    pub(crate) fn set_reassemble_utf8(&mut self, reassemble: bool) {
+        self.reassemble_utf8 = reassemble;
+    }
+
+    /// Gets a key code above those used by curses that is not registered.
+    pub(crate) fn unused_code(&self) -> i32 {
+        self.keymap.unused_code()
+    }
+
+    /// Registers the key codes curses assigns to keys with modifiers, as
+    /// named in terminfo.
+    pub(crate) fn register_terminfo_keys(&mut self) {
+        let key_names = {
+            let mut key_names = HashMap::new();
+
+            key_names.insert("DC", Key::Delete);
+            key_names.insert("DN", Key::Down);
+            key_names.insert("END", Key::End);
+            key_names.insert("HOM", Key::Home);
+            key_names.insert("IC", Key::Insert);
+            key_names.insert("LFT", Key::Left);
+            key_names.insert("NXT", Key::PageDown);
+            key_names.insert("PRV", Key::PageUp);
+            key_names.insert("RIT", Key::Right);
+            key_names.insert("UP", Key::Up);
+
+            key_names
+        };
+
+        for code in 512..1024 {
+            let name = match curses::keyname(code) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if !name.starts_with('k') {
+                continue;
+            }
+
+            let (key_name, modifier) = name[1..].split_at(name.len() - 2);
+            let key = match key_names.get(key_name) {
+                Some(&key) => key,
+                None => continue,
+            };
+            let modifier = match modifier {
+                "3" => Modifier::Alt,
+                "4" => Modifier::Shift | Modifier::Alt,
+                "5" => Modifier::Ctrl,
+                "6" => Modifier::Ctrl | Modifier::Shift,
+                "7" => Modifier::Ctrl | Modifier::Shift | Modifier::Alt,
+                _ => continue,
+            };
+
+            self.keymap
+                .insert(code, Event::with_modifier(key, modifier));
+        }
+    }
+
+    /// Decodes an input read from curses.
+    ///
+    /// Gives at least one event, mouse clicks give both a press and a
+    /// release, except within a bracketed paste, where inputs give no events
+    /// until the paste ends. With the progressive keyboard protocol, the
+    /// characters of an escape sequence likewise wait for it to end.
+    ///
+    /// An escape followed by a key gives the key with Alt, as terminals send
+    /// it, and otherwise gives the escape key once the next input arrives or
+    /// the decoder is [flushed](#method.flush).
+    ///
+    /// Bytes curses can't decode are reassembled into the UTF-8 characters
+    /// they encode, or taken as Latin-1 characters if they aren't UTF-8.
+    ///
+    /// *Mouse inputs read the mouse event from curses, and resize inputs give
+    /// a resize event without resizing curses.*
+    pub fn decode_input(&mut self, input: Input) -> Vec<Event> {
+        let inputs = self.reassemble(input);
+        inputs
+            .into_iter()
+            .flat_map(|input| self.decode(input))
+            .collect()
+    }
+
+    /// Buffers the bytes of a partial UTF-8 character, giving the inputs that
+    /// are complete.
+    fn reassemble(&mut self, input: Input) -> Vec<Input> {
+        let byte = match input {
+            Input::Unknown(code) if (0x80..=0xFF).contains(&code) => code as u8,
+            Input::Character(c) if self.reassemble_utf8 && ('\u{80}'..='\u{ff}').contains(&c) => {
+                c as u8
+            }
+            input => {
+                let mut inputs = self.take_utf8();
+                inputs.push(input);
+                return inputs;
+            }
+        };
+
+        let mut inputs = Vec::new();
+        if byte & 0xC0 != 0x80 {
+            // A new character starts, so any partial one is not UTF-8.
+            inputs = self.take_utf8();
+        }
+        self.utf8.push(byte);
+        let len = match self.utf8[0] {
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => 1,
+        };
+        if len == 1 {
+            inputs.extend(self.take_utf8());
+        } else if self.utf8.len() == len {
+            match str::from_utf8(&self.utf8)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => {
+                    self.utf8.clear();
+                    inputs.push(Input::Character(c));
+                }
+                None => inputs.extend(self.take_utf8()),
+            }
+        }
+        inputs
+    }
+
+    /// Takes the bytes of a partial UTF-8 character as Latin-1 characters.
+    fn take_utf8(&mut self) -> Vec<Input> {
+        self.utf8
+            .drain(..)
+            .map(|byte| Input::Character(char::from(byte)))
+            .collect()
+    }
+
+    fn decode(&mut self, input: Input) -> Vec<Event> {
+        if let Some((start, end)) = self.paste_codes {
+            match input {
+                Input::Unknown(code) if code == start => {
+                    self.paste = Some(String::new());
+                    return Vec::new();
+                }
+                Input::Unknown(code) if code == end => {
+                    return self.paste.take().map(Event::Paste).into_iter().collect();
+                }
+                _ => {}
+            }
+        }
+        if let Some(ref mut paste) = self.paste {
+            match input {
+                Input::Character('\r') | Input::KeyEnter => paste.push('\n'),
+                Input::Character(c) => paste.push(c),
+                Input::KeyBTab | Input::KeySTab => paste.push('\t'),
+                _ => debug!("ignoring {:?} in paste", input),
+            }
+            return Vec::new();
+        }
+
+        if self.raw_escapes {
+            match input {
+                Input::Character(c) if c == '\x1B' || !self.pending.is_empty() => {
+                    let mut buf = [0; 4];
+                    return self.push_bytes(c.encode_utf8(&mut buf).as_bytes());
+                }
+                _ if !self.pending.is_empty() => {
+                    let mut events = self.flush();
+                    events.extend(self.decode(input));
+                    return events;
+                }
+                _ => {}
+            }
+        } else if input == Input::Character('\x1B') && !self.escape {
+            // Terminals send Alt as an escape before the key, which curses
+            // gives once it has waited for the rest of a known sequence.
+            self.escape = true;
+            return Vec::new();
+        } else if self.escape {
+            self.escape = false;
+            if input == Input::Character('\x1B') {
+                self.escape = true;
+                return vec![Event::key(Key::Escape)];
+            }
+            let mut events = self.decode(input);
+            match events.first_mut() {
+                Some(&mut Event::Key {
+                    ref mut modifier, ..
+                }) => *modifier |= Modifier::Alt,
+                _ => events.insert(0, Event::key(Key::Escape)),
+            }
+            return events;
+        }
+
+        let ev = match input {
+            Input::Character(c) => char_event(c),
+            Input::KeyEnter => Event::key(Key::Enter),
+            Input::KeyBackspace => Event::key(Key::Backspace),
+
+            Input::KeyBTab | Input::KeySTab => Event::with_modifier(Key::Tab, Modifier::Shift),
+            Input::KeyCTab => Event::with_modifier(Key::Tab, Modifier::Ctrl),
+            Input::KeyCATab => Event::with_modifier(Key::Tab, Modifier::Ctrl | Modifier::Alt),
+
+            Input::Unknown(code) => self.keymap.get(code).cloned().unwrap_or_else(|| {
+                warn!("unknown key: {}", code);
+                Event::Unknown(split_i32(code))
+            }),
+
+            Input::KeyUp => Event::key(Key::Up),
+            Input::KeyDown => Event::key(Key::Down),
+            Input::KeyLeft => Event::key(Key::Left),
+            Input::KeyRight => Event::key(Key::Right),
+
+            Input::KeySR => Event::with_modifier(Key::Up, Modifier::Shift),
+            Input::KeySF => Event::with_modifier(Key::Down, Modifier::Shift),
+            Input::KeySLeft => Event::with_modifier(Key::Left, Modifier::Shift),
+            Input::KeySRight => Event::with_modifier(Key::Right, Modifier::Shift),
+
+            Input::KeyBreak => Event::key(Key::Break),
+            Input::KeyIC => Event::key(Key::Insert),
+            Input::KeyDC => Event::key(Key::Delete),
+            Input::KeyHome => Event::key(Key::Home),
+            Input::KeyEnd => Event::key(Key::End),
+            Input::KeyPPage => Event::key(Key::PageUp),
+            Input::KeyNPage => Event::key(Key::PageDown),
+
+            Input::KeySIC => Event::with_modifier(Key::Insert, Modifier::Shift),
+            Input::KeySDC => Event::with_modifier(Key::Delete, Modifier::Shift),
+            Input::KeySHome => Event::with_modifier(Key::Home, Modifier::Shift),
+            Input::KeySEnd => Event::with_modifier(Key::End, Modifier::Shift),
+            Input::KeySPrevious => Event::with_modifier(Key::PageUp, Modifier::Shift),
+            Input::KeySNext => Event::with_modifier(Key::PageDown, Modifier::Shift),
+
+            Input::KeyF0 => Event::key(Key::F0),
+            Input::KeyF1 => Event::key(Key::F1),
+            Input::KeyF2 => Event::key(Key::F2),
+            Input::KeyF3 => Event::key(Key::F3),
+            Input::KeyF4 => Event::key(Key::F4),
+            Input::KeyF5 => Event::key(Key::F5),
+            Input::KeyF6 => Event::key(Key::F6),
+            Input::KeyF7 => Event::key(Key::F7),
+            Input::KeyF8 => Event::key(Key::F8),
+            Input::KeyF9 => Event::key(Key::F9),
+            Input::KeyF10 => Event::key(Key::F10),
+            Input::KeyF11 => Event::key(Key::F11),
+            Input::KeyF12 => Event::key(Key::F12),
+
+            Input::KeyA1 => Event::with_modifier(Key::Char('7'), Modifier::Keypad),
+            Input::KeyA3 => Event::with_modifier(Key::Char('9'), Modifier::Keypad),
+            Input::KeyB2 => Event::with_modifier(Key::Char('5'), Modifier::Keypad),
+            Input::KeyC1 => Event::with_modifier(Key::Char('1'), Modifier::Keypad),
+            Input::KeyC3 => Event::with_modifier(Key::Char('3'), Modifier::Keypad),
+
+            Input::KeyResize => Event::Resize,
+
+            Input::KeyMouse => {
+                return match curses::getmouse() {
+                    Ok(mevent) => self.decode_mouse(&mevent),
+                    Err(code) => vec![Event::Unknown(split_i32(code))],
+                };
+            }
+
+            _ => Event::Refresh,
+        };
+        vec![ev]
+    }
+
+    /// Decodes a mouse event read from curses.
+    ///
+    /// Gives at least one event, mouse clicks give both a press and a
+    /// release.
+    pub fn decode_mouse(&mut self, mevent: &curses::MEVENT) -> Vec<Event> {
+        let mut modifier = Modifier::None;
+        for &(mask, flag) in &[
+            (curses::BUTTON_CTRL, Modifier::Ctrl),
+            (curses::BUTTON_SHIFT, Modifier::Shift),
+            (curses::BUTTON_ALT, Modifier::Alt),
+        ] {
+            if mevent.bstate & mask != 0 {
+                modifier |= flag;
+            }
+        }
+
+        let bstate =
+            mevent.bstate & !(curses::BUTTON_CTRL | curses::BUTTON_SHIFT | curses::BUTTON_ALT);
+        let (pos, pixel) = self.mouse_position(mevent.x.max(0) as usize, mevent.y.max(0) as usize);
+        let make_event = |event| Event::Mouse {
+            pos,
+            pixel,
+            event,
+            modifier,
+        };
+
+        // Movement with no button held is only reported in any-motion mode.
+        if bstate == curses::REPORT_MOUSE_POSITION {
+            let event = self
+                .last_mouse_button
+                .map_or(MouseEvent::Moved, MouseEvent::Hold);
+            return vec![make_event(event)];
+        }
+
+        let mut bare_event = bstate & ((1 << 25) - 1);
+        let mut events = Vec::new();
+        while bare_event != 0 {
+            let single_event = 1 << bare_event.trailing_zeros();
+            bare_event ^= single_event;
+
+            on_mouse_event(single_event, |e| events.push(e));
+        }
+
+        match events.last() {
+            Some(&MouseEvent::Press(button)) | Some(&MouseEvent::Hold(button)) => {
+                self.last_mouse_button = Some(button)
+            }
+            Some(&MouseEvent::Release(_)) => self.last_mouse_button = None,
+            Some(_) => {}
+            None => {
+                debug!("no event parsed");
+                return vec![Event::Unknown(Vec::new())];
+            }
+        }
+        events.into_iter().map(make_event).collect()
+    }
+
+    /// Decodes the bytes sent by the terminal.
+    ///
+    /// Understands ANSI and xterm key sequences, SGR mouse reports and UTF-8
+    /// text. Sequences that are not understood give unknown events.
+    ///
+    /// *The bytes are decoded as a whole, along with any bytes pending from
+    /// [`push_bytes`](#method.push_bytes), so an escape at the end is the
+    /// escape key.*
+    pub fn decode_bytes(&mut self, bytes: &[u8]) -> Vec<Event> {
+        let mut events = self.push_bytes(bytes);
+        events.extend(self.flush());
+        events
+    }
+
+    /// Decodes bytes as they arrive from the terminal.
+    ///
+    /// Sequences cut short at the end of the bytes are kept pending until
+    /// more bytes are pushed, or until they are [flushed](#method.flush).
+    ///
+    /// *Pending sequences that grow implausibly long give unknown events.*
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Vec<Event> {
+        let mut pending = mem::take(&mut self.pending);
+        pending.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        let mut start = 0;
+        while start < pending.len() {
+            match self.parse(&pending[start..]) {
+                Parsed::Events(evs, len) => {
+                    events.extend(evs);
+                    start += len;
+                }
+                // Pastes can be any length, so only their end is awaited.
+                Parsed::Incomplete
+                    if pending.len() - start > MAX_PENDING
+                        && !pending[start..].starts_with(PASTE_START) =>
+                {
+                    events.push(Event::Unknown(pending[start..].to_vec()));
+                    start = pending.len();
+                }
+                Parsed::Incomplete => break,
+            }
+        }
+        pending.drain(..start);
+        self.pending = pending;
+        events
+    }
+
+    /// Decodes the pending bytes, and any escape waiting for the key it
+    /// prefixes, as if no more input is coming.
+    ///
+    /// This should be called when no input has arrived for a short time, so
+    /// that an escape on its own is the escape key.
+    pub fn flush(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        for input in self.take_utf8() {
+            events.extend(self.decode(input));
+        }
+        if self.escape {
+            self.escape = false;
+            events.push(Event::key(Key::Escape));
+        }
+
+        let pending = mem::take(&mut self.pending);
+        let mut rest = &pending[..];
+        while !rest.is_empty() {
+            let len = match self.parse(rest) {
+                Parsed::Events(evs, len) => {
+                    events.extend(evs);
+                    len
+                }
+                Parsed::Incomplete if rest.starts_with(PASTE_START) => {
+                    events.push(Event::Paste(paste_text(&rest[PASTE_START.len()..])));
+                    rest.len()
+                }
+                Parsed::Incomplete if rest[0] == 0x1B => {
+                    events.push(Event::key(Key::Escape));
+                    1
+                }
+                Parsed::Incomplete => {
+                    events.push(Event::Unknown(rest.to_vec()));
+                    rest.len()
+                }
+            };
+            rest = &rest[len..];
+        }
+        events
+    }
+
+    /// Gets the bytes of a partial sequence waiting for more bytes.
+    pub fn pending_bytes(&self) -> &[u8] {
+        &self.pending
+    }
+
+    /// Encodes an event as the bytes an xterm compatible terminal sends for
+    /// it, which decode back into the same event.
+    ///
+    /// Gives `None` for events the terminal does not send, such as resizes,
+    /// for keys or modifiers it has no sequence for, and for key releases.
+    ///
+    /// *Mouse events are encoded as SGR reports at their cell position, and
+    /// pastes are encoded as bracketed pastes.*
+    pub fn encode(event: &Event) -> Option<Vec<u8>> {
+        match *event {
+            Event::Key { key, modifier, .. } if !event.is_key_release() => {
+                encode_key(key, modifier)
+            }
+            Event::Mouse {
+                pos,
+                event,
+                modifier,
+                ..
+            } => {
+                let (code, last) = match event {
+                    MouseEvent::Press(button) => (button_code(button)?, 'M'),
+                    MouseEvent::Release(button) => (button_code(button)?, 'm'),
+                    MouseEvent::Hold(button) => (button_code(button)? | 32, 'M'),
+                    MouseEvent::Moved => (35, 'M'),
+                    MouseEvent::WheelUp => (64, 'M'),
+                    MouseEvent::WheelDown => (65, 'M'),
+                    MouseEvent::WheelLeft => (66, 'M'),
+                    MouseEvent::WheelRight => (67, 'M'),
+                };
+                let code = code | mouse_modifier_bits(modifier);
+                Some(format!("\x1B[<{};{};{}{}", code, pos.0 + 1, pos.1 + 1, last).into_bytes())
+            }
+            Event::Paste(ref text) => {
+                let mut bytes = PASTE_START.to_vec();
+                bytes.extend_from_slice(text.as_bytes());
+                bytes.extend_from_slice(PASTE_END);
+                Some(bytes)
+            }
+            Event::Unknown(ref bytes) => Some(bytes.clone()),
+            _ => None,
+        }
+    }
+
+    /// Parses the sequence at the start of the bytes.
+    fn parse(&mut self, bytes: &[u8]) -> Parsed {
+        let len = match bytes[0] {
+            0x1B => return self.parse_escape(bytes),
+            b if b < 0x80 => return Parsed::Events(vec![char_event(char::from(b))], 1),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            b => return Parsed::Events(vec![Event::Unknown(vec![b])], 1),
+        };
+
+        let available = &bytes[1..bytes.len().min(len)];
+        if available.iter().any(|&b| b & 0xC0 != 0x80) {
+            return Parsed::Events(vec![Event::Unknown(vec![bytes[0]])], 1);
+        }
+        if bytes.len() < len {
+            return Parsed::Incomplete;
+        }
+        match str::from_utf8(&bytes[..len]) {
+            Ok(s) => Parsed::Events(s.chars().map(char_event).collect(), len),
+            Err(_) => Parsed::Events(vec![Event::Unknown(bytes[..len].to_vec())], len),
+        }
+    }
+
+    /// Parses a sequence starting with an escape.
+    fn parse_escape(&mut self, bytes: &[u8]) -> Parsed {
+        match bytes.get(1) {
+            None => Parsed::Incomplete,
+            Some(b'[') => self.parse_csi(bytes),
+            Some(b'O') => match bytes.get(2) {
+                None => Parsed::Incomplete,
+                Some(&b) => {
+                    let ev = match (ss3_key(b), keypad_char(b)) {
+                        (Some(key), _) => Event::key(key),
+                        (None, Some(c)) => Event::with_modifier(Key::Char(c), Modifier::Keypad),
+                        (None, None) => Event::Unknown(bytes[..3].to_vec()),
+                    };
+                    Parsed::Events(vec![ev], 3)
+                }
+            },
+            // An escape before another escape is only alt when it starts a
+            // sequence, which also keeps runs of escapes from nesting.
+            Some(0x1B) => match bytes.get(2) {
+                None => Parsed::Incomplete,
+                Some(b'[') | Some(b'O') => with_alt(self.parse_escape(&bytes[1..])),
+                Some(_) => Parsed::Events(vec![Event::key(Key::Escape)], 1),
+            },
+            // Alt is sent as an escape before the key.
+            Some(_) => with_alt(self.parse(&bytes[1..])),
+        }
+    }
+
+    /// Parses a control sequence, `ESC [ params final`.
+    fn parse_csi(&mut self, bytes: &[u8]) -> Parsed {
+        let end = match bytes[2..].iter().position(|&b| !(0x20..=0x3F).contains(&b)) {
+            Some(i) => i + 2,
+            None => return Parsed::Incomplete,
+        };
+        let seq = &bytes[..=end];
+        let unknown = || Parsed::Events(vec![Event::Unknown(seq.to_vec())], seq.len());
+        if !(0x40..=0x7E).contains(&bytes[end]) {
+            return unknown();
+        }
+
+        let params = match str::from_utf8(&bytes[2..end]) {
+            Ok(params) => params,
+            Err(_) => return unknown(),
+        };
+        let final_byte = bytes[end];
+
+        if let Some(params) = params.strip_prefix('<') {
+            return match self.sgr_mouse(params, final_byte == b'm') {
+                Some(ev) => Parsed::Events(vec![ev], seq.len()),
+                None => unknown(),
+            };
+        }
+
+        if params == "200" && final_byte == b'~' {
+            return paste(bytes);
+        }
+
+        // Parameters may have subparameters after colons, such as the event
+        // type of the progressive keyboard protocol, `CSI code ; mods:type u`.
+        let fields: Vec<Vec<u32>> = params
+            .split(';')
+            .map(|field| field.split(':').map(|n| n.parse().unwrap_or(0)).collect())
+            .collect();
+        let nums: Vec<u32> = fields.iter().map(|field| field[0]).collect();
+        let modifier = nums.get(1).map_or(Modifier::None, |&n| csi_modifier(n));
+        let kind = match fields.get(1).and_then(|field| field.get(1)) {
+            Some(2) => KeyEventKind::Repeat,
+            Some(3) => KeyEventKind::Release,
+            _ => KeyEventKind::Press,
+        };
+
+        let key = match final_byte {
+            b'u' => kitty_key(nums[0]),
+            b'Z' => Some((Key::Tab, Modifier::Shift)),
+            b'~' => tilde_key(nums[0]).map(|key| (key, Modifier::None)),
+            b => ss3_key(b).map(|key| (key, Modifier::None)),
+        };
+        match key {
+            Some((key, extra)) => Parsed::Events(
+                vec![Event::Key {
+                    key,
+                    modifier: modifier | extra,
+                    repeat_count: 0,
+                    kind,
+                }],
+                seq.len(),
+            ),
+            None => unknown(),
+        }
+    }
+
+    /// Parses an SGR mouse report, `ESC [ < button ; x ; y M`, released with a
+    /// final `m`.
+    fn sgr_mouse(&mut self, params: &str, release: bool) -> Option<Event> {
+        let mut nums = params.split(';').map(|n| n.parse::<usize>().ok());
+        let code = nums.next()??;
+        let x = nums.next()??;
+        let y = nums.next()??;
+
+        let button = match code & 0b11 {
+            0 => Some(MouseButton::Left),
+            1 => Some(MouseButton::Middle),
+            2 => Some(MouseButton::Right),
+            _ => None,
+        };
+        let mut modifier = Modifier::None;
+        for &(bit, flag) in &[
+            (4, Modifier::Shift),
+            (8, Modifier::Alt),
+            (16, Modifier::Ctrl),
+        ] {
+            if code & bit != 0 {
+                modifier |= flag;
+            }
+        }
+
+        let event = if code & 64 != 0 {
+            match code & 0b11 {
+                0 => MouseEvent::WheelUp,
+                1 => MouseEvent::WheelDown,
+                2 => MouseEvent::WheelLeft,
+                _ => MouseEvent::WheelRight,
+            }
+        } else if code & 32 != 0 {
+            button
+                .or(self.last_mouse_button)
+                .map_or(MouseEvent::Moved, MouseEvent::Hold)
+        } else if release {
+            MouseEvent::Release(button.or(self.last_mouse_button.take())?)
+        } else {
+            let button = button?;
+            self.last_mouse_button = Some(button);
+            MouseEvent::Press(button)
+        };
+
+        let (pos, pixel) = self.mouse_position(x.saturating_sub(1), y.saturating_sub(1));
+        Some(Event::Mouse {
+            pos,
+            pixel,
+            event,
+            modifier,
+        })
+    }
+
+    /// Converts a reported mouse position into a cell position, and the
+    /// pixel position when reported in pixels.
+    fn mouse_position(&self, x: usize, y: usize) -> ((usize, usize), Option<(usize, usize)>) {
+        match self.cell_size {
+            Some((width, height)) => ((x / width.max(1), y / height.max(1)), Some((x, y))),
+            None => ((x, y), None),
+        }
+    }
+}
+
+/// Adds alt to the first key of a parsed escape sequence, counting the
+/// escape before it.
+fn with_alt(parsed: Parsed) -> Parsed {
+    match parsed {
+        Parsed::Events(mut evs, len) => {
+            if let Some(&mut Event::Key {
+                ref mut modifier, ..
+            }) = evs.first_mut()
+            {
+                *modifier |= Modifier::Alt;
+            }
+            Parsed::Events(evs, len + 1)
+        }
+        Parsed::Incomplete => Parsed::Incomplete,
+    }
+}
+
+/// Parses a bracketed paste, `ESC [ 200 ~ text ESC [ 201 ~`.
+fn paste(bytes: &[u8]) -> Parsed {
+    let text = &bytes[PASTE_START.len()..];
+    match text.windows(PASTE_END.len()).position(|w| w == PASTE_END) {
+        Some(end) => Parsed::Events(
+            vec![Event::Paste(paste_text(&text[..end]))],
+            PASTE_START.len() + end + PASTE_END.len(),
+        ),
+        None => Parsed::Incomplete,
+    }
+}
+
+/// Decodes pasted bytes as text, with line endings as `\n`.
+fn paste_text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+}
+
+/// Encodes a key with modifiers as the sequence sent by xterm.
+fn encode_key(key: Key, modifier: Modifier) -> Option<Vec<u8>> {
+    if modifier.contains(Modifier::Keypad) {
+        // Keypad characters have their own sequences, which only carry alt.
+        let b = match key {
+            Key::Char(c) if (modifier - Modifier::Alt) == Modifier::Keypad => {
+                (b'X'..=b'y').find(|&b| keypad_char(b) == Some(c))?
+            }
+            _ => return None,
+        };
+        let mut bytes = Vec::new();
+        if modifier.contains(Modifier::Alt) {
+            bytes.push(0x1B);
+        }
+        bytes.extend_from_slice(&[0x1B, b'O', b]);
+        return Some(bytes);
+    }
+
+    let param =
+        1 + if modifier.contains(Modifier::Shift) {
+            1
+        } else {
+            0
+        } + if modifier.contains(Modifier::Alt) {
+            2
+        } else {
+            0
+        } + if modifier.contains(Modifier::Ctrl) {
+            4
+        } else {
+            0
+        };
+
+    let csi = |prefix: &str, last: char| {
+        if param == 1 {
+            Some(format!("\x1B{}{}", prefix, last).into_bytes())
+        } else {
+            Some(format!("\x1B[1;{}{}", param, last).into_bytes())
+        }
+    };
+    let tilde = |n: u32| {
+        if param == 1 {
+            Some(format!("\x1B[{}~", n).into_bytes())
+        } else {
+            Some(format!("\x1B[{};{}~", n, param).into_bytes())
+        }
+    };
+
+    match key {
+        Key::Up => csi("[", 'A'),
+        Key::Down => csi("[", 'B'),
+        Key::Right => csi("[", 'C'),
+        Key::Left => csi("[", 'D'),
+        Key::Home => csi("[", 'H'),
+        Key::End => csi("[", 'F'),
+        Key::F1 => csi("O", 'P'),
+        Key::F2 => csi("O", 'Q'),
+        Key::F3 => csi("O", 'R'),
+        Key::F4 => csi("O", 'S'),
+        Key::Insert => tilde(2),
+        Key::Delete => tilde(3),
+        Key::PageUp => tilde(5),
+        Key::PageDown => tilde(6),
+        Key::F5 => tilde(15),
+        Key::F6 => tilde(17),
+        Key::F7 => tilde(18),
+        Key::F8 => tilde(19),
+        Key::F9 => tilde(20),
+        Key::F10 => tilde(21),
+        Key::F11 => tilde(23),
+        Key::F12 => tilde(24),
+        Key::Tab if modifier == Modifier::Shift => Some(b"\x1B[Z".to_vec()),
+        _ => {
+            let mut bytes = Vec::new();
+            if modifier.contains(Modifier::Alt) {
+                bytes.push(0x1B);
+            }
+            // Other keys can only carry modifiers the terminal distinguishes.
+            let plain = modifier - Modifier::Alt;
+            let byte = match key {
+                Key::Enter if plain.is_empty() => b'\r',
+                Key::Backspace if plain.is_empty() => 0x7F,
+                Key::Tab if plain.is_empty() => b'\t',
+                Key::Escape if modifier.is_empty() => 0x1B,
+                Key::Char(c) if plain == Modifier::Ctrl && c.is_ascii_lowercase() => {
+                    c as u8 - b'a' + 1
+                }
+                Key::Char(c) if plain.is_empty() && !c.is_control() => {
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                    return Some(bytes);
+                }
+                _ => return None,
+            };
+            bytes.push(byte);
+            Some(bytes)
+        }
+    }
+}
+
+/// Gets the SGR code bits of the modifiers held during a mouse event.
+fn mouse_modifier_bits(modifier: Modifier) -> usize {
+    let mut bits = 0;
+    if modifier.contains(Modifier::Shift) {
+        bits |= 4;
+    }
+    if modifier.contains(Modifier::Alt) {
+        bits |= 8;
+    }
+    if modifier.contains(Modifier::Ctrl) {
+        bits |= 16;
+    }
+    bits
+}
+
+/// Gets the SGR button code of a mouse button.
+fn button_code(button: MouseButton) -> Option<usize> {
+    match button {
+        MouseButton::Left => Some(0),
+        MouseButton::Middle => Some(1),
+        MouseButton::Right => Some(2),
+        _ => None,
+    }
+}
+
+/// Decodes a character, including control characters.
+fn char_event(c: char) -> Event {
+    match c {
+        '\n' | '\r' => Event::key(Key::Enter),
+        '\u{7f}' | '\u{8}' => Event::key(Key::Backspace),
+        '\u{9}' => Event::key(Key::Tab),
+        '\u{1b}' => Event::key(Key::Escape),
+        c if (c as u32) <= 26 => {
+            Event::with_modifier(Key::Char((b'a' - 1 + c as u8) as char), Modifier::Ctrl)
+        }
+        c => Event::key(Key::Char(c)),
+    }
+}
+
+/// Decodes the modifier parameter of a control sequence.
+fn csi_modifier(n: u32) -> Modifier {
+    let bits = n.saturating_sub(1);
+    let mut modifier = Modifier::None;
+    if bits & 1 != 0 {
+        modifier |= Modifier::Shift;
+    }
+    if bits & (2 | 8) != 0 {
+        modifier |= Modifier::Alt;
+    }
+    if bits & 4 != 0 {
+        modifier |= Modifier::Ctrl;
+    }
+    modifier
+}
+
+/// Decodes the final byte of an SS3 sequence, or of a control sequence with
+/// the same meaning.
+fn ss3_key(b: u8) -> Option<Key> {
+    Some(match b {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        b'H' => Key::Home,
+        b'F' => Key::End,
+        b'M' => Key::Enter,
+        b'P' => Key::F1,
+        b'Q' => Key::F2,
+        b'R' => Key::F3,
+        b'S' => Key::F4,
+        _ => return None,
+    })
+}
+
+/// Decodes the final byte of an SS3 sequence sent by a keypad key in
+/// application keypad mode.
+fn keypad_char(b: u8) -> Option<char> {
+    Some(match b {
+        b'p'..=b'y' => (b'0' + (b - b'p')) as char,
+        b'j' => '*',
+        b'k' => '+',
+        b'l' => ',',
+        b'm' => '-',
+        b'n' => '.',
+        b'o' => '/',
+        b'X' => '=',
+        _ => return None,
+    })
+}
+
+/// Decodes the key code of a `CSI u` sequence, giving the modifiers implied
+/// by it, such as for keypad keys.
+fn kitty_key(code: u32) -> Option<(Key, Modifier)> {
+    let keypad = |key| Some((key, Modifier::Keypad));
+    let key = match code {
+        8 | 127 => Key::Backspace,
+        9 => Key::Tab,
+        13 => Key::Enter,
+        27 => Key::Escape,
+        57362 => Key::Break,
+        57376 => Key::F13,
+        57377 => Key::F14,
+        57378 => Key::F15,
+        57399..=57408 => return keypad(Key::Char((b'0' + (code - 57399) as u8) as char)),
+        57409 => return keypad(Key::Char('.')),
+        57410 => return keypad(Key::Char('/')),
+        57411 => return keypad(Key::Char('*')),
+        57412 => return keypad(Key::Char('-')),
+        57413 => return keypad(Key::Char('+')),
+        57414 => return keypad(Key::Enter),
+        57415 => return keypad(Key::Char('=')),
+        57416 => return keypad(Key::Char(',')),
+        57417 => return keypad(Key::Left),
+        57418 => return keypad(Key::Right),
+        57419 => return keypad(Key::Up),
+        57420 => return keypad(Key::Down),
+        57421 => return keypad(Key::PageUp),
+        57422 => return keypad(Key::PageDown),
+        57423 => return keypad(Key::Home),
+        57424 => return keypad(Key::End),
+        57425 => return keypad(Key::Insert),
+        57426 => return keypad(Key::Delete),
+        // The rest of the private use area holds keys with no equivalent,
+        // such as media keys and the modifiers themselves.
+        0xE000..=0xF8FF => return None,
+        code => Key::Char(::std::char::from_u32(code)?),
+    };
+    Some((key, Modifier::None))
+}
+
+/// Decodes the number of a `CSI number ~` sequence.
+fn tilde_key(n: u32) -> Option<Key> {
+    Some(match n {
+        1 | 7 => Key::Home,
+        2 => Key::Insert,
+        3 => Key::Delete,
+        4 | 8 => Key::End,
+        5 => Key::PageUp,
+        6 => Key::PageDown,
+        11 => Key::F1,
+        12 => Key::F2,
+        13 => Key::F3,
+        14 => Key::F4,
+        15 => Key::F5,
+        17 => Key::F6,
+        18 => Key::F7,
+        19 => Key::F8,
+        20 => Key::F9,
+        21 => Key::F10,
+        23 => Key::F11,
+        24 => Key::F12,
+        _ => return None,
+    })
+}
+
+fn on_mouse_event<F: FnMut(MouseEvent)>(bare_event: curses::mmask_t, mut f: F) {
+    let button = get_mouse_button(bare_event);
+    match bare_event {
+        curses::BUTTON4_PRESSED => f(MouseEvent::WheelUp),
+        curses::BUTTON5_PRESSED => f(MouseEvent::WheelDown),
+        curses::BUTTON1_RELEASED
+        | curses::BUTTON2_RELEASED
+        | curses::BUTTON3_RELEASED
+        | curses::BUTTON4_RELEASED
+        | curses::BUTTON5_RELEASED => f(MouseEvent::Release(button)),
+        curses::BUTTON1_PRESSED | curses::BUTTON2_PRESSED | curses::BUTTON3_PRESSED => {
+            f(MouseEvent::Press(button))
+        }
+        curses::BUTTON1_CLICKED
+        | curses::BUTTON2_CLICKED
+        | curses::BUTTON3_CLICKED
+        | curses::BUTTON4_CLICKED
+        | curses::BUTTON5_CLICKED => {
+            f(MouseEvent::Press(button));
+            f(MouseEvent::Release(button));
+        }
+        curses::BUTTON1_DOUBLE_CLICKED
+        | curses::BUTTON2_DOUBLE_CLICKED
+        | curses::BUTTON3_DOUBLE_CLICKED
+        | curses::BUTTON4_DOUBLE_CLICKED
+        | curses::BUTTON5_DOUBLE_CLICKED => {
+            for _ in 0..2 {
+                f(MouseEvent::Press(button));
+                f(MouseEvent::Release(button));
+            }
+        }
+        curses::BUTTON1_TRIPLE_CLICKED
+        | curses::BUTTON2_TRIPLE_CLICKED
+        | curses::BUTTON3_TRIPLE_CLICKED
+        | curses::BUTTON4_TRIPLE_CLICKED
+        | curses::BUTTON5_TRIPLE_CLICKED => {
+            for _ in 0..3 {
+                f(MouseEvent::Press(button));
+                f(MouseEvent::Release(button));
+            }
+        }
+        _ => debug!("unknown event: {:032b}", bare_event),
+    }
+}
+
+fn get_mouse_button(bare_event: curses::mmask_t) -> MouseButton {
+    match bare_event {
+        curses::BUTTON1_RELEASED
+        | curses::BUTTON1_PRESSED
+        | curses::BUTTON1_CLICKED
+        | curses::BUTTON1_DOUBLE_CLICKED
+        | curses::BUTTON1_TRIPLE_CLICKED => MouseButton::Left,
+        curses::BUTTON2_RELEASED
+        | curses::BUTTON2_PRESSED
+        | curses::BUTTON2_CLICKED
+        | curses::BUTTON2_DOUBLE_CLICKED
+        | curses::BUTTON2_TRIPLE_CLICKED => MouseButton::Middle,
+        curses::BUTTON3_RELEASED
+        | curses::BUTTON3_PRESSED
+        | curses::BUTTON3_CLICKED
+        | curses::BUTTON3_DOUBLE_CLICKED
+        | curses::BUTTON3_TRIPLE_CLICKED => MouseButton::Right,
+        curses::BUTTON4_RELEASED
+        | curses::BUTTON4_PRESSED
+        | curses::BUTTON4_CLICKED
+        | curses::BUTTON4_DOUBLE_CLICKED
+        | curses::BUTTON4_TRIPLE_CLICKED => MouseButton::Button4,
+        curses::BUTTON5_RELEASED
+        | curses::BUTTON5_PRESSED
+        | curses::BUTTON5_CLICKED
+        | curses::BUTTON5_DOUBLE_CLICKED
+        | curses::BUTTON5_TRIPLE_CLICKED => MouseButton::Button5,
+        _ => MouseButton::Other,
+    }
+}
+
+fn split_i32(code: i32) -> Vec<u8> {
+    (0..4).map(|i| ((code >> (8 * i)) & 0xFF) as u8).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decoder;
+    use event::{Event, Key, Modifier, MouseButton, MouseEvent};
+
+    fn mouse(pos: (usize, usize), event: MouseEvent, modifier: Modifier) -> Event {
+        Event::Mouse {
+            pos,
+            pixel: None,
+            event,
+            modifier,
+        }
+    }
+
+    #[test]
+    fn sequence_split_across_pushes() {
+        let mut decoder = Decoder::new();
+        assert_eq!(decoder.push_bytes(b"\x1B["), vec![]);
+        assert_eq!(decoder.pending_bytes(), b"\x1B[");
+        assert_eq!(decoder.push_bytes(b"1;5"), vec![]);
+        assert_eq!(
+            decoder.push_bytes(b"Cx"),
+            vec![
+                Event::with_modifier(Key::Right, Modifier::Ctrl),
+                Event::key(Key::Char('x')),
+            ]
+        );
+        assert!(decoder.pending_bytes().is_empty());
+    }
+
+    #[test]
+    fn utf8_split_across_pushes() {
+        let mut decoder = Decoder::new();
+        let bytes = "é€".as_bytes();
+        assert_eq!(decoder.push_bytes(&bytes[..1]), vec![]);
+        assert_eq!(
+            decoder.push_bytes(&bytes[1..3]),
+            vec![Event::key(Key::Char('é'))]
+        );
+        assert_eq!(
+            decoder.push_bytes(&bytes[3..]),
+            vec![Event::key(Key::Char('€'))]
+        );
+    }
+
+    #[test]
+    fn lone_escape_waits_for_flush() {
+        let mut decoder = Decoder::new();
+        assert_eq!(decoder.push_bytes(b"\x1B"), vec![]);
+        assert_eq!(decoder.flush(), vec![Event::key(Key::Escape)]);
+        assert!(decoder.pending_bytes().is_empty());
+        assert_eq!(decoder.flush(), vec![]);
+    }
+
+    #[test]
+    fn escape_before_key_is_alt() {
+        let mut decoder = Decoder::new();
+        assert_eq!(
+            decoder.decode_bytes(b"\x1Ba"),
+            vec![Event::with_modifier(Key::Char('a'), Modifier::Alt)]
+        );
+        // Split, the escape waits for the key.
+        assert_eq!(decoder.push_bytes(b"\x1B"), vec![]);
+        assert_eq!(
+            decoder.push_bytes(b"b"),
+            vec![Event::with_modifier(Key::Char('b'), Modifier::Alt)]
+        );
+    }
+
+    #[test]
+    fn double_escape() {
+        let mut decoder = Decoder::new();
+        assert_eq!(
+            decoder.decode_bytes(b"\x1B\x1B"),
+            vec![Event::key(Key::Escape), Event::key(Key::Escape)]
+        );
+    }
+
+    #[test]
+    fn sgr_mouse_reports() {
+        let mut decoder = Decoder::new();
+        assert_eq!(
+            decoder.decode_bytes(b"\x1B[<0;5;3M\x1B[<0;5;3m"),
+            vec![
+                mouse((4, 2), MouseEvent::Press(MouseButton::Left), Modifier::None),
+                mouse(
+                    (4, 2),
+                    MouseEvent::Release(MouseButton::Left),
+                    Modifier::None
+                ),
+            ]
+        );
+        assert_eq!(
+            decoder.decode_bytes(b"\x1B[<64;1;1M"),
+            vec![mouse((0, 0), MouseEvent::WheelUp, Modifier::None)]
+        );
+        assert_eq!(decoder.push_bytes(b"\x1B[<2;10"), vec![]);
+        assert_eq!(
+            decoder.push_bytes(b";20M"),
+            vec![mouse(
+                (9, 19),
+                MouseEvent::Press(MouseButton::Right),
+                Modifier::None
+            )]
+        );
+    }
+
+    #[test]
+    fn bracketed_paste() {
+        let mut decoder = Decoder::new();
+        assert_eq!(
+            decoder.decode_bytes(b"\x1B[200~hello\nworld\x1B[201~"),
+            vec![Event::Paste("hello\nworld".to_owned())]
+        );
+        // Escapes inside a paste are part of it, and it can arrive in pieces.
+        assert_eq!(decoder.push_bytes(b"\x1B[200~a\x1B"), vec![]);
+        assert_eq!(decoder.push_bytes(b"[Ab"), vec![]);
+        assert_eq!(
+            decoder.push_bytes(b"\x1B[201~"),
+            vec![Event::Paste("a\x1B[Ab".to_owned())]
+        );
+    }
+
+    #[test]
+    fn encodings_round_trip() {
+        let mut events = vec![
+            Event::key(Key::Char('a')),
+            Event::key(Key::Char('€')),
+            Event::key(Key::Enter),
+            Event::key(Key::Tab),
+            Event::key(Key::Backspace),
+            Event::key(Key::Escape),
+            Event::key(Key::Delete),
+            Event::key(Key::PageDown),
+            Event::key(Key::F5),
+            Event::key(Key::F12),
+            Event::with_modifier(Key::Char('q'), Modifier::Alt),
+            Event::with_modifier(Key::Char('w'), Modifier::Ctrl),
+            Event::with_modifier(Key::Up, Modifier::Shift),
+            Event::with_modifier(Key::Home, Modifier::Ctrl | Modifier::Alt),
+            Event::Paste("two\nlines".to_owned()),
+            mouse((0, 0), MouseEvent::Press(MouseButton::Left), Modifier::None),
+            mouse(
+                (79, 23),
+                MouseEvent::Hold(MouseButton::Middle),
+                Modifier::Ctrl,
+            ),
+            mouse((3, 4), MouseEvent::Moved, Modifier::None),
+            mouse((3, 4), MouseEvent::WheelDown, Modifier::Shift),
+        ];
+        events.extend(
+            [
+                Key::Left,
+                Key::Right,
+                Key::Down,
+                Key::Insert,
+                Key::End,
+                Key::PageUp,
+            ]
+            .iter()
+            .map(|&key| Event::key(key)),
+        );
+
+        for event in events {
+            let bytes = match Decoder::encode(&event) {
+                Some(bytes) => bytes,
+                None => panic!("{:?} has no encoding", event),
+            };
+            // Byte by byte, as from the slowest of terminals.
+            let mut decoder = Decoder::new();
+            let mut decoded = Vec::new();
+            for byte in &bytes {
+                decoded.extend(decoder.push_bytes(&[*byte]));
+            }
+            decoded.extend(decoder.flush());
+            assert_eq!(decoded, vec![event.clone()], "decoding {:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn unencodable_events() {
+        assert_eq!(Decoder::encode(&Event::Resize), None);
+        assert_eq!(Decoder::encode(&Event::Tick), None);
+    }
+}
@@ -1,5 +1,8 @@
+#![allow(non_upper_case_globals)]
+
 /// Represents a key on a keyboard.
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Key {
     /// A character.
     Char(char),
@@ -71,8 +74,131 @@ pub enum Key {
     F15,
 }
 
+impl Key {
+    /// Gets a key from its name, such as `"up"` or `"f5"`, ignoring case.
+    ///
+    /// A single character names the character key.
+    pub fn from_name(name: &str) -> Option<Key> {
+        let mut chars = name.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            return Some(Key::Char(ch));
+        }
+
+        let key = match &*name.to_ascii_lowercase() {
+            "enter" | "return" => Key::Enter,
+            "backspace" => Key::Backspace,
+            "tab" => Key::Tab,
+            "escape" | "esc" => Key::Escape,
+            "space" => Key::Char(' '),
+            "up" => Key::Up,
+            "down" => Key::Down,
+            "left" => Key::Left,
+            "right" => Key::Right,
+            "break" => Key::Break,
+            "insert" => Key::Insert,
+            "delete" => Key::Delete,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            "f0" => Key::F0,
+            "f1" => Key::F1,
+            "f2" => Key::F2,
+            "f3" => Key::F3,
+            "f4" => Key::F4,
+            "f5" => Key::F5,
+            "f6" => Key::F6,
+            "f7" => Key::F7,
+            "f8" => Key::F8,
+            "f9" => Key::F9,
+            "f10" => Key::F10,
+            "f11" => Key::F11,
+            "f12" => Key::F12,
+            "f13" => Key::F13,
+            "f14" => Key::F14,
+            "f15" => Key::F15,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// Gets the name of the key, as accepted by
+    /// [`from_name`](#method.from_name).
+    pub fn name(&self) -> String {
+        let name = match *self {
+            Key::Char(' ') => "Space",
+            Key::Char(ch) => return ch.to_string(),
+            Key::Enter => "Enter",
+            Key::Backspace => "Backspace",
+            Key::Tab => "Tab",
+            Key::Escape => "Escape",
+            Key::Up => "Up",
+            Key::Down => "Down",
+            Key::Left => "Left",
+            Key::Right => "Right",
+            Key::Break => "Break",
+            Key::Insert => "Insert",
+            Key::Delete => "Delete",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::PageUp => "PageUp",
+            Key::PageDown => "PageDown",
+            Key::F0 => "F0",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+        };
+        name.to_owned()
+    }
+
+    /// Checks whether the key is used for navigation, such as the arrow keys.
+    pub fn is_navigation(&self) -> bool {
+        matches!(
+            *self,
+            Key::Up
+                | Key::Down
+                | Key::Left
+                | Key::Right
+                | Key::Home
+                | Key::End
+                | Key::PageUp
+                | Key::PageDown
+        )
+    }
+}
+
+/// Represents what happened to the key of a key event.
+///
+/// *Terminals only report repeats and releases with the progressive keyboard
+/// protocol, see
+/// [`Builder::kitty_keyboard`](../window/struct.Builder.html#method.kitty_keyboard).*
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum KeyEventKind {
+    /// The key was pressed.
+    #[default]
+    Press,
+    /// The key repeated while held.
+    Repeat,
+    /// The key was released.
+    Release,
+}
+
 bitflags! {
     /// Represents modifier keys pressed during a key event.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct Modifier: u8 {
         /// No modifiers.
         const None = 0b000;
@@ -83,5 +209,8 @@ bitflags! {
         const Shift = 0b010;
         /// Alt.
         const Alt = 0b100;
+        /// The key is on the numeric keypad, as reported in application
+        /// keypad mode.
+        const Keypad = 0b1000;
     }
 }
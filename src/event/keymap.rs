@@ -0,0 +1,71 @@
+use std::collections::hash_map::{self, HashMap};
+
+use event::Event;
+
+/// Maps curses key codes to the events they are decoded as.
+///
+/// *Codes below `256` are characters and codes curses gives a standard
+/// meaning are decoded without the keymap, so mapping them has no effect.*
+#[derive(Clone, Debug, Default)]
+pub struct Keymap {
+    codes: HashMap<i32, Event>,
+}
+
+impl Keymap {
+    /// Creates an empty keymap.
+    pub fn new() -> Keymap {
+        Keymap::default()
+    }
+
+    /// Maps a key code to an event, giving the event it was mapped to before.
+    pub fn insert(&mut self, code: i32, event: Event) -> Option<Event> {
+        self.codes.insert(code, event)
+    }
+
+    /// Removes the mapping of a key code, giving the event it was mapped to.
+    pub fn remove(&mut self, code: i32) -> Option<Event> {
+        self.codes.remove(&code)
+    }
+
+    /// Gets the event a key code is mapped to.
+    pub fn get(&self, code: i32) -> Option<&Event> {
+        self.codes.get(&code)
+    }
+
+    /// Gets the number of mapped key codes.
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Checks whether no key codes are mapped.
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Iterates over the mapped key codes and their events, in no particular
+    /// order.
+    pub fn iter(&self) -> KeymapIter<'_> {
+        KeymapIter {
+            inner: self.codes.iter(),
+        }
+    }
+
+    /// Gets a key code above those used by curses that is not mapped.
+    pub(crate) fn unused_code(&self) -> i32 {
+        self.codes.keys().cloned().max().unwrap_or(0).max(1023) + 1
+    }
+}
+
+/// An iterator over the mappings of a [`Keymap`](struct.Keymap.html).
+#[derive(Clone, Debug)]
+pub struct KeymapIter<'a> {
+    inner: hash_map::Iter<'a, i32, Event>,
+}
+
+impl<'a> Iterator for KeymapIter<'a> {
+    type Item = (i32, &'a Event);
+
+    fn next(&mut self) -> Option<(i32, &'a Event)> {
+        self.inner.next().map(|(&code, event)| (code, event))
+    }
+}
@@ -1,41 +1,294 @@
 //! Events module.
 
+mod bindings;
+mod debounce;
+mod decoder;
 mod key;
+mod keymap;
 mod mouse;
+mod normalize;
+mod record;
+mod source;
+mod user;
 
-pub use self::key::{Key, Modifier};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use layout::Rect;
+
+pub use self::bindings::{Bindings, Resolved};
+pub use self::debounce::{Debounce, Throttle};
+pub use self::decoder::Decoder;
+pub(crate) use self::decoder::{PASTE_END, PASTE_START};
+pub use self::key::{Key, KeyEventKind, Modifier};
+pub use self::keymap::{Keymap, KeymapIter};
 pub use self::mouse::{MouseButton, MouseEvent};
+pub use self::normalize::Normalize;
+pub use self::record::{Recorder, ReplaySource};
+pub use self::source::{EventSource, LineSource, NonBlocking};
+pub use self::user::UserEvent;
 
 /// Represents an event in the window.
+///
+/// *Events, and the keys and mouse events in them, implement `Serialize` and
+/// `Deserialize` with the `serde` feature.*
 #[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Event {
     /// A terminal refresh event.
     Refresh,
     /// A terminal resize event.
     Resize,
+    /// A tick of a frame loop, given once per frame by
+    /// [`Window::run`](../window/struct.Window.html#method.run).
+    Tick,
+    /// No input has been given for the window's
+    /// [idle timeout](../window/struct.Window.html#method.set_idle_timeout),
+    /// with the time since the last input.
+    ///
+    /// *Given once each time the window becomes idle.*
+    Idle(Duration),
     /// A key press event.
     Key {
         /// The key pressed.
         key: Key,
         /// The key press modifier.
         modifier: Modifier,
+        /// The number of times the key has repeated while held, `0` for the
+        /// initial press.
+        repeat_count: u32,
+        /// Whether the key was pressed, repeated or released.
+        kind: KeyEventKind,
     },
     /// A mouse event.
     Mouse {
         /// The position of the mouse.
         pos: (usize, usize),
+        /// The position of the mouse in pixels, relative to the top left of
+        /// the terminal, when reported with
+        /// [`Builder::pixel_mouse`](../window/struct.Builder.html#method.pixel_mouse).
+        pixel: Option<(usize, usize)>,
         /// The mouse event.
         event: MouseEvent,
+        /// The modifier keys held during the event.
+        ///
+        /// *Terminals often keep some combinations for themselves, such as
+        /// Shift to select text.*
+        modifier: Modifier,
+    },
+    /// Text pasted into the terminal, with line endings as `\n`.
+    ///
+    /// *Pastes are only told apart from typing on terminals that support
+    /// bracketed paste.*
+    Paste(String),
+    /// A request to quit, such as from a signal.
+    Quit(QuitReason),
+    /// A watched file changed, as sent by a
+    /// [`FileWatcher`](../watch/struct.FileWatcher.html).
+    FileChanged(PathBuf),
+    /// A message from an [`EventSource`](trait.EventSource.html), such as a
+    /// line read by a [`LineSource`](struct.LineSource.html).
+    Message {
+        /// The name of the source.
+        source: String,
+        /// The text of the message.
+        text: String,
     },
+    /// A message from the application, such as one sent from a worker
+    /// thread through an [`EventSender`](../window/struct.EventSender.html).
+    ///
+    /// *Skipped when serializing, and not recorded.*
+    #[cfg_attr(feature = "serde", serde(skip))]
+    User(UserEvent),
     /// An unknown event.
     Unknown(Vec<u8>),
 }
 
+/// Represents the reason for a quit event.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QuitReason {
+    /// An interrupt, usually from `Ctrl-C` (`SIGINT`).
+    Interrupt,
+    /// A request to terminate (`SIGTERM`).
+    Terminate,
+    /// The terminal was closed (`SIGHUP`).
+    Hangup,
+}
+
 impl Event {
+    /// Translates the event into coordinates relative to the given rect.
+    ///
+    /// Mouse events outside of the rect give `None`, all other events are
+    /// returned unchanged.
+    pub fn relative_to(&self, rect: Rect) -> Option<Event> {
+        match *self {
+            Event::Mouse {
+                pos,
+                pixel,
+                event,
+                modifier,
+            } => rect.to_local(pos).map(|pos| Event::Mouse {
+                pos,
+                pixel,
+                event,
+                modifier,
+            }),
+            ref ev => Some(ev.clone()),
+        }
+    }
+
+    /// Describes the event in a human readable form, such as
+    /// `Ctrl+Shift+Left` or `Mouse drag Left at (10,4)`.
+    ///
+    /// *Key descriptions can be parsed back as key bindings.*
+    pub fn describe(&self) -> String {
+        match *self {
+            Event::Refresh => "Refresh".to_owned(),
+            Event::Resize => "Resize".to_owned(),
+            Event::Tick => "Tick".to_owned(),
+            Event::Idle(idle) => format!("Idle ({:.1}s)", idle.as_secs_f64()),
+            Event::Key {
+                key,
+                modifier,
+                repeat_count,
+                kind,
+            } => {
+                let mut desc = modifier_prefix(modifier);
+                desc.push_str(&key.name());
+                if repeat_count > 0 {
+                    desc.push_str(&format!(" (repeat {})", repeat_count));
+                } else if kind == KeyEventKind::Repeat {
+                    desc.push_str(" (repeat)");
+                }
+                if kind == KeyEventKind::Release {
+                    desc.push_str(" (release)");
+                }
+                desc
+            }
+            Event::Mouse {
+                pos,
+                event,
+                modifier,
+                ..
+            } => {
+                let action = match event {
+                    MouseEvent::Press(button) => format!("press {}", button_name(button)),
+                    MouseEvent::Release(button) => format!("release {}", button_name(button)),
+                    MouseEvent::Hold(button) => format!("drag {}", button_name(button)),
+                    MouseEvent::Moved => "move".to_owned(),
+                    MouseEvent::WheelUp => "wheel up".to_owned(),
+                    MouseEvent::WheelDown => "wheel down".to_owned(),
+                    MouseEvent::WheelLeft => "wheel left".to_owned(),
+                    MouseEvent::WheelRight => "wheel right".to_owned(),
+                };
+                format!(
+                    "Mouse {}{} at ({},{})",
+                    modifier_prefix(modifier),
+                    action,
+                    pos.0,
+                    pos.1
+                )
+            }
+            Event::Paste(ref text) => format!("Paste ({} chars)", text.chars().count()),
+            Event::Quit(reason) => {
+                let reason = match reason {
+                    QuitReason::Interrupt => "interrupt",
+                    QuitReason::Terminate => "terminate",
+                    QuitReason::Hangup => "hangup",
+                };
+                format!("Quit ({})", reason)
+            }
+            Event::FileChanged(ref path) => format!("File changed ({})", path.display()),
+            Event::Message {
+                ref source,
+                ref text,
+            } => {
+                format!("Message from {} ({} chars)", source, text.chars().count())
+            }
+            Event::User(_) => "User event".to_owned(),
+            Event::Unknown(ref bytes) => {
+                let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                format!("Unknown [{}]", hex.join(" "))
+            }
+        }
+    }
+
+    /// Parses a key event with modifiers, such as `ctrl+shift+left`.
+    pub(crate) fn parse_key(desc: &str) -> Option<Event> {
+        let mut parts: Vec<&str> = desc.split('+').map(str::trim).collect();
+        // A `+` on its own, or after the last separator, is the plus key
+        // itself, leaving two empty parts at the end.
+        if parts.len() >= 2 && parts[parts.len() - 2..].iter().all(|part| part.is_empty()) {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let (key, modifiers) = parts.split_last()?;
+
+        let mut modifier = Modifier::None;
+        for name in modifiers {
+            modifier |= match &*name.to_ascii_lowercase() {
+                "ctrl" | "control" => Modifier::Ctrl,
+                "shift" => Modifier::Shift,
+                "alt" | "meta" => Modifier::Alt,
+                "keypad" | "kp" => Modifier::Keypad,
+                _ => return None,
+            };
+        }
+        Key::from_name(key).map(|key| Event::with_modifier(key, modifier))
+    }
+
     pub(crate) fn key(key: Key) -> Event {
+        Event::with_modifier(key, Modifier::None)
+    }
+
+    pub(crate) fn with_modifier(key: Key, modifier: Modifier) -> Event {
         Event::Key {
             key,
-            modifier: Modifier::None,
+            modifier,
+            repeat_count: 0,
+            kind: KeyEventKind::Press,
+        }
+    }
+
+    /// Checks whether the event is a key release.
+    ///
+    /// *Releases are only reported with the progressive keyboard protocol, and
+    /// are ignored by the widgets of the crate.*
+    pub fn is_key_release(&self) -> bool {
+        matches!(
+            *self,
+            Event::Key {
+                kind: KeyEventKind::Release,
+                ..
+            }
+        )
+    }
+}
+
+/// Names the modifiers held, such as `Ctrl+Shift+`.
+fn modifier_prefix(modifier: Modifier) -> String {
+    let mut prefix = String::new();
+    for &(flag, name) in &[
+        (Modifier::Ctrl, "Ctrl+"),
+        (Modifier::Shift, "Shift+"),
+        (Modifier::Alt, "Alt+"),
+        (Modifier::Keypad, "Keypad+"),
+    ] {
+        if modifier.contains(flag) {
+            prefix.push_str(name);
         }
     }
+    prefix
+}
+
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "Left",
+        MouseButton::Middle => "Middle",
+        MouseButton::Right => "Right",
+        MouseButton::Button4 => "Button4",
+        MouseButton::Button5 => "Button5",
+        MouseButton::Other => "Other",
+    }
 }
@@ -1,5 +1,6 @@
 /// Represents a mouse event.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseEvent {
     /// Button press.
     Press(MouseButton),
@@ -7,10 +8,23 @@ pub enum MouseEvent {
     Release(MouseButton),
     /// Mouse drag.
     Hold(MouseButton),
+    /// Mouse movement with no button held.
+    ///
+    /// *Only reported with
+    /// [`Builder::mouse_motion`](../window/struct.Builder.html#method.mouse_motion).*
+    Moved,
     /// Scroll up.
     WheelUp,
     /// Scroll down.
     WheelDown,
+    /// Scroll left.
+    ///
+    /// *Only reported by terminals sending SGR mouse reports.*
+    WheelLeft,
+    /// Scroll right.
+    ///
+    /// *Only reported by terminals sending SGR mouse reports.*
+    WheelRight,
 }
 
 impl MouseEvent {
@@ -25,6 +39,7 @@ impl MouseEvent {
 
 /// Represents a button on a mouse.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseButton {
     /// Left click.
     Left,
@@ -0,0 +1,103 @@
+#![allow(non_upper_case_globals)]
+
+use event::{Event, Key, Modifier};
+
+bitflags! {
+    /// Selects the key aliases merged by
+    /// [`Event::normalize`](enum.Event.html#method.normalize), so key
+    /// bindings only need to handle the canonical key.
+    pub struct Normalize: u8 {
+        /// No normalization.
+        const None = 0b000;
+
+        /// Control characters become the keys they are sent for, Ctrl+H
+        /// becomes Backspace, Ctrl+I Tab, Ctrl+M and Ctrl+J Enter, and Ctrl+[
+        /// Escape.
+        ///
+        /// *The raw control characters without a letter also become Ctrl
+        /// keys, such as `\x1F` becoming Ctrl+_ and NUL becoming Ctrl+Space.*
+        const Control = 0b001;
+        /// Keypad keys become the main keys, dropping
+        /// [`Modifier::Keypad`](struct.Modifier.html#associatedconstant.Keypad).
+        const Keypad = 0b010;
+        /// Shift is dropped from characters it already changed, Shift+a
+        /// becomes A and Shift+! becomes !.
+        ///
+        /// *Shift+Space is kept, since the space is not changed by shift.*
+        const Shift = 0b100;
+
+        /// All normalizations.
+        const All = Self::Control.bits | Self::Keypad.bits | Self::Shift.bits;
+    }
+}
+
+impl Default for Normalize {
+    fn default() -> Normalize {
+        Normalize::None
+    }
+}
+
+impl Event {
+    /// Maps terminal-specific key aliases to the canonical key, as selected by
+    /// the policy.
+    ///
+    /// *Events other than key presses are returned unchanged.*
+    pub fn normalize(self, policy: Normalize) -> Event {
+        let (mut key, mut modifier, repeat_count, kind) = match self {
+            Event::Key {
+                key,
+                modifier,
+                repeat_count,
+                kind,
+            } => (key, modifier, repeat_count, kind),
+            ev => return ev,
+        };
+
+        if policy.contains(Normalize::Keypad) {
+            modifier.remove(Modifier::Keypad);
+        }
+
+        if policy.contains(Normalize::Shift) && modifier.contains(Modifier::Shift) {
+            if let Key::Char(c) = key {
+                let mut upper = c.to_uppercase();
+                match (upper.next(), upper.next()) {
+                    (Some(upper), None) if c != ' ' && !c.is_control() => {
+                        key = Key::Char(upper);
+                        modifier.remove(Modifier::Shift);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if policy.contains(Normalize::Control) {
+            if let Some((alias, ctrl)) = control_alias(key, modifier) {
+                key = alias;
+                modifier.set(Modifier::Ctrl, ctrl);
+            }
+        }
+
+        Event::Key {
+            key,
+            modifier,
+            repeat_count,
+            kind,
+        }
+    }
+}
+
+/// Gets the canonical key for a control alias, and whether it is still
+/// pressed with Ctrl.
+fn control_alias(key: Key, modifier: Modifier) -> Option<(Key, bool)> {
+    let ctrl = modifier.contains(Modifier::Ctrl);
+    match key {
+        Key::Char('h') if ctrl => Some((Key::Backspace, false)),
+        Key::Char('i') if ctrl => Some((Key::Tab, false)),
+        Key::Char('m') | Key::Char('j') if ctrl => Some((Key::Enter, false)),
+        Key::Char('[') if ctrl => Some((Key::Escape, false)),
+        Key::Char('`') | Key::Char('@') if ctrl => Some((Key::Char(' '), true)),
+        Key::Char('\0') => Some((Key::Char(' '), true)),
+        Key::Char(c @ '\u{1C}'..='\u{1F}') => Some((Key::Char((c as u8 + 0x40) as char), true)),
+        _ => None,
+    }
+}
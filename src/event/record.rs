@@ -0,0 +1,556 @@
+use std::fmt::Write as FmtWrite;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::str::Split;
+use std::time::{Duration, Instant};
+
+use event::{Event, EventSource, Key, KeyEventKind, Modifier, MouseButton, MouseEvent, QuitReason};
+
+/// The first line of a recording, naming its format.
+const HEADER: &str = "termui-events 1";
+
+/// Records events with the time they happened, written one per line, so they
+/// can be played back with a [`ReplaySource`](struct.ReplaySource.html).
+///
+/// Each line gives the milliseconds since recording started, followed by the
+/// event, such as `1520 key Up 0 0 press` or `2210 paste some%20text`.
+/// Text is written as UTF-8, with spaces, `%` and control characters escaped
+/// as `%` and two hex digits.
+///
+/// *The format is kept stable, and recordings start with a line naming its
+/// version.*
+pub struct Recorder {
+    writer: Box<dyn Write>,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Starts recording to the writer, writing the header line.
+    pub fn new<W: Write + 'static>(writer: W) -> io::Result<Recorder> {
+        let mut writer: Box<dyn Write> = Box::new(writer);
+        writeln!(writer, "{}", HEADER)?;
+        Ok(Recorder {
+            writer,
+            start: Instant::now(),
+        })
+    }
+
+    /// Records an event as happening now.
+    ///
+    /// *[User events](enum.Event.html#variant.User) cannot be written, so
+    /// are skipped.*
+    pub fn record(&mut self, event: &Event) -> io::Result<()> {
+        let elapsed = self.start.elapsed();
+        self.record_at(event, elapsed)
+    }
+
+    /// Records an event as happening the given time after recording started,
+    /// such as when writing out events kept from earlier.
+    pub(crate) fn record_at(&mut self, event: &Event, elapsed: Duration) -> io::Result<()> {
+        if let Event::User(_) = *event {
+            return Ok(());
+        }
+        writeln!(
+            self.writer,
+            "{} {}",
+            elapsed.as_millis(),
+            format_event(event)
+        )?;
+        self.writer.flush()
+    }
+}
+
+impl ::std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("Recorder")
+            .field("start", &self.start)
+            .finish()
+    }
+}
+
+/// An event source playing back a recording made by a
+/// [`Recorder`](struct.Recorder.html), giving each event once as much time
+/// has passed since the first poll as had passed when it was recorded.
+///
+/// *Lines that cannot be read are skipped with a warning. The source closes
+/// at the end of the recording, or if it is not a recording at all.*
+#[derive(Debug)]
+pub struct ReplaySource<R> {
+    reader: BufReader<R>,
+    start: Option<Instant>,
+    next: Option<(Duration, Event)>,
+    line: usize,
+    closed: bool,
+}
+
+impl<R: Read> ReplaySource<R> {
+    /// Creates a source playing back the recording read from the reader.
+    pub fn new(reader: R) -> ReplaySource<R> {
+        let mut source = ReplaySource {
+            reader: BufReader::new(reader),
+            start: None,
+            next: None,
+            line: 0,
+            closed: false,
+        };
+        match source.read_line() {
+            Some(ref header) if header == HEADER => {}
+            _ => {
+                warn!("not an event recording");
+                source.closed = true;
+            }
+        }
+        source
+    }
+
+    fn read_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line += 1;
+                let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+                line.truncate(len);
+                Some(line)
+            }
+            Err(err) => {
+                warn!("could not read event recording: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Reads the next event of the recording, closing the source at its end.
+    pub(crate) fn read_next(&mut self) -> Option<(Duration, Event)> {
+        while !self.closed {
+            let line = match self.read_line() {
+                Some(line) => line,
+                None => {
+                    self.closed = true;
+                    break;
+                }
+            };
+            match parse_line(&line) {
+                Some(next) => return Some(next),
+                None if line.is_empty() => {}
+                None => warn!("skipping line {} of event recording", self.line),
+            }
+        }
+        None
+    }
+}
+
+impl<R: Read> EventSource for ReplaySource<R> {
+    fn poll(&mut self) -> Vec<Event> {
+        let elapsed = self.start.get_or_insert_with(Instant::now).elapsed();
+        let mut events = Vec::new();
+        loop {
+            if self.next.is_none() {
+                self.next = self.read_next();
+            }
+            match self.next {
+                Some((at, _)) if at <= elapsed => {}
+                _ => break,
+            }
+            if let Some((_, event)) = self.next.take() {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed && self.next.is_none()
+    }
+}
+
+/// Writes an event as it appears in a recording.
+fn format_event(event: &Event) -> String {
+    match *event {
+        Event::Refresh => "refresh".to_owned(),
+        Event::Resize => "resize".to_owned(),
+        Event::Tick => "tick".to_owned(),
+        Event::Idle(idle) => format!("idle {}", idle.as_millis()),
+        Event::Key {
+            key,
+            modifier,
+            repeat_count,
+            kind,
+        } => {
+            let kind = match kind {
+                KeyEventKind::Press => "press",
+                KeyEventKind::Repeat => "repeat",
+                KeyEventKind::Release => "release",
+            };
+            format!(
+                "key {} {} {} {}",
+                escape(&key.name()),
+                modifier.bits(),
+                repeat_count,
+                kind
+            )
+        }
+        Event::Mouse {
+            pos,
+            pixel,
+            event,
+            modifier,
+        } => {
+            let pixel = match pixel {
+                Some((x, y)) => format!("{},{}", x, y),
+                None => "-".to_owned(),
+            };
+            let event = match event {
+                MouseEvent::Press(button) => format!("press:{}", button_name(button)),
+                MouseEvent::Release(button) => format!("release:{}", button_name(button)),
+                MouseEvent::Hold(button) => format!("hold:{}", button_name(button)),
+                MouseEvent::Moved => "moved".to_owned(),
+                MouseEvent::WheelUp => "wheel-up".to_owned(),
+                MouseEvent::WheelDown => "wheel-down".to_owned(),
+                MouseEvent::WheelLeft => "wheel-left".to_owned(),
+                MouseEvent::WheelRight => "wheel-right".to_owned(),
+            };
+            format!(
+                "mouse {},{} {} {} {}",
+                pos.0,
+                pos.1,
+                pixel,
+                event,
+                modifier.bits()
+            )
+        }
+        Event::Paste(ref text) => format!("paste {}", escape(text)),
+        Event::Quit(reason) => {
+            let reason = match reason {
+                QuitReason::Interrupt => "interrupt",
+                QuitReason::Terminate => "terminate",
+                QuitReason::Hangup => "hangup",
+            };
+            format!("quit {}", reason)
+        }
+        Event::FileChanged(ref path) => format!("file {}", escape(&path.to_string_lossy())),
+        Event::Message {
+            ref source,
+            ref text,
+        } => format!("message {} {}", escape(source), escape(text)),
+        Event::User(_) => "user".to_owned(),
+        Event::Unknown(ref bytes) => {
+            let mut hex = String::from("unknown ");
+            for byte in bytes {
+                let _ = write!(hex, "{:02x}", byte);
+            }
+            hex
+        }
+    }
+}
+
+/// Reads a line of a recording, giving the time and the event.
+fn parse_line(line: &str) -> Option<(Duration, Event)> {
+    let mut fields = line.split(' ');
+    let millis = fields.next()?.parse().ok()?;
+    let event = parse_event(&mut fields)?;
+    if fields.next().is_some() {
+        return None;
+    }
+    Some((Duration::from_millis(millis), event))
+}
+
+fn parse_event(fields: &mut Split<'_, char>) -> Option<Event> {
+    let event = match fields.next()? {
+        "refresh" => Event::Refresh,
+        "resize" => Event::Resize,
+        "tick" => Event::Tick,
+        "idle" => Event::Idle(Duration::from_millis(fields.next()?.parse().ok()?)),
+        "key" => {
+            let key = Key::from_name(&unescape(fields.next()?)?)?;
+            let modifier = Modifier::from_bits(fields.next()?.parse().ok()?)?;
+            let repeat_count = fields.next()?.parse().ok()?;
+            let kind = match fields.next()? {
+                "press" => KeyEventKind::Press,
+                "repeat" => KeyEventKind::Repeat,
+                "release" => KeyEventKind::Release,
+                _ => return None,
+            };
+            Event::Key {
+                key,
+                modifier,
+                repeat_count,
+                kind,
+            }
+        }
+        "mouse" => {
+            let pos = parse_pair(fields.next()?)?;
+            let pixel = match fields.next()? {
+                "-" => None,
+                pixel => Some(parse_pair(pixel)?),
+            };
+            let event = match fields.next()? {
+                "moved" => MouseEvent::Moved,
+                "wheel-up" => MouseEvent::WheelUp,
+                "wheel-down" => MouseEvent::WheelDown,
+                "wheel-left" => MouseEvent::WheelLeft,
+                "wheel-right" => MouseEvent::WheelRight,
+                event => {
+                    let mut parts = event.splitn(2, ':');
+                    let (action, button) = (parts.next()?, parse_button(parts.next()?)?);
+                    match action {
+                        "press" => MouseEvent::Press(button),
+                        "release" => MouseEvent::Release(button),
+                        "hold" => MouseEvent::Hold(button),
+                        _ => return None,
+                    }
+                }
+            };
+            let modifier = Modifier::from_bits(fields.next()?.parse().ok()?)?;
+            Event::Mouse {
+                pos,
+                pixel,
+                event,
+                modifier,
+            }
+        }
+        "paste" => Event::Paste(unescape(fields.next()?)?),
+        "quit" => Event::Quit(match fields.next()? {
+            "interrupt" => QuitReason::Interrupt,
+            "terminate" => QuitReason::Terminate,
+            "hangup" => QuitReason::Hangup,
+            _ => return None,
+        }),
+        "file" => Event::FileChanged(PathBuf::from(unescape(fields.next()?)?)),
+        "message" => Event::Message {
+            source: unescape(fields.next()?)?,
+            text: unescape(fields.next()?)?,
+        },
+        "unknown" => Event::Unknown(parse_hex(fields.next()?)?),
+        _ => return None,
+    };
+    Some(event)
+}
+
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "left",
+        MouseButton::Middle => "middle",
+        MouseButton::Right => "right",
+        MouseButton::Button4 => "4",
+        MouseButton::Button5 => "5",
+        MouseButton::Other => "other",
+    }
+}
+
+fn parse_button(name: &str) -> Option<MouseButton> {
+    let button = match name {
+        "left" => MouseButton::Left,
+        "middle" => MouseButton::Middle,
+        "right" => MouseButton::Right,
+        "4" => MouseButton::Button4,
+        "5" => MouseButton::Button5,
+        "other" => MouseButton::Other,
+        _ => return None,
+    };
+    Some(button)
+}
+
+fn parse_pair(field: &str) -> Option<(usize, usize)> {
+    let mut parts = field.splitn(2, ',');
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+fn parse_hex(field: &str) -> Option<Vec<u8>> {
+    if !field.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..field.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(field.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Escapes text so it is a single field without line breaks.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch == ' ' || ch == '%' || ch.is_ascii_control() {
+            let _ = write!(out, "%{:02x}", ch as u8);
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(field.len());
+    let mut rest = field.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = ::std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::{self, Write};
+    use std::path::PathBuf;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use super::{format_event, parse_line, Recorder, ReplaySource};
+    use event::{
+        Event, Key, KeyEventKind, Modifier, MouseButton, MouseEvent, QuitReason, UserEvent,
+    };
+
+    /// A writer whose bytes can be read back once the recorder has them.
+    #[derive(Clone, Default)]
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn key(key: Key, modifier: Modifier, repeat_count: u32, kind: KeyEventKind) -> Event {
+        Event::Key {
+            key,
+            modifier,
+            repeat_count,
+            kind,
+        }
+    }
+
+    fn mouse(pixel: Option<(usize, usize)>, event: MouseEvent, modifier: Modifier) -> Event {
+        Event::Mouse {
+            pos: (12, 3),
+            pixel,
+            event,
+            modifier,
+        }
+    }
+
+    /// One of every event that can be recorded, with awkward text.
+    fn events() -> Vec<Event> {
+        let mut events = vec![
+            Event::Refresh,
+            Event::Resize,
+            Event::Tick,
+            Event::Idle(Duration::from_millis(1500)),
+            key(Key::Char('a'), Modifier::None, 0, KeyEventKind::Press),
+            key(Key::Char(' '), Modifier::Ctrl, 0, KeyEventKind::Press),
+            key(Key::Char('%'), Modifier::Alt, 0, KeyEventKind::Press),
+            key(Key::Char('é'), Modifier::None, 3, KeyEventKind::Repeat),
+            key(
+                Key::Enter,
+                Modifier::Shift | Modifier::Ctrl,
+                0,
+                KeyEventKind::Release,
+            ),
+            key(Key::F12, Modifier::Keypad, 0, KeyEventKind::Press),
+            mouse(None, MouseEvent::Moved, Modifier::None),
+            mouse(Some((96, 50)), MouseEvent::WheelUp, Modifier::Shift),
+            mouse(None, MouseEvent::WheelDown, Modifier::None),
+            mouse(None, MouseEvent::WheelLeft, Modifier::None),
+            mouse(None, MouseEvent::WheelRight, Modifier::Alt),
+            Event::Paste(String::new()),
+            Event::Paste("two words\nand 100% a\ttab\r\n".to_owned()),
+            Event::Paste("%20 is not a space".to_owned()),
+            Event::Quit(QuitReason::Interrupt),
+            Event::Quit(QuitReason::Terminate),
+            Event::Quit(QuitReason::Hangup),
+            Event::FileChanged(PathBuf::from("/tmp/my notes.txt")),
+            Event::FileChanged(PathBuf::new()),
+            Event::Message {
+                source: "log tail".to_owned(),
+                text: "line one".to_owned(),
+            },
+            Event::Message {
+                source: String::new(),
+                text: String::new(),
+            },
+            Event::Unknown(vec![0x1B, b'[', 0xFF]),
+            Event::Unknown(Vec::new()),
+        ];
+        let buttons = [
+            MouseButton::Left,
+            MouseButton::Middle,
+            MouseButton::Right,
+            MouseButton::Button4,
+            MouseButton::Button5,
+            MouseButton::Other,
+        ];
+        for &button in &buttons {
+            events.push(mouse(None, MouseEvent::Press(button), Modifier::None));
+            events.push(mouse(None, MouseEvent::Release(button), Modifier::Ctrl));
+            events.push(mouse(
+                Some((1, 2)),
+                MouseEvent::Hold(button),
+                Modifier::None,
+            ));
+        }
+        events
+    }
+
+    #[test]
+    fn every_event_round_trips() {
+        for (i, event) in events().into_iter().enumerate() {
+            let line = format!("{} {}", i, format_event(&event));
+            assert!(!line.contains('\n'), "{:?} written over lines", event);
+            assert_eq!(
+                parse_line(&line),
+                Some((Duration::from_millis(i as u64), event)),
+                "parsing {:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn recording_replays() {
+        let out = Shared::default();
+        let mut recorder = Recorder::new(out.clone()).unwrap();
+        let events = events();
+        for (i, event) in events.iter().enumerate() {
+            recorder
+                .record_at(event, Duration::from_millis(10 * i as u64))
+                .unwrap();
+        }
+        // User events can't be written, so are left out.
+        recorder.record(&Event::User(UserEvent::new(1))).unwrap();
+        drop(recorder);
+
+        let bytes = out.0.borrow().clone();
+        let mut replay = ReplaySource::new(&bytes[..]);
+        for (i, event) in events.into_iter().enumerate() {
+            assert_eq!(
+                replay.read_next(),
+                Some((Duration::from_millis(10 * i as u64), event))
+            );
+        }
+        assert_eq!(replay.read_next(), None);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(parse_line(""), None);
+        assert_eq!(parse_line("10"), None);
+        assert_eq!(parse_line("x tick"), None);
+        assert_eq!(parse_line("10 user"), None);
+        assert_eq!(parse_line("10 tick extra"), None);
+        assert_eq!(parse_line("10 paste %2"), None);
+        assert_eq!(parse_line("10 unknown abc"), None);
+        assert_eq!(parse_line("10 quit sleepy"), None);
+    }
+}
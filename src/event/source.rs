@@ -0,0 +1,161 @@
+use std::io::{self, ErrorKind, Read};
+use std::mem;
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+
+use event::Event;
+
+/// The most bytes read from a source in one poll, so a busy feed can't hold
+/// up terminal input.
+const READ_LIMIT: usize = 64 * 1024;
+
+/// Represents a source of events polled alongside terminal input, added with
+/// [`Window::add_source`](../window/struct.Window.html#method.add_source).
+///
+/// Sources are polled each time the window polls for events, and their
+/// events are delivered after terminal input, like those sent by handles.
+pub trait EventSource {
+    /// Takes the events that are ready, without blocking.
+    fn poll(&mut self) -> Vec<Event>;
+
+    /// Checks whether the source is finished, so it can be dropped.
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+/// A stream that can be read without blocking.
+pub trait NonBlocking: Read {
+    /// Moves the stream in or out of non-blocking mode.
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()>;
+}
+
+impl NonBlocking for TcpStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        TcpStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+#[cfg(unix)]
+impl NonBlocking for UnixStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        UnixStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+/// An event source reading lines from a stream, such as a socket, giving an
+/// [`Event::Message`](enum.Event.html#variant.Message) for each line.
+///
+/// *Lines are decoded as UTF-8 lossily, without their line endings. The
+/// source closes when the stream ends or fails, after giving any final line
+/// without an ending.*
+#[derive(Debug)]
+pub struct LineSource<S> {
+    stream: S,
+    name: String,
+    pending: Vec<u8>,
+    closed: bool,
+}
+
+impl<S: NonBlocking> LineSource<S> {
+    /// Creates a source reading lines from the stream, tagging its messages
+    /// with the name.
+    pub fn new<N: Into<String>>(stream: S, name: N) -> io::Result<LineSource<S>> {
+        stream.set_nonblocking(true)?;
+        Ok(LineSource {
+            stream,
+            name: name.into(),
+            pending: Vec::new(),
+            closed: false,
+        })
+    }
+
+    /// Gets the name the messages are tagged with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the stream lines are read from.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    fn message(&self, line: &[u8]) -> Event {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        Event::Message {
+            source: self.name.clone(),
+            text: String::from_utf8_lossy(line).into_owned(),
+        }
+    }
+}
+
+impl LineSource<TcpStream> {
+    /// Connects to a TCP server, reading the lines it sends.
+    pub fn tcp<A: ToSocketAddrs, N: Into<String>>(
+        addr: A,
+        name: N,
+    ) -> io::Result<LineSource<TcpStream>> {
+        LineSource::new(TcpStream::connect(addr)?, name)
+    }
+}
+
+#[cfg(unix)]
+impl LineSource<UnixStream> {
+    /// Connects to a Unix socket, reading the lines it sends.
+    pub fn unix<P: AsRef<Path>, N: Into<String>>(
+        path: P,
+        name: N,
+    ) -> io::Result<LineSource<UnixStream>> {
+        LineSource::new(UnixStream::connect(path)?, name)
+    }
+}
+
+impl<S: NonBlocking> EventSource for LineSource<S> {
+    fn poll(&mut self) -> Vec<Event> {
+        if self.closed {
+            return Vec::new();
+        }
+
+        let mut buf = [0; 4096];
+        let mut read = 0;
+        while read < READ_LIMIT {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    self.closed = true;
+                    break;
+                }
+                Ok(n) => {
+                    self.pending.extend_from_slice(&buf[..n]);
+                    read += n;
+                }
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    warn!("closing event source {}: {}", self.name, err);
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        let mut start = 0;
+        while let Some(end) = self.pending[start..].iter().position(|&b| b == b'\n') {
+            events.push(self.message(&self.pending[start..start + end]));
+            start += end + 1;
+        }
+        self.pending.drain(..start);
+        if self.closed && !self.pending.is_empty() {
+            let rest = mem::take(&mut self.pending);
+            events.push(self.message(&rest));
+        }
+        events
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
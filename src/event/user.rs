@@ -0,0 +1,56 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// A message defined by the application, carried by an
+/// [`Event::User`](enum.Event.html#variant.User), such as the result of work
+/// done on another thread.
+///
+/// ```no_run
+/// use termui::event::{Event, UserEvent};
+///
+/// struct Downloaded(usize);
+///
+/// # let ev = Event::User(UserEvent::new(Downloaded(3)));
+/// if let Event::User(ref user) = ev {
+///     if let Some(&Downloaded(bytes)) = user.downcast_ref::<Downloaded>() {
+///         println!("downloaded {} bytes", bytes);
+///     }
+/// }
+/// ```
+///
+/// *The message is shared between clones of the event, and two user events
+/// are only equal if they share the same message.*
+#[derive(Clone)]
+pub struct UserEvent(Arc<dyn Any + Send + Sync>);
+
+impl UserEvent {
+    /// Wraps a message.
+    pub fn new<T: Any + Send + Sync>(message: T) -> UserEvent {
+        UserEvent(Arc::new(message))
+    }
+
+    /// Checks whether the message is of the given type.
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+
+    /// Gets the message, if it is of the given type.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl PartialEq for UserEvent {
+    fn eq(&self, other: &UserEvent) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for UserEvent {}
+
+impl fmt::Debug for UserEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("UserEvent(..)")
+    }
+}
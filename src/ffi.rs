@@ -0,0 +1,41 @@
+//! Raw curses functions not wrapped by pancurses.
+
+use std::os::raw::{c_char, c_int, c_void};
+
+/// An opaque curses window pointer.
+pub type WindowPtr = *mut c_void;
+
+extern "C" {
+    pub static curscr: WindowPtr;
+
+    pub fn slk_init(fmt: c_int) -> c_int;
+    pub fn slk_set(labnum: c_int, label: *const c_char, fmt: c_int) -> c_int;
+    pub fn slk_label(labnum: c_int) -> *mut c_char;
+    pub fn slk_refresh() -> c_int;
+    pub fn slk_noutrefresh() -> c_int;
+    pub fn slk_clear() -> c_int;
+    pub fn slk_restore() -> c_int;
+    pub fn slk_touch() -> c_int;
+
+    pub fn ripoffline(line: c_int, init: extern "C" fn(WindowPtr, c_int) -> c_int) -> c_int;
+
+    pub fn isendwin() -> bool;
+    pub fn key_defined(definition: *const c_char) -> c_int;
+    pub fn tigetstr(capname: *const c_char) -> *mut c_char;
+
+    pub fn newwin(nlines: c_int, ncols: c_int, begin_y: c_int, begin_x: c_int) -> WindowPtr;
+    pub fn delwin(win: WindowPtr) -> c_int;
+    pub fn untouchwin(win: WindowPtr) -> c_int;
+    pub fn keypad(win: WindowPtr, bf: bool) -> c_int;
+    pub fn nodelay(win: WindowPtr, bf: bool) -> c_int;
+    pub fn wtimeout(win: WindowPtr, delay: c_int);
+    pub fn wgetch(win: WindowPtr) -> c_int;
+    pub fn ungetch(ch: c_int) -> c_int;
+    pub fn define_key(definition: *const c_char, keycode: c_int) -> c_int;
+
+    pub fn getmaxx(win: WindowPtr) -> c_int;
+    pub fn werase(win: WindowPtr) -> c_int;
+    pub fn wnoutrefresh(win: WindowPtr) -> c_int;
+    pub fn wredrawln(win: WindowPtr, beg_line: c_int, num_lines: c_int) -> c_int;
+    pub fn mvwaddnstr(win: WindowPtr, y: c_int, x: c_int, s: *const c_char, n: c_int) -> c_int;
+}
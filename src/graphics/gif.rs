@@ -0,0 +1,402 @@
+use std::time::Duration;
+
+use graphics::{Bitmap, Frame};
+use style::Rgb;
+
+/// The largest code used by GIF LZW compression.
+const MAX_CODES: usize = 4096;
+
+/// Reads structured data from a GIF file.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn u8(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.pos)?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        let lo = self.u8()?;
+        let hi = self.u8()?;
+        Some(u16::from(lo) | u16::from(hi) << 8)
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn color_table(&mut self, packed: u8) -> Option<Vec<Rgb>> {
+        let len = 2 << (packed & 0x07);
+        let bytes = self.bytes(len * 3)?;
+        Some(
+            bytes
+                .chunks(3)
+                .map(|c| Rgb::new(c[0], c[1], c[2]))
+                .collect(),
+        )
+    }
+
+    /// Reads a sequence of data sub-blocks into a single buffer.
+    fn sub_blocks(&mut self) -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        loop {
+            let len = usize::from(self.u8()?);
+            if len == 0 {
+                return Some(out);
+            }
+            out.extend_from_slice(self.bytes(len)?);
+        }
+    }
+}
+
+/// Decompresses GIF LZW data into color indices.
+fn lzw_decode(min_size: u8, data: &[u8], len: usize) -> Option<Vec<u8>> {
+    if min_size == 0 || min_size > 11 {
+        return None;
+    }
+
+    let clear = 1usize << min_size;
+    let end = clear + 1;
+    let mut prefix = vec![0u16; MAX_CODES];
+    let mut suffix = vec![0u8; MAX_CODES];
+    for (i, s) in suffix.iter_mut().enumerate().take(clear) {
+        *s = i as u8;
+    }
+
+    let mut size = min_size + 1;
+    let mut next = clear + 2;
+    let mut prev: Option<usize> = None;
+    let (mut bits, mut nbits) = (0u32, 0u8);
+    let mut input = data.iter();
+    let mut out = Vec::with_capacity(len);
+    let mut stack = Vec::new();
+
+    while out.len() < len {
+        while nbits < size {
+            match input.next() {
+                Some(&byte) => {
+                    bits |= u32::from(byte) << nbits;
+                    nbits += 8;
+                }
+                None => return Some(out),
+            }
+        }
+        let code = (bits & ((1 << size) - 1)) as usize;
+        bits >>= size;
+        nbits -= size;
+
+        if code == clear {
+            size = min_size + 1;
+            next = clear + 2;
+            prev = None;
+            continue;
+        } else if code == end {
+            break;
+        }
+
+        let p = match prev {
+            Some(p) => p,
+            None if code < clear => {
+                out.push(code as u8);
+                prev = Some(code);
+                continue;
+            }
+            None => return None,
+        };
+
+        // A code one past the table repeats the previous string, followed by
+        // its own first byte.
+        let mut c = match code {
+            _ if code < next => code,
+            _ if code == next => p,
+            _ => return None,
+        };
+        stack.clear();
+        while c >= clear {
+            stack.push(suffix[c]);
+            c = usize::from(prefix[c]);
+        }
+        let head = c as u8;
+        stack.push(head);
+        out.extend(stack.iter().rev());
+        if code == next {
+            out.push(head);
+        }
+
+        if next < MAX_CODES {
+            prefix[next] = p as u16;
+            suffix[next] = head;
+            next += 1;
+            if next == 1 << size && size < 12 {
+                size += 1;
+            }
+        }
+        prev = Some(code);
+    }
+
+    out.truncate(len);
+    Some(out)
+}
+
+/// Gets the order in which the rows of an image are stored.
+fn row_order(height: usize, interlaced: bool) -> Vec<usize> {
+    if !interlaced {
+        return (0..height).collect();
+    }
+    [(0, 8), (4, 8), (2, 4), (1, 2)]
+        .iter()
+        .flat_map(|&(start, step)| (start..height).step_by(step))
+        .collect()
+}
+
+/// Decodes the frames of a GIF image.
+///
+/// Each frame is composed onto the frames before it, following the disposal
+/// method of the previous frame, so frames can be displayed on their own.
+///
+/// Gives `None` if the data is not a valid GIF image.
+pub fn decode_gif(data: &[u8]) -> Option<Vec<Frame>> {
+    let mut reader = Reader { data, pos: 0 };
+    match reader.bytes(6)? {
+        b"GIF87a" | b"GIF89a" => {}
+        _ => return None,
+    }
+
+    let width = usize::from(reader.u16()?);
+    let height = usize::from(reader.u16()?);
+    let packed = reader.u8()?;
+    reader.bytes(2)?;
+    let global = if packed & 0x80 != 0 {
+        Some(reader.color_table(packed)?)
+    } else {
+        None
+    };
+
+    let mut canvas = Bitmap::new(width, height);
+    let mut frames = Vec::new();
+    let mut delay = Duration::from_millis(0);
+    let mut transparent = None;
+    let mut disposal = 0;
+
+    loop {
+        match reader.u8()? {
+            // Extension.
+            0x21 => {
+                let label = reader.u8()?;
+                let block = reader.sub_blocks()?;
+                if label == 0xF9 && block.len() >= 4 {
+                    disposal = (block[0] >> 2) & 0x07;
+                    let centis = u64::from(block[1]) | u64::from(block[2]) << 8;
+                    delay = Duration::from_millis(centis * 10);
+                    transparent = if block[0] & 0x01 != 0 {
+                        Some(block[3])
+                    } else {
+                        None
+                    };
+                }
+            }
+            // Image descriptor.
+            0x2C => {
+                let left = usize::from(reader.u16()?);
+                let top = usize::from(reader.u16()?);
+                let w = usize::from(reader.u16()?);
+                let h = usize::from(reader.u16()?);
+                let packed = reader.u8()?;
+                let local = if packed & 0x80 != 0 {
+                    Some(reader.color_table(packed)?)
+                } else {
+                    None
+                };
+                let table = local.as_ref().or(global.as_ref())?;
+                let min_size = reader.u8()?;
+                let indices = lzw_decode(min_size, &reader.sub_blocks()?, w * h)?;
+
+                let previous = if disposal == 3 {
+                    Some(canvas.clone())
+                } else {
+                    None
+                };
+                let rows = row_order(h, packed & 0x40 != 0);
+                for (i, &index) in indices.iter().enumerate() {
+                    if Some(index) == transparent {
+                        continue;
+                    }
+                    if let Some(&rgb) = table.get(usize::from(index)) {
+                        canvas.set(left + i % w, top + rows[i / w], Some(rgb));
+                    }
+                }
+                frames.push(Frame {
+                    bitmap: canvas.clone(),
+                    delay,
+                });
+
+                match disposal {
+                    2 => {
+                        for y in top..top + h {
+                            for x in left..left + w {
+                                canvas.set(x, y, None);
+                            }
+                        }
+                    }
+                    3 => canvas = previous.unwrap_or(canvas),
+                    _ => {}
+                }
+                delay = Duration::from_millis(0);
+                transparent = None;
+                disposal = 0;
+            }
+            // Trailer.
+            0x3B => break,
+            _ => return None,
+        }
+    }
+
+    if frames.is_empty() {
+        None
+    } else {
+        Some(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_gif, lzw_decode};
+    use style::Rgb;
+
+    /// Packs LZW codes, each given with its size in bits, least significant
+    /// bit first.
+    fn pack(codes: &[(u16, u8)]) -> Vec<u8> {
+        let (mut bits, mut nbits) = (0u32, 0u8);
+        let mut out = Vec::new();
+        for &(code, size) in codes {
+            bits |= u32::from(code) << nbits;
+            nbits += size;
+            while nbits >= 8 {
+                out.push(bits as u8);
+                bits >>= 8;
+                nbits -= 8;
+            }
+        }
+        if nbits > 0 {
+            out.push(bits as u8);
+        }
+        out
+    }
+
+    /// Builds a single frame GIF image with an 8 color global table, where
+    /// color `i` is `Rgb::new(i, 0, 0)`.
+    fn gif(width: u8, height: u8, interlaced: bool, data: &[u8]) -> Vec<u8> {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&[width, 0, height, 0, 0x82, 0, 0]);
+        for i in 0..8 {
+            gif.extend_from_slice(&[i, 0, 0]);
+        }
+        gif.extend_from_slice(&[0x2C, 0, 0, 0, 0, width, 0, height, 0]);
+        gif.push(if interlaced { 0x40 } else { 0 });
+        gif.push(3);
+        for block in data.chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend_from_slice(block);
+        }
+        gif.extend_from_slice(&[0, 0x3B]);
+        gif
+    }
+
+    #[test]
+    fn decodes_literals() {
+        let data = pack(&[(4, 3), (1, 3), (3, 3), (2, 3), (5, 3)]);
+        assert_eq!(lzw_decode(2, &data, 3), Some(vec![1, 3, 2]));
+    }
+
+    #[test]
+    fn grows_code_size() {
+        // The table is full at 8 entries, so codes after the fourth are
+        // 4 bits wide.
+        let data = pack(&[(4, 3), (0, 3), (1, 3), (6, 3), (7, 4), (5, 4)]);
+        assert_eq!(lzw_decode(2, &data, 6), Some(vec![0, 1, 0, 1, 1, 0]));
+    }
+
+    #[test]
+    fn decodes_code_not_yet_in_table() {
+        // Code 6 is the next entry to be added, so it is the previous
+        // string followed by its own first byte.
+        let data = pack(&[(4, 3), (1, 3), (6, 3), (5, 3)]);
+        assert_eq!(lzw_decode(2, &data, 3), Some(vec![1, 1, 1]));
+
+        let data = pack(&[(4, 3), (1, 3), (2, 3), (7, 3), (5, 3)]);
+        assert_eq!(lzw_decode(2, &data, 4), Some(vec![1, 2, 2, 2]));
+    }
+
+    #[test]
+    fn clear_resets_table() {
+        let data = pack(&[
+            (4, 3),
+            (0, 3),
+            (1, 3),
+            (6, 3),
+            (7, 4),
+            // The clear code is given at the grown size.
+            (4, 4),
+            (2, 3),
+            (2, 3),
+            // Code 6 now refers to the new table.
+            (6, 3),
+            (5, 4),
+        ]);
+        assert_eq!(
+            lzw_decode(2, &data, 10),
+            Some(vec![0, 1, 0, 1, 1, 0, 2, 2, 2, 2])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        // Code 7 is past the next entry to be added.
+        let data = pack(&[(4, 3), (1, 3), (7, 3), (5, 3)]);
+        assert_eq!(lzw_decode(2, &data, 3), None);
+        assert_eq!(lzw_decode(0, &data, 3), None);
+        assert_eq!(lzw_decode(12, &data, 3), None);
+    }
+
+    #[test]
+    fn stops_at_length() {
+        let data = pack(&[(4, 3), (1, 3), (2, 3), (6, 3), (5, 3)]);
+        assert_eq!(lzw_decode(2, &data, 2), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn decodes_interlaced_rows() {
+        // Each row of a 1x8 image has the color of the order it is stored in.
+        let data = pack(&[
+            (8, 4),
+            (0, 4),
+            (1, 4),
+            (2, 4),
+            (3, 4),
+            (4, 4),
+            (5, 4),
+            (6, 4),
+            (7, 5),
+            (9, 5),
+        ]);
+        let frames = decode_gif(&gif(1, 8, false, &data)).unwrap();
+        assert_eq!(frames.len(), 1);
+        let rows: Vec<_> = (0..8).map(|y| frames[0].bitmap.get(0, y)).collect();
+        let expected: Vec<_> = (0..8).map(|i| Some(Rgb::new(i, 0, 0))).collect();
+        assert_eq!(rows, expected);
+
+        let frames = decode_gif(&gif(1, 8, true, &data)).unwrap();
+        let rows: Vec<_> = (0..8).map(|y| frames[0].bitmap.get(0, y)).collect();
+        let expected: Vec<_> = [0, 4, 2, 5, 1, 6, 3, 7]
+            .iter()
+            .map(|&i| Some(Rgb::new(i, 0, 0)))
+            .collect();
+        assert_eq!(rows, expected);
+    }
+}
@@ -0,0 +1,128 @@
+//! Graphics module.
+//!
+//! Provides bitmaps and the encoders for terminal graphics protocols.
+
+mod gif;
+mod sixel;
+
+pub use self::gif::decode_gif;
+pub use self::sixel::encode_sixel;
+
+use std::time::Duration;
+
+use style::Rgb;
+
+/// Represents an image as a grid of pixels.
+///
+/// *Pixels set to `None` are transparent.*
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Option<Rgb>>,
+}
+
+impl Bitmap {
+    /// Creates a transparent bitmap of the given size.
+    pub fn new(width: usize, height: usize) -> Bitmap {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![None; width * height],
+        }
+    }
+
+    /// Creates a bitmap from rows of RGBA bytes.
+    ///
+    /// Gives `None` if the buffer does not hold exactly `width * height`
+    /// pixels.
+    ///
+    /// *Pixels with an alpha below 128 are treated as transparent.*
+    pub fn from_rgba(width: usize, height: usize, rgba: &[u8]) -> Option<Bitmap> {
+        if rgba.len() != width * height * 4 {
+            return None;
+        }
+
+        let pixels = rgba
+            .chunks(4)
+            .map(|px| {
+                if px[3] < 128 {
+                    None
+                } else {
+                    Some(Rgb::new(px[0], px[1], px[2]))
+                }
+            })
+            .collect();
+        Some(Bitmap {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Gets the width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the pixel at the given position.
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x]
+        } else {
+            None
+        }
+    }
+
+    /// Sets the pixel at the given position.
+    ///
+    /// *Positions outside the bitmap are ignored.*
+    pub fn set(&mut self, x: usize, y: usize, pixel: Option<Rgb>) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = pixel;
+        }
+    }
+
+    /// Scales the bitmap to fit within the given size, keeping its aspect
+    /// ratio.
+    ///
+    /// The result has exactly the given size, with the image centered and
+    /// the remaining space left transparent.
+    pub fn letterbox(&self, width: usize, height: usize) -> Bitmap {
+        let mut out = Bitmap::new(width, height);
+        if self.width == 0 || self.height == 0 || width == 0 || height == 0 {
+            return out;
+        }
+
+        // Pick the largest scale that fits both dimensions.
+        let (w, h) = if width * self.height <= height * self.width {
+            (width, (self.height * width / self.width).max(1))
+        } else {
+            ((self.width * height / self.height).max(1), height)
+        };
+        let (left, top) = ((width - w) / 2, (height - h) / 2);
+
+        for y in 0..h {
+            let src_y = y * self.height / h;
+            for x in 0..w {
+                let src_x = x * self.width / w;
+                out.set(left + x, top + y, self.get(src_x, src_y));
+            }
+        }
+        out
+    }
+}
+
+/// Represents a single frame of an animation.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Frame {
+    /// The image shown during the frame.
+    pub bitmap: Bitmap,
+    /// How long the frame is shown for.
+    pub delay: Duration,
+}
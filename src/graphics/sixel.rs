@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use graphics::Bitmap;
+use style::Rgb;
+
+/// The number of levels per channel in the sixel palette.
+const LEVELS: u8 = 6;
+
+/// Maps a color onto the index of the nearest palette entry.
+fn palette_index(rgb: Rgb) -> usize {
+    let level = |c: u8| usize::from((u16::from(c) * u16::from(LEVELS - 1) + 127) / 255);
+    let n = usize::from(LEVELS);
+    (level(rgb.r) * n + level(rgb.g)) * n + level(rgb.b)
+}
+
+/// Writes a run of identical sixels, compressing it if worthwhile.
+fn push_run(out: &mut String, sixel: u8, count: usize) {
+    let ch = char::from(63 + sixel);
+    if count > 3 {
+        let _ = write!(out, "!{}{}", count, ch);
+    } else {
+        for _ in 0..count {
+            out.push(ch);
+        }
+    }
+}
+
+/// Encodes the bitmap as a sixel image.
+///
+/// Colors are quantized onto a 216 color cube, and transparent pixels leave
+/// the terminal background visible.
+pub fn encode_sixel(bitmap: &Bitmap) -> String {
+    let (width, height) = (bitmap.width(), bitmap.height());
+    let n = usize::from(LEVELS);
+    let colors = n * n * n;
+
+    // `P2 = 1` keeps pixels that are never drawn transparent.
+    let mut out = String::from("\x1BP0;1;0q");
+    let _ = write!(out, "\"1;1;{};{}", width, height);
+    for i in 0..colors {
+        let pct = |level: usize| level * 100 / (n - 1);
+        let _ = write!(
+            out,
+            "#{};2;{};{};{}",
+            i,
+            pct(i / (n * n)),
+            pct(i / n % n),
+            pct(i % n)
+        );
+    }
+
+    let mut band = vec![0u8; colors * width];
+    let mut used = vec![false; colors];
+    for top in (0..height).step_by(6) {
+        for b in band.iter_mut() {
+            *b = 0;
+        }
+        for u in used.iter_mut() {
+            *u = false;
+        }
+
+        for dy in 0..6.min(height - top) {
+            for x in 0..width {
+                if let Some(rgb) = bitmap.get(x, top + dy) {
+                    let index = palette_index(rgb);
+                    band[index * width + x] |= 1 << dy;
+                    used[index] = true;
+                }
+            }
+        }
+
+        let mut first = true;
+        for index in (0..colors).filter(|&i| used[i]) {
+            if !first {
+                out.push('$');
+            }
+            first = false;
+
+            let _ = write!(out, "#{}", index);
+            let row = &band[index * width..(index + 1) * width];
+            let mut x = 0;
+            while x < width {
+                let sixel = row[x];
+                let count = row[x..].iter().take_while(|&&s| s == sixel).count();
+                // Empty sixels at the end of a row need not be sent.
+                if sixel != 0 || x + count < width {
+                    push_run(&mut out, sixel, count);
+                }
+                x += count;
+            }
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1B\\");
+    out
+}
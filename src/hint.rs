@@ -0,0 +1,199 @@
+//! Hint module.
+//!
+//! Provides a keyboard driven way of clicking on regions, by overlaying short
+//! labels on every region of a [`HitMap`](../layout/struct.HitMap.html).
+
+use backend::Backend;
+use event::{Event, Key, Modifier, MouseButton, MouseEvent};
+use layout::{HitMap, Rect};
+use style::{Attr, Style};
+use window::Window;
+
+/// The default characters used to build labels.
+pub const DEFAULT_ALPHABET: &str = "asdfghjkl";
+
+/// The result of passing an event to the hint mode.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum HintEvent<T> {
+    /// The hint mode is inactive, the event should be handled normally.
+    Ignored,
+    /// The event was consumed by the hint mode.
+    Consumed,
+    /// A label was selected, giving the region id and a synthesized click.
+    Click(T, [Event; 2]),
+}
+
+/// A link hint mode for clicking regions without a mouse.
+#[derive(Clone, Debug)]
+pub struct HintMode<T> {
+    alphabet: Vec<char>,
+    hints: Vec<(String, Rect, T)>,
+    typed: String,
+    active: bool,
+}
+
+impl<T: Copy> HintMode<T> {
+    /// Creates a new inactive hint mode using the default alphabet.
+    pub fn new() -> HintMode<T> {
+        HintMode::with_alphabet(DEFAULT_ALPHABET)
+    }
+
+    /// Creates a new inactive hint mode using the characters of `alphabet`
+    /// for labels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alphabet has fewer than 2 characters.
+    pub fn with_alphabet(alphabet: &str) -> HintMode<T> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        assert!(alphabet.len() >= 2, "hint alphabet too small");
+
+        HintMode {
+            alphabet,
+            hints: Vec::new(),
+            typed: String::new(),
+            active: false,
+        }
+    }
+
+    /// Checks whether the hint mode is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Activates the hint mode, labelling every region of the hit map.
+    pub fn activate(&mut self, map: &HitMap<T>) {
+        let regions = map.regions();
+
+        let mut len = 1;
+        let mut capacity = self.alphabet.len();
+        while capacity < regions.len() {
+            len += 1;
+            capacity *= self.alphabet.len();
+        }
+
+        self.hints = regions
+            .iter()
+            .enumerate()
+            .map(|(i, &(rect, id))| (self.label(i, len), rect, id))
+            .collect();
+        self.typed.clear();
+        self.active = !self.hints.is_empty();
+    }
+
+    /// Deactivates the hint mode.
+    pub fn deactivate(&mut self) {
+        self.hints.clear();
+        self.typed.clear();
+        self.active = false;
+    }
+
+    /// Handles an event, consuming key presses while the hint mode is active.
+    ///
+    /// *Escape cancels the hint mode and Backspace removes the last typed
+    /// character.*
+    pub fn handle_event(&mut self, event: &Event) -> HintEvent<T> {
+        if !self.active {
+            return HintEvent::Ignored;
+        }
+        if event.is_key_release() {
+            return HintEvent::Consumed;
+        }
+
+        let ch = match *event {
+            Event::Key {
+                key: Key::Char(ch),
+                modifier,
+                ..
+            } if modifier == Modifier::None => ch,
+            Event::Key {
+                key: Key::Backspace,
+                ..
+            } => {
+                self.typed.pop();
+                return HintEvent::Consumed;
+            }
+            Event::Key {
+                key: Key::Escape, ..
+            } => {
+                self.deactivate();
+                return HintEvent::Consumed;
+            }
+            Event::Key { .. } => return HintEvent::Consumed,
+            _ => return HintEvent::Ignored,
+        };
+
+        self.typed.push(ch);
+        if !self
+            .hints
+            .iter()
+            .any(|hint| hint.0.starts_with(&self.typed))
+        {
+            self.typed.pop();
+            return HintEvent::Consumed;
+        }
+
+        let selected = self
+            .hints
+            .iter()
+            .find(|hint| hint.0 == self.typed)
+            .map(|&(_, rect, id)| (rect, id));
+
+        match selected {
+            Some((rect, id)) => {
+                self.deactivate();
+
+                let pos = (rect.x, rect.y);
+                let click = |event| Event::Mouse {
+                    pos,
+                    pixel: None,
+                    event,
+                    modifier: Modifier::None,
+                };
+                HintEvent::Click(
+                    id,
+                    [
+                        click(MouseEvent::Press(MouseButton::Left)),
+                        click(MouseEvent::Release(MouseButton::Left)),
+                    ],
+                )
+            }
+            None => HintEvent::Consumed,
+        }
+    }
+
+    /// Renders the labels matching the typed prefix over their regions.
+    pub fn render<B: Backend>(&self, window: &Window<B>) {
+        if !self.active {
+            return;
+        }
+
+        for &(ref label, rect, _) in &self.hints {
+            if label.starts_with(&self.typed) {
+                window.print_styled(
+                    rect.y,
+                    rect.x,
+                    &label[self.typed.len()..],
+                    Style::new().attr(Attr::Reverse),
+                );
+            }
+        }
+    }
+
+    fn label(&self, mut index: usize, len: usize) -> String {
+        let base = self.alphabet.len();
+
+        let mut label = vec![self.alphabet[0]; len];
+        for ch in label.iter_mut().rev() {
+            *ch = self.alphabet[index % base];
+            index /= base;
+        }
+        label.into_iter().collect()
+    }
+}
+
+impl<T: Copy> Default for HintMode<T> {
+    fn default() -> HintMode<T> {
+        HintMode::new()
+    }
+}
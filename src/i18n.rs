@@ -0,0 +1,250 @@
+//! Localization module.
+//!
+//! Every user-visible string rendered by the crate itself is looked up through
+//! a [`Localizer`](trait.Localizer.html), falling back to English.
+
+use std::borrow::Cow;
+use std::env;
+use std::ffi::CStr;
+
+use libc;
+
+/// Represents a user-visible string rendered by the crate.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Message {
+    /// The label of a button accepting a dialog.
+    Ok,
+    /// The label of a button dismissing a dialog.
+    Cancel,
+    /// The label of a button answering yes.
+    Yes,
+    /// The label of a button answering no.
+    No,
+    /// Shown when the terminal is too small to render the interface.
+    TerminalTooSmall,
+    /// Shown before the current page number, as in "Page 3 of 12".
+    Page,
+    /// Shown between the current page number and the number of pages.
+    PageOf,
+    /// The title of a locked screen.
+    Locked,
+    /// Shown on a locked screen unlocked by any key.
+    PressAnyKey,
+    /// Shown before the passphrase typed into a locked screen.
+    Passphrase,
+    /// Shown when the passphrase typed into a locked screen is refused.
+    WrongPassphrase,
+    /// The title of the dialog confirming a quit, and the label of its button
+    /// quitting.
+    Quit,
+    /// Shown before the names of what has unsaved changes when confirming a
+    /// quit.
+    UnsavedChanges,
+}
+
+impl Message {
+    /// Gets the English text of the message.
+    pub fn english(self) -> &'static str {
+        match self {
+            Message::Ok => "OK",
+            Message::Cancel => "Cancel",
+            Message::Yes => "Yes",
+            Message::No => "No",
+            Message::TerminalTooSmall => "Terminal too small",
+            Message::Page => "Page",
+            Message::PageOf => "of",
+            Message::Locked => "Locked",
+            Message::PressAnyKey => "Press any key to unlock",
+            Message::Passphrase => "Passphrase:",
+            Message::WrongPassphrase => "Wrong passphrase",
+            Message::Quit => "Quit",
+            Message::UnsavedChanges => "Unsaved changes will be lost:",
+        }
+    }
+}
+
+/// Translates the strings rendered by the crate.
+pub trait Localizer {
+    /// Gets the translation of a message, or `None` to use the English text.
+    fn localize(&self, message: Message) -> Option<Cow<'static, str>>;
+}
+
+/// The default localizer, using the English text for every message.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct English;
+
+impl Localizer for English {
+    fn localize(&self, message: Message) -> Option<Cow<'static, str>> {
+        Some(Cow::Borrowed(message.english()))
+    }
+}
+
+/// Represents how numbers are written, such as `1,234.5` or `1.234,5`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct NumberFormat {
+    /// The character between the whole and fractional parts.
+    pub decimal: char,
+    /// The character between groups of three digits of the whole part, if
+    /// they are grouped.
+    pub grouping: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> NumberFormat {
+        NumberFormat {
+            decimal: '.',
+            grouping: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Gets the format of the numeric locale.
+    ///
+    /// *The locale is only set up once a window is created, so before then
+    /// this is the `C` locale, with a `.` and no grouping.*
+    pub fn locale() -> NumberFormat {
+        let conv = unsafe { libc::localeconv() };
+        if conv.is_null() {
+            return NumberFormat::default();
+        }
+        let first = |ptr: *const libc::c_char| {
+            if ptr.is_null() {
+                None
+            } else {
+                unsafe { CStr::from_ptr(ptr) }
+                    .to_str()
+                    .ok()
+                    .and_then(|s| s.chars().next())
+            }
+        };
+        let conv = unsafe { &*conv };
+        NumberFormat {
+            decimal: first(conv.decimal_point).unwrap_or('.'),
+            grouping: first(conv.thousands_sep),
+        }
+    }
+
+    /// Formats a number with the given number of decimal places.
+    pub fn format(&self, value: f64, decimals: usize) -> String {
+        let digits = format!("{:.*}", decimals, value.abs());
+        let (whole, fraction) = match digits.find('.') {
+            Some(i) => (&digits[..i], Some(&digits[i + 1..])),
+            None => (&digits[..], None),
+        };
+
+        let mut out = String::with_capacity(digits.len() + whole.len() / 3 + 1);
+        if value.is_sign_negative() && digits.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            out.push('-');
+        }
+        for (i, ch) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i).is_multiple_of(3) {
+                if let Some(grouping) = self.grouping {
+                    out.push(grouping);
+                }
+            }
+            out.push(ch);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.decimal);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// Parses a number written in the format, with or without grouping.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let text: String = text
+            .trim()
+            .chars()
+            .filter(|&ch| Some(ch) != self.grouping)
+            .map(|ch| if ch == self.decimal { '.' } else { ch })
+            .collect();
+        text.parse().ok().filter(|value: &f64| value.is_finite())
+    }
+}
+
+/// Represents the character encoding of the locale.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Encoding {
+    /// UTF-8.
+    Utf8,
+    /// ISO-8859-1.
+    Latin1,
+    /// 7-bit ASCII.
+    Ascii,
+    /// Another encoding, with the name reported by the locale.
+    Other(String),
+}
+
+impl Encoding {
+    /// Parses an encoding from its codeset name.
+    pub fn from_name(name: &str) -> Encoding {
+        let normalized: String = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        match &*normalized {
+            "utf8" => Encoding::Utf8,
+            "iso88591" | "latin1" => Encoding::Latin1,
+            "ansix341968" | "ascii" | "usascii" | "646" | "c" | "posix" => Encoding::Ascii,
+            _ => Encoding::Other(name.to_owned()),
+        }
+    }
+
+    /// Checks whether UTF-8 output is safe in this encoding.
+    pub fn is_utf8(&self) -> bool {
+        *self == Encoding::Utf8
+    }
+}
+
+/// Initializes the locale from the environment and detects its encoding.
+///
+/// *Must be called before curses is initialized.*
+pub(crate) fn init_locale() -> Encoding {
+    unsafe { libc::setlocale(libc::LC_ALL, b"\0".as_ptr() as *const _) };
+    detect_encoding()
+}
+
+#[cfg(unix)]
+fn detect_encoding() -> Encoding {
+    let codeset = unsafe { libc::nl_langinfo(libc::CODESET) };
+    if codeset.is_null() {
+        return encoding_from_env();
+    }
+
+    let name = unsafe { CStr::from_ptr(codeset) }.to_string_lossy();
+    if name.is_empty() {
+        encoding_from_env()
+    } else {
+        Encoding::from_name(&name)
+    }
+}
+
+#[cfg(not(unix))]
+fn detect_encoding() -> Encoding {
+    encoding_from_env()
+}
+
+/// Gets the name of the character type locale from the `LC_ALL`, `LC_CTYPE`
+/// and `LANG` environment variables.
+pub(crate) fn locale_name() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Detects the encoding from the locale environment variables.
+fn encoding_from_env() -> Encoding {
+    match locale_name() {
+        Some(locale) => match locale.find('.') {
+            Some(i) => Encoding::from_name(locale[i + 1..].split('@').next().unwrap_or("")),
+            None => Encoding::from_name(&locale),
+        },
+        None => Encoding::Ascii,
+    }
+}
@@ -0,0 +1,81 @@
+use event::Event;
+
+use super::Rect;
+
+/// Maps screen cells to the regions drawn over them during a frame.
+///
+/// Regions are registered while rendering, regions inserted later are treated
+/// as drawn on top of earlier ones. Lookups are constant time.
+#[derive(Clone, Debug, Default)]
+pub struct HitMap<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<usize>>,
+    regions: Vec<(Rect, T)>,
+}
+
+impl<T: Copy> HitMap<T> {
+    /// Creates an empty hit map covering a screen of the given size.
+    pub fn new(width: usize, height: usize) -> HitMap<T> {
+        HitMap {
+            width,
+            height,
+            cells: vec![None; width * height],
+            regions: Vec::new(),
+        }
+    }
+
+    /// Clears all regions, resizing the map for a new frame while keeping
+    /// its memory.
+    pub fn reset(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.cells.clear();
+        self.cells.resize(width * height, None);
+        self.regions.clear();
+    }
+
+    /// Registers a region, clipped to the screen.
+    pub fn insert(&mut self, rect: Rect, id: T) {
+        let index = self.regions.len();
+        self.regions.push((rect, id));
+
+        for y in rect.y..rect.bottom().min(self.height) {
+            let row = y * self.width;
+            for x in rect.x..rect.right().min(self.width) {
+                self.cells[row + x] = Some(index);
+            }
+        }
+    }
+
+    /// Gets the topmost region at the given position.
+    pub fn region_at(&self, pos: (usize, usize)) -> Option<(Rect, T)> {
+        let (x, y) = pos;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.cells[y * self.width + x].map(|index| self.regions[index])
+    }
+
+    /// Gets the id of the topmost region at the given position.
+    pub fn hit(&self, pos: (usize, usize)) -> Option<T> {
+        self.region_at(pos).map(|(_, id)| id)
+    }
+
+    /// Gets all registered regions, in the order they were inserted.
+    pub fn regions(&self) -> &[(Rect, T)] {
+        &self.regions
+    }
+
+    /// Finds the region under a mouse event, giving its id and the event
+    /// translated into coordinates local to the region.
+    pub fn dispatch(&self, event: &Event) -> Option<(T, Event)> {
+        match *event {
+            Event::Mouse { pos, .. } => self
+                .region_at(pos)
+                .and_then(|(rect, id)| event.relative_to(rect).map(|ev| (id, ev))),
+            _ => None,
+        }
+    }
+}
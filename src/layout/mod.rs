@@ -0,0 +1,12 @@
+//! Layout module.
+//!
+//! Rects locate parts of the window, and a [`Layout`](struct.Layout.html)
+//! splits one into parts by constraints.
+
+mod hit_map;
+mod rect;
+mod split;
+
+pub use self::hit_map::HitMap;
+pub use self::rect::Rect;
+pub use self::split::{Constraint, Direction, Layout};
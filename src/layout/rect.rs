@@ -0,0 +1,52 @@
+/// Represents a rectangular region of the window.
+///
+/// *Positions are given as `(x, y)`, matching mouse event positions.*
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Rect {
+    /// The column of the left edge.
+    pub x: usize,
+    /// The row of the top edge.
+    pub y: usize,
+    /// The width in columns.
+    pub width: usize,
+    /// The height in rows.
+    pub height: usize,
+}
+
+impl Rect {
+    /// Creates a new rect.
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Gets the column one past the right edge.
+    pub fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// Gets the row one past the bottom edge.
+    pub fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    /// Checks whether the rect contains the given position.
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        let (x, y) = pos;
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Translates a position into coordinates relative to the rect, if it is
+    /// contained in the rect.
+    pub fn to_local(&self, pos: (usize, usize)) -> Option<(usize, usize)> {
+        if self.contains(pos) {
+            Some((pos.0 - self.x, pos.1 - self.y))
+        } else {
+            None
+        }
+    }
+}
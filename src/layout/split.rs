@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+
+use backend::Backend;
+use layout::Rect;
+use trace::{Phase, Span};
+use window::Window;
+
+/// Represents the size asked for by one part of a [`Layout`](struct.Layout.html).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Constraint {
+    /// Exactly the given number of cells.
+    Length(usize),
+    /// A percentage of the area.
+    Percent(u16),
+    /// A fraction of the area, as numerator and denominator.
+    Ratio(u32, u32),
+    /// At least the given number of cells, growing into the space left over.
+    Min(usize),
+    /// At most the given number of cells.
+    Max(usize),
+}
+
+/// Represents the direction an area is split in.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Direction {
+    /// Split into columns, left to right.
+    Horizontal,
+    /// Split into rows, top to bottom.
+    Vertical,
+}
+
+/// Splits an area into parts sized by constraints.
+///
+/// ```no_run
+/// use termui::layout::{Constraint, Layout};
+/// use termui::Window;
+///
+/// let win = Window::new();
+/// let columns = Layout::horizontal([Constraint::Percent(30), Constraint::Min(20)]);
+///
+/// let parts = columns.split_window(&win);
+/// let (sidebar, main) = (parts[0], parts[1]);
+/// ```
+///
+/// Parts are first given the size their constraint asks for, then the space
+/// left over is shared between the [`Min`](enum.Constraint.html#variant.Min)
+/// parts. When the area is too small the last parts are cut short.
+///
+/// *The last split is cached, so splitting the same area again is cheap and
+/// a resized window is split again as soon as its size changes.*
+#[derive(Clone, Debug)]
+pub struct Layout {
+    direction: Direction,
+    constraints: Vec<Constraint>,
+    cache: RefCell<Option<(Rect, Vec<Rect>)>>,
+}
+
+impl Layout {
+    /// Creates a layout splitting in the given direction.
+    pub fn new<I>(direction: Direction, constraints: I) -> Layout
+    where
+        I: IntoIterator<Item = Constraint>,
+    {
+        Layout {
+            direction,
+            constraints: constraints.into_iter().collect(),
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Creates a layout splitting into columns.
+    pub fn horizontal<I>(constraints: I) -> Layout
+    where
+        I: IntoIterator<Item = Constraint>,
+    {
+        Layout::new(Direction::Horizontal, constraints)
+    }
+
+    /// Creates a layout splitting into rows.
+    pub fn vertical<I>(constraints: I) -> Layout
+    where
+        I: IntoIterator<Item = Constraint>,
+    {
+        Layout::new(Direction::Vertical, constraints)
+    }
+
+    /// Gets the direction the layout splits in.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Gets the constraints of the parts.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Splits an area into one rect for each constraint.
+    pub fn split(&self, area: Rect) -> Vec<Rect> {
+        if let Some((ref cached, ref rects)) = *self.cache.borrow() {
+            if *cached == area {
+                return rects.clone();
+            }
+        }
+
+        let span = Span::enter(Phase::Layout);
+        let total = match self.direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        };
+        let rects: Vec<Rect> = self
+            .sizes(total)
+            .into_iter()
+            .scan(0, |offset, size| {
+                let start = *offset;
+                *offset += size;
+                Some(match self.direction {
+                    Direction::Horizontal => Rect::new(area.x + start, area.y, size, area.height),
+                    Direction::Vertical => Rect::new(area.x, area.y + start, area.width, size),
+                })
+            })
+            .collect();
+        span.record("areas", rects.len());
+        *self.cache.borrow_mut() = Some((area, rects.clone()));
+        rects
+    }
+
+    /// Splits the whole of the window, at its current size.
+    pub fn split_window<B: Backend>(&self, win: &Window<B>) -> Vec<Rect> {
+        let (rows, cols) = win.get_size();
+        self.split(Rect::new(0, 0, cols, rows))
+    }
+
+    /// Computes the size of each part along the direction of the split.
+    fn sizes(&self, total: usize) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self
+            .constraints
+            .iter()
+            .map(|&constraint| match constraint {
+                Constraint::Length(n) | Constraint::Min(n) | Constraint::Max(n) => n,
+                Constraint::Percent(percent) => total * percent.min(100) as usize / 100,
+                Constraint::Ratio(_, 0) => 0,
+                Constraint::Ratio(num, den) => total * num.min(den) as usize / den as usize,
+            })
+            .collect();
+
+        let used: usize = sizes.iter().sum();
+        if used > total {
+            let mut left = total;
+            for size in &mut sizes {
+                *size = (*size).min(left);
+                left -= *size;
+            }
+            return sizes;
+        }
+
+        let growing: Vec<usize> = (0..self.constraints.len())
+            .filter(|&i| matches!(self.constraints[i], Constraint::Min(_)))
+            .collect();
+        let spare = total - used;
+        for (n, &i) in growing.iter().enumerate() {
+            // The earlier parts take the remainder.
+            sizes[i] += spare / growing.len() + usize::from(n < spare % growing.len());
+        }
+        sizes
+    }
+}
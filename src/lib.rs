@@ -4,14 +4,59 @@
 
 #[macro_use]
 extern crate bitflags;
+#[cfg(feature = "async")]
+extern crate futures_core;
+#[cfg(feature = "hyphenation")]
+extern crate hyphenation;
+extern crate libc;
 #[macro_use]
 extern crate log;
 pub extern crate pancurses as curses;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "bidi")]
+extern crate unicode_bidi;
+extern crate unicode_width;
 
+pub mod announce;
+pub mod backend;
+pub mod bench;
+pub mod bus;
+pub mod caps;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "devtools")]
+pub mod devtools;
+pub mod diagnostics;
+pub mod draw;
+pub mod error;
 pub mod event;
+mod ffi;
+pub mod graphics;
+pub mod hint;
+pub mod i18n;
+pub mod layout;
+pub mod process;
+pub mod prompt;
+#[cfg(unix)]
+pub mod session;
+pub mod style;
+pub mod text;
+mod trace;
+#[cfg(feature = "watch")]
+pub mod watch;
+pub mod widget;
 pub mod window;
 
+#[doc(no_inline)]
+pub use error::{Error, Result};
+
 #[doc(no_inline)]
 pub use event::{Event, Key};
 #[doc(no_inline)]
+pub use prompt::{confirm, pick, progress};
+#[doc(no_inline)]
 pub use window::Window;
@@ -0,0 +1,193 @@
+//! Process module.
+//!
+//! Runs a command in the background, collecting the lines it writes so they
+//! can be shown in a [`LogView`](../widget/struct.LogView.html).
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender, TryIter};
+use std::thread;
+
+use style::Style;
+use widget::LogView;
+use window::WindowHandle;
+
+/// Represents the stream a line of output was written to.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Source {
+    /// The standard output.
+    Stdout,
+    /// The standard error.
+    Stderr,
+}
+
+/// Represents a line written by a process, without its line ending.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct OutputLine {
+    /// The stream the line was written to.
+    pub source: Source,
+    /// The text of the line, which may contain ANSI escape sequences.
+    pub text: String,
+}
+
+/// Represents a running command, with its stdout and stderr read line by line
+/// on background threads.
+///
+/// The lines are collected with [`lines`](#method.lines) or
+/// [`drain_into`](#method.drain_into) on the UI thread. When spawned with a
+/// [`WindowHandle`](../window/struct.WindowHandle.html) the window is woken
+/// with an [`Event::Refresh`](../event/enum.Event.html#variant.Refresh)
+/// whenever lines arrive and when the output closes, so the UI can sleep in
+/// the meantime.
+///
+/// *The command's stdin is closed, since the terminal belongs to the UI.
+/// Dropping it kills the command if it is still running, and waits for it to
+/// exit.*
+#[derive(Debug)]
+pub struct ProcessOutput {
+    child: Child,
+    lines: Receiver<OutputLine>,
+    status: Option<ExitStatus>,
+    stderr_style: Style,
+}
+
+impl ProcessOutput {
+    /// Spawns the command, reading its output.
+    pub fn spawn(command: &mut Command) -> io::Result<ProcessOutput> {
+        ProcessOutput::start(command, None)
+    }
+
+    /// Spawns the command, reading its output and waking the window of the
+    /// handle as lines arrive.
+    pub fn spawn_with_handle(
+        command: &mut Command,
+        handle: WindowHandle,
+    ) -> io::Result<ProcessOutput> {
+        ProcessOutput::start(command, Some(handle))
+    }
+
+    fn start(command: &mut Command, handle: Option<WindowHandle>) -> io::Result<ProcessOutput> {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (sender, lines) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            read_lines(stdout, Source::Stdout, sender.clone(), handle.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            read_lines(stderr, Source::Stderr, sender, handle);
+        }
+
+        Ok(ProcessOutput {
+            child,
+            lines,
+            status: None,
+            stderr_style: Style::default(),
+        })
+    }
+
+    /// Gets the OS-assigned process identifier of the command.
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Takes the lines written since the last call, without blocking.
+    pub fn lines(&self) -> TryIter<'_, OutputLine> {
+        self.lines.try_iter()
+    }
+
+    /// Sets the style lines written to stderr are shown in by
+    /// [`drain_into`](#method.drain_into).
+    pub fn set_stderr_style(&mut self, style: Style) {
+        self.stderr_style = style;
+    }
+
+    /// Appends the lines written since the last call to a log view, giving
+    /// the number of lines added.
+    pub fn drain_into(&self, view: &mut LogView) -> usize {
+        let mut count = 0;
+        for line in self.lines() {
+            match line.source {
+                Source::Stdout => view.push_line(&line.text),
+                Source::Stderr => view.push_line_styled(&line.text, self.stderr_style),
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Gets the exit status of the command, if it has exited.
+    ///
+    /// *Lines written just before exiting may still be waiting to be taken.*
+    pub fn status(&mut self) -> io::Result<Option<ExitStatus>> {
+        if self.status.is_none() {
+            self.status = self.child.try_wait()?;
+        }
+        Ok(self.status)
+    }
+
+    /// Waits for the command to exit, giving its exit status.
+    pub fn wait(&mut self) -> io::Result<ExitStatus> {
+        let status = self.child.wait()?;
+        self.status = Some(status);
+        Ok(status)
+    }
+
+    /// Kills the command, if it is still running.
+    pub fn kill(&mut self) -> io::Result<()> {
+        if self.status()?.is_some() {
+            return Ok(());
+        }
+        self.child.kill()
+    }
+}
+
+impl Drop for ProcessOutput {
+    fn drop(&mut self) {
+        if self.status.is_none() {
+            // Waiting reaps the killed command, so it isn't left a zombie.
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+/// Reads the lines of a stream on a background thread, sending them until the
+/// stream closes or the receiver is dropped.
+fn read_lines<R: Read + Send + 'static>(
+    stream: R,
+    source: Source,
+    sender: Sender<OutputLine>,
+    handle: Option<WindowHandle>,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            while buf.last() == Some(&b'\n') || buf.last() == Some(&b'\r') {
+                buf.pop();
+            }
+            let line = OutputLine {
+                source,
+                text: String::from_utf8_lossy(&buf).into_owned(),
+            };
+            if sender.send(line).is_err() {
+                return;
+            }
+            if let Some(ref handle) = handle {
+                handle.request_redraw();
+            }
+        }
+        if let Some(ref handle) = handle {
+            handle.request_redraw();
+        }
+    });
+}
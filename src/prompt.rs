@@ -0,0 +1,268 @@
+//! Prompt module.
+//!
+//! Provides one-call helpers for command line tools that want a little bit of
+//! interface, without setting up their own window and event loop. Each helper
+//! draws an [inline](../window/struct.Builder.html#method.inline) window and
+//! tears it down again before returning.
+
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use event::{Event, Key};
+use i18n::Message;
+use style::{Attr, Style};
+use window::{CursorStyle, Window};
+
+/// The most items shown at once by [`pick`](fn.pick.html).
+const PICK_ROWS: usize = 10;
+
+/// How long to wait between polls when there are no events.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The least time between redraws of a progress bar.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+fn inline_window(rows: usize) -> Option<Window> {
+    match Window::builder()
+        .inline(rows)
+        .quit_signals(true)
+        .try_build()
+    {
+        Ok(ref window) if window.is_headless() => None,
+        Ok(window) => Some(window),
+        Err(err) => {
+            warn!("could not show prompt: {}", err);
+            None
+        }
+    }
+}
+
+fn wait_event(window: &mut Window) -> Event {
+    loop {
+        match window.poll_event() {
+            Some(ev) => return ev,
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Lets the user pick one of the items, filtering them with a fuzzy search.
+///
+/// Gives `None` if the user cancelled, or there is no terminal to ask on.
+pub fn pick<T: fmt::Display>(items: Vec<T>) -> Option<T> {
+    let labels: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+    let rows = labels.len().clamp(1, PICK_ROWS);
+    let mut window = inline_window(rows + 1)?;
+
+    let mut query = String::new();
+    let mut selected = 0;
+    loop {
+        let matches = fuzzy_filter(&query, &labels);
+        selected = selected.min(matches.len().saturating_sub(1));
+
+        window.erase();
+        window.print(0, 0, format!("> {}", query));
+        let offset = (selected + 1).saturating_sub(rows);
+        for (row, &index) in matches.iter().skip(offset).take(rows).enumerate() {
+            if offset + row == selected {
+                window.print_styled(row + 1, 0, &labels[index], Style::new().attr(Attr::Reverse));
+            } else {
+                window.print(row + 1, 0, &labels[index]);
+            }
+        }
+        window.request_cursor(CursorStyle::Bar(0, 2 + window.display_width(&query)));
+        window.refresh();
+
+        match wait_event(&mut window) {
+            Event::Key { key, .. } => match key {
+                Key::Enter => {
+                    let index = *matches.get(selected)?;
+                    return items.into_iter().nth(index);
+                }
+                Key::Escape => return None,
+                Key::Up => selected = selected.saturating_sub(1),
+                Key::Down => selected += 1,
+                Key::Backspace => {
+                    query.pop();
+                }
+                Key::Char(ch) if !ch.is_control() => {
+                    query.push(ch);
+                    selected = 0;
+                }
+                _ => {}
+            },
+            Event::Quit(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Asks the user a yes or no question.
+///
+/// Gives `false` if the user answered no or cancelled, or there is no terminal
+/// to ask on.
+pub fn confirm<S: AsRef<str>>(msg: S) -> bool {
+    let mut window = match inline_window(1) {
+        Some(window) => window,
+        None => return false,
+    };
+
+    let yes = window.localize(Message::Yes);
+    let no = window.localize(Message::No);
+    let initial = |word: &str| {
+        word.chars()
+            .next()
+            .map(|ch| ch.to_lowercase().collect::<String>())
+    };
+    let (yes_key, no_key) = (initial(&yes), initial(&no));
+
+    let prompt = format!("{} ({}/{}) ", msg.as_ref(), yes, no);
+    window.print(0, 0, &prompt);
+    loop {
+        window.request_cursor(CursorStyle::Bar(0, window.display_width(&prompt)));
+        window.refresh();
+
+        match wait_event(&mut window) {
+            Event::Key {
+                key: Key::Char(ch), ..
+            } => {
+                let ch = Some(ch.to_lowercase().collect::<String>());
+                if ch == yes_key {
+                    return true;
+                } else if ch == no_key {
+                    return false;
+                }
+            }
+            Event::Key {
+                key: Key::Escape, ..
+            }
+            | Event::Quit(_) => return false,
+            _ => {}
+        }
+    }
+}
+
+/// Wraps an iterator, showing a progress bar while it is consumed.
+///
+/// The total is taken from the size hint of the iterator, when it is unknown
+/// only the count is shown.
+///
+/// *Without a terminal the items are passed through without any progress.*
+pub fn progress<I: IntoIterator>(iter: I) -> Progress<I::IntoIter> {
+    let iter = iter.into_iter();
+    Progress {
+        total: iter.size_hint().1,
+        iter,
+        count: 0,
+        window: inline_window(1),
+        drawn: None,
+    }
+}
+
+/// An iterator showing a progress bar, see [`progress`](fn.progress.html).
+pub struct Progress<I> {
+    iter: I,
+    total: Option<usize>,
+    count: usize,
+    window: Option<Window>,
+    drawn: Option<Instant>,
+}
+
+impl<I> Progress<I> {
+    fn draw(&mut self, force: bool) {
+        let window = match self.window {
+            Some(ref window) => window,
+            None => return,
+        };
+        let now = Instant::now();
+        if !force
+            && self
+                .drawn
+                .is_some_and(|drawn| now - drawn < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        self.drawn = Some(now);
+
+        let (_, cols) = window.get_size();
+        let line = match self.total {
+            Some(total) if total > 0 => {
+                let label = format!(" {}/{}", self.count, total);
+                let width = cols.saturating_sub(label.len() + 2);
+                let filled = width * self.count.min(total) / total;
+                format!(
+                    "[{}{}]{}",
+                    "#".repeat(filled),
+                    " ".repeat(width - filled),
+                    label
+                )
+            }
+            _ => self.count.to_string(),
+        };
+
+        window.erase();
+        window.print(0, 0, line);
+        window.refresh();
+    }
+}
+
+impl<I: Iterator> Iterator for Progress<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Some(item) => {
+                self.count += 1;
+                self.draw(false);
+                Some(item)
+            }
+            None => {
+                self.draw(true);
+                self.window = None;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> fmt::Debug for Progress<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("total", &self.total)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+/// Gives the indices of the labels matching the query, best matches first.
+///
+/// A label matches if it contains the characters of the query in order,
+/// ignoring case. Matches with the characters closer together rank higher.
+fn fuzzy_filter(query: &str, labels: &[String]) -> Vec<usize> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+
+    let mut scored: Vec<(usize, usize)> = labels
+        .iter()
+        .enumerate()
+        .filter_map(|(index, label)| fuzzy_score(&query, label).map(|score| (score, index)))
+        .collect();
+    scored.sort_by_key(|&(score, _)| score);
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+fn fuzzy_score(query: &[char], label: &str) -> Option<usize> {
+    let mut chars = label.chars().flat_map(char::to_lowercase).enumerate();
+    let mut first = None;
+    let mut last = 0;
+    for &wanted in query {
+        let (pos, _) = chars.find(|&(_, ch)| ch == wanted)?;
+        first = first.or(Some(pos));
+        last = pos;
+    }
+    Some(first.map_or(0, |first| last - first + first / 4))
+}
@@ -0,0 +1,430 @@
+//! Session module.
+//!
+//! Splits an application from the terminal it is shown on, like `tmux`. The
+//! application draws into a [`SessionBackend`](struct.SessionBackend.html)
+//! listening on a Unix socket, and keeps running in the background while a
+//! client started with [`attach`](fn.attach.html) shows it on a real
+//! terminal. The client can detach, or be cut off with its SSH connection,
+//! and attach again later to find the screen as it was left.
+//!
+//! ```no_run
+//! use termui::session::SessionBackend;
+//! use termui::Window;
+//!
+//! let backend = SessionBackend::listen("/tmp/app.sock").unwrap();
+//! let mut win = Window::with_backend(backend);
+//! loop {
+//!     while let Some(event) = win.poll_event() {
+//!         // ...
+//!     }
+//!     win.refresh();
+//!     # break;
+//! }
+//! ```
+//!
+//! *Only available on Unix.*
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, ErrorKind, Read, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use libc;
+
+use backend::{terminal_size, AnsiScreen, Backend};
+use caps::{Capabilities, Features};
+use event::{Decoder, Event};
+use style::{ColorCaps, Style};
+use text::AmbiguousWidth;
+use window::CursorStyle;
+
+/// The key that detaches the client, `Ctrl-\`.
+pub const DETACH_KEY: u8 = 0x1C;
+
+/// The size of the screen until a client tells its own.
+const DEFAULT_SIZE: (usize, usize) = (24, 80);
+/// How long the server waits on a client that isn't reading before cutting
+/// it off.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long the client waits for input or output before checking its size.
+const POLL_INTERVAL: i32 = 100;
+
+/// A message carrying input bytes from the client.
+const MSG_INPUT: u8 = 0;
+/// A message carrying the size of the client's terminal.
+const MSG_RESIZE: u8 = 1;
+
+/// A backend drawing for clients attached over a Unix socket, see the
+/// [module](index.html) documentation.
+///
+/// The screen is kept in memory while no client is attached, and sent in
+/// full to a client when it attaches. A client attaching while another is
+/// attached takes over from it.
+///
+/// *Clients are assumed to be xterm compatible terminals with 256 colors.
+/// The socket file is removed when the backend is dropped.*
+#[derive(Debug)]
+pub struct SessionBackend {
+    path: PathBuf,
+    listener: UnixListener,
+    client: RefCell<Option<UnixStream>>,
+    received: Vec<u8>,
+    decoder: Decoder,
+    events: VecDeque<Event>,
+    screen: AnsiScreen,
+    capabilities: Capabilities,
+}
+
+impl SessionBackend {
+    /// Creates a backend listening for clients on the socket at the given
+    /// path, replacing any stale socket file left there.
+    ///
+    /// *Fails with `AlreadyExists` if something other than a socket is at the
+    /// path.*
+    pub fn listen<P: AsRef<Path>>(path: P) -> io::Result<SessionBackend> {
+        let path = path.as_ref().to_path_buf();
+        if UnixStream::connect(&path).is_err() {
+            // Only a socket nobody listens on is stale, anything else is kept.
+            match fs::symlink_metadata(&path) {
+                Ok(meta) if meta.file_type().is_socket() => fs::remove_file(&path)?,
+                Ok(_) => {
+                    return Err(io::Error::new(
+                        ErrorKind::AlreadyExists,
+                        format!("{} exists and is not a socket", path.display()),
+                    ))
+                }
+                Err(_) => {}
+            }
+        }
+        let listener = UnixListener::bind(&path)?;
+        listener.set_nonblocking(true)?;
+
+        let mut capabilities = Capabilities::none();
+        capabilities.colors = ColorCaps {
+            has_colors: true,
+            can_change_color: false,
+            colors: 256,
+            pairs: 256 * 256,
+            truecolor: false,
+            default_colors: true,
+        };
+        // Clients are xterm compatible, so they take cursor shapes.
+        capabilities.features = Features::CursorShape;
+
+        Ok(SessionBackend {
+            path,
+            listener,
+            client: RefCell::new(None),
+            received: Vec::new(),
+            decoder: Decoder::new(),
+            events: VecDeque::new(),
+            screen: AnsiScreen::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1),
+            capabilities,
+        })
+    }
+
+    /// Gets the path of the socket clients attach to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks whether a client is attached.
+    pub fn is_attached(&self) -> bool {
+        self.client.borrow().is_some()
+    }
+
+    /// Accepts a waiting client, taking over from the attached one.
+    fn accept(&mut self) {
+        match self.listener.accept() {
+            Ok((client, _)) => {
+                if client.set_nonblocking(true).is_err()
+                    || client.set_write_timeout(Some(WRITE_TIMEOUT)).is_err()
+                {
+                    return;
+                }
+                info!("client attached to {}", self.path.display());
+                *self.client.borrow_mut() = Some(client);
+                self.received.clear();
+                self.screen.replay(&self.capabilities);
+            }
+            Err(ref err) if err.kind() == ErrorKind::WouldBlock => {}
+            Err(err) => warn!("could not accept client: {}", err),
+        }
+    }
+
+    /// Reads the messages the client has sent.
+    fn receive(&mut self) {
+        let mut buf = [0; 4096];
+        loop {
+            let read = match *self.client.borrow_mut() {
+                Some(ref mut client) => client.read(&mut buf),
+                None => return,
+            };
+            match read {
+                Ok(0) => return self.detach(),
+                Ok(n) => self.received.extend_from_slice(&buf[..n]),
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => return self.detach(),
+            }
+        }
+
+        let mut start = 0;
+        while self.received.len() - start >= 5 {
+            let kind = self.received[start];
+            let mut len = [0; 4];
+            len.copy_from_slice(&self.received[start + 1..start + 5]);
+            let len = u32::from_be_bytes(len) as usize;
+            if self.received.len() - start - 5 < len {
+                break;
+            }
+            let payload = self.received[start + 5..start + 5 + len].to_vec();
+            start += 5 + len;
+
+            match kind {
+                MSG_INPUT => {
+                    let events = self.decoder.decode_bytes(&payload);
+                    self.events.extend(events);
+                }
+                MSG_RESIZE if payload.len() == 4 => {
+                    let rows = u16::from_be_bytes([payload[0], payload[1]]) as usize;
+                    let cols = u16::from_be_bytes([payload[2], payload[3]]) as usize;
+                    if self.screen.size() != (rows, cols) {
+                        self.screen.resize(rows, cols);
+                        self.events.push_back(Event::Resize);
+                    }
+                    self.screen.replay(&self.capabilities);
+                }
+                _ => {}
+            }
+        }
+        self.received.drain(..start);
+    }
+
+    fn detach(&self) {
+        if self.client.borrow_mut().take().is_some() {
+            info!("client detached from {}", self.path.display());
+        }
+    }
+
+    /// Sends the queued output to the client, detaching it if it can't keep
+    /// up.
+    fn send(&self, out: &str) {
+        let failed = match *self.client.borrow_mut() {
+            Some(ref mut client) => write_all(client, out.as_bytes()).is_err(),
+            None => false,
+        };
+        if failed {
+            self.detach();
+        }
+    }
+}
+
+impl Drop for SessionBackend {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl Backend for SessionBackend {
+    fn size(&self) -> (usize, usize) {
+        self.screen.size()
+    }
+
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    fn erase(&self) {
+        self.screen.erase();
+    }
+
+    fn print(&self, row: usize, col: usize, text: &str, style: Style) {
+        self.screen.print(row, col, text, style, &self.capabilities);
+    }
+
+    fn print_here(&self, text: &str, style: Style) {
+        let (row, col) = self.cursor();
+        self.print(row, col, text, style);
+    }
+
+    fn insert_char(&self, row: usize, col: usize, ch: char) {
+        self.screen.insert_char(row, col, ch, &self.capabilities);
+    }
+
+    fn delete_char(&self, row: usize, col: usize) {
+        self.screen.delete_char(row, col, &self.capabilities);
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.screen.cursor()
+    }
+
+    fn move_cursor(&self, row: usize, col: usize) {
+        self.screen.move_cursor(row, col);
+    }
+
+    fn set_ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        self.screen.set_ambiguous_width(ambiguous);
+    }
+
+    fn present(&self, cursor: CursorStyle) {
+        if let Some(frame) = self.screen.take_frame(cursor, &self.capabilities) {
+            self.send(&frame);
+        }
+    }
+
+    fn read_event(&mut self) -> Option<Event> {
+        if self.events.is_empty() {
+            self.accept();
+            self.receive();
+            self.send(&self.screen.take_output());
+        }
+        self.events.pop_front()
+    }
+}
+
+/// Writes all of the bytes to a non-blocking stream, waiting for it to drain
+/// for up to the write timeout.
+fn write_all(stream: &mut UnixStream, mut bytes: &[u8]) -> io::Result<()> {
+    while !bytes.is_empty() {
+        match stream.write(bytes) {
+            Ok(0) => return Err(io::Error::new(ErrorKind::WriteZero, "client closed")),
+            Ok(n) => bytes = &bytes[n..],
+            Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(ref err) if err.kind() == ErrorKind::WouldBlock => {
+                if !wait_for(
+                    stream.as_raw_fd(),
+                    libc::POLLOUT,
+                    WRITE_TIMEOUT.as_millis() as i32,
+                ) {
+                    return Err(io::Error::new(ErrorKind::TimedOut, "client not reading"));
+                }
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// Waits for a file descriptor to be ready, giving whether it became ready
+/// before the timeout.
+fn wait_for(fd: libc::c_int, events: libc::c_short, timeout: i32) -> bool {
+    let mut fd = libc::pollfd {
+        fd,
+        events,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut fd, 1, timeout) > 0 }
+}
+
+/// Attaches the terminal to the session listening on the socket at the given
+/// path, showing it until the session ends or the client detaches with
+/// [`DETACH_KEY`](constant.DETACH_KEY.html).
+///
+/// The terminal is switched to the alternate screen in raw mode, with mouse
+/// reporting and bracketed paste, and restored before returning.
+pub fn attach<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let mut server = UnixStream::connect(path)?;
+
+    let stdin = libc::STDIN_FILENO;
+    let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+    if unsafe { libc::tcgetattr(stdin, &mut termios) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut raw = termios;
+    unsafe {
+        libc::cfmakeraw(&mut raw);
+        libc::tcsetattr(stdin, libc::TCSANOW, &raw);
+    }
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "\x1B[?1049h\x1B[?1002h\x1B[?1006h\x1B[?2004h");
+    let _ = stdout.flush();
+
+    let result = relay(&mut server);
+
+    let _ = write!(
+        stdout,
+        "\x1B[0m\x1B[0 q\x1B[?25h\x1B[?2004l\x1B[?1006l\x1B[?1002l\x1B[?1049l"
+    );
+    let _ = stdout.flush();
+    unsafe { libc::tcsetattr(stdin, libc::TCSANOW, &termios) };
+    result
+}
+
+/// Relays input to the server and its output to the terminal, until either
+/// side ends.
+fn relay(server: &mut UnixStream) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let mut size = None;
+    let mut buf = [0; 4096];
+    loop {
+        let now = terminal_size();
+        if now != size {
+            size = now;
+            if let Some((rows, cols)) = now {
+                let mut payload = (rows as u16).to_be_bytes().to_vec();
+                payload.extend_from_slice(&(cols as u16).to_be_bytes());
+                send_message(server, MSG_RESIZE, &payload)?;
+            }
+        }
+
+        let mut fds = [
+            libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: server.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        if unsafe { libc::poll(fds.as_mut_ptr(), 2, POLL_INTERVAL) } < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+
+        if fds[0].revents != 0 {
+            let n = io::stdin().read(&mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            let input = &buf[..n];
+            match input.iter().position(|&b| b == DETACH_KEY) {
+                Some(index) => {
+                    send_message(server, MSG_INPUT, &input[..index])?;
+                    return Ok(());
+                }
+                None => send_message(server, MSG_INPUT, input)?,
+            }
+        }
+        if fds[1].revents != 0 {
+            let n = server.read(&mut buf)?;
+            if n == 0 {
+                return Ok(());
+            }
+            stdout.write_all(&buf[..n])?;
+            stdout.flush()?;
+        }
+    }
+}
+
+fn send_message(server: &mut UnixStream, kind: u8, payload: &[u8]) -> io::Result<()> {
+    if payload.is_empty() && kind == MSG_INPUT {
+        return Ok(());
+    }
+    let mut message = vec![kind];
+    message.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    message.extend_from_slice(payload);
+    server.write_all(&message)
+}
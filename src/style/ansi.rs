@@ -0,0 +1,202 @@
+use style::{Attr, Color, Rgb, Style, UnderlineStyle};
+
+/// The columns between tab stops.
+const TAB_WIDTH: usize = 8;
+
+/// Parses text containing ANSI escape sequences, such as the output of a
+/// command, into runs of styled text.
+///
+/// SGR sequences set the style of the text that follows, and other escape
+/// sequences and control characters are dropped. The style carries over from
+/// one line to the next, as it would on a terminal.
+///
+/// *Colors and attributes reset by the text are left unset in its styles, so
+/// they inherit the style the text is drawn within.*
+#[derive(Clone, Debug, Default)]
+pub struct AnsiParser {
+    style: Style,
+}
+
+impl AnsiParser {
+    /// Creates a new parser, starting in the default style.
+    pub fn new() -> AnsiParser {
+        AnsiParser::default()
+    }
+
+    /// Gets the style the next text is in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Parses a line of text into runs of text and their styles.
+    ///
+    /// *A carriage return starts the line over, as progress bars do.*
+    pub fn parse(&mut self, line: &str) -> Vec<(String, Style)> {
+        let mut runs: Vec<(String, Style)> = Vec::new();
+        let mut col = 0;
+        let mut chars = line.chars().peekable();
+        while let Some(ch) = chars.next() {
+            let text = match ch {
+                '\x1B' => {
+                    match chars.next() {
+                        Some('[') => {
+                            let mut params = String::new();
+                            for ch in chars.by_ref() {
+                                if ('\x40'..='\x7E').contains(&ch) {
+                                    if ch == 'm' {
+                                        self.apply_sgr(&params);
+                                    }
+                                    break;
+                                }
+                                params.push(ch);
+                            }
+                        }
+                        // Operating system commands end with BEL or ST.
+                        Some(']') => {
+                            while let Some(ch) = chars.next() {
+                                if ch == '\x07'
+                                    || (ch == '\x1B' && chars.next_if_eq(&'\\').is_some())
+                                {
+                                    break;
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                '\r' => {
+                    runs.clear();
+                    col = 0;
+                    continue;
+                }
+                '\t' => {
+                    let spaces = TAB_WIDTH - col % TAB_WIDTH;
+                    col += spaces;
+                    " ".repeat(spaces)
+                }
+                ch if ch.is_control() => continue,
+                ch => {
+                    col += 1;
+                    ch.to_string()
+                }
+            };
+
+            match runs.last_mut() {
+                Some(&mut (ref mut run, style)) if style == self.style => run.push_str(&text),
+                _ => runs.push((text, self.style)),
+            }
+        }
+        runs
+    }
+
+    /// Applies the parameters of an SGR sequence to the current style.
+    fn apply_sgr(&mut self, params: &str) {
+        let params: Vec<&str> = params.split(';').collect();
+        let mut i = 0;
+        while i < params.len() {
+            let mut sub = params[i].split(':');
+            let code = sub
+                .next()
+                .and_then(|code| code.parse::<u16>().ok())
+                .unwrap_or(0);
+            let style = &mut self.style;
+            match code {
+                0 => *style = Style::default(),
+                1 => style.attr |= Attr::Bold,
+                2 => style.attr |= Attr::Dim,
+                3 => style.attr |= Attr::Italic,
+                4 => {
+                    let shape = match sub.next() {
+                        Some("0") => None,
+                        Some("2") => Some(UnderlineStyle::Double),
+                        Some("3") => Some(UnderlineStyle::Curly),
+                        Some("4") => Some(UnderlineStyle::Dotted),
+                        Some("5") => Some(UnderlineStyle::Dashed),
+                        _ => Some(UnderlineStyle::Single),
+                    };
+                    match shape {
+                        Some(shape) => *style = style.underline(shape),
+                        None => style.attr -= Attr::Underline,
+                    }
+                }
+                7 => style.attr |= Attr::Reverse,
+                21 => *style = style.underline(UnderlineStyle::Double),
+                22 => style.attr -= Attr::Bold | Attr::Dim,
+                23 => style.attr -= Attr::Italic,
+                24 => style.attr -= Attr::Underline,
+                27 => style.attr -= Attr::Reverse,
+                30..=37 => style.fg = Some(basic(code - 30)),
+                39 => style.fg = None,
+                40..=47 => style.bg = Some(basic(code - 40)),
+                49 => style.bg = None,
+                59 => style.underline_color = None,
+                90..=97 => style.fg = Some(Color::Indexed((code - 90 + 8) as u8)),
+                100..=107 => style.bg = Some(Color::Indexed((code - 100 + 8) as u8)),
+                38 | 48 | 58 => {
+                    // Extended colors are given either as subparameters or as
+                    // the parameters that follow.
+                    let args: Vec<&str> = if params[i].contains(':') {
+                        sub.collect()
+                    } else {
+                        let args = params[i + 1..]
+                            .iter()
+                            .cloned()
+                            .take(match params.get(i + 1) {
+                                Some(&"5") => 2,
+                                Some(&"2") => 4,
+                                _ => 1,
+                            });
+                        let args: Vec<&str> = args.collect();
+                        i += args.len();
+                        args
+                    };
+                    let color = extended(&args);
+                    match code {
+                        38 => style.fg = color.or(style.fg),
+                        48 => style.bg = color.or(style.bg),
+                        _ => style.underline_color = color.or(style.underline_color),
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Gets a color of the basic 8-color palette.
+fn basic(index: u16) -> Color {
+    [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ][index as usize]
+}
+
+/// Parses the arguments of an extended color, `5;n` or `2;r;g;b`.
+///
+/// *The color space of the subparameter form `2::r:g:b` is skipped.*
+fn extended(args: &[&str]) -> Option<Color> {
+    let num = |arg: Option<&&str>| arg.and_then(|arg| arg.parse::<u8>().ok());
+    match args.first() {
+        Some(&"5") => num(args.get(1)).map(Color::Indexed),
+        Some(&"2") => {
+            let rgb = if args.len() > 4 {
+                &args[2..]
+            } else {
+                &args[1..]
+            };
+            match (num(rgb.first()), num(rgb.get(1)), num(rgb.get(2))) {
+                (Some(r), Some(g), Some(b)) => Some(Color::Rgb(Rgb::new(r, g, b))),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,45 @@
+#![allow(non_upper_case_globals)]
+
+use curses;
+
+bitflags! {
+    /// Represents text attributes.
+    pub struct Attr: u8 {
+        /// No attributes.
+        const None = 0;
+
+        /// Bold text.
+        const Bold = 1 << 0;
+        /// Underlined text.
+        const Underline = 1 << 1;
+        /// Reversed foreground and background colors.
+        const Reverse = 1 << 2;
+        /// Dim text.
+        const Dim = 1 << 3;
+        /// Italic text.
+        const Italic = 1 << 4;
+    }
+}
+
+impl Attr {
+    /// Converts the attributes into curses attributes.
+    pub(crate) fn to_curses(self) -> curses::chtype {
+        let flags = [
+            (Attr::Bold, curses::A_BOLD),
+            (Attr::Underline, curses::A_UNDERLINE),
+            (Attr::Reverse, curses::A_REVERSE),
+            (Attr::Dim, curses::A_DIM),
+            (Attr::Italic, curses::A_ITALIC),
+        ];
+        flags
+            .iter()
+            .filter(|&&(attr, _)| self.contains(attr))
+            .fold(0, |acc, &(_, flag)| acc | flag)
+    }
+}
+
+impl Default for Attr {
+    fn default() -> Attr {
+        Attr::None
+    }
+}
@@ -0,0 +1,242 @@
+use std::env;
+
+use curses;
+
+/// Represents a 24-bit RGB color.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Rgb {
+    /// Red component.
+    pub r: u8,
+    /// Green component.
+    pub g: u8,
+    /// Blue component.
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Creates a new RGB color.
+    pub fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    /// Converts the color into the `0..=1000` component scale used by curses.
+    pub(crate) fn to_curses(self) -> (i16, i16, i16) {
+        let scale = |c: u8| (i32::from(c) * 1000 / 255) as i16;
+        (scale(self.r), scale(self.g), scale(self.b))
+    }
+}
+
+/// Represents the color capabilities of the terminal.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ColorCaps {
+    /// Whether the terminal supports colors.
+    pub has_colors: bool,
+    /// Whether the terminal can redefine its palette.
+    pub can_change_color: bool,
+    /// The number of colors in the palette.
+    pub colors: usize,
+    /// The number of color pairs available.
+    pub pairs: usize,
+    /// Whether the terminal supports 24-bit colors.
+    pub truecolor: bool,
+    /// Whether the terminal's own foreground and background can be used in
+    /// color pairs, keeping transparent backgrounds.
+    pub default_colors: bool,
+}
+
+impl ColorCaps {
+    /// Detects the color capabilities from curses, given whether
+    /// `use_default_colors` succeeded.
+    ///
+    /// *Truecolor support is detected from the `COLORTERM` environment
+    /// variable.*
+    pub(crate) fn detect(default_colors: bool) -> ColorCaps {
+        let has_colors = curses::has_colors();
+        let truecolor = match env::var("COLORTERM") {
+            Ok(ref value) => value == "truecolor" || value == "24bit",
+            Err(_) => false,
+        };
+
+        ColorCaps {
+            has_colors,
+            can_change_color: has_colors && curses::can_change_color(),
+            colors: if has_colors {
+                curses::COLORS().max(0) as usize
+            } else {
+                0
+            },
+            pairs: if has_colors {
+                curses::COLOR_PAIRS().max(0) as usize
+            } else {
+                0
+            },
+            truecolor: has_colors && truecolor,
+            default_colors: has_colors && default_colors,
+        }
+    }
+}
+
+/// Represents a color used for text.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Color {
+    /// Black.
+    Black,
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Magenta.
+    Magenta,
+    /// Cyan.
+    Cyan,
+    /// White.
+    White,
+    /// A color from the 256-color palette.
+    Indexed(u8),
+    /// A 24-bit RGB color.
+    ///
+    /// *Terminals with fewer colors show the nearest color of their palette.*
+    Rgb(Rgb),
+    /// The terminal's own foreground or background color.
+    ///
+    /// A default background is left transparent on terminals with background
+    /// images. *Terminals that can't use their default colors in color pairs
+    /// show white text on black instead.*
+    Default,
+}
+
+/// The colors of the standard 16-color palette, as used by xterm.
+const BASIC: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xCD, 0x00, 0x00),
+    (0x00, 0xCD, 0x00),
+    (0xCD, 0xCD, 0x00),
+    (0x00, 0x00, 0xEE),
+    (0xCD, 0x00, 0xCD),
+    (0x00, 0xCD, 0xCD),
+    (0xE5, 0xE5, 0xE5),
+    (0x7F, 0x7F, 0x7F),
+    (0xFF, 0x00, 0x00),
+    (0x00, 0xFF, 0x00),
+    (0xFF, 0xFF, 0x00),
+    (0x5C, 0x5C, 0xFF),
+    (0xFF, 0x00, 0xFF),
+    (0x00, 0xFF, 0xFF),
+    (0xFF, 0xFF, 0xFF),
+];
+
+/// The component levels of the 6x6x6 color cube in the 256-color palette.
+const CUBE: [u8; 6] = [0x00, 0x5F, 0x87, 0xAF, 0xD7, 0xFF];
+
+impl Color {
+    /// Approximates the RGB value of the color, assuming the default xterm
+    /// palette.
+    ///
+    /// *The terminal's default color is unknown, and is approximated as
+    /// white.*
+    pub fn to_rgb(self) -> Rgb {
+        let index = match self {
+            Color::Rgb(rgb) => return rgb,
+            Color::Default => return Rgb::new(0xE5, 0xE5, 0xE5),
+            Color::Indexed(index) => index,
+            named => named.palette_index(),
+        };
+        match index {
+            0..=15 => {
+                let (r, g, b) = BASIC[usize::from(index)];
+                Rgb::new(r, g, b)
+            }
+            16..=231 => {
+                let i = usize::from(index - 16);
+                Rgb::new(CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+            }
+            _ => {
+                let level = 8 + (index - 232) * 10;
+                Rgb::new(level, level, level)
+            }
+        }
+    }
+
+    /// Gets the index of the nearest of the eight basic colors, or `None`
+    /// for the terminal's default color.
+    pub(crate) fn basic_index(self) -> Option<u8> {
+        match self {
+            Color::Default => None,
+            color => Some(color.to_curses(8) as u8),
+        }
+    }
+
+    /// Gets the palette index of a named color.
+    fn palette_index(self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::Indexed(index) => index,
+            Color::Rgb(_) | Color::Default => 0,
+        }
+    }
+
+    /// Gets the curses color number to use on a terminal with the given number
+    /// of colors.
+    ///
+    /// *The default color is `-1`.*
+    pub(crate) fn to_curses(self, colors: usize) -> i16 {
+        match self {
+            Color::Default => -1,
+            Color::Indexed(index) if usize::from(index) < colors => i16::from(index),
+            Color::Rgb(_) | Color::Indexed(_) => {
+                let rgb = self.to_rgb();
+                if colors >= 256 {
+                    i16::from(nearest_256(rgb))
+                } else {
+                    let count = colors.clamp(1, 16);
+                    let (index, _) = BASIC[..count]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &(r, g, b))| (i, distance(rgb, Rgb::new(r, g, b))))
+                        .min_by_key(|&(_, dist)| dist)
+                        .unwrap_or((0, 0));
+                    index as i16
+                }
+            }
+            named => i16::from(named.palette_index()),
+        }
+    }
+}
+
+/// Gives the squared distance between two colors.
+fn distance(a: Rgb, b: Rgb) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+    d(a.r, b.r) + d(a.g, b.g) + d(a.b, b.b)
+}
+
+/// Finds the nearest color in the cube or grayscale ramp of the 256-color
+/// palette.
+fn nearest_256(rgb: Rgb) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&i| (i32::from(CUBE[i]) - i32::from(c)).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb.r), level(rgb.g), level(rgb.b));
+    let cube = (16 + 36 * r + 6 * g + b) as u8;
+
+    let avg = (u32::from(rgb.r) + u32::from(rgb.g) + u32::from(rgb.b)) / 3;
+    let gray = 232 + (avg.saturating_sub(8) / 10).min(23) as u8;
+
+    if distance(rgb, Color::Indexed(gray).to_rgb()) < distance(rgb, Color::Indexed(cube).to_rgb()) {
+        gray
+    } else {
+        cube
+    }
+}
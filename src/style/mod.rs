@@ -0,0 +1,211 @@
+//! Style module.
+
+mod ansi;
+mod attr;
+mod color;
+mod pairs;
+mod underline;
+
+pub use self::ansi::AnsiParser;
+pub use self::attr::Attr;
+pub use self::color::{Color, ColorCaps, Rgb};
+pub use self::underline::UnderlineStyle;
+
+pub(crate) use self::pairs::ColorPairs;
+
+/// Represents the style of text, its colors and attributes.
+///
+/// Styles cascade, each one [patched](#method.patch) over the style it is
+/// drawn within: the window's style, then the styles of the containers given
+/// to [`Window::with_style`](../window/struct.Window.html#method.with_style),
+/// then the widget's style, then the style of the text itself. A field left
+/// unset inherits, while [`Color::Default`] and
+/// [`attr_off`](#method.attr_off) reset what would be inherited.
+///
+/// *Colors left unset by the whole cascade use the terminal default.*
+///
+/// [`Color::Default`]: enum.Color.html#variant.Default
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Style {
+    /// The foreground color.
+    pub fg: Option<Color>,
+    /// The background color.
+    pub bg: Option<Color>,
+    /// The text attributes.
+    pub attr: Attr,
+    /// The text attributes turned off, when inherited.
+    pub attr_off: Attr,
+    /// The shape of the underline, when underlined.
+    pub underline: UnderlineStyle,
+    /// The color of the underline, when underlined, or `None` for the
+    /// foreground color.
+    pub underline_color: Option<Color>,
+}
+
+impl Style {
+    /// Creates a new style, with default colors and no attributes.
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Creates a style resetting everything it is patched over, to the
+    /// terminal's colors and no attributes.
+    pub fn reset() -> Style {
+        Style {
+            fg: Some(Color::Default),
+            bg: Some(Color::Default),
+            attr: Attr::None,
+            attr_off: Attr::all(),
+            underline: UnderlineStyle::Single,
+            underline_color: Some(Color::Default),
+        }
+    }
+
+    /// Sets the foreground color.
+    pub fn fg(mut self, color: Color) -> Style {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Sets the background color.
+    pub fn bg(mut self, color: Color) -> Style {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Adds text attributes.
+    pub fn attr(mut self, attr: Attr) -> Style {
+        self.attr |= attr;
+        self.attr_off -= attr;
+        self
+    }
+
+    /// Turns off text attributes, even when inherited.
+    pub fn attr_off(mut self, attr: Attr) -> Style {
+        self.attr -= attr;
+        self.attr_off |= attr;
+        self
+    }
+
+    /// Underlines the text with the given shape.
+    pub fn underline(mut self, underline: UnderlineStyle) -> Style {
+        self.attr |= Attr::Underline;
+        self.attr_off -= Attr::Underline;
+        self.underline = underline;
+        self
+    }
+
+    /// Sets the color of the underline.
+    ///
+    /// *Only shown on terminals with styled underlines, others underline in
+    /// the foreground color.*
+    pub fn underline_color(mut self, color: Color) -> Style {
+        self.underline_color = Some(color);
+        self
+    }
+
+    /// Patches another style over this one, giving the style of text drawn in
+    /// the other style within this one.
+    ///
+    /// Colors set by the other style replace those of this style, and its
+    /// attributes are added to those of this style after removing its
+    /// [`attr_off`](#method.attr_off) attributes. The underline shape is
+    /// taken from the other style when it adds an underline.
+    pub fn patch(self, other: Style) -> Style {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            attr: (self.attr - other.attr_off) | other.attr,
+            attr_off: (self.attr_off - other.attr) | other.attr_off,
+            underline: if other.attr.contains(Attr::Underline) {
+                other.underline
+            } else {
+                self.underline
+            },
+            underline_color: other.underline_color.or(self.underline_color),
+        }
+    }
+
+    /// Maps the colors of the style to attributes, for screens without
+    /// color, so what the colors told apart still stands out.
+    ///
+    /// A background color reverses the text, as for a highlighted row. Red,
+    /// yellow and magenta text is bold, blue and cyan text is underlined,
+    /// and other colors are dropped. Colors from the palette are mapped as
+    /// the nearest of the eight basic colors.
+    pub fn monochrome(self) -> Style {
+        let mut attr = self.attr;
+        match self.fg.and_then(Color::basic_index) {
+            Some(1) | Some(3) | Some(5) => attr |= Attr::Bold,
+            Some(4) | Some(6) => attr |= Attr::Underline,
+            _ => {}
+        }
+        match self.bg.and_then(Color::basic_index) {
+            None | Some(0) => {}
+            Some(_) => attr |= Attr::Reverse,
+        }
+        Style {
+            fg: None,
+            bg: None,
+            attr,
+            attr_off: self.attr_off - attr,
+            underline_color: None,
+            ..self
+        }
+    }
+
+    /// Checks whether the text has an underline beyond a plain one, which
+    /// curses can't draw.
+    pub(crate) fn has_styled_underline(&self) -> bool {
+        self.attr.contains(Attr::Underline)
+            && (self.underline != UnderlineStyle::Single || self.underline_color.is_some())
+    }
+
+    /// Builds the SGR sequence drawing text in the style, as curses would on a
+    /// terminal with the given colors, along with its styled underline.
+    pub(crate) fn sgr(&self, colors: ColorCaps) -> String {
+        let mut params = vec!["0".to_owned()];
+        for &(attr, code) in &[
+            (Attr::Bold, "1"),
+            (Attr::Dim, "2"),
+            (Attr::Italic, "3"),
+            (Attr::Reverse, "7"),
+        ] {
+            if self.attr.contains(attr) {
+                params.push(code.to_owned());
+            }
+        }
+        if self.attr.contains(Attr::Underline) {
+            params.push(format!("4:{}", self.underline.code()));
+        }
+
+        if colors.has_colors && (self.fg.is_some() || self.bg.is_some()) {
+            for &(color, fallback, base) in
+                &[(self.fg, Color::White, 30), (self.bg, Color::Black, 40)]
+            {
+                let color = match color.unwrap_or(Color::Default) {
+                    Color::Default if !colors.default_colors => fallback,
+                    color => color,
+                };
+                params.push(match color.to_curses(colors.colors) {
+                    -1 => format!("{}", base + 9),
+                    n @ 0..=7 => format!("{}", base + n),
+                    n @ 8..=15 => format!("{}", base + 60 + n - 8),
+                    n => format!("{};5;{}", base + 8, n),
+                });
+            }
+        }
+        match self.underline_color {
+            Some(Color::Default) => params.push("59".to_owned()),
+            Some(color) if colors.truecolor => {
+                let rgb = color.to_rgb();
+                params.push(format!("58:2::{}:{}:{}", rgb.r, rgb.g, rgb.b));
+            }
+            Some(color) if colors.has_colors => {
+                params.push(format!("58:5:{}", color.to_curses(256)));
+            }
+            _ => {}
+        }
+        format!("\x1B[{}m", params.join(";"))
+    }
+}
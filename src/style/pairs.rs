@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use curses;
+
+/// The most pairs that fit in the color bits of a `chtype`, since
+/// `COLOR_PAIR` shifts the pair number into the attributes unmasked.
+const MAX_PAIRS: usize = 256;
+
+/// Allocates curses color pairs for foreground and background combinations.
+///
+/// *Colors are given as curses color numbers, where `-1` is the terminal
+/// default.*
+#[derive(Debug, Default)]
+pub(crate) struct ColorPairs {
+    pairs: HashMap<(i16, i16), i16>,
+    limit: usize,
+}
+
+impl ColorPairs {
+    /// Creates a new allocator for the given number of pairs, of which at most
+    /// 256 are used.
+    pub fn new(limit: usize) -> ColorPairs {
+        ColorPairs {
+            pairs: HashMap::new(),
+            limit,
+        }
+    }
+
+    /// Gets the pair for the colors, allocating it if needed.
+    ///
+    /// Gives `None` when every pair is in use.
+    pub fn get(&mut self, fg: i16, bg: i16) -> Option<i16> {
+        if fg < 0 && bg < 0 {
+            return Some(0);
+        }
+        if let Some(&pair) = self.pairs.get(&(fg, bg)) {
+            return Some(pair);
+        }
+
+        // Pair 0 is reserved for the default colors.
+        let pair = self.pairs.len() + 1;
+        if pair >= self.limit.min(MAX_PAIRS) {
+            warn!("out of color pairs, ignoring colors");
+            return None;
+        }
+        curses::init_pair(pair as i16, fg, bg);
+        self.pairs.insert((fg, bg), pair as i16);
+        Some(pair as i16)
+    }
+}
@@ -0,0 +1,30 @@
+/// Represents the shape of an underline.
+///
+/// *Terminals without styled underlines show a plain underline instead.*
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum UnderlineStyle {
+    /// A single straight line.
+    #[default]
+    Single,
+    /// Two straight lines.
+    Double,
+    /// A wavy line, as used for spelling errors.
+    Curly,
+    /// A dotted line.
+    Dotted,
+    /// A dashed line.
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Gets the subparameter selecting the underline in `SGR 4:x`.
+    pub(crate) fn code(self) -> u8 {
+        match self {
+            UnderlineStyle::Single => 1,
+            UnderlineStyle::Double => 2,
+            UnderlineStyle::Curly => 3,
+            UnderlineStyle::Dotted => 4,
+            UnderlineStyle::Dashed => 5,
+        }
+    }
+}
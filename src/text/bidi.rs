@@ -0,0 +1,83 @@
+//! Bidirectional text support.
+//!
+//! *Reordering requires the `bidi` feature, without it text is always kept in
+//! logical order.*
+
+use std::borrow::Cow;
+
+#[cfg(feature = "bidi")]
+use unicode_bidi::BidiInfo;
+
+/// Reorders a line of text from logical order into the visual order it should
+/// be displayed in.
+#[cfg(feature = "bidi")]
+pub fn visual_order(line: &str) -> Cow<'_, str> {
+    let info = BidiInfo::new(line, None);
+    if !info.has_rtl() {
+        return Cow::Borrowed(line);
+    }
+
+    let mut visual = String::with_capacity(line.len());
+    for para in &info.paragraphs {
+        visual.push_str(&info.reorder_line(para, para.range.clone()));
+    }
+    Cow::Owned(visual)
+}
+
+/// Reorders a line of text from logical order into the visual order it should
+/// be displayed in.
+#[cfg(not(feature = "bidi"))]
+pub fn visual_order(line: &str) -> Cow<'_, str> {
+    Cow::Borrowed(line)
+}
+
+/// Maps the logical index of each character in a line to its index in visual
+/// order.
+///
+/// Cursors in text fields move through the logical indices, and are displayed
+/// at the mapped visual index.
+#[cfg(feature = "bidi")]
+pub fn visual_map(line: &str) -> Vec<usize> {
+    let info = BidiInfo::new(line, None);
+    if !info.has_rtl() {
+        return (0..line.chars().count()).collect();
+    }
+
+    let mut logical = vec![0; line.len()];
+    for (index, (byte, _)) in line.char_indices().enumerate() {
+        logical[byte] = index;
+    }
+
+    let mut map = vec![0; line.chars().count()];
+    let mut visual = 0;
+    for para in &info.paragraphs {
+        let (levels, runs) = info.visual_runs(para, para.range.clone());
+        for run in runs {
+            let chars: Vec<usize> = line[run.clone()]
+                .char_indices()
+                .map(|(byte, _)| logical[run.start + byte])
+                .collect();
+
+            let mut place = |index: usize| {
+                map[index] = visual;
+                visual += 1;
+            };
+            if levels[run.start].is_rtl() {
+                chars.into_iter().rev().for_each(&mut place);
+            } else {
+                chars.into_iter().for_each(&mut place);
+            }
+        }
+    }
+    map
+}
+
+/// Maps the logical index of each character in a line to its index in visual
+/// order.
+///
+/// Cursors in text fields move through the logical indices, and are displayed
+/// at the mapped visual index.
+#[cfg(not(feature = "bidi"))]
+pub fn visual_map(line: &str) -> Vec<usize> {
+    (0..line.chars().count()).collect()
+}
@@ -0,0 +1,12 @@
+//! Text module.
+
+mod bidi;
+mod width;
+mod wrap;
+
+pub use self::bidi::{visual_map, visual_order};
+pub use self::width::{
+    char_width, display_width, is_non_breaking_space, str_width, truncate, AmbiguousWidth,
+    SOFT_HYPHEN,
+};
+pub use self::wrap::{wrap, Hyphenator, Wrapper};
@@ -0,0 +1,96 @@
+use std::env;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use i18n;
+
+/// Represents how East Asian ambiguous-width characters are measured.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum AmbiguousWidth {
+    /// Ambiguous characters take a single column.
+    #[default]
+    Narrow,
+    /// Ambiguous characters take two columns, as in CJK contexts.
+    Wide,
+}
+
+impl AmbiguousWidth {
+    /// Detects the ambiguous width from the environment.
+    ///
+    /// The `TERMUI_AMBIWIDTH` variable may be set to `narrow` or `wide`,
+    /// otherwise Chinese, Japanese and Korean locales are treated as wide.
+    pub fn detect() -> AmbiguousWidth {
+        match env::var("TERMUI_AMBIWIDTH").as_ref().map(String::as_str) {
+            Ok("wide") | Ok("2") => return AmbiguousWidth::Wide,
+            Ok("narrow") | Ok("1") => return AmbiguousWidth::Narrow,
+            _ => {}
+        }
+
+        match i18n::locale_name() {
+            Some(ref locale)
+                if locale.starts_with("zh")
+                    || locale.starts_with("ja")
+                    || locale.starts_with("ko") =>
+            {
+                AmbiguousWidth::Wide
+            }
+            _ => AmbiguousWidth::Narrow,
+        }
+    }
+}
+
+/// The soft hyphen, marking where a word may be broken with a hyphen, and not
+/// shown anywhere else.
+pub const SOFT_HYPHEN: char = '\u{AD}';
+
+/// Checks whether a character is a space that lines are never broken at,
+/// such as the no-break space.
+pub fn is_non_breaking_space(ch: char) -> bool {
+    matches!(ch, '\u{A0}' | '\u{2007}' | '\u{202F}')
+}
+
+/// Gets the number of columns a character occupies.
+///
+/// *Control characters and soft hyphens have no width.*
+pub fn char_width(ch: char, ambiguous: AmbiguousWidth) -> usize {
+    if ch == SOFT_HYPHEN {
+        return 0;
+    }
+    let width = match ambiguous {
+        AmbiguousWidth::Narrow => ch.width(),
+        AmbiguousWidth::Wide => ch.width_cjk(),
+    };
+    width.unwrap_or(0)
+}
+
+/// Gets the number of columns a string occupies.
+pub fn str_width(s: &str, ambiguous: AmbiguousWidth) -> usize {
+    match ambiguous {
+        AmbiguousWidth::Narrow => s.width(),
+        AmbiguousWidth::Wide => s.width_cjk(),
+    }
+}
+
+/// Gets the number of columns a string occupies, with ambiguous characters
+/// taking a single column.
+///
+/// *Use [`Window::display_width`](../struct.Window.html#method.display_width)
+/// to measure with the window's ambiguous width setting.*
+pub fn display_width(s: &str) -> usize {
+    str_width(s, AmbiguousWidth::Narrow)
+}
+
+/// Cuts a string down to fit in the given number of columns.
+///
+/// *A wide character that would straddle the limit is dropped, while zero
+/// width characters following the last character kept stay with it.*
+pub fn truncate(s: &str, width: usize, ambiguous: AmbiguousWidth) -> &str {
+    let mut used = 0;
+    for (i, ch) in s.char_indices() {
+        used += char_width(ch, ambiguous);
+        if used > width {
+            return &s[..i];
+        }
+    }
+    s
+}
@@ -0,0 +1,167 @@
+use std::mem;
+
+use super::width::{char_width, is_non_breaking_space, str_width, AmbiguousWidth, SOFT_HYPHEN};
+
+/// Finds the positions a word may be broken at with a hyphen.
+///
+/// *With the `hyphenation` feature this is implemented for
+/// `hyphenation::Standard` dictionaries.*
+pub trait Hyphenator {
+    /// Gets the byte indices at which the word may be broken, in ascending
+    /// order.
+    fn breaks(&self, word: &str) -> Vec<usize>;
+}
+
+#[cfg(feature = "hyphenation")]
+impl Hyphenator for ::hyphenation::Standard {
+    fn breaks(&self, word: &str) -> Vec<usize> {
+        ::hyphenation::Hyphenator::hyphenate(self, word).breaks
+    }
+}
+
+/// Wraps text into lines of a maximum width.
+///
+/// Lines are broken greedily between words, but never at no-break spaces.
+/// Words longer than a line are hyphenated at their soft hyphens, or if they
+/// have none where a hyphenator allows, otherwise broken between characters.
+///
+/// ```
+/// use termui::text::wrap;
+///
+/// assert_eq!(wrap("type\u{AD}set\u{AD}ting", 8), ["typeset-", "ting"]);
+/// assert_eq!(wrap("page 10\u{A0}km", 8), ["page", "10\u{A0}km"]);
+/// ```
+///
+/// *Soft hyphens are left out of the lines, unless a word is broken at one,
+/// which shows as `-`.*
+#[derive(Copy, Clone)]
+pub struct Wrapper<'a> {
+    width: usize,
+    ambiguous: AmbiguousWidth,
+    hyphenator: Option<&'a dyn Hyphenator>,
+}
+
+impl<'a> Wrapper<'a> {
+    /// Creates a new wrapper for lines of the given width.
+    pub fn new(width: usize) -> Wrapper<'a> {
+        Wrapper {
+            width: width.max(1),
+            ambiguous: AmbiguousWidth::Narrow,
+            hyphenator: None,
+        }
+    }
+
+    /// Sets how ambiguous-width characters are measured.
+    pub fn ambiguous_width(mut self, ambiguous: AmbiguousWidth) -> Wrapper<'a> {
+        self.ambiguous = ambiguous;
+        self
+    }
+
+    /// Sets the hyphenator used to break words at the end of lines.
+    pub fn hyphenator(mut self, hyphenator: &'a dyn Hyphenator) -> Wrapper<'a> {
+        self.hyphenator = Some(hyphenator);
+        self
+    }
+
+    /// Wraps the text, keeping existing line breaks.
+    pub fn wrap(&self, text: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for para in text.split('\n') {
+            let mut line = String::new();
+            let mut line_width = 0;
+
+            let words = para
+                .split(|ch: char| ch.is_whitespace() && !is_non_breaking_space(ch))
+                .filter(|word| !word.is_empty());
+            for mut word in words {
+                loop {
+                    let word_width = str_width(word, self.ambiguous);
+                    let sep = if line.is_empty() { 0 } else { 1 };
+
+                    if line_width + sep + word_width <= self.width {
+                        if sep == 1 {
+                            line.push(' ');
+                        }
+                        push_visible(&mut line, word);
+                        line_width += sep + word_width;
+                        break;
+                    }
+
+                    let space = self.width.saturating_sub(line_width + sep);
+                    if let Some(split) = self.hyphen_split(word, space) {
+                        if sep == 1 {
+                            line.push(' ');
+                        }
+                        push_visible(&mut line, &word[..split]);
+                        line.push('-');
+                        lines.push(mem::take(&mut line));
+                        line_width = 0;
+                        word = &word[split..];
+                        continue;
+                    }
+
+                    if !line.is_empty() {
+                        lines.push(mem::take(&mut line));
+                        line_width = 0;
+                        continue;
+                    }
+
+                    let split = self.hard_split(word);
+                    let mut part = String::new();
+                    push_visible(&mut part, &word[..split]);
+                    lines.push(part);
+                    word = &word[split..];
+                }
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Finds the last hyphenation point at which the start of the word and a
+    /// hyphen fit in `space` columns.
+    ///
+    /// *The soft hyphens of a word are its hyphenation points, taking the
+    /// place of the hyphenator's.*
+    fn hyphen_split(&self, word: &str, space: usize) -> Option<usize> {
+        let breaks = if word.contains(SOFT_HYPHEN) {
+            word.match_indices(SOFT_HYPHEN)
+                .map(|(i, hyphen)| i + hyphen.len())
+                .collect()
+        } else {
+            self.hyphenator?.breaks(word)
+        };
+
+        breaks
+            .into_iter()
+            .rev()
+            .filter(|&i| i > 0 && i < word.len() && word.is_char_boundary(i))
+            .find(|&i| str_width(&word[..i], self.ambiguous) < space)
+    }
+
+    /// Finds the longest start of the word that fits on a line, always taking
+    /// at least one character.
+    fn hard_split(&self, word: &str) -> usize {
+        let mut width = 0;
+        for (i, ch) in word.char_indices() {
+            width += char_width(ch, self.ambiguous);
+            if width > self.width && i > 0 {
+                return i;
+            }
+        }
+        word.len()
+    }
+}
+
+/// Adds a piece of a word to a line, leaving out its soft hyphens.
+fn push_visible(line: &mut String, word: &str) {
+    line.extend(word.chars().filter(|&ch| ch != SOFT_HYPHEN));
+}
+
+/// Wraps text into lines of a maximum width.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    Wrapper::new(width).wrap(text)
+}
@@ -0,0 +1,60 @@
+//! Spans for the phases of a frame, given to `tracing` subscribers when the
+//! `tracing` feature is enabled, and compiled away otherwise.
+
+#[cfg(feature = "tracing")]
+use tracing::field::Empty;
+#[cfg(feature = "tracing")]
+use tracing::span::EnteredSpan;
+
+/// A phase of reading input or drawing a frame.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Phase {
+    /// Reading and decoding an event from the terminal, with the number of
+    /// `events` given.
+    Decode,
+    /// Splitting an area, with the number of `areas` it was split into.
+    Layout,
+    /// Comparing the drawn buffer with the terminal, with the number of
+    /// `cells` changed and the `runs` of them sent to the backend.
+    Draw,
+    /// Presenting the frame on the terminal.
+    Flush,
+}
+
+/// The span of a phase, left when dropped.
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    inner: EnteredSpan,
+}
+
+impl Span {
+    /// Enters the span of a phase.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn enter(phase: Phase) -> Span {
+        let span = match phase {
+            Phase::Decode => tracing::trace_span!("decode", events = Empty),
+            Phase::Layout => tracing::trace_span!("layout", areas = Empty),
+            Phase::Draw => tracing::trace_span!("draw", cells = Empty, runs = Empty),
+            Phase::Flush => tracing::trace_span!("flush"),
+        };
+        Span {
+            inner: span.entered(),
+        }
+    }
+
+    /// Enters the span of a phase.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn enter(_phase: Phase) -> Span {
+        Span {}
+    }
+
+    /// Records a field of the phase.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn record(&self, field: &'static str, value: usize) {
+        self.inner.record(field, value);
+    }
+
+    /// Records a field of the phase.
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn record(&self, _field: &'static str, _value: usize) {}
+}
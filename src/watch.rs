@@ -0,0 +1,114 @@
+//! Watch module.
+//!
+//! Watches files for changes, delivering
+//! [`Event::FileChanged`](../event/enum.Event.html#variant.FileChanged)
+//! through a window's event queue so displayed content can be reloaded.
+//!
+//! *Requires the `watch` feature.*
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use event::Event;
+use window::WindowHandle;
+
+/// How often files are checked by default.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What is known of a file, to tell when it changed: its modification time
+/// and length, or `None` if it doesn't exist.
+type Stamp = Option<(Option<SystemTime>, u64)>;
+
+type Paths = Arc<Mutex<HashMap<PathBuf, Stamp>>>;
+
+/// Watches files for changes on a background thread, sending an
+/// [`Event::FileChanged`](../event/enum.Event.html#variant.FileChanged) to
+/// the window of its handle for each change.
+///
+/// Files are polled for their modification time and length, so it works on
+/// every platform and filesystem. Creating and removing a watched file are
+/// changes too.
+///
+/// *A watched directory changes when entries are added to or removed from
+/// it, not when the files in it are modified. The thread stops when the
+/// watcher is dropped.*
+#[derive(Debug)]
+pub struct FileWatcher {
+    paths: Paths,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FileWatcher {
+    /// Creates a watcher sending events to the window of the handle.
+    pub fn new(handle: WindowHandle) -> FileWatcher {
+        FileWatcher::with_interval(handle, DEFAULT_INTERVAL)
+    }
+
+    /// Creates a watcher checking the files at the given interval.
+    pub fn with_interval(handle: WindowHandle, interval: Duration) -> FileWatcher {
+        let paths = Paths::default();
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let watched = paths.clone();
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                for (path, stamp) in lock(&watched).iter_mut() {
+                    let now = stamp_of(path);
+                    if now != *stamp {
+                        *stamp = now;
+                        handle.push_event(Event::FileChanged(path.clone()));
+                    }
+                }
+            }
+        });
+
+        FileWatcher {
+            paths,
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Starts watching a file.
+    pub fn watch<P: Into<PathBuf>>(&self, path: P) {
+        let path = path.into();
+        let stamp = stamp_of(&path);
+        lock(&self.paths).insert(path, stamp);
+    }
+
+    /// Stops watching a file.
+    pub fn unwatch<P: AsRef<Path>>(&self, path: P) {
+        lock(&self.paths).remove(path.as_ref());
+    }
+
+    /// Gets the files being watched.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        lock(&self.paths).keys().cloned().collect()
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        // Disconnecting the channel wakes the thread to stop.
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn lock(paths: &Mutex<HashMap<PathBuf, Stamp>>) -> MutexGuard<'_, HashMap<PathBuf, Stamp>> {
+    paths.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn stamp_of(path: &Path) -> Stamp {
+    fs::metadata(path)
+        .ok()
+        .map(|meta| (meta.modified().ok(), meta.len()))
+}
@@ -0,0 +1,158 @@
+use std::time::{Duration, Instant};
+
+use backend::Backend;
+use event::Event;
+use graphics::{self, Frame};
+use layout::Rect;
+use widget::{Image, Widget};
+use window::Window;
+
+/// The shortest time a frame is shown for.
+///
+/// *Many animations use a zero delay, expecting viewers to slow them down.*
+const MIN_DELAY: Duration = Duration::from_millis(20);
+
+/// Represents an animated image, such as an animated GIF.
+///
+/// The animation advances when it is [ticked](#method.tick), dropping frames
+/// it has fallen behind on rather than slowing down.
+///
+/// *Each frame keeps its own encoded image, so looping animations are only
+/// encoded once.*
+#[derive(Debug)]
+pub struct Animation {
+    frames: Vec<(Image, Duration)>,
+    current: usize,
+    started: Instant,
+    paused: Option<Instant>,
+}
+
+impl Animation {
+    /// Creates a new animation from its frames.
+    ///
+    /// *Gives `None` if there are no frames.*
+    pub fn new(frames: Vec<Frame>) -> Option<Animation> {
+        if frames.is_empty() {
+            return None;
+        }
+
+        let frames = frames
+            .into_iter()
+            .map(|frame| (Image::new(frame.bitmap), frame.delay.max(MIN_DELAY)))
+            .collect();
+        Some(Animation {
+            frames,
+            current: 0,
+            started: Instant::now(),
+            paused: None,
+        })
+    }
+
+    /// Creates a new animation from a GIF image.
+    ///
+    /// *Gives `None` if the data is not a valid GIF image.*
+    pub fn from_gif(data: &[u8]) -> Option<Animation> {
+        graphics::decode_gif(data).and_then(Animation::new)
+    }
+
+    /// Gets the index of the frame being shown.
+    pub fn frame_index(&self) -> usize {
+        self.current
+    }
+
+    /// Gets the number of frames in the animation.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Pauses the animation on the current frame.
+    pub fn pause(&mut self) {
+        if self.paused.is_none() {
+            self.paused = Some(Instant::now());
+        }
+    }
+
+    /// Resumes the animation, continuing the current frame from where it was
+    /// paused.
+    pub fn resume(&mut self) {
+        if let Some(paused) = self.paused.take() {
+            self.started += paused.elapsed();
+        }
+    }
+
+    /// Checks whether the animation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Advances the animation to the frame that should be shown at the given
+    /// time.
+    ///
+    /// Returns whether the frame changed, meaning the animation needs to be
+    /// rendered again.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.paused.is_some() || self.frames.len() < 2 {
+            return false;
+        }
+
+        let mut elapsed = now.saturating_duration_since(self.started);
+        let total: Duration = self.frames.iter().map(|&(_, delay)| delay).sum();
+        if elapsed >= total {
+            // Skip any whole loops that were missed.
+            let loops = (elapsed.as_nanos() / total.as_nanos()) as u32;
+            self.started += total * loops;
+            elapsed -= total * loops;
+        }
+
+        let previous = self.current;
+        while elapsed >= self.frames[self.current].1 {
+            let delay = self.frames[self.current].1;
+            elapsed -= delay;
+            self.started += delay;
+            self.current = (self.current + 1) % self.frames.len();
+        }
+
+        if self.current != previous {
+            self.frames[self.current].0.invalidate();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Gets the time until the next frame is due, useful as a timeout when
+    /// waiting for events.
+    ///
+    /// *Gives `None` if the animation is paused or has a single frame.*
+    pub fn time_to_next_frame(&self, now: Instant) -> Option<Duration> {
+        if self.paused.is_some() || self.frames.len() < 2 {
+            return None;
+        }
+        let due = self.started + self.frames[self.current].1;
+        Some(due.saturating_duration_since(now))
+    }
+
+    /// Handles an event, invalidating the animation if the screen was
+    /// cleared.
+    pub fn handle_event(&mut self, event: &Event) {
+        for &mut (ref mut image, _) in &mut self.frames {
+            image.handle_event(event);
+        }
+    }
+
+    /// Renders the current frame into the given area of the window.
+    pub fn render<B: Backend>(&mut self, area: Rect, win: &Window<B>) {
+        self.frames[self.current].0.render(area, win);
+    }
+}
+
+impl<B: Backend> Widget<B> for Animation {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        Animation::render(self, area, win);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        Animation::handle_event(self, event);
+        false
+    }
+}
@@ -0,0 +1,138 @@
+use backend::Backend;
+use event::Event;
+use widget::Widget;
+use window::CursesBackend;
+
+/// What became of an event given to a [`Component`](trait.Component.html).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum EventResult<M> {
+    /// The event was used, and goes no further.
+    Consumed,
+    /// The event was not used, so the parent gets to handle it.
+    Ignored,
+    /// The event was used, and led to a message for the parent, such as an
+    /// item being picked from a list.
+    Bubble(M),
+}
+
+impl<M> EventResult<M> {
+    /// Checks whether the event was used, with or without a message.
+    pub fn is_used(&self) -> bool {
+        !matches!(*self, EventResult::Ignored)
+    }
+
+    /// Converts the message, if any, such as into the message type of a
+    /// parent wrapping it.
+    pub fn map<N, F: FnOnce(M) -> N>(self, f: F) -> EventResult<N> {
+        match self {
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+            EventResult::Bubble(message) => EventResult::Bubble(f(message)),
+        }
+    }
+}
+
+/// Whether a [widget](trait.Widget.html#method.handle_event) used an event.
+impl<M> From<bool> for EventResult<M> {
+    fn from(used: bool) -> EventResult<M> {
+        if used {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+}
+
+/// Represents a widget taking part in [`dispatch`](fn.dispatch.html), either
+/// on its own or as a container of other components, one of which has the
+/// focus.
+///
+/// ```no_run
+/// use termui::event::{Event, Key};
+/// use termui::layout::Rect;
+/// use termui::widget::{dispatch, Component, EventResult, List, Widget};
+/// use termui::Window;
+///
+/// enum Msg {
+///     Picked(usize),
+/// }
+///
+/// struct Picker {
+///     list: List,
+/// }
+///
+/// impl Widget for Picker {
+///     fn render(&mut self, area: Rect, win: &Window) {
+///         self.list.render(area, win);
+///     }
+/// }
+///
+/// impl Component<Msg> for Picker {
+///     fn handle(&mut self, event: &Event) -> EventResult<Msg> {
+///         match *event {
+///             Event::Key { key: Key::Enter, .. } if !event.is_key_release() => {
+///                 match self.list.selected() {
+///                     Some(index) => EventResult::Bubble(Msg::Picked(index)),
+///                     None => EventResult::Consumed,
+///                 }
+///             }
+///             _ => <List as Widget>::handle_event(&mut self.list, event).into(),
+///         }
+///     }
+/// }
+///
+/// # let event = Event::Tick;
+/// let mut picker = Picker { list: List::new(vec!["one", "two"]) };
+/// if let EventResult::Bubble(Msg::Picked(index)) = dispatch(&mut picker, &event) {
+///     println!("picked {}", index);
+/// }
+/// ```
+///
+/// A container gives its focused child from
+/// [`focused_child`](#method.focused_child), and only handles what the child
+/// ignores, or the messages bubbled up from it.
+pub trait Component<M, B: Backend = CursesBackend>: Widget<B> {
+    /// Gets the child with the focus, if this is a container and one has it.
+    fn focused_child(&mut self) -> Option<&mut dyn Component<M, B>> {
+        None
+    }
+
+    /// Handles an event not used by the focused child, or given straight
+    /// to this component.
+    ///
+    /// *Uses [`handle_event`](trait.Widget.html#method.handle_event) by
+    /// default, which gives no messages.*
+    fn handle(&mut self, event: &Event) -> EventResult<M> {
+        self.handle_event(event).into()
+    }
+
+    /// Handles a message bubbled up from the focused child, giving what to
+    /// pass on to the parent.
+    ///
+    /// *Passes the message on by default.*
+    fn handle_message(&mut self, message: M) -> EventResult<M> {
+        EventResult::Bubble(message)
+    }
+}
+
+/// Routes an event down the focus path from a component, giving it to the
+/// deepest focused component first.
+///
+/// Each component on the way back up handles the event if everything below
+/// ignored it, or the message bubbled up from below, until one consumes it.
+/// What is left at the top is given back, such as a message for the
+/// application.
+pub fn dispatch<M, B: Backend>(
+    component: &mut dyn Component<M, B>,
+    event: &Event,
+) -> EventResult<M> {
+    let result = match component.focused_child() {
+        Some(child) => dispatch(child, event),
+        None => EventResult::Ignored,
+    };
+    match result {
+        EventResult::Consumed => EventResult::Consumed,
+        EventResult::Ignored => component.handle(event),
+        EventResult::Bubble(message) => component.handle_message(message),
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt;
+
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::Window;
+
+/// Represents a date and time to the minute, without a time zone.
+///
+/// *Formatted as `YYYY-MM-DD HH:MM`.*
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct DateTime {
+    /// The year, from 0 to 9999.
+    pub year: u16,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+}
+
+impl DateTime {
+    /// Creates a date and time, if it is valid.
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8) -> Option<DateTime> {
+        let datetime = DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        };
+        if datetime.is_valid() {
+            Some(datetime)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether each part is in range, including the day in its month.
+    pub fn is_valid(&self) -> bool {
+        self.year <= 9999
+            && (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+    }
+}
+
+impl Default for DateTime {
+    fn default() -> DateTime {
+        DateTime {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+/// Gets the number of days in a month of a year.
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The parts of a date and time edited on their own.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Segment {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+}
+
+/// The segments in the order they are shown.
+const SEGMENTS: [Segment; 5] = [
+    Segment::Year,
+    Segment::Month,
+    Segment::Day,
+    Segment::Hour,
+    Segment::Minute,
+];
+
+impl Segment {
+    /// Gets the columns of the segment in the formatted date and time.
+    fn columns(self) -> (usize, usize) {
+        match self {
+            Segment::Year => (0, 4),
+            Segment::Month => (5, 2),
+            Segment::Day => (8, 2),
+            Segment::Hour => (11, 2),
+            Segment::Minute => (14, 2),
+        }
+    }
+
+    fn index(self) -> usize {
+        SEGMENTS
+            .iter()
+            .position(|&segment| segment == self)
+            .unwrap_or(0)
+    }
+}
+
+/// Represents an input for a date and time, edited one part at a time.
+///
+/// ```text
+/// 2026-10-14 16:20
+/// ```
+///
+/// Left and Right, or Home and End, move between the year, month, day, hour
+/// and minute, and Up and Down change the one selected, wrapping around
+/// within its range. Typing digits replaces the part once it is filled, or
+/// Enter is pressed, then moves on to the next. Clicking selects a part, and
+/// the mouse wheel changes it.
+///
+/// *Days are kept within their month, and the value within the
+/// [range](#method.set_range) when one is set. Events are ignored while the
+/// input is not focused.*
+#[derive(Clone, Debug)]
+pub struct DateTimeInput {
+    value: DateTime,
+    range: Option<(DateTime, DateTime)>,
+    segment: Segment,
+    typed: String,
+    focused: bool,
+    area: Option<Rect>,
+    style: Style,
+    selected_style: Style,
+}
+
+impl Default for DateTimeInput {
+    fn default() -> DateTimeInput {
+        DateTimeInput::new(DateTime::default())
+    }
+}
+
+impl DateTimeInput {
+    /// Creates a focused input with the given value, selecting the year.
+    pub fn new(value: DateTime) -> DateTimeInput {
+        DateTimeInput {
+            value,
+            range: None,
+            segment: Segment::Year,
+            typed: String::new(),
+            focused: true,
+            area: None,
+            style: Style::default(),
+            selected_style: Style::new().attr(Attr::Reverse),
+        }
+    }
+
+    /// Gets the value entered.
+    pub fn value(&self) -> DateTime {
+        self.value
+    }
+
+    /// Replaces the value, kept within the range, and forgets any digits
+    /// typed.
+    pub fn set_value(&mut self, value: DateTime) {
+        self.value = value;
+        self.typed.clear();
+        self.validate();
+    }
+
+    /// Gets the earliest and latest values allowed, if limited.
+    pub fn range(&self) -> Option<(DateTime, DateTime)> {
+        self.range
+    }
+
+    /// Limits the values allowed to between the earliest and latest, or
+    /// allows any valid value with `None`.
+    pub fn set_range(&mut self, range: Option<(DateTime, DateTime)>) {
+        self.range = range;
+        self.validate();
+    }
+
+    /// Checks whether the input is focused.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether the input is focused, receiving key events and showing
+    /// the selected part.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Gets the style the input is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the input is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the selected part while focused.
+    pub fn selected_style(&self) -> Style {
+        self.selected_style
+    }
+
+    /// Sets the style patched over the selected part while focused, reversed
+    /// by default.
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    /// Selects a part, committing any digits typed.
+    fn select(&mut self, segment: Segment) {
+        self.commit();
+        self.segment = segment;
+    }
+
+    /// Moves the selection by the given number of parts.
+    fn move_by(&mut self, delta: isize) {
+        let index = self.segment.index() as isize + delta;
+        let index = index.clamp(0, SEGMENTS.len() as isize - 1) as usize;
+        self.select(SEGMENTS[index]);
+    }
+
+    /// Changes the selected part by the given amount, wrapping around its
+    /// range.
+    fn step(&mut self, delta: i32) {
+        self.commit();
+        let value = &mut self.value;
+        let wrap = |current: i32, min: i32, max: i32| {
+            (current - min + delta).rem_euclid(max - min + 1) + min
+        };
+        match self.segment {
+            Segment::Year => value.year = (i32::from(value.year) + delta).clamp(0, 9999) as u16,
+            Segment::Month => value.month = wrap(i32::from(value.month), 1, 12) as u8,
+            Segment::Day => {
+                let days = days_in_month(value.year, value.month);
+                value.day = wrap(i32::from(value.day), 1, i32::from(days)) as u8;
+            }
+            Segment::Hour => value.hour = wrap(i32::from(value.hour), 0, 23) as u8,
+            Segment::Minute => value.minute = wrap(i32::from(value.minute), 0, 59) as u8,
+        }
+        self.validate();
+    }
+
+    /// Adds a typed digit, moving on to the next part once it is filled.
+    fn type_digit(&mut self, digit: char) {
+        self.typed.push(digit);
+        if self.typed.len() == self.segment.columns().1 {
+            let segment = self.segment;
+            self.commit();
+            if let Some(&next) = SEGMENTS.get(segment.index() + 1) {
+                self.segment = next;
+            }
+        }
+    }
+
+    /// Puts the digits typed into the selected part, kept in its range.
+    fn commit(&mut self) {
+        let typed = match self.typed.parse::<u16>() {
+            Ok(typed) => typed,
+            Err(_) => return self.typed.clear(),
+        };
+        self.typed.clear();
+        let value = &mut self.value;
+        let part = |max: u16| typed.min(max) as u8;
+        match self.segment {
+            Segment::Year => value.year = typed.min(9999),
+            Segment::Month => value.month = part(12).max(1),
+            Segment::Day => value.day = part(31).max(1),
+            Segment::Hour => value.hour = part(23),
+            Segment::Minute => value.minute = part(59),
+        }
+        self.validate();
+    }
+
+    /// Keeps the day within its month and the value within the range.
+    fn validate(&mut self) {
+        let days = days_in_month(self.value.year, self.value.month);
+        self.value.day = self.value.day.clamp(1, days);
+        if let Some((min, max)) = self.range {
+            self.value = self.value.max(min).min(max.max(min));
+        }
+    }
+
+    /// Gets the part shown at a column, if any.
+    fn segment_at(col: usize) -> Option<Segment> {
+        SEGMENTS.iter().cloned().find(|segment| {
+            let (start, width) = segment.columns();
+            col >= start && col < start + width
+        })
+    }
+}
+
+impl<B: Backend> Widget<B> for DateTimeInput {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let text = self.value.to_string();
+        win.print_styled(area.y, area.x, fit(&text, area.width, win), self.style);
+        if self.focused {
+            let (start, width) = self.segment.columns();
+            if start < area.width {
+                let part = if self.typed.is_empty() {
+                    text[start..start + width].to_owned()
+                } else {
+                    format!("{:_<width$}", self.typed, width = width)
+                };
+                let part = fit(&part, width.min(area.width - start), win);
+                win.print_styled(
+                    area.y,
+                    area.x + start,
+                    part,
+                    self.style.patch(self.selected_style),
+                );
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Char(c) if c.is_ascii_digit() => self.type_digit(c),
+                Key::Backspace if !self.typed.is_empty() => {
+                    self.typed.pop();
+                }
+                Key::Enter if !self.typed.is_empty() => self.move_by(1),
+                Key::Escape if !self.typed.is_empty() => self.typed.clear(),
+                Key::Left => self.move_by(-1),
+                Key::Right => self.move_by(1),
+                Key::Home => self.select(Segment::Year),
+                Key::End => self.select(Segment::Minute),
+                Key::Up => self.step(1),
+                Key::Down => self.step(-1),
+                _ => return false,
+            },
+            Event::Mouse { pos, event, .. } => {
+                let (x, _) = match self.area.and_then(|area| area.to_local(pos)) {
+                    Some(local) => local,
+                    None => return false,
+                };
+                let segment = match DateTimeInput::segment_at(x) {
+                    Some(segment) => segment,
+                    None => return false,
+                };
+                match event {
+                    MouseEvent::Press(MouseButton::Left) => self.select(segment),
+                    MouseEvent::WheelUp => {
+                        self.select(segment);
+                        self.step(1);
+                    }
+                    MouseEvent::WheelDown => {
+                        self.select(segment);
+                        self.step(-1);
+                    }
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,278 @@
+use std::borrow::Cow;
+
+use backend::Backend;
+use event::{Event, Key, Modifier, MouseButton, MouseEvent};
+use i18n::Message;
+use layout::Rect;
+use style::{Attr, Style};
+use text;
+use widget::overlay::{self, Overlay};
+use widget::Widget;
+use window::Window;
+
+/// The label of a button, looked up through the localizer when it is one of
+/// the crate's own.
+#[derive(Clone, Debug)]
+enum Label {
+    Message(Message),
+    Text(String),
+}
+
+impl Label {
+    fn text<B: Backend>(&self, win: &Window<B>) -> Cow<'static, str> {
+        match *self {
+            Label::Message(message) => win.localize(message),
+            Label::Text(ref text) => Cow::Owned(text.clone()),
+        }
+    }
+}
+
+/// Represents a message box with a row of buttons, drawn centered over the
+/// rest of the window while it is open.
+///
+/// ```text
+/// ┌ Quit ──────────────────────┐
+/// │                            │
+/// │ Save changes before        │
+/// │ quitting?                  │
+/// │                            │
+/// │          [ OK ] [ Cancel ] │
+/// └────────────────────────────┘
+/// ```
+///
+/// While open, the dialog takes every key, mouse and paste event, so events
+/// should be given to it before the rest of the interface. Left and Right,
+/// or Tab and Shift+Tab, move between the buttons, Enter or a click picks
+/// one, and Escape cancels. Once dismissed, the next render puts back what
+/// was underneath. When the area is too small for the whole dialog, a
+/// notice that the terminal is too small is drawn in its place.
+///
+/// ```no_run
+/// # use termui::widget::{Dialog, Widget};
+/// # use termui::Window;
+/// # let mut win = Window::new();
+/// let mut dialog = Dialog::new("Quit", "Save changes before quitting?");
+/// dialog.open();
+/// while dialog.is_open() {
+///     if let Some(ev) = win.poll_event() {
+///         <Dialog as Widget>::handle_event(&mut dialog, &ev);
+///     }
+///     let (rows, cols) = win.get_size();
+///     dialog.render(termui::layout::Rect::new(0, 0, cols, rows), &win);
+///     win.refresh();
+/// }
+/// let save = dialog.is_confirmed();
+/// ```
+///
+/// *The buttons are OK and Cancel unless [set](#method.set_buttons), with the
+/// first button confirming.*
+#[derive(Clone, Debug)]
+pub struct Dialog {
+    title: String,
+    message: String,
+    buttons: Vec<Label>,
+    selected: usize,
+    answer: Option<usize>,
+    overlay: Overlay,
+    button_areas: Vec<Rect>,
+    style: Style,
+    selected_style: Style,
+}
+
+impl Dialog {
+    /// Creates a closed dialog with the given title and message.
+    pub fn new<S: Into<String>, T: Into<String>>(title: S, message: T) -> Dialog {
+        Dialog {
+            title: title.into(),
+            message: message.into(),
+            buttons: vec![Label::Message(Message::Ok), Label::Message(Message::Cancel)],
+            selected: 0,
+            answer: None,
+            overlay: Overlay::default(),
+            button_areas: Vec::new(),
+            style: Style::default(),
+            selected_style: Style::new().attr(Attr::Reverse),
+        }
+    }
+
+    /// Sets the message shown.
+    pub fn set_message<S: Into<String>>(&mut self, message: S) {
+        self.message = message.into();
+    }
+
+    /// Replaces the buttons, in the order they are shown.
+    ///
+    /// *With no buttons the dialog can only be cancelled.*
+    pub fn set_buttons<I, S>(&mut self, buttons: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.buttons = buttons
+            .into_iter()
+            .map(|button| Label::Text(button.into()))
+            .collect();
+        self.selected = 0;
+    }
+
+    /// Opens the dialog, selecting the first button.
+    pub fn open(&mut self) {
+        self.selected = 0;
+        self.answer = None;
+        self.overlay.open();
+    }
+
+    /// Closes the dialog as if it was cancelled.
+    pub fn close(&mut self) {
+        self.answer = None;
+        self.overlay.close();
+    }
+
+    /// Checks whether the dialog is open.
+    pub fn is_open(&self) -> bool {
+        self.overlay.is_open()
+    }
+
+    /// Gets the index of the button picked when the dialog was last
+    /// dismissed, or `None` if it was cancelled or is still open.
+    pub fn answer(&self) -> Option<usize> {
+        self.answer
+    }
+
+    /// Checks whether the first button was picked when the dialog was last
+    /// dismissed.
+    pub fn is_confirmed(&self) -> bool {
+        self.answer == Some(0)
+    }
+
+    /// Gets the style the dialog is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the dialog is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the selected button.
+    pub fn selected_style(&self) -> Style {
+        self.selected_style
+    }
+
+    /// Sets the style patched over the selected button, reversed by default.
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    fn pick(&mut self, button: usize) {
+        self.answer = Some(button);
+        self.overlay.close();
+    }
+}
+
+impl<B: Backend> Widget<B> for Dialog {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.button_areas.clear();
+        let labels: Vec<String> = self
+            .buttons
+            .iter()
+            .map(|button| format!("[ {} ]", button.text(win)))
+            .collect();
+        let buttons_width = labels
+            .iter()
+            .map(|label| win.display_width(label) + 1)
+            .sum::<usize>();
+
+        // As wide as the title or buttons, with the message wrapped to half of
+        // the area if it is longer.
+        let title_width = win.display_width(&self.title) + 4;
+        let wrap_width = (area.width / 2).max(buttons_width).max(title_width);
+        let lines: Vec<String> = self
+            .message
+            .lines()
+            .flat_map(|line| text::wrap(line, wrap_width.saturating_sub(4).max(1)))
+            .collect();
+        let text_width = lines
+            .iter()
+            .map(|line| win.display_width(line))
+            .max()
+            .unwrap_or(0);
+        let width = (text_width + 4).max(buttons_width + 3).max(title_width);
+        let height = lines.len() + 5;
+        if width > area.width || height > area.height {
+            // Cut down, the message or buttons would be lost.
+            let notice = win.localize(Message::TerminalTooSmall);
+            let rect = overlay::centered(area, win.display_width(&notice), 1);
+            if self.overlay.begin(rect, win) && rect.width > 0 && rect.height > 0 {
+                let notice = super::truncate(&notice, rect.width, win);
+                win.print_styled(rect.y, rect.x, notice, self.style);
+                self.overlay.end(win);
+            }
+            return;
+        }
+
+        let rect = overlay::centered(area, width, height);
+        if !self.overlay.begin(rect, win) {
+            return;
+        }
+
+        overlay::draw_frame(rect, &self.title, self.style, win);
+        let inner = rect.width - 4;
+        for (i, line) in lines.iter().take(rect.height.saturating_sub(5)).enumerate() {
+            let line = super::truncate(line, inner, win);
+            win.print_styled(rect.y + 2 + i, rect.x + 2, line, self.style);
+        }
+
+        let row = rect.bottom() - 2;
+        let mut col = (rect.right() - 2)
+            .saturating_sub(buttons_width.saturating_sub(1))
+            .max(rect.x + 1);
+        for (i, label) in labels.iter().enumerate() {
+            let width = win.display_width(label);
+            if col + width > rect.right() - 1 {
+                break;
+            }
+            let style = if i == self.selected {
+                self.style.patch(self.selected_style)
+            } else {
+                self.style
+            };
+            win.print_styled(row, col, label, style);
+            self.button_areas.push(Rect::new(col, row, width, 1));
+            col += width + 1;
+        }
+        self.overlay.end(win);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.overlay.is_open() || !overlay::captures(event) {
+            return false;
+        }
+        let count = self.buttons.len();
+        match *event {
+            Event::Key { key, modifier, .. } if !event.is_key_release() => match key {
+                Key::Left if count > 0 => self.selected = (self.selected + count - 1) % count,
+                Key::Tab if count > 0 && modifier.contains(Modifier::Shift) => {
+                    self.selected = (self.selected + count - 1) % count
+                }
+                Key::Right | Key::Tab if count > 0 => self.selected = (self.selected + 1) % count,
+                Key::Enter if self.selected < count => self.pick(self.selected),
+                Key::Escape => self.close(),
+                _ => {}
+            },
+            Event::Mouse {
+                pos,
+                event: MouseEvent::Press(MouseButton::Left),
+                ..
+            } => {
+                if let Some(button) = self.button_areas.iter().position(|area| area.contains(pos)) {
+                    self.pick(button);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+}
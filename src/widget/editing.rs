@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+
+/// The most text a kill ring keeps, dropping the oldest beyond it.
+const KILL_RING_SIZE: usize = 32;
+
+/// Which characters make up words when moving or deleting by word.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum WordChars {
+    /// Letters, digits and underscores, as in identifiers.
+    #[default]
+    Identifier,
+    /// Letters and digits, so `_` and `-` end words too.
+    Alphanumeric,
+    /// Everything but whitespace, as shells split words.
+    NonWhitespace,
+    /// Letters and digits, along with the given characters.
+    Including(String),
+}
+
+impl WordChars {
+    /// Checks whether a character is part of a word.
+    pub fn is_word(&self, ch: char) -> bool {
+        match *self {
+            WordChars::Identifier => ch.is_alphanumeric() || ch == '_',
+            WordChars::Alphanumeric => ch.is_alphanumeric(),
+            WordChars::NonWhitespace => !ch.is_whitespace(),
+            WordChars::Including(ref extra) => ch.is_alphanumeric() || extra.contains(ch),
+        }
+    }
+
+    /// Gets the start of the word before the given byte index, skipping the
+    /// characters between words first.
+    pub(crate) fn word_start(&self, text: &str, index: usize) -> usize {
+        let mut chars = text[..index].char_indices().rev().peekable();
+        while chars.next_if(|&(_, ch)| !self.is_word(ch)).is_some() {}
+        let mut start = chars.peek().map_or(0, |&(i, _)| i);
+        while let Some((i, _)) = chars.next_if(|&(_, ch)| self.is_word(ch)) {
+            start = i;
+        }
+        start
+    }
+
+    /// Gets the end of the word after the given byte index, skipping the
+    /// characters between words first.
+    pub(crate) fn word_end(&self, text: &str, index: usize) -> usize {
+        let mut chars = text[index..].char_indices().peekable();
+        while chars.next_if(|&(_, ch)| !self.is_word(ch)).is_some() {}
+        while chars.next_if(|&(_, ch)| self.is_word(ch)).is_some() {}
+        chars.peek().map_or(text.len(), |&(i, _)| index + i)
+    }
+}
+
+/// Text cut from editing widgets, kept to be yanked back, newest first.
+///
+/// ```
+/// use termui::widget::KillRing;
+///
+/// let ring = KillRing::new();
+/// ring.push("first");
+/// ring.push("second");
+/// assert_eq!(ring.get(0).as_deref(), Some("second"));
+/// assert_eq!(ring.get(1).as_deref(), Some("first"));
+/// ```
+///
+/// *Clones share the same text, so text cut in one widget can be yanked in
+/// another.*
+#[derive(Clone, Default)]
+pub struct KillRing {
+    entries: Rc<RefCell<VecDeque<String>>>,
+}
+
+impl KillRing {
+    /// Creates an empty kill ring.
+    pub fn new() -> KillRing {
+        KillRing::default()
+    }
+
+    /// Adds text as the newest in the ring.
+    pub fn push<S: Into<String>>(&self, text: S) {
+        let mut entries = self.entries.borrow_mut();
+        entries.push_front(text.into());
+        entries.truncate(KILL_RING_SIZE);
+    }
+
+    /// Joins text onto the newest in the ring, before it if the text was cut
+    /// from before the cursor, so kills in a row yank back as one.
+    pub(crate) fn extend(&self, text: &str, before: bool) {
+        let mut entries = self.entries.borrow_mut();
+        match entries.front_mut() {
+            Some(newest) if before => newest.insert_str(0, text),
+            Some(newest) => newest.push_str(text),
+            None => entries.push_front(text.to_owned()),
+        }
+    }
+
+    /// Gets the text the given number of kills back, wrapping around the
+    /// ring.
+    pub fn get(&self, index: usize) -> Option<String> {
+        let entries = self.entries.borrow();
+        if entries.is_empty() {
+            None
+        } else {
+            Some(entries[index % entries.len()].clone())
+        }
+    }
+
+    /// Gets the number of kills kept.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Checks whether nothing has been killed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    /// Forgets all the text kept.
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
+
+impl fmt::Debug for KillRing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KillRing")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// How editing widgets such as [`TextInput`](struct.TextInput.html) move by
+/// words, cut and yank text, and type over it.
+///
+/// ```no_run
+/// use termui::widget::{EditConfig, TextInput, WordChars};
+///
+/// let mut config = EditConfig::new();
+/// config.set_word_chars(WordChars::NonWhitespace);
+///
+/// let mut name = TextInput::new();
+/// let mut path = TextInput::new();
+/// name.set_edit_config(config.clone());
+/// path.set_edit_config(config);
+/// ```
+///
+/// Clones share the [kill ring](struct.KillRing.html), so giving each widget
+/// of an application a clone of one config makes editing feel the same
+/// throughout, with text cut in one widget yanked back in another.
+#[derive(Clone, Debug, Default)]
+pub struct EditConfig {
+    word_chars: WordChars,
+    kill_ring: KillRing,
+    overwrite: bool,
+}
+
+impl EditConfig {
+    /// Creates a config with identifier words, an empty kill ring, inserting
+    /// text.
+    pub fn new() -> EditConfig {
+        EditConfig::default()
+    }
+
+    /// Gets which characters make up words.
+    pub fn word_chars(&self) -> &WordChars {
+        &self.word_chars
+    }
+
+    /// Sets which characters make up words.
+    pub fn set_word_chars(&mut self, word_chars: WordChars) {
+        self.word_chars = word_chars;
+    }
+
+    /// Gets the kill ring text is cut to.
+    pub fn kill_ring(&self) -> &KillRing {
+        &self.kill_ring
+    }
+
+    /// Sets the kill ring text is cut to, such as one shared with other
+    /// configs.
+    pub fn set_kill_ring(&mut self, kill_ring: KillRing) {
+        self.kill_ring = kill_ring;
+    }
+
+    /// Checks whether typing replaces the character under the cursor instead
+    /// of inserting before it.
+    pub fn is_overwrite(&self) -> bool {
+        self.overwrite
+    }
+
+    /// Sets whether typing replaces the character under the cursor instead
+    /// of inserting before it.
+    ///
+    /// *Widgets toggle this on their own copy when Insert is pressed.*
+    pub fn set_overwrite(&mut self, overwrite: bool) {
+        self.overwrite = overwrite;
+    }
+}
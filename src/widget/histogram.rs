@@ -0,0 +1,262 @@
+use backend::Backend;
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::Window;
+
+/// The blocks drawn at the top of a bar, by eighths of a cell filled.
+const EIGHTHS: [&str; 8] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇"];
+
+/// How the samples of a [`Histogram`](struct.Histogram.html) are split into
+/// bins.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Binning {
+    /// As many bins as Sturges' rule gives for the number of samples, one more
+    /// than its base two logarithm.
+    #[default]
+    Sturges,
+    /// The given number of bins, spread over the range of the samples.
+    Count(usize),
+    /// Bins of the given width, lined up on multiples of it.
+    Width(f64),
+}
+
+/// Represents a histogram of raw samples, counting the samples falling in
+/// each bin and drawing the counts as bars, with the range of the samples
+/// below.
+///
+/// ```text
+///             ▅▅ ██ ▁▁
+///          ▄▄ ██ ██ ██
+///       ▁▁ ██ ██ ██ ██ ▅▅ ▁▁
+/// ▁▁ ▂▂ ██ ██ ██ ██ ██ ██ ██ ▁▁
+/// 0.69                      5.05
+/// ```
+///
+/// With a [log scale](#method.set_log_scale) the bars grow with the
+/// logarithm of the counts, so small bins still show beside a tall one.
+///
+/// *Samples that are not finite are left out. Bins that do not fit across the
+/// area are cut off, and the blocks are drawn as `#` in ASCII fallback mode,
+/// rounded to whole cells.*
+#[derive(Clone, Debug, Default)]
+pub struct Histogram {
+    samples: Vec<f64>,
+    binning: Binning,
+    log_scale: bool,
+    style: Style,
+    bar_style: Style,
+}
+
+impl Histogram {
+    /// Creates a histogram of the given samples, binned by Sturges' rule.
+    pub fn new<I: IntoIterator<Item = f64>>(samples: I) -> Histogram {
+        Histogram {
+            samples: samples.into_iter().collect(),
+            ..Histogram::default()
+        }
+    }
+
+    /// Gets the samples.
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Replaces the samples.
+    pub fn set_samples<I: IntoIterator<Item = f64>>(&mut self, samples: I) {
+        self.samples = samples.into_iter().collect();
+    }
+
+    /// Adds a sample.
+    pub fn push(&mut self, sample: f64) {
+        self.samples.push(sample);
+    }
+
+    /// Gets how the samples are split into bins.
+    pub fn binning(&self) -> Binning {
+        self.binning
+    }
+
+    /// Sets how the samples are split into bins.
+    pub fn set_binning(&mut self, binning: Binning) {
+        self.binning = binning;
+    }
+
+    /// Checks whether the bars grow with the logarithm of the counts.
+    pub fn is_log_scale(&self) -> bool {
+        self.log_scale
+    }
+
+    /// Sets whether the bars grow with the logarithm of the counts.
+    pub fn set_log_scale(&mut self, log_scale: bool) {
+        self.log_scale = log_scale;
+    }
+
+    /// Gets the bins, as the start and end of each and the number of samples
+    /// in it.
+    ///
+    /// *A sample on the edge between two bins falls in the later one, except
+    /// at the end of the last bin.*
+    pub fn bins(&self) -> Vec<(f64, f64, usize)> {
+        let finite = || {
+            self.samples
+                .iter()
+                .cloned()
+                .filter(|sample| sample.is_finite())
+        };
+        let (min, max) = finite().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), sample| {
+            (min.min(sample), max.max(sample))
+        });
+        let count = finite().count();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let (start, width, bins) = match self.binning {
+            Binning::Width(width) if width > 0.0 => {
+                let start = (min / width).floor() * width;
+                let bins = ((max - start) / width).floor() as usize + 1;
+                (start, width, bins)
+            }
+            binning => {
+                let bins = match binning {
+                    Binning::Count(bins) => bins.max(1),
+                    _ => (count as f64).log2().ceil() as usize + 1,
+                };
+                let width = (max - min) / bins as f64;
+                (min, width, bins)
+            }
+        };
+        if width <= 0.0 {
+            return vec![(min, max, count)];
+        }
+
+        let mut counts = vec![0; bins];
+        for sample in finite() {
+            let bin = ((sample - start) / width).floor() as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let from = start + width * i as f64;
+                (from, from + width, count)
+            })
+            .collect()
+    }
+
+    /// Gets the style the histogram is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the histogram is drawn in, patched over the style of
+    /// where it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the bars.
+    pub fn bar_style(&self) -> Style {
+        self.bar_style
+    }
+
+    /// Sets the style patched over the bars.
+    pub fn set_bar_style(&mut self, style: Style) {
+        self.bar_style = style;
+    }
+
+    /// Scales a count to a share of the tallest bar.
+    fn scale(&self, count: usize, tallest: usize) -> f64 {
+        if tallest == 0 {
+            0.0
+        } else if self.log_scale {
+            (1.0 + count as f64).ln() / (1.0 + tallest as f64).ln()
+        } else {
+            count as f64 / tallest as f64
+        }
+    }
+}
+
+/// Formats a bound of the range with at most two decimals.
+fn label(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" => "0".to_owned(),
+        text => text.to_owned(),
+    }
+}
+
+impl<B: Backend> Widget<B> for Histogram {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let bins = self.bins();
+        let axis = (area.height >= 2 && !bins.is_empty()) as usize;
+        let height = area.height - axis;
+
+        // Wide areas give each bar several columns, with a gap between.
+        let each = (area.width / bins.len().max(1)).max(1);
+        let bar = if each >= 3 { each - 1 } else { each };
+        let tallest = bins.iter().map(|&(_, _, count)| count).max().unwrap_or(0);
+        let eighths: Vec<usize> = bins
+            .iter()
+            .take(area.width.div_ceil(each))
+            .map(|&(_, _, count)| {
+                let eighths = self.scale(count, tallest) * (height * 8) as f64;
+                match eighths.round() as usize {
+                    // A bin with samples always shows.
+                    0 if count > 0 => 1,
+                    eighths => eighths,
+                }
+            })
+            .collect();
+
+        let ascii = win.ascii_mode();
+        for y in 0..height {
+            // The eighths of a cell below this row.
+            let below = (height - 1 - y) * 8;
+            win.print_styled(area.y + y, area.x, " ".repeat(area.width), self.style);
+            for (i, &filled) in eighths.iter().enumerate() {
+                let block = match filled.saturating_sub(below) {
+                    filled if filled >= 8 => {
+                        if ascii {
+                            "#"
+                        } else {
+                            "█"
+                        }
+                    }
+                    filled if ascii && filled >= 4 => "#",
+                    _ if ascii => continue,
+                    0 => continue,
+                    filled => EIGHTHS[filled],
+                };
+                let x = i * each;
+                let width = bar.min(area.width - x);
+                win.print_styled(
+                    area.y + y,
+                    area.x + x,
+                    block.repeat(width),
+                    self.style.patch(self.bar_style),
+                );
+            }
+        }
+
+        if axis == 1 {
+            let first = label(bins[0].0);
+            let last = label(bins[bins.len() - 1].1);
+            let width = (eighths.len() * each).min(area.width);
+            let gap = width.saturating_sub(first.len() + last.len()).max(1);
+            let line = format!("{}{}{}", first, " ".repeat(gap), last);
+            win.print_styled(
+                area.bottom() - 1,
+                area.x,
+                fit(&line, area.width, win),
+                self.style.patch(Style::new().attr(Attr::Dim)),
+            );
+        }
+    }
+}
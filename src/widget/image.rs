@@ -0,0 +1,223 @@
+use std::rc::Rc;
+
+use backend::Backend;
+use event::{Event, MouseEvent};
+use graphics::{self, Bitmap};
+use layout::Rect;
+use widget::Widget;
+use window::Window;
+
+/// The characters used to shade images on terminals without graphics, from
+/// darkest to brightest.
+const RAMP: &[u8] = b" .:-=+*#%@";
+
+/// Identifies an encoded payload by the size it was encoded for.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct CacheKey {
+    width: usize,
+    height: usize,
+    cell_size: Option<(usize, usize)>,
+}
+
+/// Represents an image scaled to fit the area it is rendered into.
+///
+/// The image is letterboxed to keep its aspect ratio, and drawn with sixel
+/// graphics when the terminal supports them. Otherwise it is shaded using
+/// text.
+///
+/// *The encoded image is cached between frames, and only sent to the
+/// terminal again when its area changes, the screen is redrawn or scrolled,
+/// or it is [invalidated](#method.invalidate).*
+#[derive(Debug)]
+pub struct Image {
+    bitmap: Bitmap,
+    cache: Option<(CacheKey, Rc<str>)>,
+    /// The area the image was last placed in, and the viewport of the window
+    /// at the time.
+    placed: Option<(Rect, usize)>,
+}
+
+impl Image {
+    /// Creates a new image widget.
+    pub fn new(bitmap: Bitmap) -> Image {
+        Image {
+            bitmap,
+            cache: None,
+            placed: None,
+        }
+    }
+
+    /// Gets the bitmap being displayed.
+    pub fn bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    /// Replaces the bitmap being displayed.
+    pub fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+        self.cache = None;
+        self.placed = None;
+    }
+
+    /// Marks the image as needing to be drawn again on the next render.
+    ///
+    /// *This should be called whenever the area under the image may have been
+    /// overwritten, such as after scrolling.*
+    pub fn invalidate(&mut self) {
+        self.placed = None;
+    }
+
+    /// Handles an event, invalidating the image if the screen was cleared or
+    /// may have scrolled.
+    pub fn handle_event(&mut self, event: &Event) {
+        match *event {
+            Event::Resize | Event::Refresh => self.invalidate(),
+            Event::Mouse {
+                event:
+                    MouseEvent::WheelUp
+                    | MouseEvent::WheelDown
+                    | MouseEvent::WheelLeft
+                    | MouseEvent::WheelRight,
+                ..
+            } => self.invalidate(),
+            _ => {}
+        }
+    }
+
+    /// Renders the image into the given area of the window.
+    pub fn render<B: Backend>(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let cell_size = win.cell_size().filter(|_| win.capabilities().sixel);
+        let key = CacheKey {
+            width: area.width,
+            height: area.height,
+            cell_size,
+        };
+        let payload = match self.cache {
+            Some((cached, ref payload)) if cached == key => payload.clone(),
+            _ => {
+                let payload: Rc<str> = match cell_size {
+                    Some((w, h)) => {
+                        let scaled = self.bitmap.letterbox(area.width * w, area.height * h);
+                        graphics::encode_sixel(&scaled).into()
+                    }
+                    None => self.shade(area.width, area.height).into(),
+                };
+                self.cache = Some((key, payload.clone()));
+                payload
+            }
+        };
+
+        if cell_size.is_some() {
+            // Keep curses from drawing its own contents over the image.
+            let blank = " ".repeat(area.width);
+            for row in area.y..area.bottom() {
+                win.print(row, area.x, &blank);
+            }
+            let placement = (area, win.viewport());
+            if self.placed != Some(placement) {
+                win.place_graphic(area.y, area.x, payload);
+                self.placed = Some(placement);
+            }
+        } else {
+            for (row, line) in (area.y..area.bottom()).zip(payload.lines()) {
+                win.print(row, area.x, line);
+            }
+            self.placed = Some((area, win.viewport()));
+        }
+    }
+
+    /// Shades the image into lines of text.
+    ///
+    /// *Cells are roughly twice as tall as they are wide, so each cell covers
+    /// two rows of pixels.*
+    fn shade(&self, width: usize, height: usize) -> String {
+        let scaled = self.bitmap.letterbox(width, height * 2);
+        let mut out = String::with_capacity((width + 1) * height);
+        for row in 0..height {
+            if row > 0 {
+                out.push('\n');
+            }
+            for col in 0..width {
+                let pixels = [scaled.get(col, row * 2), scaled.get(col, row * 2 + 1)];
+                let (sum, count) = pixels.iter().flatten().fold((0, 0), |(sum, count), rgb| {
+                    let luma =
+                        299 * u32::from(rgb.r) + 587 * u32::from(rgb.g) + 114 * u32::from(rgb.b);
+                    (sum + luma / 1000, count + 1)
+                });
+                out.push(match sum.checked_div(count) {
+                    Some(luma) => char::from(RAMP[luma as usize * (RAMP.len() - 1) / 255]),
+                    None => ' ',
+                });
+            }
+        }
+        out
+    }
+}
+
+impl<B: Backend> Widget<B> for Image {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        Image::render(self, area, win);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        Image::handle_event(self, event);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Image;
+    use backend::TestBackend;
+    use event::{Event, Modifier, MouseEvent};
+    use graphics::Bitmap;
+    use layout::Rect;
+    use style::Rgb;
+    use text::AmbiguousWidth;
+    use window::Window;
+
+    fn window() -> Window<TestBackend> {
+        let mut backend = TestBackend::new(4, 8);
+        backend.capabilities_mut().sixel = true;
+        backend.capabilities_mut().cell_size = Some((2, 4));
+        Window::with_backend(backend)
+    }
+
+    /// Renders the image and refreshes, giving the number of graphics drawn.
+    fn draw(image: &mut Image, win: &Window<TestBackend>) -> usize {
+        image.render(Rect::new(0, 0, 4, 2), win);
+        win.refresh();
+        win.backend().graphics().len()
+    }
+
+    #[test]
+    fn places_again_when_screen_changes() {
+        let mut win = window();
+        let mut bitmap = Bitmap::new(2, 2);
+        bitmap.set(0, 0, Some(Rgb::new(255, 0, 0)));
+        let mut image = Image::new(bitmap);
+        assert_eq!(draw(&mut image, &win), 1);
+        assert_eq!(draw(&mut image, &win), 0);
+
+        image.handle_event(&Event::Mouse {
+            pos: (0, 0),
+            pixel: None,
+            event: MouseEvent::WheelDown,
+            modifier: Modifier::None,
+        });
+        assert_eq!(draw(&mut image, &win), 1);
+        assert_eq!(draw(&mut image, &win), 0);
+
+        // Both redraw the whole screen, without an event to go by.
+        win.set_ambiguous_width(AmbiguousWidth::Wide);
+        assert_eq!(draw(&mut image, &win), 1);
+        assert_eq!(draw(&mut image, &win), 0);
+        win.backend_mut().resize(5, 8);
+        assert_eq!(draw(&mut image, &win), 1);
+        assert_eq!(draw(&mut image, &win), 0);
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeSet;
+
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::Window;
+
+/// The number of items scrolled by the mouse wheel.
+const WHEEL_STEP: usize = 3;
+
+/// Represents a scrollable list of items, with one selected.
+///
+/// The selection is moved with the arrow keys, Page Up and Page Down, Home and
+/// End, or by clicking an item. The mouse wheel scrolls without moving the
+/// selection.
+///
+/// Items can be marked as the headers of sections, drawn bold. The header of
+/// the section at the top of the view sticks to the first row while the rest
+/// of its section is scrolled through.
+#[derive(Clone, Debug, Default)]
+pub struct List {
+    items: Vec<String>,
+    selected: Option<usize>,
+    offset: usize,
+    follow: bool,
+    headers: BTreeSet<usize>,
+    area: Option<Rect>,
+    style: Style,
+    header_style: Style,
+}
+
+impl List {
+    /// Creates a new list, with the first item selected.
+    pub fn new<I, S>(items: I) -> List
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut list = List::default();
+        list.set_items(items);
+        list
+    }
+
+    /// Gets the items in the list.
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Replaces the items in the list, keeping the selection in range.
+    pub fn set_items<I, S>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.items = items.into_iter().map(Into::into).collect();
+        self.select(self.selected.or(Some(0)));
+    }
+
+    /// Gets the style the list is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the list is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the index of the selected item.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Gets the selected item.
+    pub fn selected_item(&self) -> Option<&str> {
+        self.selected.map(|index| &*self.items[index])
+    }
+
+    /// Selects the item at the given index, scrolling it into view on the
+    /// next render.
+    ///
+    /// *Indices past the end select the last item.*
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = match index {
+            Some(_) if self.items.is_empty() => None,
+            Some(index) => Some(index.min(self.items.len() - 1)),
+            None => None,
+        };
+        self.follow = true;
+    }
+
+    /// Marks the items at the given indices as section headers, replacing
+    /// the headers marked before.
+    pub fn set_headers<I: IntoIterator<Item = usize>>(&mut self, headers: I) {
+        self.headers = headers.into_iter().collect();
+    }
+
+    /// Checks whether the item at the given index is a section header.
+    pub fn is_header(&self, index: usize) -> bool {
+        self.headers.contains(&index)
+    }
+
+    /// Gets the style patched over headers, after making them bold.
+    pub fn header_style(&self) -> Style {
+        self.header_style
+    }
+
+    /// Sets the style patched over headers, after making them bold.
+    pub fn set_header_style(&mut self, style: Style) {
+        self.header_style = style;
+    }
+
+    /// Gets the index of the first visible item.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Scrolls so the given item is the first visible, as far as the list
+    /// allows, without moving the selection.
+    pub fn scroll_to(&mut self, index: usize) {
+        self.offset = index.min(self.items.len().saturating_sub(self.page()));
+        self.follow = false;
+    }
+
+    /// Moves the selection by the given number of items.
+    fn move_by(&mut self, delta: isize) {
+        let index = match self.selected {
+            Some(index) if delta < 0 => index.saturating_sub(delta.unsigned_abs()),
+            Some(index) => index.saturating_add(delta as usize),
+            None => 0,
+        };
+        self.select(Some(index));
+    }
+
+    /// Gets the header stuck to the first row with the view scrolled to the
+    /// given offset, the header of a section that starts above it.
+    fn sticky_header(&self, offset: usize) -> Option<usize> {
+        if self.headers.contains(&offset) {
+            return None;
+        }
+        self.headers.range(..offset).next_back().cloned()
+    }
+
+    /// Gets the item shown on a row of the view.
+    fn item_at(&self, row: usize) -> usize {
+        match self.sticky_header(self.offset) {
+            Some(header) if row == 0 => header,
+            _ => self.offset + row,
+        }
+    }
+
+    /// Gets the number of items shown at once.
+    fn page(&self) -> usize {
+        self.area.map_or(1, |area| area.height.max(1))
+    }
+}
+
+impl<B: Backend> Widget<B> for List {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.height == 0 {
+            return;
+        }
+
+        if let (true, Some(selected)) = (self.follow, self.selected) {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if selected >= self.offset + area.height {
+                self.offset = selected + 1 - area.height;
+            }
+            // The selection can't hide under a sticky header.
+            if selected == self.offset && self.sticky_header(selected).is_some() {
+                self.offset -= 1;
+            }
+        }
+        self.follow = false;
+        self.offset = self
+            .offset
+            .min(self.items.len().saturating_sub(area.height));
+
+        for row in 0..area.height {
+            let index = self.item_at(row);
+            let item = self.items.get(index).map_or("", |item| &**item);
+            let line = fit(item, area.width, win);
+            let mut style = self.style;
+            if self.is_header(index) {
+                style = style
+                    .patch(Style::new().attr(Attr::Bold))
+                    .patch(self.header_style);
+            }
+            if Some(index) == self.selected {
+                style = style.patch(Style::new().attr(Attr::Reverse));
+            }
+            win.print_styled(area.y + row, area.x, line, style);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => {
+                let page = self.page() as isize;
+                match key {
+                    Key::Up => self.move_by(-1),
+                    Key::Down => self.move_by(1),
+                    Key::PageUp => self.move_by(-page),
+                    Key::PageDown => self.move_by(page),
+                    Key::Home => self.select(Some(0)),
+                    Key::End => self.select(Some(self.items.len())),
+                    _ => return false,
+                }
+                true
+            }
+            Event::Mouse { pos, event, .. } => {
+                let (_, row) = match self.area.and_then(|area| area.to_local(pos)) {
+                    Some(local) => local,
+                    None => return false,
+                };
+                let index = self.item_at(row);
+                match event {
+                    MouseEvent::Press(MouseButton::Left) if index < self.items.len() => {
+                        self.select(Some(index))
+                    }
+                    MouseEvent::WheelUp => self.offset = self.offset.saturating_sub(WHEEL_STEP),
+                    MouseEvent::WheelDown => self.offset += WHEEL_STEP,
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
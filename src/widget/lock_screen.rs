@@ -0,0 +1,333 @@
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use backend::Backend;
+use event::{Event, Key, Modifier, MouseEvent};
+use i18n::Message;
+use layout::Rect;
+use style::{Attr, Style};
+use widget::overlay::{self, Overlay};
+use widget::Widget;
+use window::Window;
+
+/// The narrowest the box on a locked screen is drawn.
+const MIN_WIDTH: usize = 28;
+
+type Check = Rc<dyn Fn(&str) -> bool>;
+
+/// How a [`LockScreen`](struct.LockScreen.html) hides what is underneath.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Cover {
+    /// Clears the whole area.
+    #[default]
+    Blank,
+    /// Fills the whole area with a shade, so it is plain the interface is
+    /// still running underneath.
+    Shade,
+}
+
+/// Represents a lock over the whole interface, such as for an operator
+/// console left unattended, hiding everything until it is unlocked.
+///
+/// ```text
+/// ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░
+/// ░░░░┌ Locked ──────────────────┐░░░░░░
+/// ░░░░│                          │░░░░░░
+/// ░░░░│ Passphrase:              │░░░░░░
+/// ░░░░│ ••••••                   │░░░░░░
+/// ░░░░│                          │░░░░░░
+/// ░░░░└──────────────────────────┘░░░░░░
+/// ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░
+/// ```
+///
+/// The screen locks when [asked](#method.lock), or once there has been no
+/// input for the [idle timeout](#method.set_idle_timeout), checked when it
+/// is [ticked](#method.tick) or given an
+/// [`Event::Tick`](../event/enum.Event.html#variant.Tick) or
+/// [`Event::Idle`](../event/enum.Event.html#variant.Idle). It should be
+/// given every event before the rest of the interface, and rendered over the
+/// whole window after it.
+///
+/// While locked, it takes every key, mouse and paste event. Any key or click
+/// unlocks it, unless a [passphrase check](#method.set_passphrase_check) is
+/// set, in which case the passphrase is typed, shown masked, and Enter
+/// unlocks the screen if the check accepts it. Escape clears what was typed.
+///
+/// *The passphrase typed is dropped as soon as it is checked. Once unlocked,
+/// the next render puts back what was underneath.*
+#[derive(Clone)]
+pub struct LockScreen {
+    timeout: Option<Duration>,
+    last_input: Instant,
+    check: Option<Check>,
+    entered: String,
+    refused: bool,
+    message: String,
+    cover: Cover,
+    overlay: Overlay,
+    style: Style,
+    error_style: Style,
+}
+
+impl Default for LockScreen {
+    fn default() -> LockScreen {
+        LockScreen::new()
+    }
+}
+
+impl LockScreen {
+    /// Creates an unlocked screen, which only locks when asked and unlocks
+    /// with any key.
+    pub fn new() -> LockScreen {
+        LockScreen {
+            timeout: None,
+            last_input: Instant::now(),
+            check: None,
+            entered: String::new(),
+            refused: false,
+            message: String::new(),
+            cover: Cover::default(),
+            overlay: Overlay::default(),
+            style: Style::default(),
+            error_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Locks the screen.
+    pub fn lock(&mut self) {
+        self.entered.clear();
+        self.refused = false;
+        self.overlay.open();
+    }
+
+    /// Unlocks the screen, without asking for the passphrase.
+    pub fn unlock(&mut self) {
+        self.entered.clear();
+        self.refused = false;
+        self.last_input = Instant::now();
+        self.overlay.close();
+    }
+
+    /// Checks whether the screen is locked.
+    pub fn is_locked(&self) -> bool {
+        self.overlay.is_open()
+    }
+
+    /// Gets how long the screen waits without input before locking.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets how long the screen waits without input before locking, or
+    /// `None` to only lock when asked, as by default.
+    pub fn set_idle_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Sets a check run on the passphrase typed when Enter is pressed,
+    /// giving whether it unlocks the screen.
+    pub fn set_passphrase_check<F>(&mut self, check: F)
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.check = Some(Rc::new(check));
+    }
+
+    /// Removes the passphrase check, so any key unlocks the screen again.
+    pub fn clear_passphrase_check(&mut self) {
+        self.check = None;
+    }
+
+    /// Gets the message shown above the prompt.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Sets a message shown above the prompt, such as who locked the
+    /// console, or an empty message to show none.
+    pub fn set_message<S: Into<String>>(&mut self, message: S) {
+        self.message = message.into();
+    }
+
+    /// Gets how what is underneath is hidden.
+    pub fn cover(&self) -> Cover {
+        self.cover
+    }
+
+    /// Sets how what is underneath is hidden, blanking it by default.
+    pub fn set_cover(&mut self, cover: Cover) {
+        self.cover = cover;
+    }
+
+    /// Gets the style the screen is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the screen is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the error shown for a wrong passphrase.
+    pub fn error_style(&self) -> Style {
+        self.error_style
+    }
+
+    /// Sets the style patched over the error shown for a wrong passphrase,
+    /// bold by default.
+    pub fn set_error_style(&mut self, style: Style) {
+        self.error_style = style;
+    }
+
+    /// Locks the screen if there has been no input for the idle timeout by
+    /// the given time.
+    ///
+    /// Returns whether it locked, meaning it needs to be rendered.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        match self.timeout {
+            Some(timeout)
+                if !self.is_locked()
+                    && now.saturating_duration_since(self.last_input) >= timeout =>
+            {
+                self.lock();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_locked(&mut self, event: &Event) {
+        let check = match self.check {
+            Some(ref check) => check.clone(),
+            None => {
+                let pressed = match *event {
+                    Event::Key { .. } => !event.is_key_release(),
+                    Event::Mouse { event, .. } => matches!(event, MouseEvent::Press(_)),
+                    _ => false,
+                };
+                if pressed {
+                    self.unlock();
+                }
+                return;
+            }
+        };
+        match *event {
+            Event::Key { .. } if event.is_key_release() => {}
+            Event::Key {
+                key: Key::Enter, ..
+            } => {
+                let accepted = check(&self.entered);
+                self.entered.clear();
+                if accepted {
+                    self.unlock();
+                } else {
+                    self.refused = true;
+                }
+            }
+            Event::Key {
+                key: Key::Escape, ..
+            } => self.entered.clear(),
+            Event::Key {
+                key: Key::Backspace,
+                ..
+            } => {
+                self.entered.pop();
+            }
+            Event::Key {
+                key: Key::Char(ch),
+                modifier,
+                ..
+            } if (modifier - Modifier::Shift).is_empty() && !ch.is_control() => {
+                self.entered.push(ch);
+                self.refused = false;
+            }
+            Event::Paste(ref text) => self.entered.push_str(text.lines().next().unwrap_or("")),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Debug for LockScreen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LockScreen")
+            .field("timeout", &self.timeout)
+            .field("last_input", &self.last_input)
+            .field("has_check", &self.check.is_some())
+            .field("refused", &self.refused)
+            .field("message", &self.message)
+            .field("cover", &self.cover)
+            .field("overlay", &self.overlay)
+            .finish()
+    }
+}
+
+impl<B: Backend> Widget<B> for LockScreen {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if !self.overlay.begin(area, win) || area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let fill = match self.cover {
+            Cover::Blank => " ",
+            Cover::Shade if win.ascii_mode() => ":",
+            Cover::Shade => "░",
+        };
+        let fill = fill.repeat(area.width);
+        for row in area.y..area.bottom() {
+            win.print_styled(row, area.x, &fill, self.style);
+        }
+
+        let prompt = if self.check.is_some() {
+            win.localize(Message::Passphrase)
+        } else {
+            win.localize(Message::PressAnyKey)
+        };
+        let error = win.localize(Message::WrongPassphrase);
+        let mut lines = Vec::new();
+        if !self.message.is_empty() {
+            lines.push((self.message.clone(), self.style));
+        }
+        lines.push((prompt.into_owned(), self.style));
+        if self.check.is_some() {
+            let mask = if win.ascii_mode() { "*" } else { "•" };
+            lines.push((mask.repeat(self.entered.chars().count()), self.style));
+            if self.refused {
+                lines.push((error.into_owned(), self.style.patch(self.error_style)));
+            }
+        }
+
+        let width = lines
+            .iter()
+            .map(|(line, _)| win.display_width(line) + 4)
+            .chain(Some(MIN_WIDTH))
+            .max()
+            .unwrap_or(MIN_WIDTH);
+        let rect = overlay::centered(area, width, lines.len() + 4);
+        if rect.width >= 5 && rect.height >= 3 {
+            let title = win.localize(Message::Locked);
+            overlay::draw_frame(rect, &title, self.style, win);
+            let inner = rect.width - 4;
+            for (i, (line, style)) in lines.iter().enumerate().take(rect.height - 3) {
+                let line = super::truncate(line, inner, win);
+                win.print_styled(rect.y + 2 + i, rect.x + 2, line, *style);
+            }
+        }
+        self.overlay.end(win);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Tick | Event::Idle(_) => return self.tick(Instant::now()),
+            _ if !overlay::captures(event) => return false,
+            _ if !self.is_locked() => {
+                self.last_input = Instant::now();
+                return false;
+            }
+            _ => self.handle_locked(event),
+        }
+        true
+    }
+}
@@ -0,0 +1,267 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use backend::Backend;
+use event::{Event, Key, MouseEvent};
+use layout::Rect;
+use style::{AnsiParser, Style};
+use text;
+use widget::{truncate, Widget};
+use window::Window;
+
+/// The number of lines scrolled by the mouse wheel.
+const WHEEL_STEP: usize = 3;
+/// The number of lines kept by default.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Represents a log of lines appended over time, such as the output of a
+/// command, parsed for ANSI colors.
+///
+/// The view follows the end of the log as lines are added, until scrolled
+/// back with the arrow keys, Page Up, Home, or the mouse wheel. Scrolling
+/// back to the end, or pressing End, follows it again. The same scrolling is
+/// available to the application, through methods such as
+/// [`scroll_up`](#method.scroll_up) and
+/// [`scroll_to_bottom`](#method.scroll_to_bottom).
+///
+/// *Lines are cut to the width of the area rather than wrapped. The oldest
+/// lines are dropped past the [capacity](#method.set_capacity).*
+#[derive(Clone, Debug)]
+pub struct LogView {
+    lines: VecDeque<Vec<(String, Style)>>,
+    capacity: usize,
+    parser: AnsiParser,
+    /// The index of the first visible line, `None` when following the end.
+    scroll: Option<usize>,
+    area: Option<Rect>,
+    style: Style,
+}
+
+impl LogView {
+    /// Creates a new, empty log view.
+    pub fn new() -> LogView {
+        LogView {
+            lines: VecDeque::new(),
+            capacity: DEFAULT_CAPACITY,
+            parser: AnsiParser::new(),
+            scroll: None,
+            area: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Gets the style the log view is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the log view is drawn in, patched over the style of
+    /// where it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the number of lines kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the number of lines kept, dropping the oldest lines past it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    /// Gets the number of lines in the log.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Checks whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Appends a line, parsing its ANSI escape sequences.
+    pub fn push_line(&mut self, line: &str) {
+        self.push_line_styled(line, Style::default());
+    }
+
+    /// Appends a line in the given style, patched under the styles of its ANSI
+    /// escape sequences.
+    pub fn push_line_styled(&mut self, line: &str, style: Style) {
+        let runs = self
+            .parser
+            .parse(line)
+            .into_iter()
+            .map(|(text, run)| (text, style.patch(run)))
+            .collect();
+        self.lines.push_back(runs);
+        self.trim();
+    }
+
+    /// Removes every line, and resets the style carried between lines.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.parser = AnsiParser::new();
+        self.scroll = None;
+    }
+
+    /// Checks whether the view follows the end of the log.
+    pub fn is_following(&self) -> bool {
+        self.scroll.is_none()
+    }
+
+    /// Gets the index of the first visible line.
+    pub fn first_line(&self) -> usize {
+        self.first_visible(self.area.map_or(1, |area| area.height))
+    }
+
+    /// Scrolls so the given line is the first visible, or follows the end of
+    /// the log when it is reached.
+    pub fn scroll_to(&mut self, line: usize) {
+        let height = self.area.map_or(1, |area| area.height);
+        let max = self.lines.len().saturating_sub(height);
+        self.scroll = if line >= max { None } else { Some(line) };
+    }
+
+    /// Scrolls back by the given number of lines, no longer following the
+    /// end of the log once it leaves the view.
+    pub fn scroll_up(&mut self, lines: usize) {
+        let first = self.first_line();
+        self.scroll_to(first.saturating_sub(lines));
+    }
+
+    /// Scrolls forward by the given number of lines, following the end of the
+    /// log again when it is reached.
+    pub fn scroll_down(&mut self, lines: usize) {
+        let first = self.first_line();
+        self.scroll_to(first.saturating_add(lines));
+    }
+
+    /// Scrolls back by a page, the height of the view less a line.
+    pub fn page_up(&mut self) {
+        let page = self.page();
+        self.scroll_up(page);
+    }
+
+    /// Scrolls forward by a page, the height of the view less a line.
+    pub fn page_down(&mut self) {
+        let page = self.page();
+        self.scroll_down(page);
+    }
+
+    /// Scrolls to the start of the log.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = Some(0);
+    }
+
+    /// Scrolls to the end of the log, following it as lines are added.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = None;
+    }
+
+    fn page(&self) -> usize {
+        self.area
+            .map_or(1, |area| area.height.saturating_sub(1).max(1))
+    }
+
+    fn first_visible(&self, height: usize) -> usize {
+        let max = self.lines.len().saturating_sub(height);
+        self.scroll.map_or(max, |scroll| scroll.min(max))
+    }
+
+    fn trim(&mut self) {
+        while self.lines.len() > self.capacity {
+            self.lines.pop_front();
+            // Keep the same lines in view while scrolled back.
+            if let Some(ref mut scroll) = self.scroll {
+                *scroll = scroll.saturating_sub(1);
+            }
+        }
+    }
+}
+
+impl Default for LogView {
+    fn default() -> LogView {
+        LogView::new()
+    }
+}
+
+impl<B: Backend> Widget<B> for LogView {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let first = self.first_visible(area.height);
+        for row in 0..area.height {
+            let mut col = 0;
+            if let Some(runs) = self.lines.get(first + row) {
+                for &(ref text, style) in visual_runs(runs).iter() {
+                    if col >= area.width {
+                        break;
+                    }
+                    let text = truncate(text, area.width - col, win);
+                    win.print_styled(area.y + row, area.x + col, text, self.style.patch(style));
+                    col += win.display_width(text);
+                }
+            }
+            win.print_styled(
+                area.y + row,
+                area.x + col,
+                " ".repeat(area.width - col),
+                self.style,
+            );
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Up => self.scroll_up(1),
+                Key::Down => self.scroll_down(1),
+                Key::PageUp => self.page_up(),
+                Key::PageDown => self.page_down(),
+                Key::Home => self.scroll_to_top(),
+                Key::End => self.scroll_to_bottom(),
+                _ => return false,
+            },
+            Event::Mouse { pos, event, .. } if self.area.is_some_and(|area| area.contains(pos)) => {
+                match event {
+                    MouseEvent::WheelUp => self.scroll_up(WHEEL_STEP),
+                    MouseEvent::WheelDown => self.scroll_down(WHEEL_STEP),
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Reorders the styled runs of a line into the visual order of its text.
+fn visual_runs(runs: &[(String, Style)]) -> Cow<'_, [(String, Style)]> {
+    let line: String = runs.iter().map(|(text, _)| &**text).collect();
+    let map = text::visual_map(&line);
+    if map.iter().enumerate().all(|(i, &at)| i == at) {
+        return Cow::Borrowed(runs);
+    }
+
+    let mut chars = vec![(' ', Style::default()); map.len()];
+    let styled = runs
+        .iter()
+        .flat_map(|&(ref text, style)| text.chars().map(move |ch| (ch, style)));
+    for (&at, styled) in map.iter().zip(styled) {
+        chars[at] = styled;
+    }
+    let mut visual: Vec<(String, Style)> = Vec::new();
+    for (ch, style) in chars {
+        match visual.last_mut() {
+            Some(&mut (ref mut text, last)) if last == style => text.push(ch),
+            _ => visual.push((ch.to_string(), style)),
+        }
+    }
+    Cow::Owned(visual)
+}
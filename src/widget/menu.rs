@@ -0,0 +1,207 @@
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::overlay::{self, Overlay};
+use widget::{fit, Widget};
+use window::Window;
+
+/// Represents a menu of items, drawn over the rest of the window at a
+/// position, such as where the mouse was clicked for a context menu.
+///
+/// ```text
+/// ┌────────┐
+/// │ Open   │
+/// │ Rename │
+/// │ Delete │
+/// └────────┘
+/// ```
+///
+/// While open, the menu takes every key, mouse and paste event. Up and Down,
+/// or moving the mouse, select an item, and Enter or a click picks it.
+/// Escape, or a click outside the menu, closes it. Once dismissed, the next
+/// render puts back what was underneath.
+///
+/// ```no_run
+/// # use termui::event::{Event, MouseButton, MouseEvent};
+/// # use termui::widget::{Menu, Widget};
+/// # let ev = Event::Refresh;
+/// let mut menu = Menu::new(vec!["Open", "Rename", "Delete"]);
+/// if let Event::Mouse {
+///     pos,
+///     event: MouseEvent::Press(MouseButton::Right),
+///     ..
+/// } = ev
+/// {
+///     menu.open_at(pos);
+/// }
+/// ```
+///
+/// *The menu opens below and to the right of its position, moving up or left
+/// where it would not fit in the area it is rendered into.*
+#[derive(Clone, Debug)]
+pub struct Menu {
+    items: Vec<String>,
+    pos: (usize, usize),
+    selected: usize,
+    answer: Option<usize>,
+    overlay: Overlay,
+    style: Style,
+    selected_style: Style,
+}
+
+impl Menu {
+    /// Creates a closed menu of the given items.
+    pub fn new<I, S>(items: I) -> Menu
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Menu {
+            items: items.into_iter().map(Into::into).collect(),
+            pos: (0, 0),
+            selected: 0,
+            answer: None,
+            overlay: Overlay::default(),
+            style: Style::default(),
+            selected_style: Style::new().attr(Attr::Reverse),
+        }
+    }
+
+    /// Gets the items of the menu.
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Opens the menu with its top left corner at the given position,
+    /// selecting the first item.
+    pub fn open_at(&mut self, pos: (usize, usize)) {
+        self.pos = pos;
+        self.selected = 0;
+        self.answer = None;
+        self.overlay.open();
+    }
+
+    /// Closes the menu without picking an item.
+    pub fn close(&mut self) {
+        self.answer = None;
+        self.overlay.close();
+    }
+
+    /// Checks whether the menu is open.
+    pub fn is_open(&self) -> bool {
+        self.overlay.is_open()
+    }
+
+    /// Gets the index of the item picked when the menu was last dismissed, or
+    /// `None` if it was closed without one or is still open.
+    pub fn answer(&self) -> Option<usize> {
+        self.answer
+    }
+
+    /// Gets the style the menu is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the menu is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the selected item.
+    pub fn selected_style(&self) -> Style {
+        self.selected_style
+    }
+
+    /// Sets the style patched over the selected item, reversed by default.
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    fn pick(&mut self, item: usize) {
+        self.answer = Some(item);
+        self.overlay.close();
+    }
+
+    /// Gets the item shown at a position, if any.
+    fn item_at(&self, pos: (usize, usize)) -> Option<usize> {
+        let rect = self.overlay.rect?;
+        let (x, y) = rect.to_local(pos)?;
+        if x == 0 || x + 1 == rect.width || y == 0 || y + 1 == rect.height {
+            return None;
+        }
+        Some(y - 1).filter(|&item| item < self.items.len())
+    }
+}
+
+impl<B: Backend> Widget<B> for Menu {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        let width = self
+            .items
+            .iter()
+            .map(|item| win.display_width(item))
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let (width, height) = (
+            width.min(area.width),
+            (self.items.len() + 2).min(area.height),
+        );
+        let x = self.pos.0.max(area.x).min(area.right() - width);
+        let y = if self.pos.1 + height <= area.bottom() {
+            self.pos.1.max(area.y)
+        } else {
+            (self.pos.1 + 1).saturating_sub(height).max(area.y)
+        };
+        let rect = Rect::new(x, y, width, height);
+        if !self.overlay.begin(rect, win) || width < 3 || height < 3 {
+            return;
+        }
+
+        overlay::draw_frame(rect, "", self.style, win);
+        for (i, item) in self.items.iter().take(height - 2).enumerate() {
+            let style = if i == self.selected {
+                self.style.patch(self.selected_style)
+            } else {
+                self.style
+            };
+            let line = format!(" {}", fit(item, width - 4, win));
+            win.print_styled(y + 1 + i, x + 1, fit(&line, width - 2, win), style);
+        }
+        self.overlay.end(win);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.overlay.is_open() || !overlay::captures(event) {
+            return false;
+        }
+        let count = self.items.len();
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Up if count > 0 => self.selected = (self.selected + count - 1) % count,
+                Key::Down if count > 0 => self.selected = (self.selected + 1) % count,
+                Key::Home => self.selected = 0,
+                Key::End => self.selected = count.saturating_sub(1),
+                Key::Enter if self.selected < count => self.pick(self.selected),
+                Key::Escape => self.close(),
+                _ => {}
+            },
+            Event::Mouse { pos, event, .. } => match (event, self.item_at(pos)) {
+                (MouseEvent::Moved, Some(item)) | (MouseEvent::Hold(_), Some(item)) => {
+                    self.selected = item
+                }
+                (MouseEvent::Press(MouseButton::Left), Some(item)) => self.pick(item),
+                (MouseEvent::Press(_), None)
+                    if !self.overlay.rect.is_some_and(|rect| rect.contains(pos)) =>
+                {
+                    self.close()
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        true
+    }
+}
@@ -0,0 +1,275 @@
+use backend::Backend;
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::Window;
+
+/// The columns left between the meters of a
+/// [`MultiMeter`](struct.MultiMeter.html) laid out side by side.
+const COLUMN_GAP: usize = 1;
+
+/// Represents a bracketed bar split into stacked segments, each in its own
+/// style, with a caption before it and its reading drawn over its right end.
+///
+/// ```text
+/// CPU[||||||||||||||||          42.0%]
+/// ```
+///
+/// Segments are drawn one after another from the left, such as the user,
+/// system and waiting shares of a processor's time, out of the
+/// [maximum](#method.set_max).
+///
+/// *The reading is the total as a percentage of the maximum unless
+/// [text](#method.set_text) is set. Segments past the maximum are cut off.*
+#[derive(Clone, Debug)]
+pub struct Meter {
+    caption: String,
+    segments: Vec<(f64, Style)>,
+    max: f64,
+    text: Option<String>,
+    style: Style,
+    caption_style: Style,
+}
+
+impl Meter {
+    /// Creates an empty meter with the given caption, out of a maximum of
+    /// `100`.
+    pub fn new<S: Into<String>>(caption: S) -> Meter {
+        Meter {
+            caption: caption.into(),
+            segments: Vec::new(),
+            max: 100.0,
+            text: None,
+            style: Style::default(),
+            caption_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Gets the caption shown before the bar.
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+
+    /// Sets the caption shown before the bar.
+    pub fn set_caption<S: Into<String>>(&mut self, caption: S) {
+        self.caption = caption.into();
+    }
+
+    /// Sets a single segment of the value, in the style of the meter.
+    pub fn set_value(&mut self, value: f64) {
+        self.segments = vec![(value, Style::default())];
+    }
+
+    /// Gets the segments, as their values and the styles patched over them.
+    pub fn segments(&self) -> &[(f64, Style)] {
+        &self.segments
+    }
+
+    /// Replaces the segments, given as their values and the styles patched
+    /// over them.
+    ///
+    /// *Negative values are drawn as zero.*
+    pub fn set_segments<I: IntoIterator<Item = (f64, Style)>>(&mut self, segments: I) {
+        self.segments = segments.into_iter().collect();
+    }
+
+    /// Gets the total of the segments.
+    pub fn total(&self) -> f64 {
+        self.segments.iter().map(|&(value, _)| value.max(0.0)).sum()
+    }
+
+    /// Gets the value a full bar stands for.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Sets the value a full bar stands for.
+    pub fn set_max(&mut self, max: f64) {
+        self.max = max;
+    }
+
+    /// Sets the reading drawn over the bar, or `None` to show the total as a
+    /// percentage.
+    pub fn set_text<S: Into<String>>(&mut self, text: Option<S>) {
+        self.text = text.map(Into::into);
+    }
+
+    /// Gets the reading drawn over the bar.
+    pub fn text(&self) -> String {
+        match self.text {
+            Some(ref text) => text.clone(),
+            None if self.max > 0.0 => format!("{:.1}%", 100.0 * self.total() / self.max),
+            None => String::new(),
+        }
+    }
+
+    /// Gets the style the meter is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the meter is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the caption.
+    pub fn caption_style(&self) -> Style {
+        self.caption_style
+    }
+
+    /// Sets the style patched over the caption, bold by default.
+    pub fn set_caption_style(&mut self, style: Style) {
+        self.caption_style = style;
+    }
+
+    /// Draws the meter on a line, with the caption padded to the given width
+    /// so the bars of meters drawn together line up.
+    fn draw<B: Backend>(&self, y: usize, x: usize, width: usize, caption: usize, win: &Window<B>) {
+        let caption = caption.min(width);
+        win.print_styled(
+            y,
+            x,
+            fit(&self.caption, caption, win),
+            self.style.patch(self.caption_style),
+        );
+        // The brackets take two columns.
+        if width < caption + 2 {
+            win.print_styled(y, x + caption, " ".repeat(width - caption), self.style);
+            return;
+        }
+        let inside = width - caption - 2;
+
+        // The style of each cell of the bar, or `None` where it is empty.
+        let mut cells: Vec<Option<Style>> = vec![None; inside];
+        if self.max > 0.0 {
+            let mut total = 0.0;
+            for &(value, style) in &self.segments {
+                let start = (total / self.max * inside as f64).round() as usize;
+                total += value.max(0.0);
+                let end = (total / self.max * inside as f64).round() as usize;
+                for cell in cells.iter_mut().take(end.min(inside)).skip(start) {
+                    *cell = Some(style);
+                }
+            }
+        }
+
+        let text = self.text();
+        let mut chars: Vec<char> = cells
+            .iter()
+            .map(|cell| if cell.is_some() { '|' } else { ' ' })
+            .collect();
+        let count = text.chars().count();
+        if win.display_width(&text) == count && count <= inside {
+            for (i, ch) in text.chars().enumerate() {
+                chars[inside - count + i] = ch;
+            }
+        }
+
+        win.print_styled(y, x + caption, "[", self.style);
+        for (i, (ch, cell)) in chars.into_iter().zip(cells).enumerate() {
+            let style = cell.map_or(self.style, |cell| self.style.patch(cell));
+            win.print_styled(y, x + caption + 1 + i, ch.to_string(), style);
+        }
+        win.print_styled(y, x + width - 1, "]", self.style);
+    }
+}
+
+impl<B: Backend> Widget<B> for Meter {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let caption = win.display_width(&self.caption);
+        self.draw(area.y, area.x, area.width, caption, win);
+    }
+}
+
+/// Represents a stack of [`Meter`](struct.Meter.html)s, one to a row, such as
+/// one for each processor core, with their bars lined up.
+///
+/// ```text
+/// 1[|||        17.0%] 3[||||||||   51.0%]
+/// 2[|||||      34.0%] 4[|||||||||||68.0%]
+/// ```
+///
+/// The meters can be laid out in several columns side by side, filling each
+/// column down before moving on to the next.
+///
+/// *Meters that do not fit in the area are left out.*
+#[derive(Clone, Debug)]
+pub struct MultiMeter {
+    meters: Vec<Meter>,
+    columns: usize,
+}
+
+impl MultiMeter {
+    /// Creates a stack of the given meters, in one column.
+    pub fn new<I: IntoIterator<Item = Meter>>(meters: I) -> MultiMeter {
+        MultiMeter {
+            meters: meters.into_iter().collect(),
+            columns: 1,
+        }
+    }
+
+    /// Gets the meters.
+    pub fn meters(&self) -> &[Meter] {
+        &self.meters
+    }
+
+    /// Gets the meters to change them, such as to update their readings.
+    pub fn meters_mut(&mut self) -> &mut Vec<Meter> {
+        &mut self.meters
+    }
+
+    /// Gets the number of columns the meters are laid out in.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Sets the number of columns the meters are laid out in.
+    ///
+    /// *Zero columns is treated as one.*
+    pub fn set_columns(&mut self, columns: usize) {
+        self.columns = columns.max(1);
+    }
+
+    /// Gets the number of rows needed to show every meter.
+    pub fn rows(&self) -> usize {
+        let columns = self.columns.max(1);
+        self.meters.len().div_ceil(columns)
+    }
+}
+
+impl<B: Backend> Widget<B> for MultiMeter {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let columns = self.columns.max(1);
+        let rows = self.rows();
+        let caption = self
+            .meters
+            .iter()
+            .map(|meter| win.display_width(&meter.caption))
+            .max()
+            .unwrap_or(0);
+        let width = area.width.saturating_sub(COLUMN_GAP * (columns - 1)) / columns;
+
+        for y in 0..area.height {
+            win.print_styled(area.y + y, area.x, " ".repeat(area.width), Style::default());
+        }
+        if width == 0 {
+            return;
+        }
+        for (i, meter) in self.meters.iter().enumerate() {
+            let (col, row) = (i / rows, i % rows);
+            if row >= area.height {
+                continue;
+            }
+            let x = area.x + col * (width + COLUMN_GAP);
+            meter.draw(area.y + row, x, width, caption, win);
+        }
+    }
+}
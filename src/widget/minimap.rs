@@ -0,0 +1,227 @@
+use backend::Backend;
+use event::{Event, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{braille, Scrollable, Widget, BRAILLE_DOTS};
+use window::Window;
+
+/// The source characters covered by each dot across.
+const CHARS_PER_DOT: usize = 2;
+/// The map rows scrolled by the mouse wheel.
+const WHEEL_STEP: usize = 3;
+
+/// Represents a compressed overview of a large block of text, such as the
+/// file shown beside it, with the visible part highlighted.
+///
+/// Each cell is a braille character of two dots across and four down. A dot
+/// is set when the source has text in the lines and columns it covers, each
+/// dot row covering [`scale`](#method.scale) lines and each dot column two
+/// characters.
+///
+/// Clicking or dragging on the map moves the viewport to center on that
+/// part of the source. The map scrolls to keep the viewport in view, and
+/// the viewport can be kept in step with the view of the text through a
+/// [`ScrollSync`](struct.ScrollSync.html).
+///
+/// *In ASCII fallback mode, cells with any dots set are drawn as `:`.*
+#[derive(Clone, Debug)]
+pub struct Minimap {
+    lines: Vec<String>,
+    scale: usize,
+    viewport: (usize, usize),
+    offset: usize,
+    area: Option<Rect>,
+    style: Style,
+    viewport_style: Style,
+}
+
+impl Default for Minimap {
+    fn default() -> Minimap {
+        Minimap {
+            lines: Vec::new(),
+            scale: 1,
+            viewport: (0, 0),
+            offset: 0,
+            area: None,
+            style: Style::default(),
+            viewport_style: Style::default().attr(Attr::Reverse),
+        }
+    }
+}
+
+impl Minimap {
+    /// Creates a minimap of the given lines.
+    pub fn new<I, S>(lines: I) -> Minimap
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut map = Minimap::default();
+        map.set_lines(lines);
+        map
+    }
+
+    /// Replaces the lines the map shows.
+    pub fn set_lines<I, S>(&mut self, lines: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.lines = lines.into_iter().map(Into::into).collect();
+    }
+
+    /// Gets the number of source lines each dot row covers.
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// Sets the number of source lines each dot row covers, so each cell
+    /// covers four times as many.
+    ///
+    /// *A scale of zero is treated as one.*
+    pub fn set_scale(&mut self, scale: usize) {
+        self.scale = scale.max(1);
+    }
+
+    /// Gets the visible part of the source, as its first line and height.
+    pub fn viewport(&self) -> (usize, usize) {
+        self.viewport
+    }
+
+    /// Sets the visible part of the source to highlight.
+    pub fn set_viewport(&mut self, first: usize, height: usize) {
+        self.viewport = (first, height);
+    }
+
+    /// Gets the style the map is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the map is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the rows showing the viewport.
+    pub fn viewport_style(&self) -> Style {
+        self.viewport_style
+    }
+
+    /// Sets the style patched over the rows showing the viewport, reversed by
+    /// default.
+    pub fn set_viewport_style(&mut self, style: Style) {
+        self.viewport_style = style;
+    }
+
+    /// Gets the source lines covered by each row of the map.
+    fn lines_per_row(&self) -> usize {
+        self.scale * BRAILLE_DOTS.len()
+    }
+
+    /// Moves the viewport to center on a source line.
+    fn jump_to(&mut self, line: usize) {
+        let (_, height) = self.viewport;
+        self.set_scroll_offset(line.saturating_sub(height / 2));
+    }
+
+    /// Draws the cells of a row of the map.
+    fn row_text(&self, row: usize, width: usize, ascii: bool) -> String {
+        let mut dots = vec![0u8; width];
+        let first = row * self.lines_per_row();
+        for (i, line) in self
+            .lines
+            .iter()
+            .skip(first)
+            .take(self.lines_per_row())
+            .enumerate()
+        {
+            let dot_row = i / self.scale;
+            for (col, ch) in line.chars().enumerate().take(width * 2 * CHARS_PER_DOT) {
+                if !ch.is_whitespace() {
+                    let dot_col = col / CHARS_PER_DOT;
+                    dots[dot_col / 2] |= BRAILLE_DOTS[dot_row][dot_col % 2];
+                }
+            }
+        }
+
+        dots.into_iter()
+            .map(|bits| match (bits, ascii) {
+                (0, _) => ' ',
+                (_, true) => ':',
+                (bits, false) => braille(bits),
+            })
+            .collect()
+    }
+}
+
+impl Scrollable for Minimap {
+    fn scroll_offset(&self) -> usize {
+        self.viewport.0
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        let (_, height) = self.viewport;
+        self.viewport.0 = offset.min(self.lines.len().saturating_sub(height));
+    }
+}
+
+impl<B: Backend> Widget<B> for Minimap {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let per_row = self.lines_per_row();
+        let rows = self.lines.len().div_ceil(per_row);
+        let (first, height) = self.viewport;
+        let top = first / per_row;
+        let bottom = (first + height.max(1) - 1) / per_row;
+        if top < self.offset {
+            self.offset = top;
+        } else if bottom >= self.offset + area.height {
+            self.offset = (bottom + 1 - area.height).min(top);
+        }
+        self.offset = self.offset.min(rows.saturating_sub(area.height));
+
+        for y in 0..area.height {
+            let row = self.offset + y;
+            let style = if row >= top && row <= bottom && height > 0 {
+                self.style.patch(self.viewport_style)
+            } else {
+                self.style
+            };
+            let text = self.row_text(row, area.width, win.ascii_mode());
+            win.print_styled(area.y + y, area.x, text, style);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let (pos, event) = match *event {
+            Event::Mouse { pos, event, .. } => (pos, event),
+            _ => return false,
+        };
+        let (_, row) = match self.area.and_then(|area| area.to_local(pos)) {
+            Some(local) => local,
+            None => return false,
+        };
+        let per_row = self.lines_per_row();
+        match event {
+            MouseEvent::Press(MouseButton::Left) | MouseEvent::Hold(MouseButton::Left) => {
+                self.jump_to((self.offset + row) * per_row + per_row / 2)
+            }
+            MouseEvent::WheelUp => {
+                let first = self.viewport.0.saturating_sub(WHEEL_STEP * per_row);
+                self.set_scroll_offset(first);
+            }
+            MouseEvent::WheelDown => {
+                let first = self.viewport.0.saturating_add(WHEEL_STEP * per_row);
+                self.set_scroll_offset(first);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,122 @@
+//! Widget module.
+//!
+//! Widgets draw themselves into an area of a window and react to events, see
+//! the [`Widget`](trait.Widget.html) trait.
+
+mod animation;
+mod component;
+mod datetime_input;
+mod dialog;
+mod editing;
+mod histogram;
+mod image;
+mod list;
+mod lock_screen;
+mod log_view;
+mod menu;
+mod meter;
+mod minimap;
+mod number_input;
+mod overlay;
+mod pagination;
+mod progress_bar;
+mod prompt;
+mod rating;
+mod remote;
+mod scroll;
+mod stream_graph;
+mod table;
+mod tag_input;
+mod task_list;
+mod taskbar;
+mod text_input;
+mod text_view;
+mod toggle_switch;
+mod tree;
+mod tree_table;
+mod tween;
+mod vu_meter;
+mod window_manager;
+mod world_map;
+
+pub use self::animation::Animation;
+pub use self::component::{dispatch, Component, EventResult};
+pub use self::datetime_input::{DateTime, DateTimeInput};
+pub use self::dialog::Dialog;
+pub use self::editing::{EditConfig, KillRing, WordChars};
+pub use self::histogram::{Binning, Histogram};
+pub use self::image::Image;
+pub use self::list::List;
+pub use self::lock_screen::{Cover, LockScreen};
+pub use self::log_view::LogView;
+pub use self::menu::Menu;
+pub use self::meter::{Meter, MultiMeter};
+pub use self::minimap::Minimap;
+pub use self::number_input::NumberInput;
+pub use self::pagination::{Pagination, Paginator};
+pub use self::progress_bar::ProgressBar;
+pub use self::prompt::Prompt;
+pub use self::rating::Rating;
+pub use self::remote::{Remote, RemoteState};
+pub use self::scroll::{ScrollSync, Scrollable};
+pub use self::stream_graph::StreamGraph;
+pub use self::table::{Table, TableCell};
+pub use self::tag_input::{SuggestionProvider, TagInput};
+pub use self::task_list::{Task, TaskList, TaskStatus};
+pub use self::taskbar::Taskbar;
+pub use self::text_input::TextInput;
+pub use self::text_view::TextView;
+pub use self::toggle_switch::ToggleSwitch;
+pub use self::tree::Tree;
+pub use self::tree_table::{TreeNode, TreeTable};
+pub use self::tween::{Easing, Interpolate, Tween};
+pub use self::vu_meter::VuMeter;
+pub use self::window_manager::{Chrome, WindowId, WindowManager};
+pub use self::world_map::{Region, WorldMap};
+
+use backend::Backend;
+use event::Event;
+use layout::Rect;
+use text;
+use window::{CursesBackend, Window};
+
+/// Represents a component drawn into an area of a window.
+///
+/// Widgets draw over the whole of their area, so the window does not need to
+/// be erased between frames.
+pub trait Widget<B: Backend = CursesBackend> {
+    /// Renders the widget into the given area of the window.
+    fn render(&mut self, area: Rect, win: &Window<B>);
+
+    /// Handles an event, giving whether the widget used it.
+    ///
+    /// *Mouse events are matched against the area the widget was last rendered
+    /// into.*
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let _ = event;
+        false
+    }
+}
+
+/// The bits of each dot of a braille character, by dot row then dot column.
+pub(crate) const BRAILLE_DOTS: [[u8; 2]; 4] =
+    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Gets the braille character with the dots of the given bits set.
+pub(crate) fn braille(bits: u8) -> char {
+    ::std::char::from_u32(0x2800 + u32::from(bits)).unwrap_or(' ')
+}
+
+/// Cuts a line down to fit in the given number of columns.
+fn truncate<'a, B: Backend>(line: &'a str, width: usize, win: &Window<B>) -> &'a str {
+    text::truncate(line, width, win.ambiguous_width())
+}
+
+/// Cuts a line down or pads it with spaces to fill the given number of
+/// columns.
+fn fit<B: Backend>(line: &str, width: usize, win: &Window<B>) -> String {
+    let line = truncate(line, width, win);
+    let mut out = line.to_owned();
+    out.push_str(&" ".repeat(width - win.display_width(line)));
+    out
+}
@@ -0,0 +1,304 @@
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use i18n::NumberFormat;
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::{CursorStyle, Window};
+
+/// Represents an input for a number, kept within a range and to a step.
+///
+/// ```text
+///    1,234.50 ▲▼
+/// ```
+///
+/// Up and Down change the number by the step, Page Up and Page Down by ten
+/// steps, and Home and End go to the minimum and maximum. Typing replaces the
+/// number, which is checked when Enter is pressed, or the input loses focus,
+/// and Escape brings the number back. The mouse wheel and clicking the
+/// arrows change the number too.
+///
+/// *Numbers are written and read in the [format](#method.set_format) of the
+/// locale. Events are ignored while the input is not focused.*
+#[derive(Clone, Debug)]
+pub struct NumberInput {
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    decimals: usize,
+    format: NumberFormat,
+    typed: Option<String>,
+    focused: bool,
+    area: Option<Rect>,
+    style: Style,
+    typed_style: Style,
+}
+
+impl Default for NumberInput {
+    fn default() -> NumberInput {
+        NumberInput::new(0.0)
+    }
+}
+
+impl NumberInput {
+    /// Creates a focused input with the given value, allowing any number in
+    /// steps of one, written in the format of the locale.
+    pub fn new(value: f64) -> NumberInput {
+        let mut input = NumberInput {
+            value: 0.0,
+            min: f64::MIN,
+            max: f64::MAX,
+            step: 1.0,
+            decimals: 0,
+            format: NumberFormat::locale(),
+            typed: None,
+            focused: true,
+            area: None,
+            style: Style::default(),
+            typed_style: Style::new().attr(Attr::Underline),
+        };
+        input.set_value(value);
+        input
+    }
+
+    /// Gets the number entered.
+    ///
+    /// *Text being typed is not counted until it is committed.*
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Replaces the number, kept within the range and to the step, and
+    /// forgets any text typed.
+    pub fn set_value(&mut self, value: f64) {
+        self.typed = None;
+        self.value = self.constrain(value);
+    }
+
+    /// Gets the smallest and largest numbers allowed.
+    pub fn range(&self) -> (f64, f64) {
+        (self.min, self.max)
+    }
+
+    /// Limits the numbers allowed to between the smallest and largest.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.min = min;
+        self.max = max.max(min);
+        self.value = self.constrain(self.value);
+    }
+
+    /// Gets the step the number changes by.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Sets the step the number changes by, with numbers kept to multiples of
+    /// the step from the minimum, or any number with a step of zero.
+    pub fn set_step(&mut self, step: f64) {
+        self.step = step.abs();
+        self.value = self.constrain(self.value);
+    }
+
+    /// Gets the number of decimal places shown.
+    pub fn decimals(&self) -> usize {
+        self.decimals
+    }
+
+    /// Sets the number of decimal places shown, none by default.
+    pub fn set_decimals(&mut self, decimals: usize) {
+        self.decimals = decimals;
+    }
+
+    /// Gets the format numbers are written and read in.
+    pub fn format(&self) -> NumberFormat {
+        self.format
+    }
+
+    /// Sets the format numbers are written and read in, the locale's by
+    /// default.
+    pub fn set_format(&mut self, format: NumberFormat) {
+        self.format = format;
+    }
+
+    /// Gets the text being typed, if any.
+    pub fn typed(&self) -> Option<&str> {
+        self.typed.as_deref()
+    }
+
+    /// Checks whether the input is focused.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether the input is focused, committing any text typed when it
+    /// loses focus.
+    pub fn set_focused(&mut self, focused: bool) {
+        if !focused {
+            self.commit();
+        }
+        self.focused = focused;
+    }
+
+    /// Gets the style the input is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the input is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over text being typed.
+    pub fn typed_style(&self) -> Style {
+        self.typed_style
+    }
+
+    /// Sets the style patched over text being typed, underlined by default.
+    pub fn set_typed_style(&mut self, style: Style) {
+        self.typed_style = style;
+    }
+
+    /// Parses the text typed into the number, if it can be read, forgetting
+    /// the text.
+    ///
+    /// *Gives whether the number could be read.*
+    pub fn commit(&mut self) -> bool {
+        let typed = match self.typed.take() {
+            Some(typed) => typed,
+            None => return true,
+        };
+        match self.format.parse(&typed) {
+            Some(value) => {
+                self.value = self.constrain(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Changes the number by the given number of steps, committing any text
+    /// typed first.
+    pub fn step_by(&mut self, steps: i32) {
+        self.commit();
+        let step = if self.step > 0.0 { self.step } else { 1.0 };
+        self.value = self.constrain(self.value + step * f64::from(steps));
+    }
+
+    /// Keeps a number within the range and to the step.
+    fn constrain(&self, value: f64) -> f64 {
+        let value = value.max(self.min).min(self.max);
+        if self.step <= 0.0 {
+            return value;
+        }
+        // Steps are counted from the minimum, or from zero without one.
+        let base = if self.min > f64::MIN { self.min } else { 0.0 };
+        let value = base + ((value - base) / self.step).round() * self.step;
+        if value > self.max {
+            value - self.step
+        } else if value < self.min {
+            value + self.step
+        } else {
+            value
+        }
+    }
+
+    /// Adds a typed character, replacing the number with the first.
+    fn type_char(&mut self, ch: char) {
+        self.typed.get_or_insert_with(String::new).push(ch);
+    }
+
+    /// Checks whether a character can be typed into a number.
+    fn accepts(&self, ch: char) -> bool {
+        ch.is_ascii_digit()
+            || (ch == '-' && self.min < 0.0)
+            || (ch == self.format.decimal && self.decimals > 0)
+    }
+}
+
+impl<B: Backend> Widget<B> for NumberInput {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let arrows = if win.ascii_mode() { "^v" } else { "▲▼" };
+        let width = area.width.saturating_sub(3);
+        let (text, style) = match self.typed {
+            Some(ref typed) => (typed.clone(), self.style.patch(self.typed_style)),
+            None => (self.format.format(self.value, self.decimals), self.style),
+        };
+
+        let text_width = win.display_width(&text).min(width);
+        let pad = width - text_width;
+        win.print_styled(area.y, area.x, " ".repeat(pad), self.style);
+        win.print_styled(area.y, area.x + pad, fit(&text, text_width, win), style);
+        if area.width >= 3 {
+            win.print_styled(area.y, area.x + width, " ", self.style);
+            win.print_styled(area.y, area.x + width + 1, arrows, self.style);
+        }
+
+        if self.focused && self.typed.is_some() && width > 0 {
+            win.request_cursor(CursorStyle::Bar(
+                area.y,
+                area.x + width.min(pad + text_width),
+            ));
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Char(ch) if self.accepts(ch) => self.type_char(ch),
+                Key::Backspace if self.typed.is_some() => {
+                    if let Some(ref mut typed) = self.typed {
+                        typed.pop();
+                    }
+                }
+                Key::Enter if self.typed.is_some() => {
+                    self.commit();
+                }
+                Key::Escape if self.typed.is_some() => self.typed = None,
+                Key::Up => self.step_by(1),
+                Key::Down => self.step_by(-1),
+                Key::PageUp => self.step_by(10),
+                Key::PageDown => self.step_by(-10),
+                Key::Home if self.min > f64::MIN => self.set_value(self.min),
+                Key::End if self.max < f64::MAX => self.set_value(self.max),
+                _ => return false,
+            },
+            Event::Mouse { pos, event, .. } => {
+                let area = match self.area {
+                    Some(area) => area,
+                    None => return false,
+                };
+                let (x, _) = match area.to_local(pos) {
+                    Some(local) => local,
+                    None => return false,
+                };
+                match event {
+                    MouseEvent::WheelUp => self.step_by(1),
+                    MouseEvent::WheelDown => self.step_by(-1),
+                    MouseEvent::Press(MouseButton::Left) if area.width >= 3 => {
+                        if x == area.width - 2 {
+                            self.step_by(1);
+                        } else if x == area.width - 1 {
+                            self.step_by(-1);
+                        } else {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
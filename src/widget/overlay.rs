@@ -0,0 +1,92 @@
+use backend::Backend;
+use draw::BorderStyle;
+use event::Event;
+use layout::Rect;
+use style::Style;
+use window::{Snapshot, Window};
+
+/// The state shared by the widgets drawn over other content, keeping what was
+/// underneath so it can be put back once they are dismissed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Overlay {
+    open: bool,
+    saved: Option<Snapshot>,
+    drawn: Option<Snapshot>,
+    pub(crate) rect: Option<Rect>,
+}
+
+impl Overlay {
+    pub(crate) fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub(crate) fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Saves what is underneath the rect, ready for the overlay to be drawn,
+    /// giving `false` once the overlay is closed.
+    ///
+    /// *If the overlay is still showing as it was last drawn, nothing was
+    /// drawn over it since, so what was underneath is put back first.*
+    pub(crate) fn begin<B: Backend>(&mut self, rect: Rect, win: &Window<B>) -> bool {
+        if let (Some(saved), Some(drawn)) = (self.saved.take(), self.drawn.take()) {
+            if win.is_unchanged(&drawn) {
+                win.restore(&saved);
+            }
+        }
+        if !self.open {
+            self.rect = None;
+            return false;
+        }
+        self.saved = Some(win.snapshot(rect));
+        self.rect = Some(rect);
+        true
+    }
+
+    /// Remembers the overlay as drawn, once it has been.
+    pub(crate) fn end<B: Backend>(&mut self, win: &Window<B>) {
+        if let Some(rect) = self.rect {
+            self.drawn = Some(win.snapshot(rect));
+        }
+    }
+}
+
+/// Checks whether an overlay captures an event while open, which is every
+/// event from the user.
+pub(crate) fn captures(event: &Event) -> bool {
+    matches!(
+        *event,
+        Event::Key { .. } | Event::Mouse { .. } | Event::Paste(_)
+    )
+}
+
+/// Gets a rect of the given size centered in an area, cut down to fit.
+pub(crate) fn centered(area: Rect, width: usize, height: usize) -> Rect {
+    let (width, height) = (width.min(area.width), height.min(area.height));
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Fills a rect and draws a border around its edge.
+pub(crate) fn draw_frame<B: Backend>(rect: Rect, title: &str, style: Style, win: &Window<B>) {
+    let blank = " ".repeat(rect.width);
+    for row in rect.y..rect.bottom() {
+        win.print_styled(row, rect.x, &blank, style);
+    }
+    win.with_style(style, |win| {
+        win.draw_box(rect, BorderStyle::Single);
+        if !title.is_empty() && rect.width > 4 {
+            let title = super::truncate(title, rect.width - 4, win);
+            win.print(rect.y, rect.x + 1, format!(" {} ", title));
+        }
+    });
+}
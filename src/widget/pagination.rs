@@ -0,0 +1,327 @@
+use std::ops::Range;
+
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use i18n::Message;
+use layout::Rect;
+use style::Style;
+use widget::{fit, Widget};
+use window::Window;
+
+/// The most digits typed when jumping to a page.
+const MAX_DIGITS: usize = 9;
+
+/// Keeps track of which page of a set of items is shown, such as the rows of
+/// a [`Table`](struct.Table.html) or the items of a [`List`](struct.List.html).
+///
+/// ```no_run
+/// use termui::widget::{List, Paginator};
+///
+/// let rows: Vec<String> = (1..=95).map(|n| format!("row {}", n)).collect();
+/// let mut pages = Paginator::new(20);
+/// pages.set_total(rows.len());
+/// pages.last_page();
+///
+/// let list = List::new(pages.slice(&rows).to_vec());
+/// assert_eq!(pages.range(), 80..95);
+/// ```
+///
+/// *Pages are counted from zero, and there is always at least one page, even
+/// with no items.*
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Paginator {
+    total: usize,
+    page_size: usize,
+    page: usize,
+}
+
+impl Paginator {
+    /// Creates a paginator of the given number of items per page, with no
+    /// items yet.
+    ///
+    /// *A page size of zero is treated as one.*
+    pub fn new(page_size: usize) -> Paginator {
+        Paginator {
+            total: 0,
+            page_size: page_size.max(1),
+            page: 0,
+        }
+    }
+
+    /// Gets the number of items.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Sets the number of items, keeping the page in range.
+    pub fn set_total(&mut self, total: usize) {
+        self.total = total;
+        self.page = self.page.min(self.pages() - 1);
+    }
+
+    /// Gets the number of items on each page.
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Sets the number of items on each page, moving to the page with the
+    /// first item of the current one.
+    ///
+    /// *A page size of zero is treated as one.*
+    pub fn set_page_size(&mut self, page_size: usize) {
+        let first = self.range().start;
+        self.page_size = page_size.max(1);
+        self.page = self.page_of(first);
+    }
+
+    /// Gets the number of pages.
+    pub fn pages(&self) -> usize {
+        self.total.div_ceil(self.page_size).max(1)
+    }
+
+    /// Gets the current page.
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Moves to the given page, or the last page if it is past the end.
+    pub fn set_page(&mut self, page: usize) {
+        self.page = page.min(self.pages() - 1);
+    }
+
+    /// Moves to the first page.
+    pub fn first_page(&mut self) {
+        self.page = 0;
+    }
+
+    /// Moves to the page before, giving whether there was one.
+    pub fn previous_page(&mut self) -> bool {
+        let page = self.page;
+        self.set_page(page.saturating_sub(1));
+        self.page != page
+    }
+
+    /// Moves to the page after, giving whether there was one.
+    pub fn next_page(&mut self) -> bool {
+        let page = self.page;
+        self.set_page(page + 1);
+        self.page != page
+    }
+
+    /// Moves to the last page.
+    pub fn last_page(&mut self) {
+        self.page = self.pages() - 1;
+    }
+
+    /// Gets the page an item is on.
+    pub fn page_of(&self, item: usize) -> usize {
+        (item / self.page_size).min(self.pages() - 1)
+    }
+
+    /// Gets the range of the items on the current page.
+    pub fn range(&self) -> Range<usize> {
+        let start = (self.page * self.page_size).min(self.total);
+        start..(start + self.page_size).min(self.total)
+    }
+
+    /// Gets the items on the current page, out of all the items.
+    ///
+    /// *The range is cut down to the items given, if there are fewer than the
+    /// total.*
+    pub fn slice<'a, T>(&self, items: &'a [T]) -> &'a [T] {
+        let range = self.range();
+        let end = range.end.min(items.len());
+        &items[range.start.min(end)..end]
+    }
+}
+
+impl Default for Paginator {
+    fn default() -> Paginator {
+        Paginator::new(1)
+    }
+}
+
+/// The controls of the pagination bar.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Control {
+    First,
+    Previous,
+    Next,
+    Last,
+}
+
+/// Represents a bar for moving between pages, showing the current page and
+/// controls for the first, previous, next and last pages.
+///
+/// ```text
+/// «  ‹  Page 3 of 12  ›  »
+/// ```
+///
+/// The controls can be clicked, or used with the arrow keys, Page Up and Page
+/// Down, Home and End. Typing a page number, then Enter, jumps to that page,
+/// and Escape forgets the number typed.
+///
+/// *The page shown is counted from one. The controls are drawn as `<<`,
+/// `<`, `>` and `>>` in ASCII fallback mode.*
+#[derive(Clone, Debug, Default)]
+pub struct Pagination {
+    pages: Paginator,
+    jump: String,
+    controls: Vec<(Range<usize>, Control)>,
+    area: Option<Rect>,
+    style: Style,
+}
+
+impl Pagination {
+    /// Creates a pagination bar over the pages of the paginator.
+    pub fn new(pages: Paginator) -> Pagination {
+        Pagination {
+            pages,
+            ..Pagination::default()
+        }
+    }
+
+    /// Gets the paginator of the pages shown.
+    pub fn paginator(&self) -> &Paginator {
+        &self.pages
+    }
+
+    /// Gets the paginator of the pages shown, mutably, such as to update the
+    /// number of items.
+    pub fn paginator_mut(&mut self) -> &mut Paginator {
+        &mut self.pages
+    }
+
+    /// Gets the page number being typed, if any.
+    pub fn jump_input(&self) -> Option<&str> {
+        if self.jump.is_empty() {
+            None
+        } else {
+            Some(&self.jump)
+        }
+    }
+
+    /// Gets the style the bar is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the bar is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn apply(&mut self, control: Control) {
+        match control {
+            Control::First => self.pages.first_page(),
+            Control::Previous => {
+                self.pages.previous_page();
+            }
+            Control::Next => {
+                self.pages.next_page();
+            }
+            Control::Last => self.pages.last_page(),
+        }
+    }
+
+    /// Jumps to the page typed, counted from one.
+    fn jump(&mut self) {
+        if let Ok(page) = self.jump.parse::<usize>() {
+            self.pages.set_page(page.saturating_sub(1));
+        }
+        self.jump.clear();
+    }
+}
+
+impl<B: Backend> Widget<B> for Pagination {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        self.controls.clear();
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let symbols = if win.ascii_mode() {
+            ["<<", "<", ">", ">>"]
+        } else {
+            ["«", "‹", "›", "»"]
+        };
+        let page = if self.jump.is_empty() {
+            (self.pages.page() + 1).to_string()
+        } else {
+            format!("[{}]", self.jump)
+        };
+        let label = format!(
+            "{} {} {} {}",
+            win.localize(Message::Page),
+            page,
+            win.localize(Message::PageOf),
+            self.pages.pages()
+        );
+
+        let mut line = String::new();
+        let mut push = |line: &mut String, text: &str, control: Option<Control>| {
+            if !line.is_empty() {
+                line.push_str("  ");
+            }
+            let start = win.display_width(line);
+            line.push_str(text);
+            if let Some(control) = control {
+                self.controls
+                    .push((start..start + win.display_width(text), control));
+            }
+        };
+        push(&mut line, symbols[0], Some(Control::First));
+        push(&mut line, symbols[1], Some(Control::Previous));
+        push(&mut line, &label, None);
+        push(&mut line, symbols[2], Some(Control::Next));
+        push(&mut line, symbols[3], Some(Control::Last));
+
+        win.print_styled(area.y, area.x, fit(&line, area.width, win), self.style);
+        for y in 1..area.height {
+            win.print_styled(area.y + y, area.x, " ".repeat(area.width), self.style);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Char(c) if c.is_ascii_digit() && self.jump.len() < MAX_DIGITS => {
+                    self.jump.push(c)
+                }
+                Key::Backspace if !self.jump.is_empty() => {
+                    self.jump.pop();
+                }
+                Key::Enter if !self.jump.is_empty() => self.jump(),
+                Key::Escape if !self.jump.is_empty() => self.jump.clear(),
+                Key::Left | Key::PageUp => self.apply(Control::Previous),
+                Key::Right | Key::PageDown => self.apply(Control::Next),
+                Key::Home => self.apply(Control::First),
+                Key::End => self.apply(Control::Last),
+                _ => return false,
+            },
+            Event::Mouse {
+                pos,
+                event: MouseEvent::Press(MouseButton::Left),
+                ..
+            } => {
+                let (x, y) = match self.area.and_then(|area| area.to_local(pos)) {
+                    Some(local) => local,
+                    None => return false,
+                };
+                let control = self
+                    .controls
+                    .iter()
+                    .find(|&(range, _)| y == 0 && range.contains(&x))
+                    .map(|&(_, control)| control);
+                match control {
+                    Some(control) => self.apply(control),
+                    None => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
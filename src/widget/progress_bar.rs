@@ -0,0 +1,120 @@
+use backend::Backend;
+use layout::Rect;
+use style::Style;
+use widget::{fit, Widget};
+use window::Window;
+
+/// Draws the inside of a progress bar of the given width.
+///
+/// *A total of zero shows a full bar.*
+pub(super) fn bar(done: u64, total: u64, width: usize, ascii: bool) -> String {
+    let filled = (width as u64 * done.min(total))
+        .checked_div(total)
+        .map_or(width, |filled| filled as usize);
+    let (full, empty) = if ascii { ("#", "-") } else { ("█", "░") };
+    format!("{}{}", full.repeat(filled), empty.repeat(width - filled))
+}
+
+/// Represents a progress bar, with an optional label and the percentage done.
+///
+/// ```text
+/// copying [██████████░░░░░░░░░░]  50%
+/// ```
+///
+/// *The bar is drawn on the first row of its area, stretched to fill it.*
+#[derive(Clone, Debug, Default)]
+pub struct ProgressBar {
+    done: u64,
+    total: u64,
+    label: String,
+    style: Style,
+}
+
+impl ProgressBar {
+    /// Creates a new progress bar with the given total.
+    pub fn new(total: u64) -> ProgressBar {
+        ProgressBar {
+            done: 0,
+            total,
+            label: String::new(),
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the progress, as the amount done out of the total.
+    pub fn set_progress(&mut self, done: u64, total: u64) {
+        self.done = done;
+        self.total = total;
+    }
+
+    /// Adds to the amount done.
+    pub fn advance(&mut self, amount: u64) {
+        self.done = self.done.saturating_add(amount);
+    }
+
+    /// Gets the amount done.
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+
+    /// Gets the total.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Gets the percentage done, from `0` to `100`.
+    ///
+    /// *A total of zero counts as finished.*
+    pub fn percent(&self) -> u64 {
+        (100 * self.done.min(self.total))
+            .checked_div(self.total)
+            .unwrap_or(100)
+    }
+
+    /// Checks whether the amount done has reached the total.
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.total
+    }
+
+    /// Sets the label shown before the bar.
+    pub fn set_label<S: Into<String>>(&mut self, label: S) {
+        self.label = label.into();
+    }
+
+    /// Gets the style the progress bar is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the progress bar is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+}
+
+impl<B: Backend> Widget<B> for ProgressBar {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.height == 0 {
+            return;
+        }
+
+        let prefix = if self.label.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", self.label)
+        };
+        let suffix = format!(" {:>3}%", self.percent());
+        // The brackets take two columns.
+        let width = area
+            .width
+            .saturating_sub(win.display_width(&prefix) + suffix.len() + 2);
+        let line = format!(
+            "{}[{}]{}",
+            prefix,
+            bar(self.done, self.total, width, win.ascii_mode()),
+            suffix
+        );
+        win.print_styled(area.y, area.x, fit(&line, area.width, win), self.style);
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::rc::Rc;
+
+use backend::Backend;
+use event::{Event, Key};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::overlay::{self, Overlay};
+use widget::{TextInput, Widget};
+use window::Window;
+
+/// The narrowest a prompt is drawn, leaving room to type.
+const MIN_WIDTH: usize = 24;
+
+type Validator = Rc<dyn Fn(&str) -> Result<(), String>>;
+
+/// Represents a box asking for a line of text, drawn centered over the rest
+/// of the window while it is open.
+///
+/// ```text
+/// ┌ Rename ──────────────────────┐
+/// │                              │
+/// │ New name:                    │
+/// │ report-final.txt             │
+/// │ A file with that name exists │
+/// └──────────────────────────────┘
+/// ```
+///
+/// While open, the prompt takes every key, mouse and paste event, editing
+/// the text like a [`TextInput`](struct.TextInput.html). Enter accepts the
+/// text, if the [validator](#method.set_validator) allows it, otherwise its
+/// error is shown below, and Escape cancels. Once dismissed, the next render
+/// puts back what was underneath.
+#[derive(Clone)]
+pub struct Prompt {
+    title: String,
+    message: String,
+    input: TextInput,
+    validator: Option<Validator>,
+    error: Option<String>,
+    answer: Option<String>,
+    overlay: Overlay,
+    style: Style,
+    error_style: Style,
+}
+
+impl Prompt {
+    /// Creates a closed prompt with the given title and message.
+    pub fn new<S: Into<String>, T: Into<String>>(title: S, message: T) -> Prompt {
+        Prompt {
+            title: title.into(),
+            message: message.into(),
+            input: TextInput::new(),
+            validator: None,
+            error: None,
+            answer: None,
+            overlay: Overlay::default(),
+            style: Style::default(),
+            error_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Sets a check run on the text when Enter is pressed, giving the error
+    /// to show if the text cannot be accepted.
+    pub fn set_validator<F>(&mut self, validator: F)
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+    }
+
+    /// Opens the prompt with the given text to start from.
+    pub fn open<S: Into<String>>(&mut self, value: S) {
+        self.input.set_value(value);
+        self.error = None;
+        self.answer = None;
+        self.overlay.open();
+    }
+
+    /// Closes the prompt as if it was cancelled.
+    pub fn close(&mut self) {
+        self.answer = None;
+        self.overlay.close();
+    }
+
+    /// Checks whether the prompt is open.
+    pub fn is_open(&self) -> bool {
+        self.overlay.is_open()
+    }
+
+    /// Gets the text being typed.
+    pub fn value(&self) -> &str {
+        self.input.value()
+    }
+
+    /// Gets the error shown for the text last entered, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Gets the text accepted when the prompt was last dismissed, or `None`
+    /// if it was cancelled or is still open.
+    pub fn answer(&self) -> Option<&str> {
+        self.answer.as_deref()
+    }
+
+    /// Gets the style the prompt is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the prompt is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+        self.input.set_style(style);
+    }
+
+    /// Gets the style patched over the error shown.
+    pub fn error_style(&self) -> Style {
+        self.error_style
+    }
+
+    /// Sets the style patched over the error shown, bold by default.
+    pub fn set_error_style(&mut self, style: Style) {
+        self.error_style = style;
+    }
+
+    fn accept(&mut self) {
+        let value = self.input.value();
+        if let Some(ref validator) = self.validator {
+            if let Err(err) = validator(value) {
+                self.error = Some(err);
+                return;
+            }
+        }
+        self.error = None;
+        self.answer = Some(value.to_owned());
+        self.overlay.close();
+    }
+}
+
+impl fmt::Debug for Prompt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Prompt")
+            .field("title", &self.title)
+            .field("message", &self.message)
+            .field("input", &self.input)
+            .field("error", &self.error)
+            .field("answer", &self.answer)
+            .field("overlay", &self.overlay)
+            .finish()
+    }
+}
+
+impl<B: Backend> Widget<B> for Prompt {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        let width = [
+            area.width / 2,
+            MIN_WIDTH,
+            win.display_width(&self.title) + 4,
+            win.display_width(&self.message) + 4,
+            self.error
+                .as_ref()
+                .map_or(0, |err| win.display_width(err) + 4),
+        ]
+        .iter()
+        .cloned()
+        .max()
+        .unwrap_or(MIN_WIDTH);
+        let rect = overlay::centered(area, width, 6);
+        if !self.overlay.begin(rect, win) || rect.width < 5 || rect.height < 4 {
+            return;
+        }
+
+        overlay::draw_frame(rect, &self.title, self.style, win);
+        let inner = rect.width - 4;
+        let (x, y) = (rect.x + 2, rect.y + 2);
+        if rect.height >= 6 {
+            let message = super::truncate(&self.message, inner, win);
+            win.print_styled(y, x, message, self.style);
+            if let Some(ref err) = self.error {
+                let err = super::truncate(err, inner, win);
+                win.print_styled(y + 2, x, err, self.style.patch(self.error_style));
+            }
+        }
+        let input_row = if rect.height >= 6 { y + 1 } else { y };
+        self.input.render(Rect::new(x, input_row, inner, 1), win);
+        self.overlay.end(win);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.overlay.is_open() || !overlay::captures(event) {
+            return false;
+        }
+        match *event {
+            Event::Key {
+                key: Key::Enter, ..
+            } if !event.is_key_release() => self.accept(),
+            Event::Key {
+                key: Key::Escape, ..
+            } if !event.is_key_release() => self.close(),
+            Event::Paste(ref text) => {
+                let mut value = self.input.value().to_owned();
+                value.push_str(text.lines().next().unwrap_or(""));
+                self.input.set_value(value);
+            }
+            _ => {
+                Widget::<B>::handle_event(&mut self.input, event);
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,163 @@
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use layout::Rect;
+use style::Style;
+use widget::Widget;
+use window::Window;
+
+/// Represents a rating out of a number of stars.
+///
+/// ```text
+/// ★★★☆☆
+/// ```
+///
+/// Left and Right change the rating by a star, Home and End set none or all
+/// of them, and typing a digit sets that many. Clicking a star sets the
+/// rating to it, and clicking the only star set clears it.
+///
+/// *Stars are drawn as `*` and `.` in ASCII fallback mode. Events are ignored
+/// while the rating is not focused.*
+#[derive(Clone, Debug)]
+pub struct Rating {
+    value: usize,
+    max: usize,
+    focused: bool,
+    area: Option<Rect>,
+    style: Style,
+    filled_style: Style,
+}
+
+impl Default for Rating {
+    fn default() -> Rating {
+        Rating::new(5)
+    }
+}
+
+impl Rating {
+    /// Creates a focused rating out of the given number of stars, with none
+    /// set.
+    pub fn new(max: usize) -> Rating {
+        Rating {
+            value: 0,
+            max,
+            focused: true,
+            area: None,
+            style: Style::default(),
+            filled_style: Style::default(),
+        }
+    }
+
+    /// Gets the number of stars set.
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    /// Sets the number of stars set, up to the most there are.
+    pub fn set_value(&mut self, value: usize) {
+        self.value = value.min(self.max);
+    }
+
+    /// Gets the number of stars.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Sets the number of stars, keeping the rating within it.
+    pub fn set_max(&mut self, max: usize) {
+        self.max = max;
+        self.value = self.value.min(max);
+    }
+
+    /// Checks whether the rating is focused.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether the rating is focused, receiving key events.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Gets the style the rating is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the rating is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the stars set.
+    pub fn filled_style(&self) -> Style {
+        self.filled_style
+    }
+
+    /// Sets the style patched over the stars set.
+    pub fn set_filled_style(&mut self, style: Style) {
+        self.filled_style = style;
+    }
+}
+
+impl<B: Backend> Widget<B> for Rating {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let (filled, empty) = if win.ascii_mode() {
+            ("*", ".")
+        } else {
+            ("★", "☆")
+        };
+        let shown = self.max.min(area.width);
+        let set = self.value.min(shown);
+        win.print_styled(
+            area.y,
+            area.x,
+            filled.repeat(set),
+            self.style.patch(self.filled_style),
+        );
+        win.print_styled(area.y, area.x + set, empty.repeat(shown - set), self.style);
+        win.print_styled(
+            area.y,
+            area.x + shown,
+            " ".repeat(area.width - shown),
+            self.style,
+        );
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Left | Key::Down if self.value > 0 => self.value -= 1,
+                Key::Right | Key::Up if self.value < self.max => self.value += 1,
+                Key::Home => self.value = 0,
+                Key::End => self.value = self.max,
+                Key::Char(ch) if ch.is_ascii_digit() => {
+                    let value = ch as usize - '0' as usize;
+                    self.set_value(value);
+                }
+                _ => return false,
+            },
+            Event::Mouse {
+                pos,
+                event: MouseEvent::Press(MouseButton::Left),
+                ..
+            } => {
+                let (x, _) = match self.area.and_then(|area| area.to_local(pos)) {
+                    Some(local) if local.0 < self.max => local,
+                    _ => return false,
+                };
+                self.value = if self.value == 1 && x == 0 { 0 } else { x + 1 };
+            }
+            _ => return false,
+        }
+        true
+    }
+}
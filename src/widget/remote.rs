@@ -0,0 +1,298 @@
+use std::fmt;
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+use backend::Backend;
+use event::Event;
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::{Window, WindowHandle};
+
+/// The widths of the bars drawn while loading, as shares of the area, taken
+/// in turn for each row.
+const SKELETON: [f64; 5] = [0.9, 0.6, 0.8, 0.7, 0.4];
+
+type Pending<T> = Box<dyn FnMut() -> Option<Result<T, String>>>;
+
+/// Where the data of a [`Remote`](struct.Remote.html) is.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum RemoteState<T> {
+    /// The data is still being fetched.
+    Loading,
+    /// The data has arrived.
+    Ready(T),
+    /// The data could not be fetched, with why.
+    Error(String),
+}
+
+/// Represents data fetched in the background, such as rows from a server,
+/// drawn as a placeholder until it arrives.
+///
+/// ```no_run
+/// use termui::widget::{List, Remote};
+/// use termui::Window;
+///
+/// # fn load_names() -> Result<Vec<String>, String> { Ok(Vec::new()) }
+/// let win = Window::new();
+/// let mut names = Remote::fetch_with_handle(load_names, win.handle()).map(List::new);
+/// ```
+///
+/// The fetch runs on a thread of its own, and its result is picked up when
+/// the remote is [polled](#method.poll), which rendering does. When fetched
+/// with a [`WindowHandle`](../window/struct.WindowHandle.html) the window is
+/// woken with an [`Event::Refresh`](../event/enum.Event.html#variant.Refresh)
+/// once the result arrives.
+///
+/// A remote widget renders as the widget once it is ready, and is given the
+/// events meanwhile. While loading it draws dim bars in the shape of lines of
+/// text, and if the fetch fails it draws the error.
+///
+/// *The fetched data is [mapped](#method.map) on the thread running the
+/// interface, so widgets that cannot be sent between threads can be built
+/// from it.*
+pub struct Remote<T> {
+    state: RemoteState<T>,
+    pending: Option<Pending<T>>,
+    style: Style,
+    placeholder_style: Style,
+    error_style: Style,
+}
+
+impl<T> Remote<T> {
+    /// Creates a remote still loading, with nothing fetching it, so the
+    /// result is [set](#method.set) later.
+    pub fn loading() -> Remote<T> {
+        Remote::with_state(RemoteState::Loading)
+    }
+
+    /// Creates a remote with its data already there.
+    pub fn ready(value: T) -> Remote<T> {
+        Remote::with_state(RemoteState::Ready(value))
+    }
+
+    fn with_state(state: RemoteState<T>) -> Remote<T> {
+        Remote {
+            state,
+            pending: None,
+            style: Style::default(),
+            placeholder_style: Style::new().attr(Attr::Dim),
+            error_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Gets where the data is.
+    pub fn state(&self) -> &RemoteState<T> {
+        &self.state
+    }
+
+    /// Checks whether the data is still being fetched.
+    pub fn is_loading(&self) -> bool {
+        matches!(self.state, RemoteState::Loading)
+    }
+
+    /// Gets the data, once it has arrived.
+    pub fn value(&self) -> Option<&T> {
+        match self.state {
+            RemoteState::Ready(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Gets the data mutably, once it has arrived.
+    pub fn value_mut(&mut self) -> Option<&mut T> {
+        match self.state {
+            RemoteState::Ready(ref mut value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Gets why the data could not be fetched.
+    pub fn error(&self) -> Option<&str> {
+        match self.state {
+            RemoteState::Error(ref err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Sets the result of fetching the data, dropping any fetch still
+    /// running.
+    pub fn set(&mut self, result: Result<T, String>) {
+        self.pending = None;
+        self.state = match result {
+            Ok(value) => RemoteState::Ready(value),
+            Err(err) => RemoteState::Error(err),
+        };
+    }
+
+    /// Picks up the result of the fetch, if it has arrived.
+    ///
+    /// Returns whether the state changed, meaning the remote needs to be
+    /// rendered again.
+    pub fn poll(&mut self) -> bool {
+        let result = match self.pending {
+            Some(ref mut pending) => match pending() {
+                Some(result) => result,
+                None => return false,
+            },
+            None => return false,
+        };
+        self.set(result);
+        true
+    }
+
+    /// Converts the data once it arrives, such as into a widget showing it.
+    ///
+    /// *Keeps the styles and any fetch still running.*
+    pub fn map<U, F>(self, mut f: F) -> Remote<U>
+    where
+        T: 'static,
+        F: FnMut(T) -> U + 'static,
+    {
+        let state = match self.state {
+            RemoteState::Loading => RemoteState::Loading,
+            RemoteState::Ready(value) => RemoteState::Ready(f(value)),
+            RemoteState::Error(err) => RemoteState::Error(err),
+        };
+        let pending = self.pending.map(|mut pending| -> Pending<U> {
+            Box::new(move || pending().map(|result| result.map(&mut f)))
+        });
+        Remote {
+            state,
+            pending,
+            style: self.style,
+            placeholder_style: self.placeholder_style,
+            error_style: self.error_style,
+        }
+    }
+
+    /// Gets the style the placeholder and error are drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the placeholder and error are drawn in, patched over
+    /// the style of where they are rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the bars drawn while loading.
+    pub fn placeholder_style(&self) -> Style {
+        self.placeholder_style
+    }
+
+    /// Sets the style patched over the bars drawn while loading, dim by
+    /// default.
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style = style;
+    }
+
+    /// Gets the style patched over the error.
+    pub fn error_style(&self) -> Style {
+        self.error_style
+    }
+
+    /// Sets the style patched over the error, bold by default.
+    pub fn set_error_style(&mut self, style: Style) {
+        self.error_style = style;
+    }
+}
+
+impl<T: Send + 'static> Remote<T> {
+    /// Starts fetching the data on a thread of its own.
+    pub fn fetch<F>(f: F) -> Remote<T>
+    where
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        let mut remote = Remote::loading();
+        remote.refetch(f, None);
+        remote
+    }
+
+    /// Starts fetching the data on a thread of its own, waking the window
+    /// through the handle once the result arrives.
+    pub fn fetch_with_handle<F>(f: F, handle: WindowHandle) -> Remote<T>
+    where
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        let mut remote = Remote::loading();
+        remote.refetch(f, Some(handle));
+        remote
+    }
+
+    /// Starts fetching the data again, loading until the new result arrives.
+    ///
+    /// *A fetch still running is dropped, and its result ignored.*
+    pub fn refetch<F>(&mut self, f: F, handle: Option<WindowHandle>)
+    where
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(f());
+            if let Some(handle) = handle {
+                handle.request_redraw();
+            }
+        });
+        self.state = RemoteState::Loading;
+        self.pending = Some(Box::new(move || match receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("the fetch panicked".to_owned())),
+        }));
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Remote<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Remote")
+            .field("state", &self.state)
+            .field("fetching", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl<B: Backend, T: Widget<B>> Widget<B> for Remote<T> {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.poll();
+        let (bar, err) = match self.state {
+            RemoteState::Ready(ref mut widget) => return widget.render(area, win),
+            RemoteState::Loading => (if win.ascii_mode() { "-" } else { "░" }, None),
+            RemoteState::Error(ref err) => (" ", Some(err.as_str())),
+        };
+
+        for y in 0..area.height {
+            let row = area.y + y;
+            win.print_styled(row, area.x, " ".repeat(area.width), self.style);
+            match err {
+                Some(err) if y == 0 => win.print_styled(
+                    row,
+                    area.x,
+                    fit(err, area.width, win),
+                    self.style.patch(self.error_style),
+                ),
+                Some(_) => {}
+                None => {
+                    let width = (area.width as f64 * SKELETON[y % SKELETON.len()]) as usize;
+                    win.print_styled(
+                        row,
+                        area.x,
+                        bar.repeat(width.max(1).min(area.width)),
+                        self.style.patch(self.placeholder_style),
+                    );
+                }
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Refresh = *event {
+            self.poll();
+        }
+        match self.state {
+            RemoteState::Ready(ref mut widget) => widget.handle_event(event),
+            _ => false,
+        }
+    }
+}
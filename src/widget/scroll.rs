@@ -0,0 +1,121 @@
+use widget::{List, LogView, TextView};
+
+/// Represents a widget with a vertical scroll offset that can be moved from
+/// outside, so it can be kept in step with others by a
+/// [`ScrollSync`](struct.ScrollSync.html).
+pub trait Scrollable {
+    /// Gets the index of the first visible line.
+    fn scroll_offset(&self) -> usize;
+
+    /// Scrolls so the given line is the first visible, as far as the content
+    /// allows.
+    fn set_scroll_offset(&mut self, offset: usize);
+}
+
+impl Scrollable for TextView {
+    fn scroll_offset(&self) -> usize {
+        self.scroll()
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_to(offset);
+    }
+}
+
+impl Scrollable for List {
+    fn scroll_offset(&self) -> usize {
+        self.offset()
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_to(offset);
+    }
+}
+
+impl Scrollable for LogView {
+    fn scroll_offset(&self) -> usize {
+        self.first_line()
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_to(offset);
+    }
+}
+
+/// Locks the scroll offsets of a group of widgets together, such as the two
+/// sides of a diff or a file and its minimap.
+///
+/// Each member has a ratio, the lines it scrolls for each line of the
+/// group, so a minimap at a tenth of the scale has a ratio of `0.1`.
+///
+/// ```no_run
+/// use termui::widget::{ScrollSync, Scrollable, TextView};
+///
+/// let mut left = TextView::new("old");
+/// let mut right = TextView::new("new");
+/// let mut sync = ScrollSync::new([1.0, 1.0]);
+///
+/// // After handling events, bring the others in line with the one scrolled.
+/// sync.sync(&mut [&mut left as &mut dyn Scrollable, &mut right]);
+/// ```
+///
+/// *Members are matched by their place in the slice given to
+/// [`sync`](#method.sync), so pass them in the same order each time.*
+#[derive(Clone, Debug, Default)]
+pub struct ScrollSync {
+    ratios: Vec<f64>,
+    offsets: Vec<Option<usize>>,
+}
+
+impl ScrollSync {
+    /// Creates a group with the given ratio for each member.
+    ///
+    /// *Ratios that aren't positive are treated as `1.0`.*
+    pub fn new<I: IntoIterator<Item = f64>>(ratios: I) -> ScrollSync {
+        let ratios: Vec<f64> = ratios
+            .into_iter()
+            .map(|ratio| if ratio > 0.0 { ratio } else { 1.0 })
+            .collect();
+        ScrollSync {
+            offsets: vec![None; ratios.len()],
+            ratios,
+        }
+    }
+
+    /// Gets the ratio of each member.
+    pub fn ratios(&self) -> &[f64] {
+        &self.ratios
+    }
+
+    /// Sets the ratio of a member, taking effect on the next sync.
+    pub fn set_ratio(&mut self, member: usize, ratio: f64) {
+        if let Some(slot) = self.ratios.get_mut(member) {
+            *slot = if ratio > 0.0 { ratio } else { 1.0 };
+            self.offsets[member] = None;
+        }
+    }
+
+    /// Scrolls every member to follow the first one that scrolled since the
+    /// last sync, giving whether one had.
+    ///
+    /// *The first sync follows the first member. Members past the number of
+    /// ratios are left alone.*
+    pub fn sync(&mut self, members: &mut [&mut dyn Scrollable]) -> bool {
+        let count = members.len().min(self.ratios.len());
+        let leader = (0..count).find(|&i| self.offsets[i] != Some(members[i].scroll_offset()));
+        let leader = match leader {
+            Some(leader) => leader,
+            None => return false,
+        };
+
+        let position = members[leader].scroll_offset() as f64 / self.ratios[leader];
+        let group = members.iter_mut().zip(&self.ratios).zip(&mut self.offsets);
+        for (i, ((member, &ratio), last)) in group.enumerate() {
+            if i != leader {
+                member.set_scroll_offset((position * ratio).round() as usize);
+            }
+            *last = Some(member.scroll_offset());
+        }
+        true
+    }
+}
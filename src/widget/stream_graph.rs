@@ -0,0 +1,201 @@
+use std::collections::VecDeque;
+
+use backend::Backend;
+use layout::Rect;
+use style::Style;
+use widget::{braille, Widget, BRAILLE_DOTS};
+use window::Window;
+
+/// Represents a line graph of a live series of samples, such as a transfer
+/// rate read many times a second, drawn in braille with the newest sample at
+/// the right edge.
+///
+/// ```text
+/// ⠙⢦    ⢠⠞⠙⣆    ⢠⠏⠹⣄
+///  ⠈⢧  ⢠⠏  ⠘⣆  ⢠⠏  ⠸⡄
+///   ⠈⢧⣠⠏    ⠘⢦⣠⠏    ⠙⣆
+/// ```
+///
+/// Each cell holds two samples across and four dots down. The samples are
+/// kept in a ring of a fixed [capacity](#method.capacity), and the plotted
+/// cells are kept between renders, so [pushing](#method.push) a sample only
+/// plots that sample, and the graph is only plotted again in full when its
+/// size or [range](#method.set_range) changes.
+///
+/// *Samples outside the range are drawn at its edge. In ASCII fallback mode,
+/// cells with any dots set are drawn as `:`.*
+#[derive(Clone, Debug)]
+pub struct StreamGraph {
+    samples: VecDeque<f64>,
+    capacity: usize,
+    pushed: u64,
+    range: (f64, f64),
+    columns: VecDeque<Vec<u8>>,
+    plotted: Option<(usize, usize)>,
+    last: Option<usize>,
+    style: Style,
+}
+
+impl StreamGraph {
+    /// Creates an empty graph keeping up to the given number of samples, over
+    /// the range `0` to `1`.
+    pub fn new(capacity: usize) -> StreamGraph {
+        StreamGraph {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            pushed: 0,
+            range: (0.0, 1.0),
+            columns: VecDeque::new(),
+            plotted: None,
+            last: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Adds a sample at the right edge, dropping the oldest once the graph
+    /// is at capacity.
+    pub fn push(&mut self, value: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+        self.pushed += 1;
+        if let Some((width, height)) = self.plotted {
+            self.plot(self.pushed - 1, value, width, height);
+        }
+    }
+
+    /// Adds several samples, oldest first.
+    pub fn extend<I: IntoIterator<Item = f64>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+
+    /// Gets the samples kept, oldest first.
+    pub fn samples(&self) -> &VecDeque<f64> {
+        &self.samples
+    }
+
+    /// Gets the newest sample.
+    pub fn last(&self) -> Option<f64> {
+        self.samples.back().cloned()
+    }
+
+    /// Removes every sample.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.plotted = None;
+    }
+
+    /// Gets the number of samples kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the number of samples kept, dropping the oldest past it.
+    ///
+    /// *Only as many samples as fit across the graph are drawn, two to a
+    /// cell.*
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+        self.plotted = None;
+    }
+
+    /// Gets the values at the bottom and top of the graph.
+    pub fn range(&self) -> (f64, f64) {
+        self.range
+    }
+
+    /// Sets the values at the bottom and top of the graph.
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        if self.range != (min, max) {
+            self.range = (min, max);
+            self.plotted = None;
+        }
+    }
+
+    /// Gets the style the graph is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the graph is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Plots every sample that fits across a graph of the given size in
+    /// cells.
+    fn replot(&mut self, width: usize, height: usize) {
+        self.columns.clear();
+        self.last = None;
+        self.plotted = Some((width, height));
+        let shown = self.samples.len().min(width * 2);
+        let first = self.pushed - shown as u64;
+        let skip = self.samples.len() - shown;
+        for i in 0..shown {
+            let value = self.samples[skip + i];
+            self.plot(first + i as u64, value, width, height);
+        }
+    }
+
+    /// Plots a sample, given by how many were pushed before it, joining it to
+    /// the one before.
+    fn plot(&mut self, index: u64, value: f64, width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (min, max) = self.range;
+        let dots = height * 4;
+        let ratio = if max > min {
+            ((max - value) / (max - min)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let row = (ratio * (dots - 1) as f64).round() as usize;
+
+        let dot = (index % 2) as usize;
+        if dot == 0 || self.columns.is_empty() {
+            self.columns.push_back(vec![0; height]);
+            while self.columns.len() > width {
+                self.columns.pop_front();
+            }
+        }
+        let column = self.columns.back_mut().expect("a column was just added");
+        let from = self.last.unwrap_or(row);
+        for y in from.min(row)..=from.max(row) {
+            column[y / 4] |= BRAILLE_DOTS[y % 4][dot];
+        }
+        self.last = Some(row);
+    }
+}
+
+impl<B: Backend> Widget<B> for StreamGraph {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        if self.plotted != Some((area.width, area.height)) {
+            self.replot(area.width, area.height);
+        }
+
+        let blank = area.width - self.columns.len();
+        for y in 0..area.height {
+            let mut line = " ".repeat(blank);
+            line.extend(self.columns.iter().map(|column| match column[y] {
+                0 => ' ',
+                _ if win.ascii_mode() => ':',
+                bits => braille(bits),
+            }));
+            win.print_styled(area.y + y, area.x, line, self.style);
+        }
+    }
+}
@@ -0,0 +1,517 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Read};
+
+use backend::Backend;
+use event::{Event, Key, Modifier, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{truncate, Scrollable, Widget};
+use window::Window;
+
+/// The number of rows scrolled by the mouse wheel.
+const WHEEL_STEP: usize = 3;
+/// The columns left between cells.
+const COLUMN_GAP: usize = 1;
+
+/// Represents a cell of a [`Table`](struct.Table.html), made of styled runs
+/// of text and spanning one or more columns.
+///
+/// Cells can be made from strings, to hold plain text.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TableCell {
+    runs: Vec<(String, Style)>,
+    columns: usize,
+}
+
+impl TableCell {
+    /// Creates a cell of plain text.
+    pub fn new<S: Into<String>>(text: S) -> TableCell {
+        TableCell::styled(text, Style::default())
+    }
+
+    /// Creates a cell of text in the given style.
+    pub fn styled<S: Into<String>>(text: S, style: Style) -> TableCell {
+        TableCell::from_runs(Some((text, style)))
+    }
+
+    /// Creates a cell of runs of text, each in its own style, such as those
+    /// given by an [`AnsiParser`](../style/struct.AnsiParser.html).
+    pub fn from_runs<I, S>(runs: I) -> TableCell
+    where
+        I: IntoIterator<Item = (S, Style)>,
+        S: Into<String>,
+    {
+        TableCell {
+            runs: runs
+                .into_iter()
+                .map(|(text, style)| (text.into(), style))
+                .collect(),
+            columns: 1,
+        }
+    }
+
+    /// Makes the cell span the given number of columns, taking the place of
+    /// the cells after it.
+    ///
+    /// *A span of zero is treated as one.*
+    pub fn span(mut self, columns: usize) -> TableCell {
+        self.columns = columns.max(1);
+        self
+    }
+
+    /// Gets the number of columns the cell spans.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Gets the runs of text in the cell.
+    pub fn runs(&self) -> &[(String, Style)] {
+        &self.runs
+    }
+
+    /// Gets the text of the cell, without its styles.
+    pub fn text(&self) -> String {
+        self.runs.iter().map(|(text, _)| &**text).collect()
+    }
+}
+
+impl<'a> From<&'a str> for TableCell {
+    fn from(text: &'a str) -> TableCell {
+        TableCell::new(text)
+    }
+}
+
+impl From<String> for TableCell {
+    fn from(text: String) -> TableCell {
+        TableCell::new(text)
+    }
+}
+
+/// Represents a grid of cells, scrolled by rows and by whole columns.
+///
+/// The first rows can be frozen as headers, drawn bold, and the first
+/// columns can be frozen too, so they stay in view while the rest of the
+/// table is scrolled past them.
+///
+/// Cells hold styled text and can span several columns. Other widgets, such
+/// as progress bars, can be drawn inside a cell by rendering them into its
+/// [`cell_area`](#method.cell_area) after the table.
+///
+/// Scrolls with the arrow keys, Page Up and Page Down, Home and End, and the
+/// mouse wheel, with Shift and the wheel scrolling sideways.
+///
+/// *Each column is as wide as its widest cell, leaving out cells spanning
+/// more than one column, which are cut to fit.*
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+    rows: Vec<Vec<TableCell>>,
+    frozen_rows: usize,
+    frozen_cols: usize,
+    scroll: (usize, usize),
+    area: Option<Rect>,
+    cell_areas: HashMap<(usize, usize), Rect>,
+    right_aligned: BTreeSet<usize>,
+    style: Style,
+    header_style: Style,
+}
+
+impl Table {
+    /// Creates a new table of the given rows of cells.
+    pub fn new<R, C, S>(rows: R) -> Table
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = S>,
+        S: Into<TableCell>,
+    {
+        let mut table = Table::default();
+        table.set_rows(rows);
+        table
+    }
+
+    /// Creates a table from CSV read from the reader, with the first record
+    /// frozen as the header row.
+    ///
+    /// Columns holding only numbers, below the header, are aligned to the
+    /// right.
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use termui::widget::Table;
+    ///
+    /// let table = Table::from_csv(File::open("data.csv").unwrap()).unwrap();
+    /// ```
+    ///
+    /// *Fields can be quoted, with doubled quotes inside them, and records can
+    /// end with either line ending. Fails if the input is not UTF-8.*
+    pub fn from_csv<R: Read>(reader: R) -> io::Result<Table> {
+        Table::from_delimited(reader, ',')
+    }
+
+    /// Creates a table like [`from_csv`](#method.from_csv), with fields
+    /// separated by the given delimiter, such as a tab.
+    pub fn from_delimited<R: Read>(mut reader: R, delimiter: char) -> io::Result<Table> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let rows = parse_delimited(&text, delimiter);
+
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let numeric = (0..columns).filter(|&col| {
+            let mut cells = rows
+                .iter()
+                .skip(1)
+                .filter_map(|row| row.get(col))
+                .filter(|cell| !cell.trim().is_empty())
+                .peekable();
+            cells.peek().is_some() && cells.all(|cell| cell.trim().parse::<f64>().is_ok())
+        });
+        let numeric: Vec<_> = numeric.collect();
+
+        let mut table = Table::new(rows);
+        table.set_frozen_rows(1);
+        table.set_right_aligned(numeric);
+        Ok(table)
+    }
+
+    /// Gets the rows of cells.
+    pub fn rows(&self) -> &[Vec<TableCell>] {
+        &self.rows
+    }
+
+    /// Replaces the rows of cells, keeping the scroll position.
+    pub fn set_rows<R, C, S>(&mut self, rows: R)
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = S>,
+        S: Into<TableCell>,
+    {
+        self.rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect())
+            .collect();
+    }
+
+    /// Replaces the cell at the given row and column, as counted in cells
+    /// rather than the columns they span, adding empty cells as needed.
+    pub fn set_cell<S: Into<TableCell>>(&mut self, row: usize, col: usize, cell: S) {
+        if self.rows.len() <= row {
+            self.rows.resize(row + 1, Vec::new());
+        }
+        let cells = &mut self.rows[row];
+        if cells.len() <= col {
+            cells.resize(col + 1, TableCell::new(""));
+        }
+        cells[col] = cell.into();
+    }
+
+    /// Gets the area of the cell covering the given row and column when the
+    /// table was last rendered, if it was in view.
+    ///
+    /// *The area of a cell partly scrolled out of view is the part still in
+    /// view.*
+    pub fn cell_area(&self, row: usize, col: usize) -> Option<Rect> {
+        let start = self.rows.get(row).and_then(|cells| {
+            cells
+                .iter()
+                .scan(0, |start, cell| {
+                    let at = *start;
+                    *start += cell.columns;
+                    Some((at, *start))
+                })
+                .find(|&(_, end)| col < end)
+                .map(|(start, _)| start)
+        })?;
+        self.cell_areas.get(&(row, start)).cloned()
+    }
+
+    /// Aligns the cells starting in the given columns to the right, and the
+    /// rest to the left.
+    pub fn set_right_aligned<I: IntoIterator<Item = usize>>(&mut self, cols: I) {
+        self.right_aligned = cols.into_iter().collect();
+    }
+
+    /// Checks whether the cells starting in a column are aligned to the
+    /// right.
+    pub fn is_right_aligned(&self, col: usize) -> bool {
+        self.right_aligned.contains(&col)
+    }
+
+    /// Gets the number of header rows frozen at the top.
+    pub fn frozen_rows(&self) -> usize {
+        self.frozen_rows
+    }
+
+    /// Freezes the first rows at the top, as headers.
+    pub fn set_frozen_rows(&mut self, rows: usize) {
+        self.frozen_rows = rows;
+    }
+
+    /// Gets the number of columns frozen at the left.
+    pub fn frozen_cols(&self) -> usize {
+        self.frozen_cols
+    }
+
+    /// Freezes the first columns at the left.
+    pub fn set_frozen_cols(&mut self, cols: usize) {
+        self.frozen_cols = cols;
+    }
+
+    /// Gets the scroll position, as the first row and column shown after the
+    /// frozen ones.
+    pub fn scroll(&self) -> (usize, usize) {
+        self.scroll
+    }
+
+    /// Scrolls so the given row and column are the first shown after the
+    /// frozen ones, as far as the table allows.
+    pub fn scroll_to(&mut self, row: usize, col: usize) {
+        self.scroll = (row.min(self.max_row()), col.min(self.max_col()));
+    }
+
+    /// Gets the style the table is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the table is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the frozen rows, after making them bold.
+    pub fn header_style(&self) -> Style {
+        self.header_style
+    }
+
+    /// Sets the style patched over the frozen rows, after making them bold.
+    pub fn set_header_style(&mut self, style: Style) {
+        self.header_style = style;
+    }
+
+    /// Gets the number of columns, the most spanned by any row.
+    fn columns(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|cells| cells.iter().map(TableCell::columns).sum())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Gets the furthest the rows can be scrolled in the last rendered area.
+    fn max_row(&self) -> usize {
+        let frozen = self.frozen_rows.min(self.rows.len());
+        let height = self
+            .area
+            .map_or(0, |area| area.height.saturating_sub(frozen));
+        (self.rows.len() - frozen).saturating_sub(height)
+    }
+
+    /// Gets the furthest the columns can be scrolled, leaving one in view.
+    fn max_col(&self) -> usize {
+        let columns = self.columns();
+        columns.saturating_sub(self.frozen_cols.min(columns) + 1)
+    }
+
+    fn scroll_by(&mut self, rows: isize, cols: isize) {
+        let shift = |at: usize, by: isize| {
+            if by < 0 {
+                at.saturating_sub(by.unsigned_abs())
+            } else {
+                at.saturating_add(by as usize)
+            }
+        };
+        let (row, col) = self.scroll;
+        self.scroll_to(shift(row, rows), shift(col, cols));
+    }
+
+    /// Draws a row at the given line of the area, recording where its cells
+    /// are drawn.
+    fn draw_row<B: Backend>(
+        &mut self,
+        index: usize,
+        y: usize,
+        columns: &[(usize, usize, usize)],
+        style: Style,
+        win: &Window<B>,
+    ) {
+        let area = match self.area {
+            Some(area) => area,
+            None => return,
+        };
+        win.print_styled(area.y + y, area.x, " ".repeat(area.width), style);
+
+        let mut start = 0;
+        for cell in &self.rows[index] {
+            let span = start..start + cell.columns;
+            start = span.end;
+            let mut shown = columns.iter().filter(|&&(col, _, _)| span.contains(&col));
+            let (x, mut width) = match shown.next() {
+                Some(&(_, x, width)) => (x, width),
+                None => continue,
+            };
+            width += shown
+                .map(|&(_, _, width)| width + COLUMN_GAP)
+                .sum::<usize>();
+            let width = width.min(area.width - x);
+            self.cell_areas.insert(
+                (index, span.start),
+                Rect::new(area.x + x, area.y + y, width, 1),
+            );
+
+            let mut col = 0;
+            if self.right_aligned.contains(&span.start) {
+                let text = win.display_width(&cell.text());
+                col = width.saturating_sub(text);
+            }
+            for &(ref text, run) in &cell.runs {
+                if col >= width {
+                    break;
+                }
+                let text = truncate(text, width - col, win);
+                win.print_styled(area.y + y, area.x + x + col, text, style.patch(run));
+                col += win.display_width(text);
+            }
+        }
+    }
+}
+
+impl Scrollable for Table {
+    fn scroll_offset(&self) -> usize {
+        self.scroll.0
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        let col = self.scroll.1;
+        self.scroll_to(offset, col);
+    }
+}
+
+impl<B: Backend> Widget<B> for Table {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let (row, col) = self.scroll;
+        self.scroll_to(row, col);
+
+        let mut widths = vec![0; self.columns()];
+        for row in &self.rows {
+            let mut start = 0;
+            for cell in row {
+                if cell.columns == 1 {
+                    let width = win.display_width(&cell.text());
+                    widths[start] = widths[start].max(width);
+                }
+                start += cell.columns;
+            }
+        }
+
+        // The shown columns, with where they start and their widths.
+        let frozen = self.frozen_cols.min(widths.len());
+        let mut columns = Vec::new();
+        let mut x = 0;
+        for col in (0..frozen).chain(frozen + self.scroll.1..widths.len()) {
+            if x >= area.width {
+                break;
+            }
+            columns.push((col, x, widths[col]));
+            x += widths[col] + COLUMN_GAP;
+        }
+
+        self.cell_areas.clear();
+        let frozen = self.frozen_rows.min(self.rows.len());
+        let shown = (0..frozen).chain(frozen + self.scroll.0..self.rows.len());
+        let mut shown = shown.take(area.height);
+        for y in 0..area.height {
+            match shown.next() {
+                Some(index) if index < frozen => {
+                    let style = self
+                        .style
+                        .patch(Style::new().attr(Attr::Bold))
+                        .patch(self.header_style);
+                    self.draw_row(index, y, &columns, style, win);
+                }
+                Some(index) => {
+                    let style = self.style;
+                    self.draw_row(index, y, &columns, style, win);
+                }
+                None => win.print_styled(area.y + y, area.x, " ".repeat(area.width), self.style),
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let page = self.area.map_or(1, |area| {
+            area.height.saturating_sub(self.frozen_rows + 1).max(1)
+        }) as isize;
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Up => self.scroll_by(-1, 0),
+                Key::Down => self.scroll_by(1, 0),
+                Key::Left => self.scroll_by(0, -1),
+                Key::Right => self.scroll_by(0, 1),
+                Key::PageUp => self.scroll_by(-page, 0),
+                Key::PageDown => self.scroll_by(page, 0),
+                Key::Home => self.scroll_to(0, 0),
+                Key::End => self.scroll_to(usize::MAX, self.scroll.1),
+                _ => return false,
+            },
+            Event::Mouse {
+                pos,
+                event,
+                modifier,
+                ..
+            } if self.area.is_some_and(|area| area.contains(pos)) => {
+                let step = WHEEL_STEP as isize;
+                let sideways = modifier.contains(Modifier::Shift);
+                match event {
+                    MouseEvent::WheelUp if sideways => self.scroll_by(0, -1),
+                    MouseEvent::WheelDown if sideways => self.scroll_by(0, 1),
+                    MouseEvent::WheelUp => self.scroll_by(-step, 0),
+                    MouseEvent::WheelDown => self.scroll_by(step, 0),
+                    MouseEvent::WheelLeft => self.scroll_by(0, -1),
+                    MouseEvent::WheelRight => self.scroll_by(0, 1),
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Splits delimited text into records of fields, unquoting quoted fields.
+fn parse_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(::std::mem::take(&mut field));
+                rows.push(::std::mem::take(&mut row));
+            }
+            ch if ch == delimiter && !quoted => row.push(::std::mem::take(&mut field)),
+            ch => field.push(ch),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
@@ -0,0 +1,382 @@
+use std::fmt;
+use std::rc::Rc;
+
+use backend::Backend;
+use event::{Event, Key, Modifier, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{truncate, Widget};
+use window::{CursorStyle, Window};
+
+/// Gives suggestions for what is being typed into a
+/// [`TagInput`](struct.TagInput.html).
+///
+/// *Implemented for closures, and for lists of words, which suggest the
+/// words starting with the text typed, ignoring case.*
+pub trait SuggestionProvider {
+    /// Gets the suggestions for the text typed, best first.
+    fn suggest(&self, input: &str) -> Vec<String>;
+}
+
+impl<F: Fn(&str) -> Vec<String>> SuggestionProvider for F {
+    fn suggest(&self, input: &str) -> Vec<String> {
+        self(input)
+    }
+}
+
+impl SuggestionProvider for Vec<String> {
+    fn suggest(&self, input: &str) -> Vec<String> {
+        let input = input.to_lowercase();
+        self.iter()
+            .filter(|word| word.to_lowercase().starts_with(&input))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Represents an input where each entry typed becomes a tag, shown as a chip
+/// that can be removed.
+///
+/// ```text
+///  rust ×   tui ×   terminal ×  cur|ses
+/// ```
+///
+/// Enter or a comma turns the text typed into a tag, and Backspace with
+/// nothing typed removes the last tag. Clicking the `×` of a chip removes it.
+/// With [suggestions](#method.set_suggestions) the first one is shown after
+/// the text, and Tab completes it, moving on to the next suggestion when
+/// pressed again. Chips wrap onto the following lines once a line is full.
+///
+/// *Tags are trimmed, and empty or repeated tags are ignored. Events are
+/// ignored while the input is not focused.*
+#[derive(Clone)]
+pub struct TagInput {
+    tags: Vec<String>,
+    input: String,
+    suggestions: Option<Rc<dyn SuggestionProvider>>,
+    completion: Option<(String, usize)>,
+    focused: bool,
+    removers: Vec<(Rect, usize)>,
+    style: Style,
+    tag_style: Style,
+    suggestion_style: Style,
+}
+
+impl Default for TagInput {
+    fn default() -> TagInput {
+        TagInput::new()
+    }
+}
+
+impl TagInput {
+    /// Creates a focused input with no tags.
+    pub fn new() -> TagInput {
+        TagInput {
+            tags: Vec::new(),
+            input: String::new(),
+            suggestions: None,
+            completion: None,
+            focused: true,
+            removers: Vec::new(),
+            style: Style::default(),
+            tag_style: Style::new().attr(Attr::Reverse),
+            suggestion_style: Style::new().attr(Attr::Dim),
+        }
+    }
+
+    /// Gets the tags entered.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Replaces the tags, skipping empty and repeated ones.
+    pub fn set_tags<I, S>(&mut self, tags: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags.clear();
+        for tag in tags {
+            self.add_tag(tag);
+        }
+    }
+
+    /// Adds a tag, giving whether it was added, which it is not if it is empty
+    /// or already there.
+    pub fn add_tag<S: Into<String>>(&mut self, tag: S) -> bool {
+        let tag = tag.into();
+        let tag = tag.trim();
+        if tag.is_empty() || self.tags.iter().any(|existing| existing == tag) {
+            return false;
+        }
+        self.tags.push(tag.to_owned());
+        true
+    }
+
+    /// Removes the tag at the given index, if there is one.
+    pub fn remove_tag(&mut self, index: usize) -> Option<String> {
+        if index < self.tags.len() {
+            Some(self.tags.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the text being typed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Sets where suggestions for the text typed come from.
+    pub fn set_suggestions<P: SuggestionProvider + 'static>(&mut self, provider: P) {
+        self.suggestions = Some(Rc::new(provider));
+        self.completion = None;
+    }
+
+    /// Gets the suggestion Tab would complete, if any.
+    pub fn suggestion(&self) -> Option<String> {
+        let suggestions = self.suggestions();
+        if suggestions.is_empty() {
+            return None;
+        }
+        let index = self
+            .completion
+            .as_ref()
+            .map_or(0, |&(_, index)| (index + 1) % suggestions.len());
+        suggestions.into_iter().nth(index)
+    }
+
+    /// Checks whether the input is focused.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether the input is focused, receiving key events and showing
+    /// the cursor.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Gets the style the input is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the input is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the chips.
+    pub fn tag_style(&self) -> Style {
+        self.tag_style
+    }
+
+    /// Sets the style patched over the chips, reversed by default.
+    pub fn set_tag_style(&mut self, style: Style) {
+        self.tag_style = style;
+    }
+
+    /// Gets the style patched over the suggestion shown after the text.
+    pub fn suggestion_style(&self) -> Style {
+        self.suggestion_style
+    }
+
+    /// Sets the style patched over the suggestion shown after the text, dim
+    /// by default.
+    pub fn set_suggestion_style(&mut self, style: Style) {
+        self.suggestion_style = style;
+    }
+
+    /// Gets the suggestions for the text typed that are not already tags.
+    fn suggestions(&self) -> Vec<String> {
+        match self.suggestions {
+            Some(ref provider) if !self.typed().is_empty() => provider
+                .suggest(self.typed())
+                .into_iter()
+                .filter(|suggestion| !self.tags.contains(suggestion))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Gets the text the suggestions are for, which is what was typed before
+    /// Tab was first pressed.
+    fn typed(&self) -> &str {
+        match self.completion {
+            Some((ref typed, _)) => typed,
+            None => &self.input,
+        }
+    }
+
+    /// Turns the text typed into a tag.
+    fn commit(&mut self) {
+        let input = ::std::mem::take(&mut self.input);
+        self.add_tag(input);
+        self.completion = None;
+    }
+
+    fn edited(&mut self) {
+        self.completion = None;
+    }
+}
+
+impl fmt::Debug for TagInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TagInput")
+            .field("tags", &self.tags)
+            .field("input", &self.input)
+            .field("completion", &self.completion)
+            .field("focused", &self.focused)
+            .finish()
+    }
+}
+
+impl<B: Backend> Widget<B> for TagInput {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.removers.clear();
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        // Lay out the chips, then the text typed, as (row, col, text, tag).
+        let mut placed: Vec<(usize, usize, String, Option<usize>)> = Vec::new();
+        let (mut row, mut col) = (0, 0);
+        let mut place = |text: String, width: usize, tag: Option<usize>| {
+            if col > 0 && col + width > area.width {
+                row += 1;
+                col = 0;
+            }
+            placed.push((row, col, text, tag));
+            col += width + 1;
+        };
+        for (i, tag) in self.tags.iter().enumerate() {
+            let chip = format!(" {} × ", tag);
+            let width = win.display_width(&chip);
+            place(chip, width, Some(i));
+        }
+        let suggestion = self.suggestion();
+        let ghost = match suggestion {
+            Some(ref suggestion) if suggestion.len() > self.input.len() => suggestion
+                .get(self.input.len()..)
+                .filter(|_| {
+                    suggestion
+                        .to_lowercase()
+                        .starts_with(&self.input.to_lowercase())
+                })
+                .unwrap_or(""),
+            _ => "",
+        };
+        let input_width = win.display_width(&self.input) + win.display_width(ghost) + 1;
+        place(String::new(), input_width.min(area.width), None);
+
+        // Scroll so the line being typed on shows.
+        let input_row = placed.last().map_or(0, |&(row, _, _, _)| row);
+        let scroll = (input_row + 1).saturating_sub(area.height);
+
+        let blank = " ".repeat(area.width);
+        for y in 0..area.height {
+            win.print_styled(area.y + y, area.x, &blank, self.style);
+        }
+        for (row, col, text, tag) in placed {
+            if row < scroll || row - scroll >= area.height {
+                continue;
+            }
+            let (y, x) = (area.y + row - scroll, area.x + col);
+            let room = area.width - col;
+            match tag {
+                Some(index) => {
+                    let chip = truncate(&text, room, win);
+                    win.print_styled(y, x, chip, self.style.patch(self.tag_style));
+                    let width = win.display_width(&text);
+                    if width <= room {
+                        self.removers
+                            .push((Rect::new(x + width - 2, y, 1, 1), index));
+                    }
+                }
+                None => {
+                    let typed = truncate(&self.input, room, win);
+                    win.print_styled(y, x, typed, self.style);
+                    let typed_width = win.display_width(typed);
+                    let ghost = truncate(ghost, room - typed_width, win);
+                    win.print_styled(
+                        y,
+                        x + typed_width,
+                        ghost,
+                        self.style.patch(self.suggestion_style),
+                    );
+                    if self.focused {
+                        let cursor = (x + typed_width).min(area.right() - 1);
+                        win.request_cursor(CursorStyle::Bar(y, cursor));
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.focused {
+            return false;
+        }
+        match *event {
+            Event::Key { key, modifier, .. } if !event.is_key_release() => match key {
+                Key::Enter | Key::Char(',') => self.commit(),
+                Key::Tab => {
+                    let suggestion = match self.suggestion() {
+                        Some(suggestion) => suggestion,
+                        None => return false,
+                    };
+                    let count = self.suggestions().len();
+                    let (typed, index) = match self.completion.take() {
+                        Some((typed, index)) => (typed, (index + 1) % count),
+                        None => (::std::mem::take(&mut self.input), 0),
+                    };
+                    self.completion = Some((typed, index));
+                    self.input = suggestion;
+                }
+                Key::Backspace if self.input.is_empty() => {
+                    if self.tags.pop().is_none() {
+                        return false;
+                    }
+                }
+                Key::Backspace => {
+                    self.input.pop();
+                    self.edited();
+                }
+                Key::Escape if !self.input.is_empty() => {
+                    self.input.clear();
+                    self.edited();
+                }
+                Key::Char(ch) if (modifier - Modifier::Shift).is_empty() && !ch.is_control() => {
+                    self.input.push(ch);
+                    self.edited();
+                }
+                _ => return false,
+            },
+            Event::Paste(ref text) => {
+                let mut parts: Vec<&str> = text.split([',', '\n']).collect();
+                let last = parts.pop().unwrap_or("");
+                for part in parts {
+                    self.input.push_str(part);
+                    self.commit();
+                }
+                self.input.push_str(last);
+                self.edited();
+            }
+            Event::Mouse {
+                pos,
+                event: MouseEvent::Press(MouseButton::Left),
+                ..
+            } => {
+                let index = match self.removers.iter().find(|(rect, _)| rect.contains(pos)) {
+                    Some(&(_, index)) => index,
+                    None => return false,
+                };
+                self.remove_tag(index);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,299 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use backend::Backend;
+use layout::Rect;
+use style::{Color, Style};
+use widget::{fit, progress_bar, Widget};
+use window::Window;
+
+/// The spinner frames shown for running tasks.
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// The spinner frames used in ascii mode.
+const ASCII_SPINNER: &[&str] = &["|", "/", "-", "\\"];
+/// How long each spinner frame is shown for.
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+/// The width of the progress bars.
+const BAR_WIDTH: usize = 20;
+
+/// Represents the status of a task.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TaskStatus {
+    /// The task is still running.
+    Running,
+    /// The task finished successfully.
+    Succeeded,
+    /// The task failed.
+    Failed,
+}
+
+#[derive(Debug)]
+struct TaskState {
+    /// Identifies the task to its handles, staying the same as tasks before
+    /// it are removed.
+    id: usize,
+    name: String,
+    message: String,
+    progress: Option<(u64, u64)>,
+    status: TaskStatus,
+    started: Instant,
+    finished: Option<Instant>,
+}
+
+/// Represents a list of concurrent tasks, showing their progress.
+///
+/// Each task is shown on its own line with a spinner, an optional progress
+/// bar, its elapsed time and a message. Finished tasks stay in the list, so
+/// the final status of every task remains visible.
+///
+/// Succeeded tasks are drawn in green and failed ones in red, patched over
+/// the style of the list.
+///
+/// *Tasks are updated through their [`Task`](struct.Task.html) handles, which
+/// can be sent to the threads doing the work.*
+#[derive(Debug)]
+pub struct TaskList {
+    tasks: Arc<Mutex<Tasks>>,
+    style: Style,
+    succeeded_style: Style,
+    failed_style: Style,
+}
+
+impl Default for TaskList {
+    fn default() -> TaskList {
+        TaskList {
+            tasks: Arc::default(),
+            style: Style::default(),
+            succeeded_style: Style::new().fg(Color::Green),
+            failed_style: Style::new().fg(Color::Red),
+        }
+    }
+}
+
+/// The tasks of a list, shared with their handles.
+#[derive(Debug, Default)]
+struct Tasks {
+    states: Vec<TaskState>,
+    next_id: usize,
+}
+
+impl Tasks {
+    fn get(&self, id: usize) -> Option<&TaskState> {
+        self.states.iter().find(|task| task.id == id)
+    }
+
+    fn get_mut(&mut self, id: usize) -> Option<&mut TaskState> {
+        self.states.iter_mut().find(|task| task.id == id)
+    }
+}
+
+/// A handle for updating a task in a [`TaskList`](struct.TaskList.html).
+#[derive(Clone, Debug)]
+pub struct Task {
+    tasks: Arc<Mutex<Tasks>>,
+    id: usize,
+}
+
+fn lock(tasks: &Mutex<Tasks>) -> MutexGuard<'_, Tasks> {
+    // A panic while updating a task cannot leave it inconsistent.
+    tasks.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl TaskList {
+    /// Creates a new, empty task list.
+    pub fn new() -> TaskList {
+        TaskList::default()
+    }
+
+    /// Gets the style the task list is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the task list is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over tasks that succeeded.
+    pub fn succeeded_style(&self) -> Style {
+        self.succeeded_style
+    }
+
+    /// Sets the style patched over tasks that succeeded, green by default.
+    pub fn set_succeeded_style(&mut self, style: Style) {
+        self.succeeded_style = style;
+    }
+
+    /// Gets the style patched over tasks that failed.
+    pub fn failed_style(&self) -> Style {
+        self.failed_style
+    }
+
+    /// Sets the style patched over tasks that failed, red by default.
+    pub fn set_failed_style(&mut self, style: Style) {
+        self.failed_style = style;
+    }
+
+    /// Starts a new running task, giving a handle for updating it.
+    pub fn add<S: Into<String>>(&self, name: S) -> Task {
+        let mut tasks = lock(&self.tasks);
+        let id = tasks.next_id;
+        tasks.next_id += 1;
+        tasks.states.push(TaskState {
+            id,
+            name: name.into(),
+            message: String::new(),
+            progress: None,
+            status: TaskStatus::Running,
+            started: Instant::now(),
+            finished: None,
+        });
+        Task {
+            tasks: self.tasks.clone(),
+            id,
+        }
+    }
+
+    /// Gets the number of tasks in the list.
+    pub fn len(&self) -> usize {
+        lock(&self.tasks).states.len()
+    }
+
+    /// Checks whether the list has no tasks.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.tasks).states.is_empty()
+    }
+
+    /// Checks whether every task in the list has finished.
+    pub fn is_finished(&self) -> bool {
+        lock(&self.tasks)
+            .states
+            .iter()
+            .all(|task| task.status != TaskStatus::Running)
+    }
+
+    /// Removes the finished tasks from the list.
+    ///
+    /// *Handles to the removed tasks stop having any effect, and handles to
+    /// the remaining tasks keep working.*
+    pub fn clear_finished(&self) {
+        let mut tasks = lock(&self.tasks);
+        tasks
+            .states
+            .retain(|task| task.status == TaskStatus::Running);
+    }
+
+    /// Renders the task list into the given area of the window.
+    ///
+    /// *When there are more tasks than rows, the most recent tasks are
+    /// shown.*
+    pub fn render<B: Backend>(&self, area: Rect, win: &Window<B>) {
+        let tasks = &lock(&self.tasks).states;
+        let now = Instant::now();
+        let ascii = win.ascii_mode();
+        let name_width = tasks
+            .iter()
+            .map(|task| win.display_width(&task.name))
+            .max()
+            .unwrap_or(0);
+
+        let skip = tasks.len().saturating_sub(area.height);
+        for (row, task) in (area.y..area.bottom()).zip(tasks.iter().skip(skip)) {
+            let elapsed = task.finished.unwrap_or(now) - task.started;
+            let icon = match task.status {
+                TaskStatus::Running => {
+                    let frames = if ascii { ASCII_SPINNER } else { SPINNER };
+                    let frame = elapsed.as_millis() / SPINNER_INTERVAL.as_millis();
+                    frames[frame as usize % frames.len()]
+                }
+                TaskStatus::Succeeded if ascii => "+",
+                TaskStatus::Succeeded => "✔",
+                TaskStatus::Failed if ascii => "x",
+                TaskStatus::Failed => "✘",
+            };
+
+            let mut line = format!("{} {}", icon, task.name);
+            line.push_str(&" ".repeat(name_width - win.display_width(&task.name)));
+            if let Some((done, total)) = task.progress {
+                line.push_str(&format!(
+                    " [{}] {}/{}",
+                    progress_bar::bar(done, total, BAR_WIDTH, ascii),
+                    done,
+                    total
+                ));
+            }
+            line.push_str(&format!(
+                " {}.{}s",
+                elapsed.as_secs(),
+                elapsed.subsec_millis() / 100
+            ));
+            if !task.message.is_empty() {
+                line.push(' ');
+                line.push_str(&task.message);
+            }
+
+            let style = match task.status {
+                TaskStatus::Running => self.style,
+                TaskStatus::Succeeded => self.style.patch(self.succeeded_style),
+                TaskStatus::Failed => self.style.patch(self.failed_style),
+            };
+            win.print_styled(row, area.x, fit(&line, area.width, win), style);
+        }
+        for row in (area.y + tasks.len().min(area.height))..area.bottom() {
+            win.print_styled(row, area.x, " ".repeat(area.width), self.style);
+        }
+    }
+}
+
+impl<B: Backend> Widget<B> for TaskList {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        TaskList::render(self, area, win);
+    }
+}
+
+impl Task {
+    fn update<F: FnOnce(&mut TaskState)>(&self, f: F) {
+        if let Some(task) = lock(&self.tasks).get_mut(self.id) {
+            f(task);
+        }
+    }
+
+    /// Sets the progress of the task.
+    pub fn set_progress(&self, done: u64, total: u64) {
+        self.update(|task| task.progress = Some((done, total)));
+    }
+
+    /// Sets the message shown after the task.
+    pub fn set_message<S: Into<String>>(&self, msg: S) {
+        let msg = msg.into();
+        self.update(|task| task.message = msg);
+    }
+
+    /// Gets the status of the task.
+    pub fn status(&self) -> TaskStatus {
+        lock(&self.tasks)
+            .get(self.id)
+            .map_or(TaskStatus::Running, |task| task.status)
+    }
+
+    /// Marks the task as having finished successfully.
+    pub fn succeed(&self) {
+        self.finish(TaskStatus::Succeeded);
+    }
+
+    /// Marks the task as having failed.
+    pub fn fail(&self) {
+        self.finish(TaskStatus::Failed);
+    }
+
+    fn finish(&self, status: TaskStatus) {
+        self.update(|task| {
+            if task.status == TaskStatus::Running {
+                task.status = status;
+                task.finished = Some(Instant::now());
+            }
+        });
+    }
+}
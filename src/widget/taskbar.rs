@@ -0,0 +1,261 @@
+use backend::Backend;
+use event::{Event, Key, Modifier, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget, WindowId, WindowManager};
+use window::Window;
+
+/// The widest an entry is drawn, in columns.
+const MAX_ENTRY_WIDTH: usize = 24;
+
+/// A window listed in a taskbar.
+#[derive(Clone, Debug)]
+struct Entry {
+    id: WindowId,
+    title: String,
+    focused: bool,
+    minimized: bool,
+    urgent: bool,
+}
+
+/// Represents a row listing the windows of a
+/// [`WindowManager`](struct.WindowManager.html), to switch between them.
+///
+/// ```no_run
+/// use termui::layout::Rect;
+/// use termui::widget::{List, Taskbar, Widget, WindowManager};
+/// use termui::Window;
+///
+/// let mut win = Window::new();
+/// let mut windows = WindowManager::new();
+/// windows.open("Notes", Rect::new(2, 1, 24, 6), List::new(vec!["buy milk"]));
+/// let mut taskbar = Taskbar::new();
+///
+/// loop {
+///     if let Some(ev) = win.poll_event() {
+///         if !<Taskbar as Widget>::handle_event(&mut taskbar, &ev) {
+///             windows.handle_event(&ev);
+///         }
+///     }
+///     taskbar.sync(&mut windows);
+///     let (rows, cols) = win.get_size();
+///     windows.render(Rect::new(0, 0, cols, rows - 1), &win);
+///     taskbar.render(Rect::new(0, rows - 1, cols, 1), &win);
+///     win.refresh();
+/// }
+/// ```
+///
+/// Each window is listed by its title, in the order they were opened, the
+/// first nine numbered. Minimized windows are listed in parentheses, and
+/// urgent ones marked with a `!`.
+///
+/// Clicking a window, or pressing Alt with its number, focuses it, restoring
+/// it if minimized. Choosing the focused window minimizes it instead.
+///
+/// *The taskbar only shows the windows as of its last
+/// [`sync`](#method.sync), which is also when the window chosen is focused.*
+#[derive(Clone, Debug)]
+pub struct Taskbar {
+    entries: Vec<Entry>,
+    chosen: Option<WindowId>,
+    /// The columns each entry was last drawn in, as start and end.
+    spans: Vec<(usize, usize)>,
+    area: Option<Rect>,
+    style: Style,
+    focus_style: Style,
+    minimized_style: Style,
+    urgent_style: Style,
+}
+
+impl Default for Taskbar {
+    fn default() -> Taskbar {
+        Taskbar {
+            entries: Vec::new(),
+            chosen: None,
+            spans: Vec::new(),
+            area: None,
+            style: Style::default(),
+            focus_style: Style::new().attr(Attr::Reverse),
+            minimized_style: Style::new().attr(Attr::Dim),
+            urgent_style: Style::new().attr(Attr::Bold),
+        }
+    }
+}
+
+impl Taskbar {
+    /// Creates a taskbar listing no windows until synced.
+    pub fn new() -> Taskbar {
+        Taskbar::default()
+    }
+
+    /// Focuses or minimizes the window chosen since the last sync, then lists
+    /// the windows of the manager afresh.
+    ///
+    /// Returns whether a window was chosen.
+    pub fn sync<B: Backend>(&mut self, windows: &mut WindowManager<B>) -> bool {
+        let chosen = self.chosen.take();
+        if let Some(id) = chosen {
+            if windows.focused() == Some(id) {
+                windows.minimize(id);
+            } else {
+                windows.focus(id);
+            }
+        }
+
+        let focused = windows.focused();
+        self.entries = windows
+            .windows()
+            .into_iter()
+            .map(|id| Entry {
+                id,
+                title: windows.title(id).unwrap_or_default().to_owned(),
+                focused: focused == Some(id),
+                minimized: windows.is_minimized(id),
+                urgent: windows.is_urgent(id),
+            })
+            .collect();
+        chosen.is_some()
+    }
+
+    /// Gets the window chosen since the last sync.
+    pub fn chosen(&self) -> Option<WindowId> {
+        self.chosen
+    }
+
+    /// Gets the style of the taskbar.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style of the taskbar, patched over the style of where it is
+    /// rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the focused window.
+    pub fn focus_style(&self) -> Style {
+        self.focus_style
+    }
+
+    /// Sets the style patched over the focused window, reversed by default.
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style = style;
+    }
+
+    /// Gets the style patched over minimized windows.
+    pub fn minimized_style(&self) -> Style {
+        self.minimized_style
+    }
+
+    /// Sets the style patched over minimized windows, dimmed by default.
+    pub fn set_minimized_style(&mut self, style: Style) {
+        self.minimized_style = style;
+    }
+
+    /// Gets the style patched over urgent windows.
+    pub fn urgent_style(&self) -> Style {
+        self.urgent_style
+    }
+
+    /// Sets the style patched over urgent windows, bold by default.
+    pub fn set_urgent_style(&mut self, style: Style) {
+        self.urgent_style = style;
+    }
+
+    fn label(index: usize, entry: &Entry) -> String {
+        let mut label = String::new();
+        if index < 9 {
+            label.push_str(&format!("{} ", index + 1));
+        }
+        if entry.urgent {
+            label.push('!');
+        }
+        if entry.minimized {
+            label.push_str(&format!("({})", entry.title));
+        } else {
+            label.push_str(&entry.title);
+        }
+        label
+    }
+
+    fn choose(&mut self, index: usize) -> bool {
+        match self.entries.get(index) {
+            Some(entry) => {
+                self.chosen = Some(entry.id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<B: Backend> Widget<B> for Taskbar {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        self.spans.clear();
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        win.print_styled(area.y, area.x, " ".repeat(area.width), self.style);
+        let count = self.entries.len().max(1);
+        let width = (area.width / count).min(MAX_ENTRY_WIDTH);
+        if width < 2 {
+            return;
+        }
+
+        let mut x = area.x;
+        for (i, entry) in self.entries.iter().enumerate() {
+            let mut style = self.style;
+            if entry.minimized {
+                style = style.patch(self.minimized_style);
+            }
+            if entry.urgent {
+                style = style.patch(self.urgent_style);
+            }
+            if entry.focused {
+                style = style.patch(self.focus_style);
+            }
+            // A column is left between entries.
+            let label = fit(&Taskbar::label(i, entry), width - 1, win);
+            win.print_styled(area.y, x, label, style);
+            self.spans.push((x, x + width - 1));
+            x += width;
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if event.is_key_release() {
+            return false;
+        }
+        match *event {
+            Event::Key {
+                key: Key::Char(c),
+                modifier: Modifier::Alt,
+                ..
+            } => match c.to_digit(10) {
+                Some(n) if n > 0 => self.choose(n as usize - 1),
+                _ => false,
+            },
+            Event::Mouse {
+                pos: (x, y),
+                event: MouseEvent::Press(MouseButton::Left),
+                ..
+            } => match self.area {
+                Some(area) if area.contains((x, y)) => {
+                    match self
+                        .spans
+                        .iter()
+                        .position(|&(start, end)| x >= start && x < end)
+                    {
+                        Some(i) => self.choose(i),
+                        None => true,
+                    }
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
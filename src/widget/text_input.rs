@@ -0,0 +1,325 @@
+use std::mem;
+
+use backend::Backend;
+use event::{Event, Key, Modifier};
+use layout::Rect;
+use style::{Attr, Style};
+use text;
+use widget::{fit, EditConfig, Widget};
+use window::{CursorStyle, Window};
+
+/// Represents a single line text input box.
+///
+/// Supports moving with the arrow keys, Home and End (or Ctrl+A and Ctrl+E),
+/// and by word with Ctrl+Left and Ctrl+Right (or Alt+B and Alt+F), and
+/// deleting with Backspace and Delete. The text scrolls sideways to keep the
+/// cursor visible.
+///
+/// Ctrl+W (or Alt+Backspace) and Alt+D cut the word before and after the
+/// cursor, Ctrl+U and Ctrl+K the start and end of the line, to the kill ring
+/// of the [edit config](struct.EditConfig.html). Ctrl+Y yanks the text back,
+/// and Alt+Y right after swaps it for older text. Insert toggles typing over
+/// the text.
+///
+/// Pasted text is inserted at the cursor, without its newlines.
+///
+/// *Events are ignored while the input is not focused.*
+#[derive(Clone, Debug)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    scroll: usize,
+    placeholder: String,
+    focused: bool,
+    config: EditConfig,
+    last_edit: LastEdit,
+    style: Style,
+}
+
+/// The edit just made, which cutting and yanking carry on from.
+#[derive(Copy, Clone, Debug)]
+enum LastEdit {
+    Other,
+    Kill,
+    /// Text yanked from the given index of the kill ring, starting at the
+    /// given byte index.
+    Yank {
+        start: usize,
+        index: usize,
+    },
+}
+
+impl Default for TextInput {
+    fn default() -> TextInput {
+        TextInput::new()
+    }
+}
+
+impl TextInput {
+    /// Creates a new empty, focused text input.
+    pub fn new() -> TextInput {
+        TextInput {
+            value: String::new(),
+            cursor: 0,
+            scroll: 0,
+            placeholder: String::new(),
+            focused: true,
+            config: EditConfig::default(),
+            last_edit: LastEdit::Other,
+            style: Style::default(),
+        }
+    }
+
+    /// Gets the text entered.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text entered, moving the cursor to the end.
+    pub fn set_value<S: Into<String>>(&mut self, value: S) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+        self.last_edit = LastEdit::Other;
+    }
+
+    /// Clears the text entered.
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+        self.scroll = 0;
+        self.last_edit = LastEdit::Other;
+    }
+
+    /// Gets the position of the cursor, as a byte index into the value.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Sets the text shown dimmed while the input is empty.
+    pub fn set_placeholder<S: Into<String>>(&mut self, placeholder: S) {
+        self.placeholder = placeholder.into();
+    }
+
+    /// Checks whether the input is focused.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether the input is focused, receiving key events and showing
+    /// the cursor.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Gets how the input edits its text.
+    pub fn edit_config(&self) -> &EditConfig {
+        &self.config
+    }
+
+    /// Sets how the input edits its text, such as a config shared by every
+    /// input of the application.
+    pub fn set_edit_config(&mut self, config: EditConfig) {
+        self.config = config;
+    }
+
+    /// Gets the style the input is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the input is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the index of the character before the cursor.
+    fn prev(&self) -> usize {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Gets the index of the character after the cursor.
+    fn next(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |ch| self.cursor + ch.len_utf8())
+    }
+
+    /// Cuts the text between the byte indices to the kill ring, joining it
+    /// onto the last cut if that was the edit before.
+    fn kill(&mut self, start: usize, end: usize, last: LastEdit) {
+        let text: String = self.value.drain(start..end).collect();
+        let ring = self.config.kill_ring();
+        match last {
+            LastEdit::Kill => ring.extend(&text, start < self.cursor),
+            _ if !text.is_empty() => ring.push(text),
+            _ => {}
+        }
+        self.cursor = start;
+        self.last_edit = LastEdit::Kill;
+    }
+
+    /// Inserts the text the given number of kills back at the cursor.
+    fn yank(&mut self, index: usize) -> bool {
+        let text = match self.config.kill_ring().get(index) {
+            Some(text) => text,
+            None => return false,
+        };
+        self.value.insert_str(self.cursor, &text);
+        self.last_edit = LastEdit::Yank {
+            start: self.cursor,
+            index,
+        };
+        self.cursor += text.len();
+        true
+    }
+}
+
+impl TextInput {
+    /// Gets the column of the cursor in the text shown in visual order, at
+    /// the character after it.
+    fn cursor_col<B: Backend>(&self, visual: &str, win: &Window<B>) -> usize {
+        let index = self.value[..self.cursor].chars().count();
+        match text::visual_map(&self.value).get(index) {
+            Some(&at) => {
+                let before: String = visual.chars().take(at).collect();
+                win.display_width(&before)
+            }
+            None => win.display_width(visual),
+        }
+    }
+}
+
+impl<B: Backend> Widget<B> for TextInput {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        if self.value.is_empty() && !self.placeholder.is_empty() {
+            let line = fit(&self.placeholder, area.width, win);
+            let style = self.style.patch(Style::new().attr(Attr::Dim));
+            win.print_styled(area.y, area.x, line, style);
+            self.scroll = 0;
+        } else {
+            // Keep the cursor inside the box, leaving room for it at the end.
+            let visual = text::visual_order(&self.value);
+            let col = self.cursor_col(&visual, win);
+            if col < self.scroll {
+                self.scroll = col;
+            } else if col >= self.scroll + area.width {
+                self.scroll = col + 1 - area.width;
+            }
+
+            let mut line = String::new();
+            let mut start = 0;
+            for ch in visual.chars() {
+                let end = start + text::char_width(ch, win.ambiguous_width());
+                if start >= self.scroll {
+                    line.push(ch);
+                } else if end > self.scroll {
+                    // Half of a wide character scrolled off the edge.
+                    line.push_str(&" ".repeat(end - self.scroll));
+                }
+                start = end;
+            }
+            win.print_styled(area.y, area.x, fit(&line, area.width, win), self.style);
+        }
+
+        if self.focused {
+            let col = self
+                .cursor_col(&text::visual_order(&self.value), win)
+                .saturating_sub(self.scroll);
+            win.request_cursor(if self.config.is_overwrite() {
+                CursorStyle::Block(area.y, area.x + col)
+            } else {
+                CursorStyle::Bar(area.y, area.x + col)
+            });
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let (key, modifier) = match *event {
+            Event::Key { key, modifier, .. } if self.focused && !event.is_key_release() => {
+                (key, modifier)
+            }
+            Event::Paste(ref text) if self.focused => {
+                self.last_edit = LastEdit::Other;
+                // The input is a single line, so newlines are left out.
+                let text: String = text.chars().filter(|ch| !ch.is_control()).collect();
+                if self.config.is_overwrite() {
+                    let end = self.value[self.cursor..]
+                        .char_indices()
+                        .nth(text.chars().count())
+                        .map_or(self.value.len(), |(i, _)| self.cursor + i);
+                    self.value.drain(self.cursor..end);
+                }
+                self.value.insert_str(self.cursor, &text);
+                self.cursor += text.len();
+                return true;
+            }
+            _ => return false,
+        };
+
+        let last = mem::replace(&mut self.last_edit, LastEdit::Other);
+        let (ctrl, alt) = (modifier == Modifier::Ctrl, modifier == Modifier::Alt);
+        let words = self.config.word_chars();
+        let (word_start, word_end) = (
+            words.word_start(&self.value, self.cursor),
+            words.word_end(&self.value, self.cursor),
+        );
+        match key {
+            Key::Char('a') if ctrl => self.cursor = 0,
+            Key::Char('e') if ctrl => self.cursor = self.value.len(),
+            Key::Char('u') if ctrl => self.kill(0, self.cursor, last),
+            Key::Char('k') if ctrl => self.kill(self.cursor, self.value.len(), last),
+            Key::Char('w') if ctrl => self.kill(word_start, self.cursor, last),
+            Key::Backspace if alt => self.kill(word_start, self.cursor, last),
+            Key::Char('d') if alt => self.kill(self.cursor, word_end, last),
+            Key::Char('b') if alt => self.cursor = word_start,
+            Key::Char('f') if alt => self.cursor = word_end,
+            Key::Left if ctrl => self.cursor = word_start,
+            Key::Right if ctrl => self.cursor = word_end,
+            Key::Char('y') if ctrl => return self.yank(0),
+            Key::Char('y') if alt => match last {
+                LastEdit::Yank { start, index } => {
+                    self.value.drain(start..self.cursor);
+                    self.cursor = start;
+                    return self.yank(index + 1);
+                }
+                _ => return false,
+            },
+            Key::Insert if modifier.is_empty() => {
+                let overwrite = self.config.is_overwrite();
+                self.config.set_overwrite(!overwrite);
+            }
+            Key::Char(ch) if (modifier - Modifier::Shift).is_empty() && !ch.is_control() => {
+                if self.config.is_overwrite() {
+                    let next = self.next();
+                    self.value.drain(self.cursor..next);
+                }
+                self.value.insert(self.cursor, ch);
+                self.cursor += ch.len_utf8();
+            }
+            Key::Backspace if self.cursor > 0 => {
+                let prev = self.prev();
+                self.value.drain(prev..self.cursor);
+                self.cursor = prev;
+            }
+            Key::Delete if self.cursor < self.value.len() => {
+                let next = self.next();
+                self.value.drain(self.cursor..next);
+            }
+            Key::Left => self.cursor = self.prev(),
+            Key::Right => self.cursor = self.next(),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.value.len(),
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,148 @@
+use backend::Backend;
+use event::{Event, Key, MouseEvent};
+use layout::Rect;
+use style::Style;
+use text::{self, AmbiguousWidth, Wrapper};
+use widget::{fit, Widget};
+use window::Window;
+
+/// The number of lines scrolled by the mouse wheel.
+const WHEEL_STEP: usize = 3;
+
+/// Represents a block of text, wrapped to the width of its area and
+/// scrolled vertically.
+///
+/// Scrolls with the arrow keys, Page Up and Page Down, Home and End, and the
+/// mouse wheel.
+///
+/// *The wrapped lines are cached until the text or width changes.*
+#[derive(Clone, Debug, Default)]
+pub struct TextView {
+    text: String,
+    scroll: usize,
+    lines: Option<(usize, AmbiguousWidth, Vec<String>)>,
+    area: Option<Rect>,
+    style: Style,
+}
+
+impl TextView {
+    /// Creates a new text view.
+    pub fn new<S: Into<String>>(text: S) -> TextView {
+        TextView {
+            text: text.into(),
+            ..TextView::default()
+        }
+    }
+
+    /// Gets the style the text view is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the text view is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the text shown.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text shown, keeping the scroll position.
+    pub fn set_text<S: Into<String>>(&mut self, text: S) {
+        self.text = text.into();
+        self.lines = None;
+    }
+
+    /// Gets the index of the first visible line.
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// Scrolls so the given line is the first visible, as far as the text
+    /// allows.
+    pub fn scroll_to(&mut self, line: usize) {
+        self.scroll = line.min(self.max_scroll());
+    }
+
+    /// Gets the furthest the text can be scrolled in the last rendered area.
+    fn max_scroll(&self) -> usize {
+        match (&self.lines, self.area) {
+            (&Some((_, _, ref lines)), Some(area)) => lines.len().saturating_sub(area.height),
+            _ => usize::MAX,
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let line = if delta < 0 {
+            self.scroll.saturating_sub(delta.unsigned_abs())
+        } else {
+            self.scroll.saturating_add(delta as usize)
+        };
+        self.scroll_to(line);
+    }
+}
+
+impl<B: Backend> Widget<B> for TextView {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let ambiguous = win.ambiguous_width();
+        match self.lines {
+            Some((width, amb, _)) if width == area.width && amb == ambiguous => {}
+            _ => {
+                let lines = Wrapper::new(area.width)
+                    .ambiguous_width(ambiguous)
+                    .wrap(&self.text);
+                self.lines = Some((area.width, ambiguous, lines));
+            }
+        }
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let lines = match self.lines {
+            Some((_, _, ref lines)) => lines,
+            None => return,
+        };
+        for row in 0..area.height {
+            let line = lines.get(self.scroll + row).map_or("", |line| &**line);
+            let line = text::visual_order(line);
+            win.print_styled(
+                area.y + row,
+                area.x,
+                fit(&line, area.width, win),
+                self.style,
+            );
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let page = self
+            .area
+            .map_or(1, |area| area.height.saturating_sub(1).max(1)) as isize;
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => match key {
+                Key::Up => self.scroll_by(-1),
+                Key::Down => self.scroll_by(1),
+                Key::PageUp => self.scroll_by(-page),
+                Key::PageDown => self.scroll_by(page),
+                Key::Home => self.scroll_to(0),
+                Key::End => self.scroll_to(usize::MAX),
+                _ => return false,
+            },
+            Event::Mouse { pos, event, .. } if self.area.is_some_and(|area| area.contains(pos)) => {
+                match event {
+                    MouseEvent::WheelUp => self.scroll_by(-(WHEEL_STEP as isize)),
+                    MouseEvent::WheelDown => self.scroll_by(WHEEL_STEP as isize),
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,207 @@
+use std::time::{Duration, Instant};
+
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{fit, Widget};
+use window::Window;
+
+/// The number of cells the knob slides across.
+const TRACK: usize = 4;
+
+/// How long the knob takes to slide from one end to the other.
+const SLIDE: Duration = Duration::from_millis(150);
+
+/// Represents a switch that is either on or off, with its knob sliding across
+/// when it is flipped.
+///
+/// ```text
+/// (   ●) Wi-Fi
+/// ```
+///
+/// Space or Enter, or a click, flips the switch. The slide moves on when the
+/// switch is [ticked](#method.tick), or given an
+/// [`Event::Tick`](../event/enum.Event.html#variant.Tick), and needs to be
+/// rendered again while [sliding](#method.is_sliding).
+///
+/// *The knob is drawn as `o` in ASCII fallback mode. Events are ignored while
+/// the switch is not focused.*
+#[derive(Clone, Debug)]
+pub struct ToggleSwitch {
+    on: bool,
+    label: String,
+    knob: usize,
+    slide: Option<(Instant, usize)>,
+    focused: bool,
+    area: Option<Rect>,
+    style: Style,
+    on_style: Style,
+}
+
+impl Default for ToggleSwitch {
+    fn default() -> ToggleSwitch {
+        ToggleSwitch::new(false)
+    }
+}
+
+impl ToggleSwitch {
+    /// Creates a focused switch, on or off, with no label.
+    pub fn new(on: bool) -> ToggleSwitch {
+        ToggleSwitch {
+            on,
+            label: String::new(),
+            knob: if on { TRACK - 1 } else { 0 },
+            slide: None,
+            focused: true,
+            area: None,
+            style: Style::default(),
+            on_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Checks whether the switch is on.
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Turns the switch on or off, moving the knob straight there.
+    pub fn set_on(&mut self, on: bool) {
+        self.on = on;
+        self.knob = if on { TRACK - 1 } else { 0 };
+        self.slide = None;
+    }
+
+    /// Flips the switch, sliding the knob across.
+    pub fn toggle(&mut self) {
+        self.on = !self.on;
+        self.slide = Some((Instant::now(), self.knob));
+    }
+
+    /// Sets the label shown after the switch.
+    pub fn set_label<S: Into<String>>(&mut self, label: S) {
+        self.label = label.into();
+    }
+
+    /// Checks whether the knob is still sliding across.
+    pub fn is_sliding(&self) -> bool {
+        self.slide.is_some()
+    }
+
+    /// Moves the knob on to where it should be at the given time.
+    ///
+    /// Returns whether it moved, meaning the switch needs to be rendered
+    /// again.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let (start, from) = match self.slide {
+            Some(slide) => slide,
+            None => return false,
+        };
+        let target = if self.on { TRACK - 1 } else { 0 };
+        let cells = from.abs_diff(target);
+        let step = SLIDE / (TRACK as u32 - 1);
+        let moved = (now.saturating_duration_since(start).as_nanos() / step.as_nanos()) as usize;
+
+        let previous = self.knob;
+        if moved >= cells {
+            self.knob = target;
+            self.slide = None;
+        } else if target > from {
+            self.knob = from + moved;
+        } else {
+            self.knob = from - moved;
+        }
+        self.knob != previous
+    }
+
+    /// Checks whether the switch is focused.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether the switch is focused, receiving key events.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Gets the style the switch is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the switch is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the switch while it is on.
+    pub fn on_style(&self) -> Style {
+        self.on_style
+    }
+
+    /// Sets the style patched over the switch while it is on, bold by
+    /// default.
+    pub fn set_on_style(&mut self, style: Style) {
+        self.on_style = style;
+    }
+}
+
+impl<B: Backend> Widget<B> for ToggleSwitch {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let knob = if win.ascii_mode() { "o" } else { "●" };
+        let track = format!(
+            "({}{}{})",
+            " ".repeat(self.knob),
+            knob,
+            " ".repeat(TRACK - 1 - self.knob)
+        );
+        let style = if self.on {
+            self.style.patch(self.on_style)
+        } else {
+            self.style
+        };
+        let width = TRACK + 2;
+        win.print_styled(
+            area.y,
+            area.x,
+            fit(&track, width.min(area.width), win),
+            style,
+        );
+        if area.width > width {
+            let label = format!(" {}", self.label);
+            win.print_styled(
+                area.y,
+                area.x + width,
+                fit(&label, area.width - width, win),
+                self.style,
+            );
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Tick => return self.tick(Instant::now()),
+            _ if !self.focused => return false,
+            Event::Key {
+                key: Key::Char(' '),
+                ..
+            }
+            | Event::Key {
+                key: Key::Enter, ..
+            } if !event.is_key_release() => self.toggle(),
+            Event::Mouse {
+                pos,
+                event: MouseEvent::Press(MouseButton::Left),
+                ..
+            } if self.area.is_some_and(|area| area.contains(pos)) => self.toggle(),
+            _ => return false,
+        }
+        true
+    }
+}
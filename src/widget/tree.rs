@@ -0,0 +1,240 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+use backend::Backend;
+use event::Event;
+use layout::Rect;
+use widget::Widget;
+use window::{CursesBackend, Window};
+
+/// A retained tree of widgets, identified by keys and kept between frames.
+///
+/// Each frame the application declares the widgets it wants between
+/// [`begin`](#method.begin) and [`end`](#method.end). A widget declared with
+/// the same key as in the previous frame is kept, along with its state such
+/// as scroll positions and cursors, and widgets no longer declared are
+/// dropped.
+///
+/// ```no_run
+/// use termui::layout::Rect;
+/// use termui::widget::{List, TextView, Tree};
+/// use termui::Window;
+///
+/// let win = Window::new();
+/// let mut tree = Tree::new();
+///
+/// tree.begin();
+/// tree.place("sidebar", Rect::new(0, 0, 20, 10), || TextView::new(""));
+/// tree.scope(|tree| {
+///     tree.place("files", Rect::new(1, 1, 18, 8), || List::new(vec!["a", "b"]));
+/// });
+/// tree.place("preview", Rect::new(20, 0, 60, 10), || TextView::new("..."));
+/// tree.end();
+///
+/// tree.render(&win);
+/// win.refresh();
+/// ```
+///
+/// *Only dirty widgets are rendered again, along with the widgets declared
+/// within their scope, since those draw over them.*
+pub struct Tree<K, B: Backend = CursesBackend> {
+    nodes: Vec<Node<K, B>>,
+    previous: HashMap<K, Node<K, B>>,
+    depth: usize,
+    focus: Option<K>,
+}
+
+struct Node<K, B: Backend> {
+    key: K,
+    depth: usize,
+    area: Rect,
+    dirty: bool,
+    widget: Box<dyn Any>,
+    render: fn(&mut dyn Any, Rect, &Window<B>),
+    handle_event: fn(&mut dyn Any, &Event) -> bool,
+}
+
+fn render<W: Widget<B> + 'static, B: Backend>(widget: &mut dyn Any, area: Rect, win: &Window<B>) {
+    if let Some(widget) = widget.downcast_mut::<W>() {
+        widget.render(area, win);
+    }
+}
+
+fn handle_event<W: Widget<B> + 'static, B: Backend>(widget: &mut dyn Any, event: &Event) -> bool {
+    widget
+        .downcast_mut::<W>()
+        .is_some_and(|widget| widget.handle_event(event))
+}
+
+impl<K: Clone + Eq + Hash, B: Backend> Tree<K, B> {
+    /// Creates an empty tree.
+    pub fn new() -> Tree<K, B> {
+        Tree {
+            nodes: Vec::new(),
+            previous: HashMap::new(),
+            depth: 0,
+            focus: None,
+        }
+    }
+
+    /// Starts declaring the widgets of a frame.
+    pub fn begin(&mut self) {
+        let nodes = mem::take(&mut self.nodes);
+        self.previous = nodes
+            .into_iter()
+            .map(|node| (node.key.clone(), node))
+            .collect();
+        self.depth = 0;
+    }
+
+    /// Finishes declaring the widgets of a frame, dropping the widgets that
+    /// were not declared again.
+    pub fn end(&mut self) {
+        self.previous.clear();
+        self.depth = 0;
+        if let Some(ref focus) = self.focus {
+            if !self.contains(focus) {
+                self.focus = None;
+            }
+        }
+    }
+
+    /// Declares a widget in the given area, giving the widget kept from the
+    /// previous frame or created by `init`.
+    ///
+    /// *A widget is created again when the key was used for a widget of
+    /// another type, and is marked dirty when new or its area changed.*
+    pub fn place<W, F>(&mut self, key: K, area: Rect, init: F) -> &mut W
+    where
+        W: Widget<B> + 'static,
+        F: FnOnce() -> W,
+    {
+        let index = match self.nodes.iter().position(|node| node.key == key) {
+            // Declared twice in a frame, so it stays where it first was.
+            Some(index) => index,
+            None => {
+                let node = self
+                    .previous
+                    .remove(&key)
+                    .filter(|node| node.widget.is::<W>());
+                let node = node.unwrap_or_else(|| Node {
+                    key,
+                    depth: 0,
+                    area,
+                    dirty: true,
+                    widget: Box::new(init()),
+                    render: render::<W, B>,
+                    handle_event: handle_event::<W, B>,
+                });
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        let node = &mut self.nodes[index];
+        node.depth = self.depth;
+        if node.area != area {
+            node.area = area;
+            node.dirty = true;
+        }
+        node.widget
+            .downcast_mut::<W>()
+            .expect("widget type is checked when placed")
+    }
+
+    /// Declares the widgets placed by the closure within the scope of the
+    /// widget placed before, such as the container they are drawn over.
+    ///
+    /// *Keys must be unique across the whole tree, not only within a scope.*
+    pub fn scope<F: FnOnce(&mut Tree<K, B>)>(&mut self, f: F) {
+        self.depth += 1;
+        f(self);
+        self.depth -= 1;
+    }
+
+    /// Checks whether a widget is declared with the key.
+    pub fn contains(&self, key: &K) -> bool {
+        self.nodes.iter().any(|node| node.key == *key)
+    }
+
+    /// Gets the widget declared with the key, if it is of the given type.
+    pub fn get_mut<W: Widget<B> + 'static>(&mut self, key: &K) -> Option<&mut W> {
+        self.nodes
+            .iter_mut()
+            .find(|node| node.key == *key)
+            .and_then(|node| node.widget.downcast_mut::<W>())
+    }
+
+    /// Marks the widget with the key as changed, so it is rendered again
+    /// along with the widgets within its scope.
+    pub fn mark_dirty(&mut self, key: &K) {
+        if let Some(node) = self.nodes.iter_mut().find(|node| node.key == *key) {
+            node.dirty = true;
+        }
+    }
+
+    /// Marks every widget as changed, such as after erasing the window.
+    pub fn invalidate(&mut self) {
+        for node in &mut self.nodes {
+            node.dirty = true;
+        }
+    }
+
+    /// Gets the key of the widget receiving key events.
+    pub fn focus(&self) -> Option<&K> {
+        self.focus.as_ref()
+    }
+
+    /// Sets the widget receiving key events.
+    pub fn set_focus(&mut self, key: Option<K>) {
+        self.focus = key;
+    }
+
+    /// Renders the dirty widgets, in the order they were declared.
+    pub fn render(&mut self, win: &Window<B>) {
+        // Widgets within the scope of one rendered again are drawn over it.
+        let mut redrawn: Option<usize> = None;
+        for node in &mut self.nodes {
+            match redrawn {
+                Some(depth) if node.depth > depth => {}
+                _ if node.dirty => redrawn = Some(node.depth),
+                _ => {
+                    redrawn = None;
+                    continue;
+                }
+            }
+            (node.render)(&mut *node.widget, node.area, win);
+            node.dirty = false;
+        }
+    }
+
+    /// Handles an event, giving the key of the widget that used it, which is
+    /// marked dirty.
+    ///
+    /// Key events go to the focused widget, and mouse events go to the
+    /// topmost widget that uses them.
+    pub fn handle_event(&mut self, event: &Event) -> Option<K> {
+        let focus = match *event {
+            Event::Mouse { .. } => None,
+            _ => Some(self.focus.clone()?),
+        };
+        for node in self.nodes.iter_mut().rev() {
+            if focus.as_ref().is_some_and(|focus| node.key != *focus) {
+                continue;
+            }
+            if (node.handle_event)(&mut *node.widget, event) {
+                node.dirty = true;
+                return Some(node.key.clone());
+            }
+        }
+        None
+    }
+}
+
+impl<K: Clone + Eq + Hash, B: Backend> Default for Tree<K, B> {
+    fn default() -> Tree<K, B> {
+        Tree::new()
+    }
+}
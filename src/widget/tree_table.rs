@@ -0,0 +1,606 @@
+use std::collections::BTreeSet;
+
+use backend::Backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{truncate, Scrollable, TableCell, Widget};
+use window::Window;
+
+/// The number of rows scrolled by the mouse wheel.
+const WHEEL_STEP: usize = 3;
+/// The columns left between cells.
+const COLUMN_GAP: usize = 1;
+/// The columns each level of the tree is indented by.
+const INDENT: usize = 2;
+
+/// Represents a node of a [`TreeTable`](struct.TreeTable.html), a row of
+/// cells with the rows nested below it.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TreeNode {
+    cells: Vec<TableCell>,
+    children: Vec<TreeNode>,
+    expanded: bool,
+}
+
+impl TreeNode {
+    /// Creates a collapsed node with no children, of the given cells.
+    pub fn new<C, S>(cells: C) -> TreeNode
+    where
+        C: IntoIterator<Item = S>,
+        S: Into<TableCell>,
+    {
+        TreeNode {
+            cells: cells.into_iter().map(Into::into).collect(),
+            children: Vec::new(),
+            expanded: false,
+        }
+    }
+
+    /// Adds a child below the node.
+    pub fn child(mut self, node: TreeNode) -> TreeNode {
+        self.children.push(node);
+        self
+    }
+
+    /// Gets the cells of the node, the first being drawn as the tree.
+    pub fn cells(&self) -> &[TableCell] {
+        &self.cells
+    }
+
+    /// Replaces the cells of the node.
+    pub fn set_cells<C, S>(&mut self, cells: C)
+    where
+        C: IntoIterator<Item = S>,
+        S: Into<TableCell>,
+    {
+        self.cells = cells.into_iter().map(Into::into).collect();
+    }
+
+    /// Gets the children of the node.
+    pub fn children(&self) -> &[TreeNode] {
+        &self.children
+    }
+
+    /// Gets the children of the node to change them.
+    pub fn children_mut(&mut self) -> &mut Vec<TreeNode> {
+        &mut self.children
+    }
+
+    /// Checks whether the children of the node are shown.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// Sets whether the children of the node are shown.
+    pub fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
+
+    /// Expands or collapses the node and every node below it.
+    pub fn set_expanded_all(&mut self, expanded: bool) {
+        self.expanded = expanded;
+        for child in &mut self.children {
+            child.set_expanded_all(expanded);
+        }
+    }
+}
+
+/// Represents a table whose rows form a tree, with the first column drawn as
+/// the tree and the others showing the cells of each node, such as the
+/// processes of a process monitor or the folders of a disk usage explorer.
+///
+/// ```text
+/// Name          Size
+/// ▾ src         48K
+///   ▸ widget   31K
+///     main.rs   2K
+/// ▸ target     1.2G
+/// ```
+///
+/// The selection is moved with Up and Down, Page Up and Page Down, Home and
+/// End, or by clicking a row. Right expands the selected node, or moves onto
+/// its first child, and Left collapses it, or moves onto its parent. Space,
+/// or clicking the marker, expands or collapses it. The mouse wheel scrolls
+/// without moving the selection.
+///
+/// Nodes are picked out by their path, the index of each node on the way
+/// down from the top of the tree.
+///
+/// *Each column is as wide as its widest cell in view, and columns past the
+/// width of the table are cut off. The markers are drawn as `+` and `-` in
+/// ASCII fallback mode.*
+#[derive(Clone, Debug, Default)]
+pub struct TreeTable {
+    headers: Vec<TableCell>,
+    nodes: Vec<TreeNode>,
+    selected: Option<Vec<usize>>,
+    offset: usize,
+    follow: bool,
+    right_aligned: BTreeSet<usize>,
+    area: Option<Rect>,
+    style: Style,
+    header_style: Style,
+    selected_style: Style,
+}
+
+impl TreeTable {
+    /// Creates a table of the given header cells and top level nodes, with
+    /// the first node selected.
+    ///
+    /// *With no header cells, no header row is drawn.*
+    pub fn new<H, S, N>(headers: H, nodes: N) -> TreeTable
+    where
+        H: IntoIterator<Item = S>,
+        S: Into<TableCell>,
+        N: IntoIterator<Item = TreeNode>,
+    {
+        let mut table = TreeTable {
+            selected_style: Style::new().attr(Attr::Reverse),
+            ..TreeTable::default()
+        };
+        table.set_headers(headers);
+        table.set_nodes(nodes);
+        table
+    }
+
+    /// Gets the header cells.
+    pub fn headers(&self) -> &[TableCell] {
+        &self.headers
+    }
+
+    /// Replaces the header cells.
+    pub fn set_headers<H, S>(&mut self, headers: H)
+    where
+        H: IntoIterator<Item = S>,
+        S: Into<TableCell>,
+    {
+        self.headers = headers.into_iter().map(Into::into).collect();
+    }
+
+    /// Gets the top level nodes.
+    pub fn nodes(&self) -> &[TreeNode] {
+        &self.nodes
+    }
+
+    /// Replaces the nodes, keeping the selection on the same path, or as
+    /// near to it as the new nodes allow.
+    pub fn set_nodes<N: IntoIterator<Item = TreeNode>>(&mut self, nodes: N) {
+        self.nodes = nodes.into_iter().collect();
+        let path = self.selected.take().unwrap_or_default();
+        self.select(Some(&path));
+    }
+
+    /// Gets the node at the given path.
+    pub fn node(&self, path: &[usize]) -> Option<&TreeNode> {
+        let (&first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.nodes.get(first)?, |node, &i| node.children.get(i))
+    }
+
+    /// Gets the node at the given path to change it.
+    ///
+    /// *Collapsing a node above the selection leaves the selection hidden
+    /// until it is moved; [`collapse`](#method.collapse) moves it up.*
+    pub fn node_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+        let (&first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.nodes.get_mut(first)?, |node, &i| {
+                node.children.get_mut(i)
+            })
+    }
+
+    /// Gets the path of the selected node.
+    pub fn selected(&self) -> Option<&[usize]> {
+        self.selected.as_deref()
+    }
+
+    /// Gets the selected node.
+    pub fn selected_node(&self) -> Option<&TreeNode> {
+        self.node(self.selected.as_ref()?)
+    }
+
+    /// Selects the node at the given path, expanding the nodes above it and
+    /// scrolling it into view on the next render.
+    ///
+    /// *Indices past the last child at a level select the last child, and a
+    /// path going below a node with no children stops at the node.*
+    pub fn select(&mut self, path: Option<&[usize]>) {
+        self.selected = path.and_then(|path| {
+            let mut valid = Vec::new();
+            for &i in path {
+                let count = match self.node(&valid) {
+                    Some(node) => node.children.len(),
+                    None if valid.is_empty() => self.nodes.len(),
+                    None => break,
+                };
+                if count == 0 {
+                    break;
+                }
+                valid.push(i.min(count - 1));
+            }
+            if valid.is_empty() && !self.nodes.is_empty() {
+                valid.push(0);
+            }
+            Some(valid).filter(|valid| !valid.is_empty())
+        });
+        if let Some(path) = self.selected.clone() {
+            for depth in 1..path.len() {
+                if let Some(node) = self.node_mut(&path[..depth]) {
+                    node.expanded = true;
+                }
+            }
+        }
+        self.follow = true;
+    }
+
+    /// Expands the node at the given path.
+    pub fn expand(&mut self, path: &[usize]) {
+        if let Some(node) = self.node_mut(path) {
+            node.expanded = true;
+        }
+    }
+
+    /// Collapses the node at the given path, moving the selection onto it if
+    /// it was below.
+    pub fn collapse(&mut self, path: &[usize]) {
+        if let Some(node) = self.node_mut(path) {
+            node.expanded = false;
+        }
+        let below = self
+            .selected
+            .as_ref()
+            .is_some_and(|selected| selected.len() > path.len() && selected.starts_with(path));
+        if below {
+            self.select(Some(path));
+        }
+    }
+
+    /// Expands or collapses every node.
+    pub fn set_expanded_all(&mut self, expanded: bool) {
+        for node in &mut self.nodes {
+            node.set_expanded_all(expanded);
+        }
+        if !expanded {
+            let top = self.selected.as_ref().map(|path| vec![path[0]]);
+            self.select(top.as_deref());
+        }
+    }
+
+    /// Aligns the cells in the given columns to the right, and the rest to
+    /// the left.
+    ///
+    /// *The first column, drawn as the tree, is always aligned to the left.*
+    pub fn set_right_aligned<I: IntoIterator<Item = usize>>(&mut self, cols: I) {
+        self.right_aligned = cols.into_iter().collect();
+    }
+
+    /// Checks whether the cells in a column are aligned to the right.
+    pub fn is_right_aligned(&self, col: usize) -> bool {
+        col > 0 && self.right_aligned.contains(&col)
+    }
+
+    /// Gets the index of the first row in view, counting the nodes shown.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Scrolls so the given row is the first in view, as far as the table
+    /// allows, without moving the selection.
+    pub fn scroll_to(&mut self, row: usize) {
+        let rows = self.visible().len();
+        self.offset = row.min(rows.saturating_sub(self.page()));
+        self.follow = false;
+    }
+
+    /// Gets the style the table is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the table is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the header row, after making it bold.
+    pub fn header_style(&self) -> Style {
+        self.header_style
+    }
+
+    /// Sets the style patched over the header row, after making it bold.
+    pub fn set_header_style(&mut self, style: Style) {
+        self.header_style = style;
+    }
+
+    /// Gets the style patched over the selected row.
+    pub fn selected_style(&self) -> Style {
+        self.selected_style
+    }
+
+    /// Sets the style patched over the selected row, reversed by default.
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    /// Gets the paths of the nodes shown, in order, those whose parents are
+    /// all expanded.
+    fn visible(&self) -> Vec<Vec<usize>> {
+        fn walk(nodes: &[TreeNode], path: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+            for (i, node) in nodes.iter().enumerate() {
+                path.push(i);
+                out.push(path.clone());
+                if node.expanded {
+                    walk(&node.children, path, out);
+                }
+                path.pop();
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.nodes, &mut Vec::new(), &mut out);
+        out
+    }
+
+    /// Gets the number of rows of nodes shown at once.
+    fn page(&self) -> usize {
+        let header = !self.headers.is_empty() as usize;
+        self.area
+            .map_or(1, |area| area.height.saturating_sub(header).max(1))
+    }
+
+    /// Moves the selection by the given number of rows.
+    fn move_by(&mut self, delta: isize) {
+        let rows = self.visible();
+        if rows.is_empty() {
+            return;
+        }
+        let index = self
+            .selected
+            .as_ref()
+            .and_then(|selected| rows.iter().position(|row| row == selected));
+        let index = match index {
+            Some(index) if delta < 0 => index.saturating_sub(delta.unsigned_abs()),
+            Some(index) => index.saturating_add(delta as usize),
+            None => 0,
+        };
+        let path = rows[index.min(rows.len() - 1)].clone();
+        self.select(Some(&path));
+    }
+
+    /// Expands or collapses the node at the given path, if it has children.
+    fn toggle(&mut self, path: &[usize]) -> bool {
+        match self.node(path) {
+            Some(node) if node.expanded => self.collapse(path),
+            Some(node) if !node.children.is_empty() => self.expand(path),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Gets the text drawn before the first cell of a node.
+    fn prefix<B: Backend>(node: &TreeNode, depth: usize, win: &Window<B>) -> String {
+        let marker = match (node.children.is_empty(), node.expanded, win.ascii_mode()) {
+            (true, _, _) => " ",
+            (false, true, false) => "▾",
+            (false, false, false) => "▸",
+            (false, true, true) => "-",
+            (false, false, true) => "+",
+        };
+        format!("{}{} ", " ".repeat(depth * INDENT), marker)
+    }
+
+    /// Draws a row of cells at the given line of the area.
+    fn draw_row<B: Backend>(
+        &self,
+        prefix: &str,
+        cells: &[TableCell],
+        y: usize,
+        widths: &[usize],
+        style: Style,
+        win: &Window<B>,
+    ) {
+        let area = match self.area {
+            Some(area) => area,
+            None => return,
+        };
+        win.print_styled(area.y + y, area.x, " ".repeat(area.width), style);
+
+        let mut x = 0;
+        for (col, &width) in widths.iter().enumerate() {
+            if x >= area.width {
+                break;
+            }
+            let width = width.min(area.width - x);
+            let mut runs: Vec<(&str, Style)> = Vec::new();
+            if col == 0 {
+                runs.push((prefix, Style::default()));
+            }
+            if let Some(cell) = cells.get(col) {
+                runs.extend(cell.runs().iter().map(|(text, run)| (&**text, *run)));
+            }
+
+            let mut at = 0;
+            if self.is_right_aligned(col) {
+                let text: usize = runs.iter().map(|(text, _)| win.display_width(text)).sum();
+                at = width.saturating_sub(text);
+            }
+            for (text, run) in runs {
+                if at >= width {
+                    break;
+                }
+                let text = truncate(text, width - at, win);
+                win.print_styled(area.y + y, area.x + x + at, text, style.patch(run));
+                at += win.display_width(text);
+            }
+            x += width + COLUMN_GAP;
+        }
+    }
+}
+
+impl Scrollable for TreeTable {
+    fn scroll_offset(&self) -> usize {
+        self.offset
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_to(offset);
+    }
+}
+
+impl<B: Backend> Widget<B> for TreeTable {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let rows = self.visible();
+        let page = self.page();
+
+        let selected = self
+            .selected
+            .as_ref()
+            .and_then(|selected| rows.iter().position(|row| row == selected));
+        if let (true, Some(selected)) = (self.follow, selected) {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if selected >= self.offset + page {
+                self.offset = selected + 1 - page;
+            }
+        }
+        self.follow = false;
+        self.offset = self.offset.min(rows.len().saturating_sub(page));
+
+        // Only the rows in view are given a prefix and measured.
+        let shown: Vec<(String, &TreeNode, bool)> = rows
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(page)
+            .filter_map(|(index, path)| {
+                let node = self.node(path)?;
+                let prefix = TreeTable::prefix(node, path.len() - 1, win);
+                Some((prefix, node, Some(index) == selected))
+            })
+            .collect();
+
+        let columns = shown
+            .iter()
+            .map(|&(_, node, _)| node.cells.len())
+            .chain(Some(self.headers.len()))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut widths = vec![0; columns];
+        for (col, cell) in self.headers.iter().enumerate() {
+            widths[col] = win.display_width(&cell.text());
+        }
+        for &(ref prefix, node, _) in &shown {
+            for (col, width) in widths.iter_mut().enumerate() {
+                let mut cell = node
+                    .cells
+                    .get(col)
+                    .map_or(0, |cell| win.display_width(&cell.text()));
+                if col == 0 {
+                    cell += win.display_width(prefix);
+                }
+                *width = (*width).max(cell);
+            }
+        }
+
+        let mut y = 0;
+        if !self.headers.is_empty() {
+            let style = self
+                .style
+                .patch(Style::new().attr(Attr::Bold))
+                .patch(self.header_style);
+            self.draw_row("", &self.headers, y, &widths, style, win);
+            y += 1;
+        }
+        for &(ref prefix, node, selected) in &shown {
+            if y >= area.height {
+                break;
+            }
+            let style = if selected {
+                self.style.patch(self.selected_style)
+            } else {
+                self.style
+            };
+            self.draw_row(prefix, &node.cells, y, &widths, style, win);
+            y += 1;
+        }
+        for y in y..area.height {
+            win.print_styled(area.y + y, area.x, " ".repeat(area.width), self.style);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Key { key, .. } if !event.is_key_release() => {
+                let page = self.page() as isize;
+                let selected = self.selected.clone().unwrap_or_default();
+                match key {
+                    Key::Up => self.move_by(-1),
+                    Key::Down => self.move_by(1),
+                    Key::PageUp => self.move_by(-page),
+                    Key::PageDown => self.move_by(page),
+                    Key::Home => self.move_by(isize::MIN),
+                    Key::End => self.move_by(isize::MAX),
+                    Key::Right => match self.node(&selected) {
+                        Some(node) if node.children.is_empty() => return false,
+                        Some(node) if node.expanded => {
+                            let mut child = selected.clone();
+                            child.push(0);
+                            self.select(Some(&child));
+                        }
+                        Some(_) => self.expand(&selected),
+                        None => return false,
+                    },
+                    Key::Left => match self.node(&selected) {
+                        Some(node) if node.expanded => self.collapse(&selected),
+                        Some(_) if selected.len() > 1 => {
+                            self.select(Some(&selected[..selected.len() - 1]))
+                        }
+                        _ => return false,
+                    },
+                    Key::Char(' ') => return self.toggle(&selected),
+                    _ => return false,
+                }
+                true
+            }
+            Event::Mouse { pos, event, .. } => {
+                let area = match self.area {
+                    Some(area) => area,
+                    None => return false,
+                };
+                let (x, y) = match area.to_local(pos) {
+                    Some(local) => local,
+                    None => return false,
+                };
+                match event {
+                    MouseEvent::Press(MouseButton::Left) => {
+                        let header = !self.headers.is_empty() as usize;
+                        let row = match y.checked_sub(header) {
+                            Some(row) => self.offset + row,
+                            None => return false,
+                        };
+                        let path = match self.visible().into_iter().nth(row) {
+                            Some(path) => path,
+                            None => return false,
+                        };
+                        let marker = (path.len() - 1) * INDENT;
+                        if x == marker && self.toggle(&path) {
+                            return true;
+                        }
+                        self.select(Some(&path));
+                    }
+                    MouseEvent::WheelUp => self.offset = self.offset.saturating_sub(WHEEL_STEP),
+                    MouseEvent::WheelDown => self.offset += WHEEL_STEP,
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
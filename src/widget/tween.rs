@@ -0,0 +1,210 @@
+use std::time::{Duration, Instant};
+
+use event::Event;
+
+/// Represents a value that can be blended with another of its kind, so a
+/// [`Tween`](struct.Tween.html) can move it smoothly between the two.
+pub trait Interpolate: Clone {
+    /// Gets the value a share of the way from this value to another, from
+    /// `0` giving this value to `1` giving the other.
+    fn interpolate(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, to: &f64, t: f64) -> f64 {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, to: &f32, t: f64) -> f32 {
+        self + (to - self) * t as f32
+    }
+}
+
+impl<A: Interpolate, B: Interpolate> Interpolate for (A, B) {
+    fn interpolate(&self, to: &(A, B), t: f64) -> (A, B) {
+        (self.0.interpolate(&to.0, t), self.1.interpolate(&to.1, t))
+    }
+}
+
+/// Blends the values in step, the values past the end of the shorter taking
+/// on the target's length at once.
+impl<T: Interpolate> Interpolate for Vec<T> {
+    fn interpolate(&self, to: &Vec<T>, t: f64) -> Vec<T> {
+        to.iter()
+            .enumerate()
+            .map(|(i, to)| match self.get(i) {
+                Some(from) => from.interpolate(to, t),
+                None => to.clone(),
+            })
+            .collect()
+    }
+}
+
+/// How a [`Tween`](struct.Tween.html) speeds up and slows down on its way.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Easing {
+    /// Moves at the same speed the whole way.
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down into the target.
+    #[default]
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Gets how far along the way the value is, with a share of the time
+    /// passed from `0` to `1`.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Represents a value moving smoothly towards its latest target, such as a
+/// reading that arrives every few seconds, so the gauge or chart showing it
+/// glides between readings rather than jumping.
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+///
+/// use termui::widget::{Meter, Tween};
+///
+/// let mut load = Tween::new(0.0, Duration::from_millis(800));
+/// let mut meter = Meter::new("Load");
+///
+/// // When a reading arrives.
+/// load.set_target(42.0);
+///
+/// // On each frame.
+/// if load.tick(Instant::now()) {
+///     meter.set_value(*load.value());
+/// }
+/// ```
+///
+/// Like an [`Animation`](struct.Animation.html), the value moves on when it
+/// is [ticked](#method.tick), or given an
+/// [`Event::Tick`](../event/enum.Event.html#variant.Tick).
+///
+/// *A new target set part way moves on from where the value is, so it never
+/// jumps.*
+#[derive(Clone, Debug)]
+pub struct Tween<T: Interpolate> {
+    from: T,
+    to: T,
+    value: T,
+    started: Instant,
+    duration: Duration,
+    easing: Easing,
+    moving: bool,
+}
+
+impl<T: Interpolate> Tween<T> {
+    /// Creates a value at rest, taking the given time to reach each new
+    /// target.
+    pub fn new(value: T, duration: Duration) -> Tween<T> {
+        Tween {
+            from: value.clone(),
+            to: value.clone(),
+            value,
+            started: Instant::now(),
+            duration,
+            easing: Easing::default(),
+            moving: false,
+        }
+    }
+
+    /// Gets the value, as of the last tick.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Gets the value being moved towards.
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Starts moving towards a new target from where the value is now.
+    pub fn set_target(&mut self, target: T) {
+        self.set_target_at(target, Instant::now());
+    }
+
+    /// Starts moving towards a new target from where the value is at the
+    /// given time.
+    pub fn set_target_at(&mut self, target: T, now: Instant) {
+        self.tick(now);
+        self.from = self.value.clone();
+        self.to = target;
+        self.started = now;
+        self.moving = true;
+    }
+
+    /// Sets the value at once, without moving there.
+    pub fn jump_to(&mut self, value: T) {
+        self.from = value.clone();
+        self.to = value.clone();
+        self.value = value;
+        self.moving = false;
+    }
+
+    /// Checks whether the value is still on its way to the target.
+    pub fn is_moving(&self) -> bool {
+        self.moving
+    }
+
+    /// Gets the time taken to reach each new target.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Sets the time taken to reach each new target.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    /// Gets how the value speeds up and slows down.
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Sets how the value speeds up and slows down, easing out by default.
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    /// Moves the value on to where it should be at the given time.
+    ///
+    /// Returns whether it moved, meaning whatever shows it needs to be
+    /// rendered again.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if !self.moving {
+            return false;
+        }
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed >= self.duration {
+            self.value = self.to.clone();
+            self.moving = false;
+        } else {
+            let t = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+            self.value = self.from.interpolate(&self.to, self.easing.apply(t));
+        }
+        true
+    }
+
+    /// Handles an event, moving the value on when given a tick.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Tick => self.tick(Instant::now()),
+            _ => false,
+        }
+    }
+}
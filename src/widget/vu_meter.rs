@@ -0,0 +1,253 @@
+use std::time::{Duration, Instant};
+
+use backend::Backend;
+use event::Event;
+use layout::Rect;
+use style::{Color, Style};
+use widget::Widget;
+use window::Window;
+
+/// The blocks drawn at the end of the bar, by eighths of a cell filled.
+const EIGHTHS: [&str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// The quietest level shown, in decibels, when levels are given in
+/// decibels.
+const FLOOR_DB: f64 = -60.0;
+
+/// Represents a level meter for audio, such as the volume of a microphone,
+/// jumping up with the level and falling back smoothly, with the loudest
+/// recent level held as a peak marker.
+///
+/// ```text
+/// ████████████████████▋         ▌
+/// ```
+///
+/// The bar falls back at the [decay](#method.set_decay) rate, and the peak
+/// is held for a [while](#method.set_hold) before falling the same way. Both
+/// move on when the meter is [ticked](#method.tick), or given an
+/// [`Event::Tick`](../event/enum.Event.html#variant.Tick), and the meter
+/// needs to be rendered again while [moving](#method.is_moving). Only the
+/// cells that changed are sent to the terminal, so the meter can be rendered
+/// at the full frame rate.
+///
+/// The bar is drawn in the [warning style](#method.set_warn_style) past the
+/// warning level and the [danger style](#method.set_danger_style) past the
+/// danger level, yellow and red by default.
+///
+/// *Levels are from `0` to `1`. The blocks are drawn as `#`, and the peak as
+/// `|`, in ASCII fallback mode.*
+#[derive(Clone, Debug)]
+pub struct VuMeter {
+    level: f64,
+    shown: f64,
+    peak: f64,
+    peak_at: Instant,
+    ticked: Instant,
+    decay: f64,
+    hold: Duration,
+    zones: (f64, f64),
+    style: Style,
+    warn_style: Style,
+    danger_style: Style,
+}
+
+impl Default for VuMeter {
+    fn default() -> VuMeter {
+        VuMeter::new()
+    }
+}
+
+impl VuMeter {
+    /// Creates a silent meter, falling back over half a second and holding
+    /// peaks for a second.
+    pub fn new() -> VuMeter {
+        let now = Instant::now();
+        VuMeter {
+            level: 0.0,
+            shown: 0.0,
+            peak: 0.0,
+            peak_at: now,
+            ticked: now,
+            decay: 2.0,
+            hold: Duration::from_secs(1),
+            zones: (0.7, 0.9),
+            style: Style::default(),
+            warn_style: Style::new().fg(Color::Yellow),
+            danger_style: Style::new().fg(Color::Red),
+        }
+    }
+
+    /// Gets the level last given.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Sets the level, which the bar jumps up to or falls back towards.
+    ///
+    /// *Levels outside `0` to `1` are clamped.*
+    pub fn set_level(&mut self, level: f64) {
+        let level = if level.is_nan() {
+            0.0
+        } else {
+            level.clamp(0.0, 1.0)
+        };
+        self.level = level;
+        self.shown = self.shown.max(level);
+        if level >= self.peak {
+            self.peak = level;
+            self.peak_at = Instant::now();
+        }
+    }
+
+    /// Sets the level in decibels, from silence at -60 dB up to 0 dB.
+    pub fn set_level_db(&mut self, db: f64) {
+        self.set_level((db - FLOOR_DB) / -FLOOR_DB);
+    }
+
+    /// Gets the level the bar is drawn at.
+    pub fn shown(&self) -> f64 {
+        self.shown
+    }
+
+    /// Gets the level the peak marker is drawn at.
+    pub fn peak(&self) -> f64 {
+        self.peak
+    }
+
+    /// Gets how much of the full scale the bar and peak fall each second.
+    pub fn decay(&self) -> f64 {
+        self.decay
+    }
+
+    /// Sets how much of the full scale the bar and peak fall each second.
+    pub fn set_decay(&mut self, decay: f64) {
+        self.decay = decay.max(0.0);
+    }
+
+    /// Gets how long the peak is held before falling.
+    pub fn hold(&self) -> Duration {
+        self.hold
+    }
+
+    /// Sets how long the peak is held before falling.
+    pub fn set_hold(&mut self, hold: Duration) {
+        self.hold = hold;
+    }
+
+    /// Sets the levels past which the bar is drawn in the warning and danger
+    /// styles, `0.7` and `0.9` by default.
+    pub fn set_zones(&mut self, warn: f64, danger: f64) {
+        self.zones = (warn, danger);
+    }
+
+    /// Checks whether the bar or the peak is still falling.
+    pub fn is_moving(&self) -> bool {
+        self.shown > self.level || self.peak > self.shown
+    }
+
+    /// Moves the bar and the peak on to where they should be at the given
+    /// time.
+    ///
+    /// Returns whether either moved, meaning the meter needs to be rendered
+    /// again.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.ticked);
+        self.ticked = now;
+        let fall = self.decay * elapsed.as_secs_f64();
+        let (shown, peak) = (self.shown, self.peak);
+
+        self.shown = (self.shown - fall).max(self.level);
+        let held = self.peak_at + self.hold;
+        if now > held {
+            // Only the part of the time since the hold ended counts.
+            let since = now.saturating_duration_since(held).min(elapsed);
+            self.peak -= self.decay * since.as_secs_f64();
+        }
+        self.peak = self.peak.max(self.shown);
+        self.shown != shown || self.peak != peak
+    }
+
+    /// Gets the style the meter is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the meter is drawn in, patched over the style of where
+    /// it is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the bar past the warning level.
+    pub fn warn_style(&self) -> Style {
+        self.warn_style
+    }
+
+    /// Sets the style patched over the bar past the warning level, yellow by
+    /// default.
+    pub fn set_warn_style(&mut self, style: Style) {
+        self.warn_style = style;
+    }
+
+    /// Gets the style patched over the bar past the danger level.
+    pub fn danger_style(&self) -> Style {
+        self.danger_style
+    }
+
+    /// Sets the style patched over the bar past the danger level, red by
+    /// default.
+    pub fn set_danger_style(&mut self, style: Style) {
+        self.danger_style = style;
+    }
+
+    /// Gets the style of the cell starting at a share of the full scale.
+    fn zone_style(&self, at: f64) -> Style {
+        let (warn, danger) = self.zones;
+        if at >= danger {
+            self.style.patch(self.danger_style)
+        } else if at >= warn {
+            self.style.patch(self.warn_style)
+        } else {
+            self.style
+        }
+    }
+}
+
+impl<B: Backend> Widget<B> for VuMeter {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let ascii = win.ascii_mode();
+        let width = area.width;
+        let eighths = (self.shown * (width * 8) as f64).round() as usize;
+        let peak = ((self.peak * width as f64).ceil() as usize).clamp(1, width) - 1;
+        let show_peak = self.peak > 0.0;
+
+        for x in 0..width {
+            let filled = eighths.saturating_sub(x * 8).min(8);
+            let (cell, style) = match filled {
+                _ if show_peak && x == peak && filled < 8 => {
+                    (if ascii { "|" } else { "▌" }, self.zone_style(self.peak))
+                }
+                8 => (
+                    if ascii { "#" } else { "█" },
+                    self.zone_style(x as f64 / width as f64),
+                ),
+                filled if ascii && filled >= 4 => ("#", self.zone_style(x as f64 / width as f64)),
+                _ if ascii => (" ", self.style),
+                filled => (EIGHTHS[filled], self.zone_style(x as f64 / width as f64)),
+            };
+            for y in 0..area.height {
+                win.print_styled(area.y + y, area.x + x, cell, style);
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Tick => self.tick(Instant::now()),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,610 @@
+#![allow(non_upper_case_globals)]
+
+use std::fmt;
+
+use backend::Backend;
+use event::{Event, MouseButton, MouseEvent};
+use layout::Rect;
+use style::{Attr, Style};
+use widget::overlay::draw_frame;
+use widget::{truncate, Widget};
+use window::Window;
+
+/// The smallest a window can be resized to, as width and height.
+const MIN_SIZE: (usize, usize) = (10, 3);
+
+/// The close button drawn at the right of a title bar.
+const CLOSE_BUTTON: &str = "[x]";
+
+bitflags! {
+    /// Represents the chrome drawn around a window of a
+    /// [`WindowManager`](struct.WindowManager.html).
+    pub struct Chrome: u8 {
+        /// No chrome, the content fills the whole window.
+        const None = 0;
+
+        /// A border with the title along the top, which the window is
+        /// dragged by.
+        const TitleBar = 1 << 0;
+        /// A button at the right of the title bar closing the window.
+        const Close = 1 << 1;
+        /// Grips at the bottom corners the window is resized by.
+        const Resize = 1 << 2;
+    }
+}
+
+impl Default for Chrome {
+    fn default() -> Chrome {
+        Chrome::all()
+    }
+}
+
+/// Identifies a window of a [`WindowManager`](struct.WindowManager.html).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WindowId(usize);
+
+/// A window and what it shows.
+struct Managed<B: Backend> {
+    id: WindowId,
+    title: String,
+    rect: Rect,
+    chrome: Chrome,
+    /// The place of the window in the stacking order, higher on top.
+    depth: usize,
+    minimized: bool,
+    urgent: bool,
+    widget: Box<dyn Widget<B>>,
+}
+
+impl<B: Backend> Managed<B> {
+    /// Gets the area inside the chrome.
+    fn inner(&self) -> Rect {
+        let rect = self.rect;
+        if self.chrome.contains(Chrome::TitleBar) {
+            Rect::new(
+                rect.x + 1,
+                rect.y + 1,
+                rect.width.saturating_sub(2),
+                rect.height.saturating_sub(2),
+            )
+        } else {
+            rect
+        }
+    }
+
+    /// Finds the part of the window at a position.
+    fn part_at(&self, pos: (usize, usize)) -> Option<Part> {
+        if !self.rect.contains(pos) {
+            return None;
+        }
+        let (x, y) = pos;
+        let rect = self.rect;
+        let part = if self.chrome.contains(Chrome::TitleBar) && y == rect.y {
+            if self.has_close_button() && x + CLOSE_BUTTON.len() + 1 >= rect.right() {
+                if x + 1 < rect.right() {
+                    Part::Close
+                } else {
+                    Part::TitleBar
+                }
+            } else {
+                Part::TitleBar
+            }
+        } else if self.chrome.contains(Chrome::Resize) && y + 1 == rect.bottom() && x == rect.x {
+            Part::Grip(Corner::Left)
+        } else if self.chrome.contains(Chrome::Resize)
+            && y + 1 == rect.bottom()
+            && x + 1 == rect.right()
+        {
+            Part::Grip(Corner::Right)
+        } else {
+            Part::Content
+        };
+        Some(part)
+    }
+
+    fn has_close_button(&self) -> bool {
+        self.chrome.contains(Chrome::TitleBar | Chrome::Close)
+            && self.rect.width >= CLOSE_BUTTON.len() + 4
+    }
+}
+
+/// A part of a window the mouse can be on.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Part {
+    TitleBar,
+    Close,
+    Grip(Corner),
+    Content,
+}
+
+/// A bottom corner of a window, resized from.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Corner {
+    Left,
+    Right,
+}
+
+/// A window being dragged with the mouse.
+#[derive(Copy, Clone, Debug)]
+enum Drag {
+    /// Moving, holding the title bar at the given offset from the corner.
+    Move(WindowId, (usize, usize)),
+    /// Resizing from a corner.
+    Resize(WindowId, Corner),
+}
+
+/// Represents windows floating over the rest of the interface, each showing
+/// a widget, which can be moved, resized and closed with the mouse.
+///
+/// ```text
+/// ┌ Notes ─────────────[x]┐
+/// │ buy milk              │
+/// │ call ┌ Clock ───────[x]┐
+/// │      │ 12:04:51        │
+/// ◣──────│                 │
+///        ◣─────────────────◢
+/// ```
+///
+/// ```no_run
+/// use termui::layout::Rect;
+/// use termui::widget::{List, Widget, WindowManager};
+/// use termui::Window;
+///
+/// let win = Window::new();
+/// let mut windows = WindowManager::new();
+/// windows.open("Notes", Rect::new(2, 1, 24, 6), List::new(vec!["buy milk"]));
+/// # let (rows, cols) = win.get_size();
+/// windows.render(Rect::new(0, 0, cols, rows), &win);
+/// ```
+///
+/// Windows are stacked in the order they were last focused, the focused one
+/// on top. Pressing a window focuses it and brings it to the top. With its
+/// [chrome](struct.Chrome.html), a window is dragged by its title bar,
+/// resized by the grips at its bottom corners, and closed by the button at
+/// the right of its title bar.
+///
+/// Key and paste events go to the widget of the focused window, mouse events
+/// to the widget of the window under the mouse, and other events to every
+/// widget.
+///
+/// Windows can also be minimized, hiding them until restored, or marked
+/// urgent to ask for attention, which a [`Taskbar`](struct.Taskbar.html)
+/// listing them shows.
+///
+/// *Windows are kept inside the area the manager was last rendered into.
+/// Closing a window with its button drops it, the ids of windows closed
+/// that way can be [taken](#method.take_closed) to clean up after them.*
+pub struct WindowManager<B: Backend = ::window::CursesBackend> {
+    windows: Vec<Managed<B>>,
+    next_id: usize,
+    next_depth: usize,
+    drag: Option<Drag>,
+    closed: Vec<WindowId>,
+    area: Option<Rect>,
+    style: Style,
+    focus_style: Style,
+}
+
+impl<B: Backend> Default for WindowManager<B> {
+    fn default() -> WindowManager<B> {
+        WindowManager::new()
+    }
+}
+
+impl<B: Backend> WindowManager<B> {
+    /// Creates a manager without any windows.
+    pub fn new() -> WindowManager<B> {
+        WindowManager {
+            windows: Vec::new(),
+            next_id: 0,
+            next_depth: 0,
+            drag: None,
+            closed: Vec::new(),
+            area: None,
+            style: Style::default(),
+            focus_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Opens a window showing a widget, on top and focused, with all of the
+    /// chrome.
+    pub fn open<S, W>(&mut self, title: S, rect: Rect, widget: W) -> WindowId
+    where
+        S: Into<String>,
+        W: Widget<B> + 'static,
+    {
+        let id = WindowId(self.next_id);
+        self.next_id += 1;
+        self.windows.push(Managed {
+            id,
+            title: title.into(),
+            rect,
+            chrome: Chrome::default(),
+            depth: 0,
+            minimized: false,
+            urgent: false,
+            widget: Box::new(widget),
+        });
+        self.focus(id);
+        id
+    }
+
+    /// Closes a window, dropping its widget, giving whether it was open.
+    pub fn close(&mut self, id: WindowId) -> bool {
+        let len = self.windows.len();
+        self.windows.retain(|window| window.id != id);
+        self.stop_drag(id);
+        self.windows.len() != len
+    }
+
+    /// Takes the ids of the windows closed with their close button since
+    /// last taken.
+    pub fn take_closed(&mut self) -> Vec<WindowId> {
+        self.closed.drain(..).collect()
+    }
+
+    /// Focuses a window, bringing it to the top, giving whether it is open.
+    ///
+    /// *Focusing a window restores it if minimized, and clears its urgency.*
+    pub fn focus(&mut self, id: WindowId) -> bool {
+        let depth = self.next_depth;
+        match self.get_mut(id) {
+            Some(window) => {
+                window.depth = depth;
+                window.minimized = false;
+                window.urgent = false;
+            }
+            None => return false,
+        }
+        self.next_depth += 1;
+        true
+    }
+
+    /// Gets the focused window, the topmost one that isn't minimized.
+    pub fn focused(&self) -> Option<WindowId> {
+        self.windows
+            .iter()
+            .filter(|window| !window.minimized)
+            .max_by_key(|window| window.depth)
+            .map(|window| window.id)
+    }
+
+    /// Minimizes a window, hiding it until restored or focused, giving
+    /// whether it is open.
+    pub fn minimize(&mut self, id: WindowId) -> bool {
+        match self.get_mut(id) {
+            Some(window) => window.minimized = true,
+            None => return false,
+        }
+        self.stop_drag(id);
+        true
+    }
+
+    /// Restores a minimized window, focusing it.
+    pub fn restore(&mut self, id: WindowId) -> bool {
+        self.focus(id)
+    }
+
+    /// Checks whether a window is minimized.
+    pub fn is_minimized(&self, id: WindowId) -> bool {
+        self.get(id).is_some_and(|window| window.minimized)
+    }
+
+    /// Checks whether a window wants attention.
+    pub fn is_urgent(&self, id: WindowId) -> bool {
+        self.get(id).is_some_and(|window| window.urgent)
+    }
+
+    /// Sets whether a window wants attention, such as when something finished
+    /// in the background, until it is focused.
+    ///
+    /// *The focused window can't be made urgent.*
+    pub fn set_urgent(&mut self, id: WindowId, urgent: bool) {
+        let focused = self.focused();
+        if let Some(window) = self.get_mut(id) {
+            window.urgent = urgent && focused != Some(id);
+        }
+    }
+
+    /// Gets the open windows, in the order they were opened.
+    pub fn windows(&self) -> Vec<WindowId> {
+        self.windows.iter().map(|window| window.id).collect()
+    }
+
+    /// Checks whether a window is open.
+    pub fn contains(&self, id: WindowId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Gets the title of a window.
+    pub fn title(&self, id: WindowId) -> Option<&str> {
+        self.get(id).map(|window| &*window.title)
+    }
+
+    /// Sets the title of a window.
+    pub fn set_title<S: Into<String>>(&mut self, id: WindowId, title: S) {
+        if let Some(window) = self.get_mut(id) {
+            window.title = title.into();
+        }
+    }
+
+    /// Gets the area of a window, chrome included.
+    pub fn rect(&self, id: WindowId) -> Option<Rect> {
+        self.get(id).map(|window| window.rect)
+    }
+
+    /// Moves and resizes a window, chrome included.
+    pub fn set_rect(&mut self, id: WindowId, rect: Rect) {
+        if let Some(window) = self.get_mut(id) {
+            window.rect = rect;
+        }
+    }
+
+    /// Gets the chrome drawn around a window.
+    pub fn chrome(&self, id: WindowId) -> Option<Chrome> {
+        self.get(id).map(|window| window.chrome)
+    }
+
+    /// Sets the chrome drawn around a window, all of it by default.
+    ///
+    /// *The close button and grips are drawn on the border of the title bar,
+    /// so they need it too.*
+    pub fn set_chrome(&mut self, id: WindowId, chrome: Chrome) {
+        if let Some(window) = self.get_mut(id) {
+            window.chrome = chrome;
+        }
+    }
+
+    /// Gets the widget shown in a window.
+    pub fn widget_mut(&mut self, id: WindowId) -> Option<&mut dyn Widget<B>> {
+        match self.get_mut(id) {
+            Some(window) => Some(&mut *window.widget),
+            None => None,
+        }
+    }
+
+    /// Gets the style the chrome is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the chrome is drawn in, patched over the style of where
+    /// the manager is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the chrome of the focused window.
+    pub fn focus_style(&self) -> Style {
+        self.focus_style
+    }
+
+    /// Sets the style patched over the chrome of the focused window, bold by
+    /// default.
+    pub fn set_focus_style(&mut self, style: Style) {
+        self.focus_style = style;
+    }
+
+    fn get(&self, id: WindowId) -> Option<&Managed<B>> {
+        self.windows.iter().find(|window| window.id == id)
+    }
+
+    fn get_mut(&mut self, id: WindowId) -> Option<&mut Managed<B>> {
+        self.windows.iter_mut().find(|window| window.id == id)
+    }
+
+    /// Stops dragging a window, if it is being dragged.
+    fn stop_drag(&mut self, id: WindowId) {
+        if let Some(Drag::Move(drag, _)) | Some(Drag::Resize(drag, _)) = self.drag {
+            if drag == id {
+                self.drag = None;
+            }
+        }
+    }
+
+    /// Gets the indices of the shown windows from the top of the stack down.
+    fn top_down(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.windows.len())
+            .filter(|&i| !self.windows[i].minimized)
+            .collect();
+        order.sort_by_key(|&i| ::std::cmp::Reverse(self.windows[i].depth));
+        order
+    }
+
+    /// Finds the topmost window at a position, and the part of it there.
+    fn window_at(&self, pos: (usize, usize)) -> Option<(usize, Part)> {
+        self.top_down()
+            .into_iter()
+            .find_map(|i| self.windows[i].part_at(pos).map(|part| (i, part)))
+    }
+
+    /// Moves or resizes the window being dragged to follow the mouse.
+    fn drag_to(&mut self, drag: Drag, pos: (usize, usize)) {
+        let area = self
+            .area
+            .unwrap_or_else(|| Rect::new(0, 0, usize::MAX, usize::MAX));
+        let (x, y) = pos;
+        match drag {
+            Drag::Move(id, (dx, dy)) => {
+                if let Some(window) = self.get_mut(id) {
+                    let rect = &mut window.rect;
+                    let max_x = area.right().saturating_sub(rect.width).max(area.x);
+                    let max_y = area.bottom().saturating_sub(rect.height).max(area.y);
+                    rect.x = x.saturating_sub(dx).clamp(area.x, max_x);
+                    rect.y = y.saturating_sub(dy).clamp(area.y, max_y);
+                }
+            }
+            Drag::Resize(id, corner) => {
+                if let Some(window) = self.get_mut(id) {
+                    let rect = &mut window.rect;
+                    // The area wins over the smallest size when it is smaller,
+                    // so the bounds are applied in turn rather than clamped.
+                    let min_width = MIN_SIZE.0.min(area.width);
+                    let min_height = MIN_SIZE.1.min(area.height);
+                    let bottom = (y + 1).max(rect.y + min_height).min(area.bottom());
+                    rect.height = bottom.saturating_sub(rect.y).max(min_height);
+                    match corner {
+                        Corner::Right => {
+                            let right = (x + 1).max(rect.x + min_width).min(area.right());
+                            rect.width = right.saturating_sub(rect.x).max(min_width);
+                        }
+                        Corner::Left => {
+                            let right = rect.right();
+                            let left = x.max(area.x).min(right.saturating_sub(min_width));
+                            rect.x = left;
+                            rect.width = right - left;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles a press of the left button, focusing the window pressed and
+    /// starting a drag if it was on the chrome.
+    fn press(&mut self, event: &Event, pos: (usize, usize)) -> bool {
+        let (index, part) = match self.window_at(pos) {
+            Some(hit) => hit,
+            None => return false,
+        };
+        let (id, rect) = (self.windows[index].id, self.windows[index].rect);
+        self.focus(id);
+        match part {
+            Part::Close => {
+                self.close(id);
+                self.closed.push(id);
+            }
+            Part::TitleBar => self.drag = Some(Drag::Move(id, (pos.0 - rect.x, pos.1 - rect.y))),
+            Part::Grip(corner) => self.drag = Some(Drag::Resize(id, corner)),
+            Part::Content => {
+                self.windows[index].widget.handle_event(event);
+            }
+        }
+        true
+    }
+}
+
+impl<B: Backend> fmt::Debug for WindowManager<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let windows: Vec<_> = self
+            .windows
+            .iter()
+            .map(|window| (window.id, &window.title, window.rect))
+            .collect();
+        f.debug_struct("WindowManager")
+            .field("windows", &windows)
+            .field("focused", &self.focused())
+            .finish()
+    }
+}
+
+impl<B: Backend> Widget<B> for WindowManager<B> {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        let focused = self.focused();
+        let mut order = self.top_down();
+        order.reverse();
+
+        for i in order {
+            let window = &mut self.windows[i];
+            // Keep the window inside the area, as small as allowed.
+            let rect = &mut window.rect;
+            rect.width = rect.width.min(area.width).max(MIN_SIZE.0.min(area.width));
+            rect.height = rect
+                .height
+                .min(area.height)
+                .max(MIN_SIZE.1.min(area.height));
+            rect.x = rect
+                .x
+                .min(area.right().saturating_sub(rect.width))
+                .max(area.x);
+            rect.y = rect
+                .y
+                .min(area.bottom().saturating_sub(rect.height))
+                .max(area.y);
+            let rect = *rect;
+            if rect.width == 0 || rect.height == 0 {
+                continue;
+            }
+
+            let style = if Some(window.id) == focused {
+                self.style.patch(self.focus_style)
+            } else {
+                self.style
+            };
+            if window.chrome.contains(Chrome::TitleBar) {
+                draw_frame(rect, "", style, win);
+                let close = if window.has_close_button() {
+                    CLOSE_BUTTON.len() + 1
+                } else {
+                    0
+                };
+                if rect.width > close + 4 && !window.title.is_empty() {
+                    let title = truncate(&window.title, rect.width - close - 4, win);
+                    win.print_styled(rect.y, rect.x + 1, format!(" {} ", title), style);
+                }
+                if close > 0 {
+                    win.print_styled(rect.y, rect.right() - close, CLOSE_BUTTON, style);
+                }
+                if window.chrome.contains(Chrome::Resize) {
+                    let (left, right) = if win.ascii_mode() {
+                        ("+", "+")
+                    } else {
+                        ("◣", "◢")
+                    };
+                    let row = rect.bottom() - 1;
+                    win.print_styled(row, rect.x, left, style);
+                    win.print_styled(row, rect.right() - 1, right, style);
+                }
+            } else {
+                let blank = " ".repeat(rect.width);
+                for row in rect.y..rect.bottom() {
+                    win.print_styled(row, rect.x, &blank, self.style);
+                }
+            }
+
+            // The focused window is drawn last, so the cursor it requests wins.
+            let inner = window.inner();
+            window.widget.render(inner, win);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            Event::Mouse {
+                pos, event: mouse, ..
+            } => match (mouse, self.drag) {
+                (MouseEvent::Hold(MouseButton::Left), Some(drag)) => {
+                    self.drag_to(drag, pos);
+                    true
+                }
+                (MouseEvent::Release(MouseButton::Left), Some(_)) => {
+                    self.drag = None;
+                    true
+                }
+                (MouseEvent::Press(MouseButton::Left), _) => self.press(event, pos),
+                _ => match self.window_at(pos) {
+                    Some((i, _)) => {
+                        self.windows[i].widget.handle_event(event);
+                        true
+                    }
+                    None => false,
+                },
+            },
+            Event::Key { .. } | Event::Paste(_) => {
+                let focused = self.focused();
+                match focused.and_then(|id| self.get_mut(id)) {
+                    Some(window) => window.widget.handle_event(event),
+                    None => false,
+                }
+            }
+            _ => {
+                let mut used = false;
+                for window in &mut self.windows {
+                    used |= window.widget.handle_event(event);
+                }
+                used
+            }
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+
+use backend::Backend;
+use layout::Rect;
+use style::{Attr, Style};
+use widget::{braille, truncate, Widget, BRAILLE_DOTS};
+use window::Window;
+
+type Outline = &'static [(f64, f64)];
+
+/// Coarse outlines of the land, as points of latitude and longitude.
+#[rustfmt::skip]
+const CONTINENTS: &[(&str, &[Outline])] = &[
+    (
+        "Africa",
+        &[
+            &[
+                (37.0, 10.0), (33.0, 11.0), (31.0, 20.0), (32.0, 29.0), (31.0, 32.0),
+                (22.0, 37.0), (15.0, 40.0), (12.0, 43.0), (11.0, 51.0), (2.0, 46.0),
+                (-4.0, 40.0), (-10.0, 40.0), (-15.0, 41.0), (-20.0, 35.0), (-25.0, 33.0),
+                (-30.0, 31.0), (-34.0, 26.0), (-34.0, 19.0), (-29.0, 16.0), (-22.0, 14.0),
+                (-17.0, 12.0), (-12.0, 14.0), (-6.0, 12.0), (-1.0, 9.0), (4.0, 9.0),
+                (4.0, 6.0), (6.0, 1.0), (5.0, -4.0), (4.0, -8.0), (7.0, -13.0),
+                (10.0, -15.0), (15.0, -17.0), (21.0, -17.0), (28.0, -13.0), (32.0, -9.0),
+                (35.0, -6.0), (36.0, -1.0), (37.0, 5.0),
+            ],
+            &[
+                (-12.0, 49.0), (-16.0, 50.0), (-25.0, 47.0), (-25.0, 44.0), (-20.0, 44.0),
+                (-16.0, 44.0),
+            ],
+        ],
+    ),
+    (
+        "Antarctica",
+        &[&[
+            (-78.0, -180.0), (-76.0, -160.0), (-74.0, -130.0), (-73.0, -100.0),
+            (-70.0, -75.0), (-64.0, -58.0), (-75.0, -60.0), (-78.0, -35.0), (-72.0, -20.0),
+            (-70.0, -10.0), (-70.0, 20.0), (-66.0, 50.0), (-69.0, 70.0), (-66.0, 90.0),
+            (-66.0, 140.0), (-70.0, 165.0), (-78.0, 180.0), (-90.0, 180.0), (-90.0, -180.0),
+        ]],
+    ),
+    (
+        "Eurasia",
+        &[
+            &[
+                (36.0, -9.0), (43.0, -9.0), (44.0, -1.0), (48.0, -5.0), (51.0, 2.0),
+                (54.0, 8.0), (57.0, 8.0), (59.0, 5.0), (62.0, 5.0), (66.0, 13.0),
+                (70.0, 20.0), (71.0, 28.0), (69.0, 35.0), (67.0, 41.0), (68.0, 50.0),
+                (69.0, 60.0), (73.0, 70.0), (72.0, 80.0), (76.0, 100.0), (77.0, 106.0),
+                (74.0, 113.0), (73.0, 125.0), (72.0, 140.0), (71.0, 155.0), (70.0, 170.0),
+                (68.0, 180.0), (65.0, 180.0), (62.0, 177.0), (60.0, 170.0), (59.0, 163.0),
+                (56.0, 162.0), (51.0, 157.0), (59.0, 155.0), (59.0, 143.0), (54.0, 141.0),
+                (48.0, 140.0), (43.0, 135.0), (40.0, 129.0), (35.0, 129.0), (35.0, 126.0),
+                (38.0, 125.0), (40.0, 122.0), (37.0, 120.0), (35.0, 119.0), (31.0, 122.0),
+                (25.0, 119.0), (22.0, 114.0), (21.0, 109.0), (18.0, 106.0), (11.0, 109.0),
+                (9.0, 105.0), (10.0, 99.0), (13.0, 100.0), (7.0, 100.0), (1.0, 104.0),
+                (4.0, 100.0), (8.0, 98.0), (16.0, 97.0), (17.0, 94.0), (22.0, 91.0),
+                (20.0, 86.0), (16.0, 81.0), (13.0, 80.0), (8.0, 77.0), (10.0, 76.0),
+                (19.0, 73.0), (22.0, 70.0), (25.0, 66.0), (25.0, 57.0), (22.0, 59.0),
+                (17.0, 55.0), (13.0, 45.0), (16.0, 43.0), (22.0, 39.0), (28.0, 35.0),
+                (30.0, 33.0), (33.0, 35.0), (36.0, 36.0), (36.0, 30.0), (37.0, 27.0),
+                (40.0, 26.0), (38.0, 23.0), (40.0, 20.0), (45.0, 13.0), (41.0, 16.0),
+                (38.0, 16.0), (41.0, 13.0), (44.0, 9.0), (43.0, 4.0), (41.0, 2.0),
+                (37.0, -2.0), (36.0, -6.0),
+            ],
+            &[
+                (58.0, -5.0), (58.0, -3.0), (55.0, -1.0), (53.0, 0.0), (52.0, 2.0),
+                (51.0, 1.0), (50.0, -5.0), (52.0, -4.0), (54.0, -3.0), (55.0, -6.0),
+            ],
+            &[
+                (55.0, -7.0), (54.0, -10.0), (52.0, -10.0), (51.0, -7.0), (53.0, -6.0),
+            ],
+            &[
+                (66.0, -23.0), (66.0, -15.0), (64.0, -14.0), (63.0, -19.0), (64.0, -22.0),
+            ],
+            &[
+                (45.0, 142.0), (43.0, 145.0), (40.0, 141.0), (35.0, 140.0), (34.0, 135.0),
+                (33.0, 130.0), (35.0, 132.0), (38.0, 139.0), (41.0, 140.0),
+            ],
+            &[
+                (7.0, 117.0), (1.0, 119.0), (-4.0, 116.0), (-3.0, 111.0), (1.0, 109.0),
+                (4.0, 114.0),
+            ],
+            &[(5.0, 95.0), (-6.0, 105.0), (-5.0, 102.0), (2.0, 98.0)],
+        ],
+    ),
+    (
+        "North America",
+        &[
+            &[
+                (70.0, -165.0), (71.0, -156.0), (70.0, -141.0), (69.0, -130.0),
+                (68.0, -115.0), (68.0, -98.0), (64.0, -88.0), (59.0, -94.0), (55.0, -82.0),
+                (52.0, -79.0), (60.0, -78.0), (62.0, -73.0), (58.0, -68.0), (60.0, -64.0),
+                (53.0, -56.0), (47.0, -53.0), (45.0, -61.0), (44.0, -66.0), (41.0, -70.0),
+                (35.0, -76.0), (30.0, -81.0), (25.0, -80.0), (27.0, -82.0), (30.0, -84.0),
+                (29.0, -89.0), (29.0, -95.0), (26.0, -97.0), (21.0, -97.0), (18.0, -95.0),
+                (19.0, -91.0), (21.0, -87.0), (16.0, -88.0), (15.0, -83.0), (11.0, -84.0),
+                (9.0, -79.0), (8.0, -77.0), (7.0, -80.0), (9.0, -85.0), (13.0, -88.0),
+                (14.0, -92.0), (16.0, -95.0), (20.0, -105.0), (23.0, -106.0),
+                (31.0, -113.0), (23.0, -110.0), (29.0, -115.0), (32.0, -117.0),
+                (34.0, -120.0), (38.0, -123.0), (43.0, -124.0), (48.0, -124.0),
+                (50.0, -128.0), (55.0, -131.0), (58.0, -136.0), (60.0, -141.0),
+                (60.0, -147.0), (57.0, -154.0), (55.0, -163.0), (58.0, -157.0),
+                (60.0, -162.0), (65.0, -166.0), (66.0, -162.0), (68.0, -166.0),
+            ],
+            &[
+                (83.0, -35.0), (82.0, -60.0), (78.0, -72.0), (76.0, -68.0), (70.0, -55.0),
+                (64.0, -52.0), (60.0, -44.0), (65.0, -40.0), (70.0, -22.0), (76.0, -19.0),
+                (82.0, -20.0),
+            ],
+        ],
+    ),
+    (
+        "Oceania",
+        &[
+            &[
+                (-11.0, 142.0), (-17.0, 141.0), (-12.0, 136.0), (-12.0, 131.0),
+                (-15.0, 129.0), (-14.0, 126.0), (-18.0, 122.0), (-20.0, 119.0),
+                (-22.0, 114.0), (-26.0, 113.0), (-32.0, 115.0), (-35.0, 117.0),
+                (-34.0, 124.0), (-32.0, 127.0), (-32.0, 132.0), (-35.0, 136.0),
+                (-38.0, 140.0), (-39.0, 146.0), (-37.0, 150.0), (-33.0, 152.0),
+                (-28.0, 153.0), (-24.0, 152.0), (-19.0, 147.0), (-15.0, 145.0),
+            ],
+            &[
+                (-35.0, 173.0), (-37.0, 176.0), (-41.0, 176.0), (-46.0, 170.0),
+                (-46.0, 167.0), (-41.0, 172.0),
+            ],
+            &[
+                (-1.0, 131.0), (-3.0, 141.0), (-8.0, 148.0), (-10.0, 150.0), (-8.0, 143.0),
+                (-5.0, 135.0),
+            ],
+        ],
+    ),
+    (
+        "South America",
+        &[&[
+            (12.0, -72.0), (11.0, -64.0), (10.0, -62.0), (8.0, -60.0), (5.0, -52.0),
+            (0.0, -50.0), (-2.0, -44.0), (-5.0, -35.0), (-8.0, -35.0), (-13.0, -38.0),
+            (-23.0, -41.0), (-25.0, -48.0), (-29.0, -49.0), (-34.0, -53.0), (-36.0, -57.0),
+            (-39.0, -62.0), (-42.0, -64.0), (-47.0, -66.0), (-51.0, -69.0), (-55.0, -68.0),
+            (-54.0, -72.0), (-50.0, -75.0), (-41.0, -74.0), (-33.0, -72.0), (-24.0, -70.0),
+            (-18.0, -70.0), (-14.0, -76.0), (-6.0, -81.0), (-2.0, -81.0), (1.0, -79.0),
+            (4.0, -77.0), (8.0, -77.0), (9.0, -76.0), (11.0, -74.0),
+        ]],
+    ),
+];
+
+/// Represents an area of a [`WorldMap`](struct.WorldMap.html), such as a
+/// country, made of one or more outlines of latitude and longitude.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Region {
+    name: String,
+    outlines: Vec<Vec<(f64, f64)>>,
+}
+
+impl Region {
+    /// Creates a region of the given outlines, each a list of points of
+    /// latitude and longitude, in degrees.
+    ///
+    /// *Each outline is closed by joining its last point to its first.*
+    pub fn new<S: Into<String>>(name: S, outlines: Vec<Vec<(f64, f64)>>) -> Region {
+        Region {
+            name: name.into(),
+            outlines,
+        }
+    }
+
+    /// Gets the name of the region.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the outlines of the region.
+    pub fn outlines(&self) -> &[Vec<(f64, f64)>] {
+        &self.outlines
+    }
+
+    /// Checks whether a point is inside the region, counting the holes left
+    /// by outlines within outlines.
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        let mut inside = false;
+        for outline in &self.outlines {
+            let mut prev = match outline.last() {
+                Some(&point) => point,
+                None => continue,
+            };
+            for &point in outline {
+                let ((lat_a, lon_a), (lat_b, lon_b)) = (prev, point);
+                if (lat_a > lat) != (lat_b > lat)
+                    && lon < lon_a + (lat - lat_a) / (lat_b - lat_a) * (lon_b - lon_a)
+                {
+                    inside = !inside;
+                }
+                prev = point;
+            }
+        }
+        inside
+    }
+
+    /// Gets the latitudes and longitudes the region lies within.
+    fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let points = self.outlines.iter().flatten();
+        points.fold(
+            (
+                (f64::INFINITY, f64::NEG_INFINITY),
+                (f64::INFINITY, f64::NEG_INFINITY),
+            ),
+            |((lat_min, lat_max), (lon_min, lon_max)), &(lat, lon)| {
+                (
+                    (lat_min.min(lat), lat_max.max(lat)),
+                    (lon_min.min(lon), lon_max.max(lon)),
+                )
+            },
+        )
+    }
+}
+
+/// Represents a map of the world, drawn in braille, with regions picked out
+/// in their own styles and markers at points of latitude and longitude, such
+/// as the servers of a network dashboard.
+///
+/// ```text
+///             ⠤⣤⣤⣤⣤⣤            ⣀⣀⡀
+///  ⠘⡿⠿⠷⣶⣶⣶⣶⣶⡀⢠⣀⡀⠛⠋⠉⠑⠂⢀⠠⣴⣿⣷⣶⣶⣶⣿⣿⣿⣿⣿⣿⣿⣿⣿⠿⢿⠿⠒
+///      ⠈⢿⣿⣿⣿⣿●⠟⠛     ●⠞⠟⠿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⡿⡿⠣
+///       ⠈⠛⢿⡛⡙⠁      ⣰⣿⣿⣶⣶⣦⢿⣿⡟⠻⣿⡿⣿⣿⠿⠃⠈
+///          ⠈⠁⢠⣦⣄⡀   ⠹⠿⠿⣿⣿⣿⣷⠏  ⠙  ⠛⢀
+///            ⠻⣿⣿⣿⡶     ⢹⣿⣿⡇⡀     ⠁⠈ ⣈⣐
+///             ⣿⣿⠟⠁     ⠘⣿⠏ ⠁      ⠰⣿⠿⣿●
+/// ```
+///
+/// The map comes with coarse outlines of the continents, named `"Africa"`,
+/// `"Antarctica"`, `"Eurasia"`, `"North America"`, `"Oceania"` and
+/// `"South America"`, and finer regions, such as countries, can be added.
+/// The land is shown with an equirectangular projection of the
+/// [bounds](#method.set_bounds), the whole world by default.
+///
+/// *The land is plotted again only when the size, bounds or regions change.
+/// In ASCII fallback mode, land is drawn as `.` and markers as `*`.*
+#[derive(Clone, Debug)]
+pub struct WorldMap {
+    regions: Vec<Region>,
+    highlights: HashMap<String, Style>,
+    markers: Vec<(f64, f64, String)>,
+    bounds: ((f64, f64), (f64, f64)),
+    plotted: Option<(usize, usize)>,
+    dots: Vec<Option<usize>>,
+    area: Option<Rect>,
+    style: Style,
+    marker_style: Style,
+}
+
+impl Default for WorldMap {
+    fn default() -> WorldMap {
+        WorldMap::new()
+    }
+}
+
+impl WorldMap {
+    /// Creates a map of the whole world with the outlines of the continents.
+    pub fn new() -> WorldMap {
+        let regions = CONTINENTS.iter().map(|&(name, outlines)| {
+            Region::new(
+                name,
+                outlines.iter().map(|outline| outline.to_vec()).collect(),
+            )
+        });
+        WorldMap::with_regions(regions)
+    }
+
+    /// Creates a map of the whole world with only the given regions.
+    pub fn with_regions<I: IntoIterator<Item = Region>>(regions: I) -> WorldMap {
+        WorldMap {
+            regions: regions.into_iter().collect(),
+            highlights: HashMap::new(),
+            markers: Vec::new(),
+            bounds: ((-90.0, 90.0), (-180.0, 180.0)),
+            plotted: None,
+            dots: Vec::new(),
+            area: None,
+            style: Style::default(),
+            marker_style: Style::new().attr(Attr::Bold),
+        }
+    }
+
+    /// Gets the regions, in the order they are drawn.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// Adds a region, drawn over the regions before it where they overlap.
+    pub fn add_region(&mut self, region: Region) {
+        self.regions.push(region);
+        self.plotted = None;
+    }
+
+    /// Picks out the regions with the given name in a style, or stops
+    /// picking them out with `None`.
+    pub fn set_highlight<S: Into<String>>(&mut self, name: S, style: Option<Style>) {
+        let name = name.into();
+        match style {
+            Some(style) => self.highlights.insert(name, style),
+            None => self.highlights.remove(&name),
+        };
+    }
+
+    /// Stops picking out every region.
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// Adds a marker at a point of latitude and longitude, with a label shown
+    /// after it if there is room.
+    pub fn add_marker<S: Into<String>>(&mut self, lat: f64, lon: f64, label: S) {
+        self.markers.push((lat, lon, label.into()));
+    }
+
+    /// Removes every marker.
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+    }
+
+    /// Gets the latitudes and longitudes shown.
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        self.bounds
+    }
+
+    /// Sets the latitudes and longitudes shown, from south to north and west
+    /// to east, such as to zoom in on a continent.
+    pub fn set_bounds(&mut self, lat: (f64, f64), lon: (f64, f64)) {
+        if self.bounds != (lat, lon) {
+            self.bounds = (lat, lon);
+            self.plotted = None;
+        }
+    }
+
+    /// Gets the cell of the window a point of latitude and longitude was
+    /// drawn in when the map was last rendered, if it was in view.
+    pub fn position_of(&self, lat: f64, lon: f64) -> Option<(usize, usize)> {
+        let area = self.area?;
+        let ((south, north), (west, east)) = self.bounds;
+        let x = (lon - west) / (east - west) * area.width as f64;
+        let y = (north - lat) / (north - south) * area.height as f64;
+        if !(x >= 0.0 && y >= 0.0 && x < area.width as f64 && y < area.height as f64) {
+            return None;
+        }
+        Some((area.x + x as usize, area.y + y as usize))
+    }
+
+    /// Gets the style the map is drawn in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the map is drawn in, patched over the style of where it
+    /// is rendered.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Gets the style patched over the markers and their labels.
+    pub fn marker_style(&self) -> Style {
+        self.marker_style
+    }
+
+    /// Sets the style patched over the markers and their labels, bold by
+    /// default.
+    pub fn set_marker_style(&mut self, style: Style) {
+        self.marker_style = style;
+    }
+
+    /// Finds the region covering each dot of a map of the given size in
+    /// cells, the last drawn where regions overlap.
+    fn plot(&self, width: usize, height: usize) -> Vec<Option<usize>> {
+        let (dots_x, dots_y) = (width * 2, height * 4);
+        let ((south, north), (west, east)) = self.bounds;
+        let mut dots = vec![None; dots_x * dots_y];
+        for (index, region) in self.regions.iter().enumerate() {
+            let ((lat_min, lat_max), (lon_min, lon_max)) = region.bounds();
+            for y in 0..dots_y {
+                let lat = north - (y as f64 + 0.5) / dots_y as f64 * (north - south);
+                if lat < lat_min || lat > lat_max {
+                    continue;
+                }
+                for x in 0..dots_x {
+                    let lon = west + (x as f64 + 0.5) / dots_x as f64 * (east - west);
+                    if lon >= lon_min && lon <= lon_max && region.contains(lat, lon) {
+                        dots[y * dots_x + x] = Some(index);
+                    }
+                }
+            }
+        }
+        dots
+    }
+}
+
+impl<B: Backend> Widget<B> for WorldMap {
+    fn render(&mut self, area: Rect, win: &Window<B>) {
+        self.area = Some(area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let size = (area.width, area.height);
+        if self.plotted != Some(size) {
+            self.dots = self.plot(area.width, area.height);
+            self.plotted = Some(size);
+        }
+        let dots = &self.dots;
+
+        let ascii = win.ascii_mode();
+        let dots_x = area.width * 2;
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let mut bits = 0;
+                let mut style = self.style;
+                for (row, dot_bits) in BRAILLE_DOTS.iter().enumerate() {
+                    for (col, &bit) in dot_bits.iter().enumerate() {
+                        let region = dots[(y * 4 + row) * dots_x + x * 2 + col];
+                        if let Some(region) = region {
+                            bits |= bit;
+                            let name = &self.regions[region].name;
+                            if let Some(&highlight) = self.highlights.get(name) {
+                                style = self.style.patch(highlight);
+                            }
+                        }
+                    }
+                }
+                let ch = match bits {
+                    0 => ' ',
+                    _ if ascii => '.',
+                    bits => braille(bits),
+                };
+                win.print_styled(area.y + y, area.x + x, ch.to_string(), style);
+            }
+        }
+
+        let marker = if ascii { "*" } else { "●" };
+        let style = self.style.patch(self.marker_style);
+        for &(lat, lon, ref label) in &self.markers {
+            let (x, y) = match self.position_of(lat, lon) {
+                Some(pos) => pos,
+                None => continue,
+            };
+            win.print_styled(y, x, marker, style);
+            if !label.is_empty() && x + 2 < area.right() {
+                let label = truncate(label, area.right() - x - 2, win);
+                win.print_styled(y, x + 2, label, style);
+            }
+        }
+    }
+}
@@ -0,0 +1,811 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::ffi::CString;
+use std::fmt::Write as FmtWrite;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use std::{mem, ptr};
+
+use backend::{Backend, Buffer};
+use caps::{self, Capabilities, Features, Probe, Profile};
+use curses;
+use draw;
+use error::{Error, Result};
+use event::{Decoder, Event, PASTE_END, PASTE_START};
+use ffi;
+use style::{Color, ColorCaps, ColorPairs, Style};
+use text::AmbiguousWidth;
+
+use super::inline::Inline;
+use super::input::InputWindow;
+use super::reserved::{self, Edge};
+use super::screen;
+use super::{Builder, CursorStyle, SoftLabelFormat};
+
+/// The progressive keyboard flags requested: disambiguated keys and event
+/// types.
+const KITTY_FLAGS: u8 = 0b11;
+
+/// The backend drawing to the terminal with curses.
+///
+/// *Created by [`Window::new`](struct.Window.html#method.new) and the
+/// [`Builder`](struct.Builder.html).*
+pub struct CursesBackend {
+    /// The inner curses window.
+    pub window: curses::Window,
+
+    pub(super) input: InputWindow,
+    pub(super) decoder: Decoder,
+    pending: VecDeque<Event>,
+    pixel_mouse: bool,
+    mouse_motion: bool,
+    kitty_keyboard: bool,
+    pub(super) capabilities: Capabilities,
+    color_pairs: RefCell<ColorPairs>,
+    pub(super) palette: RefCell<HashMap<u8, SavedColor>>,
+    pub(super) soft_labels: Option<SoftLabelFormat>,
+    pub(super) reserved: Vec<(Edge, ffi::WindowPtr)>,
+    pub(super) headless: bool,
+    inline: Option<Inline>,
+    transcript: Option<RefCell<String>>,
+    graphics: RefCell<Vec<(usize, usize, Rc<str>)>>,
+    underlines: RefCell<Buffer>,
+    underline_rows: RefCell<BTreeSet<usize>>,
+    cursor_applied: Cell<CursorStyle>,
+    pub(super) line_drawing: bool,
+    pub(super) title_saved: Cell<bool>,
+    ambiguous_width: AmbiguousWidth,
+    suspended: bool,
+    shut_down: bool,
+    // Released last, after the terminal has been restored.
+    _instance: screen::Instance,
+}
+
+/// The original value of a redefined palette entry.
+pub(super) enum SavedColor {
+    /// The components reported by curses before redefinition.
+    Curses(i16, i16, i16),
+    /// The color was redefined with OSC 4 and is reset with OSC 104.
+    Osc,
+}
+
+impl CursesBackend {
+    /// Initializes curses as configured by the builder.
+    ///
+    /// *The locale must already be set, since curses reads it at init.*
+    pub(super) fn new(builder: &Builder, instance: screen::Instance) -> Result<CursesBackend> {
+        if let Some(format) = builder.soft_labels {
+            unsafe { ffi::slk_init(format.code()) };
+        }
+        reserved::request(builder.reserve_top, builder.reserve_bottom);
+
+        let (window, headless, inline) = match builder.inline {
+            Some(rows) if screen::is_interactive() => {
+                let (window, inline) = Inline::init(rows)?;
+                (window, false, Some(inline))
+            }
+            _ => {
+                let (window, headless) = screen::init()?;
+                (window, headless, None)
+            }
+        };
+        window.keypad(true);
+        window.nodelay(true);
+
+        curses::noecho();
+        curses::cbreak();
+
+        let profile = if headless || inline.is_some() {
+            Profile::Dumb
+        } else {
+            builder.profile.unwrap_or_else(Profile::detect)
+        };
+        let color_choice = builder.color_choice;
+        let default_colors = color_choice
+            .apply(profile.features())
+            .contains(Features::Color)
+            && {
+                curses::start_color();
+                curses::use_default_colors() != curses::ERR
+            };
+        let mut capabilities =
+            Capabilities::guess(ColorCaps::detect(default_colors), profile, color_choice);
+        // Quirks can't apply without a terminal to be quirky.
+        let quirks = if headless || inline.is_some() {
+            Vec::new()
+        } else {
+            builder.quirks.matching()
+        };
+        capabilities.apply_quirks(&quirks, builder.profile.is_some());
+        if !builder.mouse {
+            capabilities.features.remove(Features::Mouse);
+        }
+
+        curses::curs_set(0);
+
+        if capabilities.supports(Features::Mouse) {
+            curses::mouseinterval(0);
+            curses::mousemask(
+                curses::ALL_MOUSE_EVENTS | curses::REPORT_MOUSE_POSITION,
+                ptr::null_mut(),
+            );
+
+            print!("{}", mouse_mode(builder.mouse_motion, 'h'));
+            io::stdout().flush().expect("could not flush stdout");
+        }
+
+        let mut backend = CursesBackend {
+            window,
+
+            input: InputWindow::new(),
+            decoder: Decoder::new(),
+            pending: VecDeque::new(),
+            pixel_mouse: false,
+            mouse_motion: builder.mouse_motion,
+            kitty_keyboard: false,
+            color_pairs: RefCell::new(ColorPairs::new(capabilities.colors.pairs)),
+            capabilities,
+            palette: RefCell::new(HashMap::new()),
+            soft_labels: builder.soft_labels,
+            reserved: reserved::take(),
+            headless,
+            inline,
+            transcript: if headless && builder.transcript {
+                Some(RefCell::new(String::new()))
+            } else {
+                None
+            },
+            graphics: RefCell::new(Vec::new()),
+            underlines: RefCell::new(Buffer::new(0, 0)),
+            underline_rows: RefCell::new(BTreeSet::new()),
+            cursor_applied: Cell::new(CursorStyle::Hidden),
+            line_drawing: false,
+            title_saved: Cell::new(false),
+            ambiguous_width: AmbiguousWidth::Narrow,
+            suspended: false,
+            shut_down: false,
+            _instance: instance,
+        };
+
+        backend.decoder.register_terminfo_keys();
+        for (sequence, event) in quirks.iter().flat_map(|quirk| &quirk.keys) {
+            backend.define_key(sequence, event.clone());
+        }
+
+        if backend.capabilities.supports(Features::BracketedPaste) {
+            match (
+                backend.define_sequence(PASTE_START),
+                backend.define_sequence(PASTE_END),
+            ) {
+                (Some(start), Some(end)) => {
+                    backend.decoder.set_paste_codes(start, end);
+                    print!("\x1B[?2004h");
+                    io::stdout().flush().expect("could not flush stdout");
+                }
+                _ => backend
+                    .capabilities
+                    .features
+                    .remove(Features::BracketedPaste),
+            }
+        }
+
+        match builder.probe_timeout {
+            Some(timeout) if backend.capabilities.supports(Features::Queries) => {
+                backend.probe_capabilities(timeout)
+            }
+            _ => {}
+        }
+
+        if builder.kitty_keyboard
+            && !headless
+            && (backend.capabilities.keyboard_protocol || !backend.capabilities.probed)
+        {
+            print!("\x1B[>{}u", KITTY_FLAGS);
+            io::stdout().flush().expect("could not flush stdout");
+            backend.kitty_keyboard = true;
+            backend.decoder.set_raw_escapes(true);
+        }
+
+        if builder.pixel_mouse
+            && backend.capabilities.supports(Features::Mouse)
+            && backend.capabilities.cell_size.is_some()
+        {
+            print!("\x1B[?1016h");
+            io::stdout().flush().expect("could not flush stdout");
+            backend.pixel_mouse = true;
+            backend
+                .decoder
+                .set_pixel_mouse(backend.capabilities.cell_size);
+        }
+        Ok(backend)
+    }
+
+    /// Makes curses recognise a key sequence, delivering it as the event.
+    fn define_key(&mut self, sequence: &[u8], event: Event) {
+        if let Some(code) = self.define_sequence(sequence) {
+            self.decoder.register_keycode(code, event);
+        }
+    }
+
+    /// Makes curses recognise a key sequence, giving the key code it is read
+    /// as.
+    fn define_sequence(&mut self, sequence: &[u8]) -> Option<i32> {
+        let definition = CString::new(sequence).ok()?;
+
+        let code = match unsafe { ffi::key_defined(definition.as_ptr()) } {
+            code if code > 0 => code,
+            _ => {
+                let code = self.decoder.unused_code();
+                unsafe { ffi::define_key(definition.as_ptr(), code) };
+                // Registered so the next unused code differs.
+                self.decoder
+                    .register_keycode(code, Event::Unknown(sequence.to_vec()));
+                code
+            }
+        };
+        Some(code)
+    }
+
+    fn probe_capabilities(&mut self, timeout: Duration) {
+        self.input.keypad(false);
+        self.input.nodelay(false);
+
+        print!("{}", Probe::queries());
+        io::stdout().flush().expect("could not flush stdout");
+
+        let deadline = Instant::now() + timeout;
+        let mut probe = Probe::new(self.size());
+        let mut passthrough = Vec::new();
+
+        while !probe.is_done() {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+
+            let remaining = deadline - now;
+            self.input.timeout(remaining.as_millis().max(1) as i32);
+            match self.input.getch() {
+                Some(input) => passthrough.extend(probe.feed(input, &mut self.capabilities)),
+                None => break,
+            }
+        }
+
+        self.capabilities.probed = probe.is_done();
+        if !probe.is_done() {
+            debug!("capability probe timed out");
+        }
+
+        self.input.nodelay(true);
+        self.input.keypad(true);
+
+        for input in passthrough.iter().rev() {
+            self.input.ungetch(input);
+        }
+    }
+
+    /// Converts a style into curses attributes, allocating its color pair.
+    pub(crate) fn style_attrs(&self, style: Style) -> curses::chtype {
+        let mut attrs = style.attr.to_curses();
+        let colors = self.capabilities.colors;
+        if colors.has_colors && (style.fg.is_some() || style.bg.is_some()) {
+            // Without default colors in pairs, white on black stands in for them.
+            let number =
+                |color: Option<Color>, fallback: Color| match color.unwrap_or(Color::Default) {
+                    Color::Default if !colors.default_colors => fallback.to_curses(colors.colors),
+                    color => color.to_curses(colors.colors),
+                };
+            let pair = self.color_pairs.borrow_mut().get(
+                number(style.fg, Color::White),
+                number(style.bg, Color::Black),
+            );
+            if let Some(pair) = pair {
+                attrs |= curses::COLOR_PAIR(pair as curses::chtype);
+            }
+        }
+        attrs
+    }
+
+    /// Adds text at the cursor, drawing line characters the locale can't
+    /// encode with the alternate character set.
+    fn add_text(&self, text: &str) {
+        if !self.line_drawing || text.is_ascii() {
+            self.window.addstr(text);
+            return;
+        }
+
+        let mut rest = text;
+        while let Some((index, ch, acs)) = rest
+            .char_indices()
+            .find_map(|(index, ch)| draw::acs(ch).map(|acs| (index, ch, acs)))
+        {
+            self.window.addstr(&rest[..index]);
+            self.window.addch(acs);
+            rest = &rest[index + ch.len_utf8()..];
+        }
+        self.window.addstr(rest);
+    }
+
+    /// Keeps track of the styled underlines on screen, which curses draws as
+    /// plain underlines and are drawn over it when presented.
+    fn track_underlines(&self, row: usize, col: usize, text: &str, style: Style) {
+        if !self.capabilities.supports(Features::StyledUnderline) {
+            return;
+        }
+        let mut underlines = self.underlines.borrow_mut();
+        let (rows, cols) = self.size();
+        if underlines.size() != (rows, cols) {
+            underlines.resize(rows, cols);
+        }
+
+        let before: Vec<_> = (0..cols)
+            .filter_map(|c| underlines.get(row, c).cloned())
+            .collect();
+        underlines.put(row, col, text, style, self.ambiguous_width);
+        // Curses doesn't know the styled underline is there, so a row losing
+        // one has to be redrawn in full.
+        let lost = before.iter().enumerate().any(|(c, cell)| {
+            cell.style.has_styled_underline() && underlines.get(row, c) != Some(cell)
+        });
+        if lost {
+            self.redraw_row(row);
+        } else if style.has_styled_underline() {
+            self.underline_rows.borrow_mut().insert(row);
+        }
+    }
+
+    /// Makes curses redraw a row in full when next refreshed, drawing its
+    /// styled underlines over it again.
+    fn redraw_row(&self, row: usize) {
+        let (top, _) = self.window.get_beg_yx();
+        unsafe { ffi::wredrawln(ffi::curscr, top + row as i32, 1) };
+        self.underline_rows.borrow_mut().insert(row);
+    }
+
+    /// Draws the styled underlines of the rows over what curses drew, keeping
+    /// the cursor where curses expects it.
+    fn draw_underlines(&self, rows: BTreeSet<usize>) {
+        let underlines = self.underlines.borrow();
+        let (_, cols) = underlines.size();
+        let mut out = String::new();
+        for row in rows {
+            let mut col = 0;
+            while col < cols {
+                let style = match underlines.get(row, col) {
+                    Some(cell) if cell.style.has_styled_underline() => cell.style,
+                    _ => {
+                        col += 1;
+                        continue;
+                    }
+                };
+                let start = col;
+                let mut text = String::new();
+                while let Some(cell) = underlines.get(row, col).filter(|cell| cell.style == style) {
+                    text.push_str(&cell.symbol);
+                    col += 1;
+                }
+                out.push_str(&format!(
+                    "\x1B7\x1B[{};{}H{}{}\x1B8",
+                    row + 1,
+                    start + 1,
+                    style.sgr(self.capabilities.colors),
+                    text
+                ));
+            }
+        }
+        if !out.is_empty() {
+            print!("{}", out);
+            io::stdout().flush().expect("could not flush stdout");
+        }
+    }
+
+    fn apply_cursor(&self, style: CursorStyle) {
+        let applied = self.cursor_applied.replace(style);
+
+        if let Some((row, col)) = style.position() {
+            self.window.mv(row as i32, col as i32);
+        }
+        if style.shape_code() != applied.shape_code() {
+            match style.shape_code() {
+                Some(code) => {
+                    if self.capabilities.supports(Features::CursorShape) {
+                        print!("\x1B[{} q", code);
+                        io::stdout().flush().expect("could not flush stdout");
+                    }
+                    curses::curs_set(1);
+                }
+                None => {
+                    curses::curs_set(0);
+                }
+            }
+        }
+    }
+
+    pub(super) fn restore_terminal(&mut self) -> Result<()> {
+        if self.shut_down {
+            return Ok(());
+        }
+        self.shut_down = true;
+
+        let mut result = self.restore_palette();
+        if self.title_saved.get() {
+            print!("\x1B[23;0t");
+        }
+
+        // Suspended terminals are already out of the modes.
+        if !self.suspended {
+            self.leave_modes();
+        }
+        if let Some(ref inline) = self.inline {
+            inline.finish();
+        }
+        if let Err(err) = io::stdout().flush() {
+            result = Err(Error::Io("flushing stdout", err.kind()));
+        }
+
+        // Without a terminal there are no modes to restore, so this can only
+        // fail when headless or inline.
+        // A panic hook may have ended curses already.
+        let ended = unsafe { ffi::isendwin() }
+            || (curses::endwin() != curses::ERR && unsafe { ffi::isendwin() });
+        if !ended && !self.headless && self.inline.is_none() {
+            // Still on the alternate screen, with the program's colors.
+            result = Err(Error::Curses("endwin"));
+        }
+        result
+    }
+
+    /// Turns off the terminal modes used by the backend, logging those that
+    /// were still active.
+    fn leave_modes(&self) {
+        if self.cursor_applied.get() != CursorStyle::Hidden
+            && self.capabilities.supports(Features::CursorShape)
+        {
+            info!("resetting cursor shape");
+            print!("\x1B[0 q");
+        }
+        if self.capabilities.supports(Features::Mouse) {
+            let mut previous = 0;
+            curses::mousemask(0, &mut previous);
+            if previous != 0 {
+                info!("disabling mouse reporting");
+                if self.pixel_mouse {
+                    print!("\x1B[?1016l");
+                }
+                print!("{}", mouse_mode(self.mouse_motion, 'l'));
+            }
+        }
+        if self.capabilities.supports(Features::BracketedPaste) {
+            info!("disabling bracketed paste");
+            print!("\x1B[?2004l");
+        }
+        if self.kitty_keyboard {
+            info!("popping keyboard protocol flags");
+            print!("\x1B[<u");
+        }
+        if curses::curs_set(1) == 0 {
+            info!("showing hidden cursor");
+        }
+    }
+
+    /// Hands the bottom rows of the window over to normal output, as a
+    /// scrolling region with the terminal in its shell modes, giving the rows.
+    ///
+    /// *Gives `None` when the window isn't drawn to the terminal directly.*
+    pub(super) fn lend_rows(&self, rows: usize) -> Option<Range<usize>> {
+        let (height, _) = self.size();
+        let rows = rows.min(height);
+        if rows == 0 || self.headless || self.inline.is_some() || self.suspended {
+            return None;
+        }
+        let region = height - rows..height;
+
+        self.leave_modes();
+        curses::def_prog_mode();
+        curses::reset_shell_mode();
+
+        let (top, _) = self.window.get_beg_yx();
+        let (first, last) = (top as usize + region.start + 1, top as usize + height);
+        // The cursor is saved, since resetting the scrolling region moves it.
+        let mut out = format!("\x1B7\x1B[{};{}r", first, last);
+        for row in first..=last {
+            let _ = write!(out, "\x1B[{};1H\x1B[2K", row);
+        }
+        let _ = write!(out, "\x1B[{};1H", first);
+        print!("{}", out);
+        io::stdout().flush().expect("could not flush stdout");
+        Some(region)
+    }
+
+    /// Takes the rows lent to normal output back, redrawing them on the next
+    /// refresh.
+    pub(super) fn reclaim_rows(&self, region: Range<usize>) {
+        let _ = io::stderr().flush();
+        print!("\x1B[r\x1B8");
+        io::stdout().flush().expect("could not flush stdout");
+
+        curses::reset_prog_mode();
+        self.enter_modes();
+        for row in region {
+            self.redraw_row(row);
+        }
+    }
+
+    /// Turns the terminal modes used by the backend back on after a suspend.
+    fn enter_modes(&self) {
+        curses::curs_set(0);
+        self.cursor_applied.set(CursorStyle::Hidden);
+        if self.capabilities.supports(Features::Mouse) {
+            curses::mousemask(
+                curses::ALL_MOUSE_EVENTS | curses::REPORT_MOUSE_POSITION,
+                ptr::null_mut(),
+            );
+            print!("{}", mouse_mode(self.mouse_motion, 'h'));
+            if self.pixel_mouse {
+                print!("\x1B[?1016h");
+            }
+        }
+        if self.capabilities.supports(Features::BracketedPaste) {
+            print!("\x1B[?2004h");
+        }
+        if self.kitty_keyboard {
+            print!("\x1B[>{}u", KITTY_FLAGS);
+        }
+    }
+
+    fn restore_palette(&mut self) -> Result<()> {
+        let palette = self.palette.get_mut();
+        if !palette.is_empty() {
+            info!("restoring {} palette colors", palette.len());
+        }
+
+        let mut result = Ok(());
+        for (index, saved) in palette.drain() {
+            match saved {
+                SavedColor::Curses(r, g, b) => {
+                    // Reading the color back checks the terminal took it.
+                    if curses::init_color(i16::from(index), r, g, b) == curses::ERR
+                        || curses::color_content(i16::from(index)) != (r, g, b)
+                    {
+                        result = Err(Error::Curses("init_color"));
+                    }
+                }
+                SavedColor::Osc => print!("\x1B]104;{}\x1B\\", index),
+            }
+        }
+        result
+    }
+}
+
+impl Backend for CursesBackend {
+    fn size(&self) -> (usize, usize) {
+        let (rows, cols) = self.window.get_max_yx();
+        (rows as usize, cols as usize)
+    }
+
+    fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    fn erase(&self) {
+        self.window.erase();
+
+        let (rows, _) = self.underlines.borrow().size();
+        for row in 0..rows {
+            if has_styled_underline(&self.underlines.borrow(), row) {
+                self.redraw_row(row);
+            }
+        }
+        self.underlines.borrow_mut().clear(Style::default());
+    }
+
+    fn line_drawing(&self) -> bool {
+        self.line_drawing
+    }
+
+    fn print(&self, row: usize, col: usize, text: &str, style: Style) {
+        self.track_underlines(row, col, text, style);
+        let attrs = self.style_attrs(style);
+        self.window.attron(attrs);
+        self.window.mv(row as i32, col as i32);
+        self.add_text(text);
+        self.window.attroff(attrs);
+    }
+
+    fn print_here(&self, text: &str, style: Style) {
+        let (row, col) = self.cursor();
+        self.track_underlines(row, col, text, style);
+        let attrs = self.style_attrs(style);
+        self.window.attron(attrs);
+        self.add_text(text);
+        self.window.attroff(attrs);
+    }
+
+    fn insert_char(&self, row: usize, col: usize, ch: char) {
+        if has_styled_underline(&self.underlines.borrow(), row) {
+            self.redraw_row(row);
+        }
+        self.underlines.borrow_mut().insert_char(
+            row,
+            col,
+            ch,
+            Style::default(),
+            self.ambiguous_width,
+        );
+        self.window.mvinsch(row as i32, col as i32, ch);
+    }
+
+    fn delete_char(&self, row: usize, col: usize) {
+        if has_styled_underline(&self.underlines.borrow(), row) {
+            self.redraw_row(row);
+        }
+        self.underlines.borrow_mut().delete_char(row, col);
+        if self.window.mv(row as i32, col as i32) != curses::ERR {
+            self.window.delch();
+        }
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        let (row, col) = self.window.get_cur_yx();
+        (row as usize, col as usize)
+    }
+
+    fn move_cursor(&self, row: usize, col: usize) {
+        self.window.mv(row as i32, col as i32);
+    }
+
+    fn draw_graphic(&self, row: usize, col: usize, payload: Rc<str>) {
+        self.graphics.borrow_mut().push((row, col, payload));
+    }
+
+    fn set_ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        self.ambiguous_width = ambiguous;
+    }
+
+    fn present(&self, cursor: CursorStyle) {
+        if self.suspended {
+            return;
+        }
+        if self.soft_labels.is_some() {
+            unsafe { ffi::slk_noutrefresh() };
+        }
+        self.apply_cursor(cursor);
+        self.window.refresh();
+
+        if let Some(ref inline) = self.inline {
+            inline.paint(&self.window, self.cursor_applied.get().position());
+        }
+        let rows = mem::take(&mut *self.underline_rows.borrow_mut());
+        if !rows.is_empty() {
+            self.draw_underlines(rows);
+        }
+        let graphics = self.graphics.replace(Vec::new());
+        if !graphics.is_empty() && !self.headless && self.inline.is_none() {
+            // Graphics are drawn over the cells curses left blank, keeping
+            // the cursor where curses expects it.
+            for (row, col, payload) in graphics {
+                print!("\x1B7\x1B[{};{}H{}\x1B8", row + 1, col + 1, payload);
+            }
+            io::stdout().flush().expect("could not flush stdout");
+        }
+        if let Some(ref last) = self.transcript {
+            let frame = screen::transcript(&self.window);
+            if *last.borrow() != frame {
+                println!("{}", frame);
+                io::stdout().flush().expect("could not flush stdout");
+                *last.borrow_mut() = frame;
+            }
+        }
+    }
+
+    fn read_event(&mut self) -> Option<Event> {
+        // Reading would take the terminal back from whatever is running.
+        if self.suspended {
+            return None;
+        }
+        if let Some(ev) = self.pending.pop_front() {
+            return Some(ev);
+        }
+
+        // Inputs within a paste give no events until the paste ends.
+        let mut events = loop {
+            let input = match self.input.getch() {
+                Some(input) => input,
+                // A partial sequence with nothing following is taken as it is.
+                None => {
+                    let events = self.decoder.flush();
+                    if events.is_empty() {
+                        return None;
+                    }
+                    break events.into_iter();
+                }
+            };
+            let events = self.decoder.decode_input(input);
+            if !events.is_empty() {
+                break events.into_iter();
+            }
+        };
+        let ev = events.next()?;
+        self.pending.extend(events);
+
+        if ev == Event::Resize {
+            match self.inline {
+                Some(ref inline) => inline.resize(),
+                None => {
+                    curses::resize_term(0, 0);
+                }
+            }
+            if let Some(size) = caps::cell_size() {
+                self.capabilities.cell_size = Some(size);
+                if self.pixel_mouse {
+                    self.decoder.set_pixel_mouse(Some(size));
+                }
+            }
+        }
+        Some(ev)
+    }
+
+    fn suspend(&mut self) -> Result<()> {
+        if self.suspended || self.shut_down {
+            return Ok(());
+        }
+        self.suspended = true;
+
+        self.leave_modes();
+        if let Err(err) = io::stdout().flush() {
+            return Err(Error::Io("flushing stdout", err.kind()));
+        }
+        if curses::endwin() == curses::ERR && !self.headless && self.inline.is_none() {
+            return Err(Error::Curses("endwin"));
+        }
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        if !self.suspended || self.shut_down {
+            return Ok(());
+        }
+        self.suspended = false;
+
+        // Whatever ran in the meantime drew over the screen, so it is
+        // redrawn in full.
+        let cursor = self.cursor_applied.get();
+        self.window.clearok(true);
+        self.window.refresh();
+        self.enter_modes();
+        self.apply_cursor(cursor);
+        self.window.refresh();
+        let (rows, _) = self.underlines.borrow().size();
+        self.draw_underlines((0..rows).collect());
+        if let Err(err) = io::stdout().flush() {
+            return Err(Error::Io("flushing stdout", err.kind()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for CursesBackend {
+    fn drop(&mut self) {
+        if let Err(err) = self.restore_terminal() {
+            error!("could not restore terminal: {}", err);
+        }
+    }
+}
+
+/// Checks whether a row of the buffer has any styled underlines.
+fn has_styled_underline(buffer: &Buffer, row: usize) -> bool {
+    let (_, cols) = buffer.size();
+    (0..cols).any(|col| {
+        buffer
+            .get(row, col)
+            .is_some_and(|cell| cell.style.has_styled_underline())
+    })
+}
+
+/// Gets the sequence setting (`h`) or resetting (`l`) the mouse tracking
+/// mode, reporting drags or all movement.
+fn mouse_mode(motion: bool, set: char) -> String {
+    format!("\x1B[?{}{}", if motion { 1003 } else { 1002 }, set)
+}
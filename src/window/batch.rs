@@ -0,0 +1,140 @@
+use std::io::{self, Write};
+use std::mem;
+use std::thread;
+
+use backend::Backend;
+use curses;
+
+use super::Window;
+
+/// What a batch of updates held back, done once the outermost batch ends.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct BatchState {
+    depth: usize,
+    flush: bool,
+    refresh: bool,
+    bell: bool,
+    flash: bool,
+}
+
+/// Ends a batch when dropped, so a panicking closure doesn't leave the window
+/// holding everything back.
+struct End<'a, B: Backend + 'a>(&'a Window<B>);
+
+impl<'a, B: Backend> Drop for End<'a, B> {
+    fn drop(&mut self) {
+        let win = self.0;
+        let mut state = win.batch.get();
+        state.depth -= 1;
+        if state.depth > 0 {
+            win.batch.set(state);
+            return;
+        }
+        win.batch.set(BatchState::default());
+        let escapes = mem::take(&mut *win.held_escapes.borrow_mut());
+        if thread::panicking() {
+            return;
+        }
+
+        // Escapes go first, so redefined colors show in the frame drawn.
+        if !escapes.is_empty() {
+            print!("{}", escapes);
+            io::stdout().flush().expect("could not flush stdout");
+        }
+        if state.refresh {
+            win.refresh();
+        } else if state.flush {
+            win.flush();
+        }
+        // Only curses windows ring the bell, so only they hold it back.
+        if state.bell {
+            curses::beep();
+        }
+        if state.flash {
+            curses::flash();
+        }
+    }
+}
+
+impl<B: Backend> Window<B> {
+    /// Runs a closure updating the window, holding back what reaches the
+    /// terminal until it returns, so several widgets updating together never
+    /// show half done.
+    ///
+    /// ```no_run
+    /// # use termui::layout::Rect;
+    /// # use termui::widget::{List, TextView, Widget};
+    /// # use termui::Window;
+    /// # let win = Window::new();
+    /// # let (mut list, mut preview) = (List::new(vec!["a"]), TextView::new("a"));
+    /// # let (left, right) = (Rect::new(0, 0, 20, 10), Rect::new(20, 0, 40, 10));
+    /// win.batch(|win| {
+    ///     list.render(left, win);
+    ///     win.refresh();
+    ///     preview.render(right, win);
+    ///     win.refresh();
+    /// });
+    /// // Both widgets show in a single frame.
+    /// ```
+    ///
+    /// Flushes, refreshes, [pane](struct.Pane.html#method.refresh) refreshes,
+    /// bells and flashes made in the closure are held back, and done once
+    /// when the outermost batch ends, with the cursor last requested. So are
+    /// [title](#method.set_title) changes and colors
+    /// [redefined](#method.define_color) through escape sequences, written
+    /// in order before the refresh.
+    ///
+    /// *If the closure panics, nothing held back is done.*
+    pub fn batch<T, F: FnOnce(&Self) -> T>(&self, f: F) -> T {
+        let mut state = self.batch.get();
+        state.depth += 1;
+        self.batch.set(state);
+        let _end = End(self);
+        f(self)
+    }
+
+    /// Checks whether a batch is holding updates back.
+    pub fn is_batching(&self) -> bool {
+        self.batch.get().depth > 0
+    }
+
+    /// Holds back a flush, or a refresh if `present` is set, giving whether
+    /// it was held back.
+    pub(crate) fn defer_refresh(&self, present: bool) -> bool {
+        let mut state = self.batch.get();
+        if state.depth == 0 {
+            return false;
+        }
+        state.flush = true;
+        state.refresh |= present;
+        self.batch.set(state);
+        true
+    }
+
+    /// Writes an escape sequence straight to the terminal, or holds it back
+    /// until the batch ends.
+    pub(crate) fn write_escape(&self, escape: &str) {
+        if self.is_batching() {
+            self.held_escapes.borrow_mut().push_str(escape);
+        } else {
+            print!("{}", escape);
+            io::stdout().flush().expect("could not flush stdout");
+        }
+    }
+
+    /// Holds back a bell, or a flash if `flash` is set, giving whether it was
+    /// held back.
+    pub(crate) fn defer_alert(&self, flash: bool) -> bool {
+        let mut state = self.batch.get();
+        if state.depth == 0 {
+            return false;
+        }
+        if flash {
+            state.flash = true;
+        } else {
+            state.bell = true;
+        }
+        self.batch.set(state);
+        true
+    }
+}
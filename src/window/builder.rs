@@ -0,0 +1,283 @@
+use std::time::Duration;
+
+use caps::{ColorChoice, Profile, Quirks};
+use error::Result;
+use event::Normalize;
+
+use super::repeat::AutoRepeat;
+use super::reserved::MAX_RESERVED_LINES;
+use super::soft_labels::SoftLabelFormat;
+use super::Window;
+
+/// A builder for configuring a window before the terminal is initialized.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    pub(super) soft_labels: Option<SoftLabelFormat>,
+    pub(super) reserve_top: usize,
+    pub(super) reserve_bottom: usize,
+    pub(super) coalesce_mouse: bool,
+    pub(super) auto_repeat: Option<AutoRepeat>,
+    pub(super) normalize: Normalize,
+    pub(super) probe_timeout: Option<Duration>,
+    pub(super) profile: Option<Profile>,
+    pub(super) color_choice: ColorChoice,
+    pub(super) transcript: bool,
+    pub(super) quit_signals: bool,
+    pub(super) suspend_signal: bool,
+    pub(super) inline: Option<usize>,
+    pub(super) quirks: Quirks,
+    pub(super) mouse: bool,
+    pub(super) pixel_mouse: bool,
+    pub(super) mouse_motion: bool,
+    pub(super) kitty_keyboard: bool,
+    pub(super) monochrome: Option<bool>,
+    pub(super) ascii_mode: Option<bool>,
+}
+
+impl Builder {
+    /// Creates a new builder with the default configuration.
+    pub fn new() -> Builder {
+        Builder {
+            soft_labels: None,
+            reserve_top: 0,
+            reserve_bottom: 0,
+            coalesce_mouse: true,
+            auto_repeat: None,
+            normalize: Normalize::None,
+            probe_timeout: None,
+            profile: None,
+            color_choice: ColorChoice::detect(),
+            transcript: false,
+            quit_signals: false,
+            suspend_signal: false,
+            inline: None,
+            quirks: Quirks::builtin(),
+            mouse: true,
+            pixel_mouse: false,
+            mouse_motion: false,
+            kitty_keyboard: false,
+            monochrome: None,
+            ascii_mode: None,
+        }
+    }
+
+    /// Enables the soft label key row with the given layout.
+    pub fn soft_labels(mut self, format: SoftLabelFormat) -> Builder {
+        self.soft_labels = Some(format);
+        self
+    }
+
+    /// Reserves lines at the top of the screen, outside of the main drawing
+    /// area.
+    ///
+    /// *Curses allows at most 5 reserved lines in total, any extra lines are
+    /// ignored.*
+    pub fn reserve_top(mut self, lines: usize) -> Builder {
+        self.reserve_top = lines.min(MAX_RESERVED_LINES - self.reserve_bottom);
+        self
+    }
+
+    /// Reserves lines at the bottom of the screen, outside of the main drawing
+    /// area.
+    ///
+    /// *Curses allows at most 5 reserved lines in total, any extra lines are
+    /// ignored.*
+    pub fn reserve_bottom(mut self, lines: usize) -> Builder {
+        self.reserve_bottom = lines.min(MAX_RESERVED_LINES - self.reserve_top);
+        self
+    }
+
+    /// Sets whether bursts of pending mouse drag and move events are coalesced
+    /// into the latest position, defaults to `true`.
+    pub fn coalesce_mouse(mut self, coalesce: bool) -> Builder {
+        self.coalesce_mouse = coalesce;
+        self
+    }
+
+    /// Enables software auto-repeat for navigation keys, repeating held keys
+    /// after a delay at a steady rate.
+    pub fn auto_repeat(mut self, config: AutoRepeat) -> Builder {
+        self.auto_repeat = Some(config);
+        self
+    }
+
+    /// Sets the key aliases merged before events are delivered, defaults to
+    /// [`Normalize::None`](../event/struct.Normalize.html).
+    ///
+    /// *See [`Event::normalize`](../event/enum.Event.html#method.normalize).*
+    pub fn normalize_keys(mut self, policy: Normalize) -> Builder {
+        self.normalize = policy;
+        self
+    }
+
+    /// Probes the terminal for its capabilities at startup, waiting at most
+    /// `timeout` for the answers.
+    ///
+    /// Sends XTGETTCAP, primary device attributes (DA1) and cursor position
+    /// (DSR) queries, the answers are removed from the input stream before
+    /// events are delivered.
+    pub fn probe_capabilities(mut self, timeout: Duration) -> Builder {
+        self.probe_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the degradation profile, selecting which optional terminal
+    /// features may be used.
+    ///
+    /// Quirks can't turn on features a profile set this way rules out.
+    ///
+    /// *Defaults to the profile detected from `TERM`.*
+    pub fn profile(mut self, profile: Profile) -> Builder {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Sets whether colors are used, overriding the choice the user made
+    /// through the environment.
+    ///
+    /// *Defaults to the choice detected from `NO_COLOR`, `CLICOLOR` and
+    /// `CLICOLOR_FORCE`, see
+    /// [`ColorChoice::detect`](../caps/enum.ColorChoice.html#method.detect).*
+    pub fn color_choice(mut self, color_choice: ColorChoice) -> Builder {
+        self.color_choice = color_choice;
+        self
+    }
+
+    /// Sets whether colors are drawn as attributes instead, see
+    /// [`Window::set_monochrome`](struct.Window.html#method.set_monochrome).
+    ///
+    /// *Defaults to `true` on terminals without colors.*
+    pub fn monochrome(mut self, monochrome: bool) -> Builder {
+        self.monochrome = Some(monochrome);
+        self
+    }
+
+    /// Sets whether ASCII mode is on, see
+    /// [`Window::set_ascii_mode`](struct.Window.html#method.set_ascii_mode).
+    ///
+    /// *Defaults to `true` when the locale isn't UTF-8.*
+    pub fn ascii_mode(mut self, ascii: bool) -> Builder {
+        self.ascii_mode = Some(ascii);
+        self
+    }
+
+    /// Sets whether mouse input is used when the terminal supports it,
+    /// defaults to `true`.
+    pub fn mouse(mut self, mouse: bool) -> Builder {
+        self.mouse = mouse;
+        self
+    }
+
+    /// Sets whether mouse positions are reported in pixels (mode 1016), as
+    /// well as cells, defaults to `false`.
+    ///
+    /// *Only enabled when the size of a cell in pixels is known, see
+    /// [`Window::cell_size`](struct.Window.html#method.cell_size).*
+    pub fn pixel_mouse(mut self, pixel_mouse: bool) -> Builder {
+        self.pixel_mouse = pixel_mouse;
+        self
+    }
+
+    /// Sets whether mouse movement is reported with no button held (mode
+    /// 1003), as [`MouseEvent::Moved`](../event/enum.MouseEvent.html#variant.Moved),
+    /// defaults to `false`.
+    ///
+    /// *Terminals report every cell the mouse crosses, so only enable it for
+    /// hover effects.*
+    pub fn mouse_motion(mut self, mouse_motion: bool) -> Builder {
+        self.mouse_motion = mouse_motion;
+        self
+    }
+
+    /// Sets whether the progressive keyboard protocol of kitty is used, where
+    /// the terminal supports it, defaults to `false`.
+    ///
+    /// Keys are then reported unambiguously, so Ctrl+I is told apart from Tab
+    /// and Escape from the start of a sequence, and key repeats and releases
+    /// are reported through
+    /// [`KeyEventKind`](../event/enum.KeyEventKind.html).
+    ///
+    /// *Support is negotiated when
+    /// [probing capabilities](#method.probe_capabilities), otherwise the
+    /// protocol is requested blindly, which other terminals ignore.*
+    pub fn kitty_keyboard(mut self, kitty_keyboard: bool) -> Builder {
+        self.kitty_keyboard = kitty_keyboard;
+        self
+    }
+
+    /// Adds terminal quirks, applied after the ones shipped with the crate.
+    pub fn quirks(mut self, quirks: Quirks) -> Builder {
+        self.quirks.extend(quirks);
+        self
+    }
+
+    /// Sets whether a headless window streams a plain-text transcript of each
+    /// changed frame to stdout, defaults to `false`.
+    pub fn transcript(mut self, transcript: bool) -> Builder {
+        self.transcript = transcript;
+        self
+    }
+
+    /// Sets whether `SIGINT`, `SIGTERM` and `SIGHUP` are delivered as
+    /// [`Event::Quit`](../event/enum.Event.html#variant.Quit) instead of
+    /// killing the process, defaults to `false`.
+    ///
+    /// *The previous signal handlers are restored when the window is dropped.*
+    pub fn quit_signals(mut self, quit_signals: bool) -> Builder {
+        self.quit_signals = quit_signals;
+        self
+    }
+
+    /// Sets whether `Ctrl-Z` (`SIGTSTP`) suspends the window cleanly, defaults
+    /// to `false`.
+    ///
+    /// The window is [suspended](struct.Window.html#method.suspend) and the
+    /// process stopped while polling for events, and once continued the
+    /// window is resumed and an
+    /// [`Event::Refresh`](../event/enum.Event.html#variant.Refresh) is
+    /// delivered.
+    ///
+    /// *Curses suspends on its own otherwise, but leaves the mouse and paste
+    /// modes behind for the shell.*
+    pub fn suspend_signal(mut self, suspend_signal: bool) -> Builder {
+        self.suspend_signal = suspend_signal;
+        self
+    }
+
+    /// Draws the window inline in the bottom rows of the normal screen buffer,
+    /// instead of taking over the whole terminal.
+    ///
+    /// The existing content of the terminal is scrolled up to make room, and
+    /// the rows are cleared again when the window is dropped.
+    ///
+    /// *Only text and the bold, underline and reverse attributes are drawn
+    /// inline, and mouse input is not supported.*
+    pub fn inline(mut self, rows: usize) -> Builder {
+        self.inline = Some(rows);
+        self
+    }
+
+    /// Initializes the terminal and creates the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if curses could not be initialized or another window is already
+    /// active, see [`try_build`](#method.try_build).
+    pub fn build(self) -> Window {
+        self.try_build().expect("could not initialize terminal")
+    }
+
+    /// Initializes the terminal and creates the window, or gives an error if
+    /// curses could not be initialized.
+    ///
+    /// *Falls back to a headless window when not attached to a terminal.*
+    pub fn try_build(self) -> Result<Window> {
+        Window::with_builder(self)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
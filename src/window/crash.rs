@@ -0,0 +1,149 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use backend::Buffer;
+use caps::Capabilities;
+use event::{Event, Recorder};
+
+/// What the panic hook writes into a crash report, kept up to date by the
+/// window that set it up.
+static REPORT: Mutex<Option<CrashReport>> = Mutex::new(None);
+
+/// The state of the application kept for a crash report.
+#[derive(Debug)]
+struct CrashReport {
+    id: usize,
+    path: PathBuf,
+    capacity: usize,
+    events: VecDeque<(Instant, Event)>,
+    capabilities: Capabilities,
+    size: (usize, usize),
+    screen: Buffer,
+}
+
+/// The number of crash reports started, identifying the one kept.
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+/// A guard marking the window keeping the crash report, which stops keeping
+/// it on drop unless another has been started since.
+#[derive(Debug)]
+pub(crate) struct Guard(usize);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let mut report = lock();
+        if report.as_ref().map(|report| report.id) == Some(self.0) {
+            *report = None;
+        }
+    }
+}
+
+fn lock() -> MutexGuard<'static, Option<CrashReport>> {
+    REPORT.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Starts keeping a crash report written to the path, with up to the given
+/// number of events.
+pub(crate) fn start(path: PathBuf, capacity: usize, capabilities: Capabilities) -> Guard {
+    let id = STARTED.fetch_add(1, Ordering::SeqCst);
+    *lock() = Some(CrashReport {
+        id,
+        path,
+        capacity,
+        events: VecDeque::with_capacity(capacity),
+        capabilities,
+        size: (0, 0),
+        screen: Buffer::new(0, 0),
+    });
+    Guard(id)
+}
+
+/// Keeps an event given to the application.
+pub(crate) fn record_event(event: &Event) {
+    if let Some(ref mut report) = *lock() {
+        if report.capacity == 0 {
+            return;
+        }
+        if report.events.len() == report.capacity {
+            report.events.pop_front();
+        }
+        report.events.push_back((Instant::now(), event.clone()));
+    }
+}
+
+/// Keeps the frame last presented, with the size of the terminal.
+pub(crate) fn record_frame(size: (usize, usize), front: &Buffer) {
+    if let Some(ref mut report) = *lock() {
+        report.size = size;
+        report.screen.clone_from(front);
+    }
+}
+
+/// Writes the crash report, if one is kept, from the panic hook.
+///
+/// *Gives up rather than waiting if the panic happened while the report was
+/// being updated.*
+pub(crate) fn write_for_panic(info: &dyn Display) {
+    let guard = match REPORT.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::Poisoned(err)) => err.into_inner(),
+        Err(TryLockError::WouldBlock) => return,
+    };
+    if let Some(ref report) = *guard {
+        match write(report, info) {
+            Ok(()) => eprintln!("crash report written to {}", report.path.display()),
+            Err(err) => eprintln!(
+                "could not write crash report to {}: {}",
+                report.path.display(),
+                err
+            ),
+        }
+    }
+}
+
+fn write(report: &CrashReport, info: &dyn Display) -> io::Result<()> {
+    let mut file = File::create(&report.path)?;
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    writeln!(file, "termui crash report")?;
+    writeln!(file, "version: {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(file, "time: {} seconds since the epoch", time)?;
+    writeln!(
+        file,
+        "TERM: {}",
+        ::std::env::var("TERM").unwrap_or_default()
+    )?;
+    writeln!(file)?;
+    writeln!(file, "== panic ==")?;
+    writeln!(file, "{}", info)?;
+    writeln!(file)?;
+    writeln!(file, "== size ==")?;
+    writeln!(file, "{} rows, {} columns", report.size.0, report.size.1)?;
+    writeln!(file)?;
+    writeln!(file, "== capabilities ==")?;
+    writeln!(file, "{:#?}", report.capabilities)?;
+    writeln!(file)?;
+    writeln!(file, "== screen ==")?;
+    let (rows, _) = report.screen.size();
+    for row in 0..rows {
+        writeln!(file, "{}", report.screen.line(row))?;
+    }
+    writeln!(file)?;
+    // Last, so everything from its header on plays back as a recording.
+    writeln!(file, "== events ==")?;
+    file.flush()?;
+    let mut recorder = Recorder::new(file)?;
+    if let Some(&(first, _)) = report.events.front() {
+        for &(at, ref event) in &report.events {
+            recorder.record_at(event, at - first)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+/// Represents the cursor requested for a frame.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum CursorStyle {
+    /// No visible cursor.
+    #[default]
+    Hidden,
+    /// A block cursor at the given row and column.
+    Block(usize, usize),
+    /// A vertical bar cursor at the given row and column.
+    Bar(usize, usize),
+    /// An underline cursor at the given row and column.
+    Underline(usize, usize),
+    /// The terminal's usual cursor at the given row and column.
+    Normal(usize, usize),
+}
+
+/// Represents how the cursor is shown when no cursor is requested for a frame.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum CursorVisibility {
+    /// No visible cursor.
+    #[default]
+    Hidden,
+    /// The terminal's usual cursor.
+    Normal,
+    /// A block cursor.
+    Block,
+}
+
+impl CursorVisibility {
+    /// Gets the cursor shown at the given row and column.
+    pub(crate) fn at(self, row: usize, col: usize) -> CursorStyle {
+        match self {
+            CursorVisibility::Hidden => CursorStyle::Hidden,
+            CursorVisibility::Normal => CursorStyle::Normal(row, col),
+            CursorVisibility::Block => CursorStyle::Block(row, col),
+        }
+    }
+}
+
+impl CursorStyle {
+    /// Gets the position of the cursor, if it is visible.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match *self {
+            CursorStyle::Hidden => None,
+            CursorStyle::Block(row, col)
+            | CursorStyle::Bar(row, col)
+            | CursorStyle::Underline(row, col)
+            | CursorStyle::Normal(row, col) => Some((row, col)),
+        }
+    }
+
+    /// Gets the DECSCUSR shape code of the cursor, if it is visible.
+    pub(crate) fn shape_code(&self) -> Option<u8> {
+        match *self {
+            CursorStyle::Hidden => None,
+            CursorStyle::Normal(..) => Some(0),
+            CursorStyle::Block(..) => Some(2),
+            CursorStyle::Underline(..) => Some(4),
+            CursorStyle::Bar(..) => Some(6),
+        }
+    }
+}
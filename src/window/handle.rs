@@ -0,0 +1,146 @@
+use std::any::Any;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "async")]
+use std::task::Waker;
+
+use event::{Event, UserEvent};
+
+use super::wakeup::Wakeup;
+
+/// State shared between a window and its handles.
+#[derive(Debug, Default)]
+pub(crate) struct Shared {
+    /// Events sent from other threads, not yet moved into the window's queue.
+    pub(crate) events: Vec<Event>,
+    /// Whether a redraw has been requested since the last one was delivered.
+    pub(crate) redraw: bool,
+    /// The size of the window in rows and columns, as of the last event poll.
+    pub(crate) size: (usize, usize),
+    /// The task waiting on the window's event stream.
+    #[cfg(feature = "async")]
+    pub(crate) waker: Option<Waker>,
+    /// Wakes the window while it waits for events, once it has waited.
+    pub(crate) wakeup: Option<Arc<Wakeup>>,
+}
+
+/// A thread-safe handle to a [`Window`](struct.Window.html).
+///
+/// The window itself must stay on the thread that created it, since curses is
+/// not thread-safe. A handle can be sent to worker threads to feed events back
+/// to the UI; they are delivered by [`poll_event`](struct.Window.html#method.poll_event)
+/// on the owning thread.
+///
+/// *Handles stay valid after the window is dropped, but have no effect.*
+#[derive(Clone, Debug)]
+pub struct WindowHandle {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl WindowHandle {
+    pub(crate) fn new(shared: Arc<Mutex<Shared>>) -> WindowHandle {
+        WindowHandle { shared }
+    }
+
+    /// Sends an event to the window's event queue.
+    pub fn push_event(&self, event: Event) {
+        let mut shared = lock(&self.shared);
+        shared.events.push(event);
+        shared.wake();
+    }
+
+    /// Requests the window be redrawn, delivered as an
+    /// [`Event::Refresh`](../event/enum.Event.html#variant.Refresh).
+    ///
+    /// *Requests made before the last one was delivered are merged.*
+    pub fn request_redraw(&self) {
+        let mut shared = lock(&self.shared);
+        shared.redraw = true;
+        shared.wake();
+    }
+
+    /// Gets the size of the window in rows and columns.
+    ///
+    /// *The size is updated whenever the window polls for events.*
+    pub fn get_size(&self) -> (usize, usize) {
+        lock(&self.shared).size
+    }
+}
+
+/// Sends events to a [`Window`](struct.Window.html) from other threads, such
+/// as workers waking the interface once they have something to show.
+///
+/// Events sent are delivered by
+/// [`poll_event`](struct.Window.html#method.poll_event) on the window's
+/// thread, and wake it from [`wait_event`](struct.Window.html#method.wait_event).
+///
+/// ```no_run
+/// use std::thread;
+///
+/// use termui::Window;
+///
+/// let mut win = Window::new();
+/// let sender = win.event_sender();
+/// thread::spawn(move || {
+///     let lines = 42usize;
+///     sender.send_user(lines);
+/// });
+/// ```
+///
+/// *Senders are cheap to clone, and stay valid after the window is dropped,
+/// but have no effect.*
+#[derive(Clone, Debug)]
+pub struct EventSender {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl EventSender {
+    pub(crate) fn new(shared: Arc<Mutex<Shared>>) -> EventSender {
+        EventSender { shared }
+    }
+
+    /// Sends an event to the window.
+    pub fn send(&self, event: Event) {
+        let mut shared = lock(&self.shared);
+        shared.events.push(event);
+        shared.wake();
+    }
+
+    /// Sends a message of the application to the window, as an
+    /// [`Event::User`](../event/enum.Event.html#variant.User).
+    pub fn send_user<T: Any + Send + Sync>(&self, message: T) {
+        self.send(Event::User(UserEvent::new(message)));
+    }
+}
+
+/// Locks the shared state, recovering it if another thread panicked while
+/// holding the lock.
+pub(crate) fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl Shared {
+    /// Takes the events sent from other threads, including any requested
+    /// redraw.
+    pub(crate) fn take_events(&mut self) -> Vec<Event> {
+        let mut events = mem::take(&mut self.events);
+        if mem::take(&mut self.redraw) {
+            events.push(Event::Refresh);
+        }
+        events
+    }
+
+    /// Wakes the task waiting on the event stream, or the window waiting for
+    /// events, if any.
+    fn wake(&mut self) {
+        #[cfg(feature = "async")]
+        {
+            if let Some(waker) = self.waker.take() {
+                waker.wake();
+            }
+        }
+        if let Some(ref wakeup) = self.wakeup {
+            wakeup.wake();
+        }
+    }
+}
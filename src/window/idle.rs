@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+use event::Event;
+
+/// Tracks how long the window has been without input.
+#[derive(Debug)]
+pub(crate) struct IdleState {
+    last_input: Instant,
+    timeout: Option<Duration>,
+    fps: Option<u32>,
+    notified: bool,
+}
+
+impl IdleState {
+    pub(crate) fn new() -> IdleState {
+        IdleState {
+            last_input: Instant::now(),
+            timeout: None,
+            fps: None,
+            notified: false,
+        }
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+        self.notified = false;
+    }
+
+    pub(crate) fn fps(&self) -> Option<u32> {
+        self.fps
+    }
+
+    pub(crate) fn set_fps(&mut self, fps: Option<u32>) {
+        self.fps = fps;
+    }
+
+    /// Gets the time since the last input.
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.last_input.elapsed()
+    }
+
+    /// Checks whether the timeout has passed since the last input.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.timeout
+            .is_some_and(|timeout| self.elapsed() >= timeout)
+    }
+
+    /// Notes an event given, any input ending the idle time.
+    pub(crate) fn process(&mut self, event: &Event) {
+        match *event {
+            Event::Key { .. } | Event::Mouse { .. } | Event::Paste(_) => {
+                self.last_input = Instant::now();
+                self.notified = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Gives the idle event, once each time the window becomes idle.
+    pub(crate) fn poll(&mut self) -> Option<Event> {
+        if self.notified || !self.is_idle() {
+            return None;
+        }
+        self.notified = true;
+        Some(Event::Idle(self.elapsed()))
+    }
+}
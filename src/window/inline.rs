@@ -0,0 +1,221 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Write as FmtWrite;
+use std::io::{self, Write};
+
+use curses;
+use error::{Error, Result};
+
+/// The state of a window drawn inline, in the bottom rows of the normal
+/// screen buffer.
+///
+/// Curses draws into a virtual screen on `/dev/null`, which is copied to the
+/// terminal on each refresh. Only text and the bold, underline and reverse
+/// attributes are rendered.
+pub(crate) struct Inline {
+    rows: usize,
+    /// The row of the terminal cursor, relative to the top of the region.
+    cursor_row: Cell<usize>,
+    /// The last frame written, to skip unchanged frames.
+    last: RefCell<String>,
+    #[cfg(unix)]
+    termios: ::libc::termios,
+}
+
+#[cfg(unix)]
+impl Inline {
+    /// Initializes curses for drawing inline with the given number of rows,
+    /// scrolling the existing content of the terminal up to make room.
+    pub(crate) fn init(rows: usize) -> Result<(curses::Window, Inline)> {
+        use libc;
+
+        let rows = rows.max(1);
+
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 {
+            return Err(Error::Unsupported("inline mode"));
+        }
+        let mut raw = termios;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) };
+
+        let screen = unsafe {
+            let output = libc::fopen(
+                b"/dev/null\0".as_ptr() as *const _,
+                b"w\0".as_ptr() as *const _,
+            );
+            let input = libc::fdopen(libc::STDIN_FILENO, b"r\0".as_ptr() as *const _);
+            if output.is_null() || input.is_null() {
+                None
+            } else {
+                Some(curses::newterm(None, output, input))
+            }
+        };
+        match screen {
+            Some(screen) if !screen.is_null() => {}
+            _ => {
+                unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) };
+                return Err(Error::Curses("newterm"));
+            }
+        }
+
+        let inline = Inline {
+            rows,
+            cursor_row: Cell::new(0),
+            last: RefCell::new(String::new()),
+            termios,
+        };
+        inline.resize();
+
+        // Scroll the existing content up, leaving the cursor at the top of the
+        // region.
+        let mut out = "\n".repeat(rows - 1);
+        if rows > 1 {
+            let _ = write!(out, "\x1B[{}A", rows - 1);
+        }
+        out.push('\r');
+        print!("{}", out);
+        io::stdout().flush().expect("could not flush stdout");
+
+        Ok((curses::newwin(0, 0, 0, 0), inline))
+    }
+
+    /// Resizes the curses screen to the region, taking the width of the
+    /// terminal.
+    pub(crate) fn resize(&self) {
+        use libc;
+
+        let mut size = unsafe { std::mem::zeroed::<libc::winsize>() };
+        let cols = if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0
+            && size.ws_col > 0
+        {
+            i32::from(size.ws_col)
+        } else {
+            80
+        };
+        curses::resize_term(self.rows as i32, cols);
+        self.last.borrow_mut().clear();
+    }
+
+    /// Restores the terminal, clearing the region.
+    pub(crate) fn finish(&self) {
+        use libc;
+
+        print!("{}\x1B[J\x1B[?25h", self.to_top());
+        io::stdout().flush().expect("could not flush stdout");
+
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.termios) };
+    }
+}
+
+#[cfg(not(unix))]
+impl Inline {
+    /// Initializes curses for drawing inline with the given number of rows.
+    ///
+    /// *Inline mode is only supported on unix.*
+    pub(crate) fn init(_rows: usize) -> Result<(curses::Window, Inline)> {
+        Err(Error::Unsupported("inline mode"))
+    }
+
+    /// Resizes the curses screen to the region.
+    pub(crate) fn resize(&self) {}
+
+    /// Restores the terminal, clearing the region.
+    pub(crate) fn finish(&self) {}
+}
+
+impl Inline {
+    /// Copies the window to the region, placing the cursor at the given
+    /// position if any.
+    pub(crate) fn paint(&self, window: &curses::Window, cursor: Option<(usize, usize)>) {
+        let (rows, cols) = window.get_max_yx();
+
+        let mut frame = String::new();
+        for row in 0..rows {
+            if row > 0 {
+                frame.push_str("\r\n");
+            }
+            render_row(window, row, cols, &mut frame);
+            frame.push_str("\x1B[0m\x1B[K");
+        }
+
+        let cursor = cursor.filter(|&(row, col)| row < self.rows && col < cols as usize);
+        if *self.last.borrow() == frame && cursor.is_none() {
+            return;
+        }
+
+        let mut out = format!("\x1B[?25l{}{}", self.to_top(), frame);
+        let bottom = rows.max(1) as usize - 1;
+        match cursor {
+            Some((row, col)) => {
+                if row < bottom {
+                    let _ = write!(out, "\x1B[{}A", bottom - row);
+                }
+                out.push('\r');
+                if col > 0 {
+                    let _ = write!(out, "\x1B[{}C", col);
+                }
+                out.push_str("\x1B[?25h");
+                self.cursor_row.set(row);
+            }
+            None => self.cursor_row.set(bottom),
+        }
+
+        print!("{}", out);
+        io::stdout().flush().expect("could not flush stdout");
+        *self.last.borrow_mut() = frame;
+    }
+
+    /// Gives the escapes moving the cursor to the top left of the region.
+    fn to_top(&self) -> String {
+        match self.cursor_row.replace(0) {
+            0 => "\r".to_owned(),
+            row => format!("\x1B[{}A\r", row),
+        }
+    }
+}
+
+/// Renders a row of the window with its attributes as escapes.
+fn render_row(window: &curses::Window, row: i32, cols: i32, out: &mut String) {
+    let attrs = [
+        (curses::A_BOLD, "1"),
+        (curses::A_UNDERLINE, "4"),
+        (curses::A_REVERSE, "7"),
+    ];
+
+    let mut bytes = Vec::new();
+    let mut current = 0;
+    for col in 0..cols {
+        let ch = window.mvinch(row, col);
+        let attr = attrs.iter().fold(0, |acc, &(flag, _)| acc | (ch & flag));
+
+        if attr != current {
+            out.push_str(&String::from_utf8_lossy(&bytes));
+            bytes.clear();
+
+            out.push_str("\x1B[0");
+            for &(flag, code) in &attrs {
+                if attr & flag != 0 {
+                    out.push(';');
+                    out.push_str(code);
+                }
+            }
+            out.push('m');
+            current = attr;
+        }
+
+        // The character text is in the low byte, multi-byte characters are
+        // spread over several cells.
+        match ch as u8 {
+            0 => bytes.push(b' '),
+            byte => bytes.push(byte),
+        }
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    if current == 0 {
+        out.push_str(text.trim_end());
+    } else {
+        out.push_str(&text);
+    }
+}
@@ -0,0 +1,136 @@
+use std::os::raw::c_int;
+
+use curses::{self, Input};
+use ffi;
+
+/// The highest key code curses defines, codes above are extended keys.
+const KEY_MAX: c_int = 0o777;
+
+/// A hidden curses window used only for reading input.
+///
+/// Input is read through the raw curses functions, so extended key codes,
+/// such as those with modifiers, reach the application instead of being
+/// mangled into characters.
+pub(crate) struct InputWindow {
+    window: ffi::WindowPtr,
+}
+
+impl InputWindow {
+    /// Creates the input window, with the keypad enabled and without blocking.
+    pub(crate) fn new() -> InputWindow {
+        let window = unsafe { ffi::newwin(1, 1, 0, 0) };
+        // Reading from a touched window refreshes it, which would draw over
+        // the top left cell.
+        unsafe { ffi::untouchwin(window) };
+
+        let input = InputWindow { window };
+        input.keypad(true);
+        input.nodelay(true);
+        input
+    }
+
+    /// Sets whether curses translates key sequences into key codes.
+    pub(crate) fn keypad(&self, keypad: bool) {
+        unsafe { ffi::keypad(self.window, keypad) };
+    }
+
+    /// Sets whether reading gives `None` straight away when there is no
+    /// input.
+    pub(crate) fn nodelay(&self, nodelay: bool) {
+        unsafe { ffi::nodelay(self.window, nodelay) };
+    }
+
+    /// Sets how long reading waits for input in milliseconds, negative to
+    /// block.
+    pub(crate) fn timeout(&self, millis: i32) {
+        unsafe { ffi::wtimeout(self.window, millis) };
+    }
+
+    /// Reads the next input.
+    ///
+    /// *Extended key codes are given as `Input::Unknown`.*
+    pub(crate) fn getch(&self) -> Option<Input> {
+        let code = self.read()?;
+        if code > KEY_MAX {
+            return Some(Input::Unknown(code));
+        }
+        if let Some(input) = special_key(code) {
+            return Some(input);
+        }
+
+        // Characters arrive as UTF-8 bytes.
+        let mut bytes = vec![code as u8];
+        loop {
+            match String::from_utf8(bytes.clone()) {
+                Ok(s) => return s.chars().next().map(Input::Character),
+                Err(_) if bytes.len() < 4 => match self.read() {
+                    Some(next) if next <= 0xFF => bytes.push(next as u8),
+                    _ => return Some(Input::Unknown(code)),
+                },
+                Err(_) => return Some(Input::Unknown(code)),
+            }
+        }
+    }
+
+    /// Pushes an input back to be read again.
+    pub(crate) fn ungetch(&self, input: &Input) {
+        match *input {
+            Input::Character(ch) => {
+                let mut buf = [0; 4];
+                for &byte in ch.encode_utf8(&mut buf).as_bytes().iter().rev() {
+                    unsafe { ffi::ungetch(c_int::from(byte)) };
+                }
+            }
+            Input::Unknown(code) => unsafe {
+                ffi::ungetch(code);
+            },
+            ref special => {
+                if let Some(code) = special_code(special) {
+                    unsafe { ffi::ungetch(code) };
+                }
+            }
+        }
+    }
+
+    fn read(&self) -> Option<c_int> {
+        match unsafe { ffi::wgetch(self.window) } {
+            code if code < 0 => None,
+            code => Some(code),
+        }
+    }
+}
+
+impl Drop for InputWindow {
+    fn drop(&mut self) {
+        unsafe { ffi::delwin(self.window) };
+    }
+}
+
+// The table of special keys skips the codes between `KEY_F15` and the keys
+// following the function keys.
+const KEY_GAP: c_int = 48;
+
+fn special_key(code: c_int) -> Option<Input> {
+    let index = if code <= curses::KEY_F15 {
+        code - curses::KEY_OFFSET
+    } else {
+        code - curses::KEY_OFFSET - KEY_GAP
+    };
+    if index < 0 {
+        None
+    } else {
+        curses::SPECIAL_KEY_CODES.get(index as usize).cloned()
+    }
+}
+
+fn special_code(input: &Input) -> Option<c_int> {
+    let index = curses::SPECIAL_KEY_CODES
+        .iter()
+        .position(|special| special == input)? as c_int;
+    let code = index + curses::KEY_OFFSET;
+    if code <= curses::KEY_F15 {
+        Some(code)
+    } else {
+        Some(code + KEY_GAP)
+    }
+}
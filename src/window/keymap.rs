@@ -0,0 +1,46 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use curses;
+use event::Event;
+
+/// Loads key code mappings from a file, see
+/// [`Window::load_keycodes`](../struct.Window.html#method.load_keycodes).
+pub(crate) fn load(path: &Path) -> io::Result<Vec<(i32, Event)>> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut mappings = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_line(line) {
+            Some(mapping) => mappings.push(mapping),
+            None => warn!(
+                "invalid key code mapping at {}:{}: {}",
+                path.display(),
+                number + 1,
+                line
+            ),
+        }
+    }
+    Ok(mappings)
+}
+
+fn parse_line(line: &str) -> Option<(i32, Event)> {
+    let mut parts = line.splitn(2, '=');
+    let code = code_of(parts.next()?.trim())?;
+    let event = Event::parse_key(parts.next()?.trim())?;
+    Some((code, event))
+}
+
+/// Gets the key code of a terminfo key name, such as `kDC5`, or a number.
+fn code_of(name: &str) -> Option<i32> {
+    if let Ok(code) = name.parse() {
+        return Some(code);
+    }
+    (256..1024).find(|&code| curses::keyname(code).as_deref() == Some(name))
+}
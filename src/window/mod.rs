@@ -0,0 +1,1441 @@
+//! Window module.
+
+mod backend;
+mod batch;
+mod builder;
+mod crash;
+mod cursor;
+mod handle;
+mod idle;
+mod inline;
+mod input;
+mod keymap;
+mod pane;
+mod queue;
+mod quit;
+mod repeat;
+mod reserved;
+mod run;
+mod screen;
+mod signal;
+mod sizing;
+mod snapshot;
+mod soft_labels;
+#[cfg(feature = "async")]
+mod stream;
+mod wakeup;
+
+pub use self::backend::CursesBackend;
+pub use self::builder::Builder;
+pub use self::cursor::{CursorStyle, CursorVisibility};
+pub use self::handle::{EventSender, WindowHandle};
+pub use self::pane::Pane;
+pub use self::quit::Unsaved;
+pub use self::repeat::AutoRepeat;
+pub use self::reserved::{Edge, ReservedLine};
+pub use self::run::{ControlFlow, Frame};
+pub use self::snapshot::Snapshot;
+pub use self::soft_labels::{SoftLabelAlign, SoftLabelFormat, SoftLabels};
+#[cfg(feature = "async")]
+pub use self::stream::EventStream;
+
+use self::backend::SavedColor;
+use self::batch::BatchState;
+use self::handle::Shared;
+use self::idle::IdleState;
+use self::input::InputWindow;
+use self::queue::{EventQueue, Priority};
+use self::repeat::RepeatState;
+use self::signal::{QuitSignals, SuspendSignal};
+use self::wakeup::Wakeup;
+
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::VecDeque;
+use std::env;
+use std::io::{self, Read, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use announce::Announcer;
+use backend::{Arena, Backend, Buffer};
+use caps::{Capabilities, Features};
+use curses;
+use draw;
+use error::{Error, Result};
+use event::{
+    Bindings, Event, EventSource, Key, Keymap, Modifier, MouseEvent, Normalize, Recorder,
+    ReplaySource, Resolved,
+};
+use i18n::{self, Encoding, English, Localizer, Message};
+use layout::Rect;
+use style::{Attr, ColorCaps, Rgb, Style};
+use text::{self, AmbiguousWidth};
+use trace::{Phase, Span};
+
+/// The longest the window waits before polling its sources again.
+const WAIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The shortest time between polls while waiting, should stdin stay readable
+/// without giving events.
+const MIN_WAIT: Duration = Duration::from_millis(10);
+
+/// Represents the terminal window.
+///
+/// Drawing goes through a [`Backend`](../backend/trait.Backend.html), which is
+/// curses unless the window was created with
+/// [`with_backend`](#method.with_backend).
+///
+/// *A window must stay on the thread that created it, use
+/// [`handle`](#method.handle) to interact with it from other threads.*
+pub struct Window<B: Backend = CursesBackend> {
+    event_queue: EventQueue,
+    coalesce_mouse: bool,
+    repeat: Option<RepeatState>,
+    normalize: Normalize,
+    cursor_request: Cell<Option<CursorStyle>>,
+    batch: Cell<BatchState>,
+    held_escapes: RefCell<String>,
+    cells_sent: Cell<usize>,
+    /// Counts the times what was on the terminal was lost or moved.
+    viewport: Cell<usize>,
+    unsaved: RefCell<Vec<Weak<quit::State>>>,
+    quit_key: Cell<Option<(Key, Modifier)>>,
+    cursor_visibility: Cell<CursorVisibility>,
+    announcer: RefCell<Option<Announcer>>,
+    localizer: Box<dyn Localizer>,
+    encoding: Encoding,
+    ascii: bool,
+    ambiguous_width: AmbiguousWidth,
+    style: Cell<Style>,
+    monochrome: bool,
+    buffer: RefCell<Buffer>,
+    front: RefCell<Buffer>,
+    arena: RefCell<Arena>,
+    shared: Arc<Mutex<Shared>>,
+    quit_signals: Option<QuitSignals>,
+    suspend_signal: Option<SuspendSignal>,
+    sources: Vec<Box<dyn EventSource>>,
+    recorder: Option<Recorder>,
+    replay: Option<Box<dyn EventSource>>,
+    replayed: VecDeque<Event>,
+    crash_report: Option<crash::Guard>,
+    idle: IdleState,
+    // Dropped last, restoring the terminal.
+    backend: B,
+}
+
+impl Window {
+    /// Creates a new window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if curses could not be initialized or another window is already
+    /// active, see [`try_new`](#method.try_new).
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Window {
+        Builder::new().build()
+    }
+
+    /// Creates a new window, or gives an error if curses could not be
+    /// initialized.
+    ///
+    /// When not attached to a terminal, or when `TERM` is `dumb`, this gives a
+    /// headless window that renders nothing and receives no input, so the
+    /// application logic can still run.
+    pub fn try_new() -> Result<Window> {
+        Builder::new().try_build()
+    }
+
+    /// Creates a builder for configuring a new window.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Checks whether a window is currently active.
+    ///
+    /// Only one window can be active at a time, creating another fails with
+    /// [`Error::AlreadyActive`](../error/enum.Error.html) until it is dropped.
+    pub fn is_active() -> bool {
+        screen::is_active()
+    }
+
+    /// Installs a panic hook restoring the terminal before the panic message
+    /// is printed, which would otherwise be lost on the alternate screen.
+    ///
+    /// Mouse reporting and bracketed paste are turned off, the cursor is
+    /// shown and curses is ended, then the previous hook prints the message.
+    /// A [crash report](#method.set_crash_report) is written afterwards, if
+    /// one is kept.
+    ///
+    /// *Installing the hook more than once has no further effect.*
+    pub fn install_panic_hook() {
+        static INSTALL: Once = Once::new();
+
+        INSTALL.call_once(|| {
+            let previous = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                screen::restore_for_panic();
+                previous(info);
+                crash::write_for_panic(info);
+            }));
+        });
+    }
+
+    fn with_builder(builder: Builder) -> Result<Window> {
+        let instance = screen::Instance::acquire()?;
+
+        env::set_var("ESCDELAY", "25");
+
+        let encoding = i18n::init_locale();
+        let mut backend = CursesBackend::new(&builder, instance)?;
+        backend.line_drawing = !encoding.is_utf8();
+        // Curses gives the bytes of characters outside the locale one by one,
+        // which on a Latin-1 locale are the characters themselves.
+        backend
+            .decoder
+            .set_reassemble_utf8(!encoding.is_utf8() && encoding != Encoding::Latin1);
+        let ambiguous_width = AmbiguousWidth::detect();
+        backend.set_ambiguous_width(ambiguous_width);
+
+        Ok(Window {
+            event_queue: EventQueue::new(),
+            coalesce_mouse: builder.coalesce_mouse,
+            repeat: builder.auto_repeat.map(RepeatState::new),
+            normalize: builder.normalize,
+            cursor_request: Cell::new(None),
+            batch: Cell::default(),
+            held_escapes: RefCell::new(String::new()),
+            cells_sent: Cell::new(0),
+            viewport: Cell::new(0),
+            unsaved: RefCell::new(Vec::new()),
+            quit_key: Cell::new(Some((Key::Char('c'), Modifier::Ctrl))),
+            cursor_visibility: Cell::new(CursorVisibility::Hidden),
+            announcer: RefCell::new(None),
+            localizer: Box::new(English),
+            ascii: builder.ascii_mode.unwrap_or(!encoding.is_utf8()),
+            ambiguous_width,
+            style: Cell::new(Style::default()),
+            monochrome: builder
+                .monochrome
+                .unwrap_or(!backend.capabilities.colors.has_colors),
+            encoding,
+            buffer: RefCell::new(Buffer::new(0, 0)),
+            front: RefCell::new(Buffer::new(0, 0)),
+            arena: RefCell::new(Arena::new()),
+            shared: Arc::default(),
+            quit_signals: if builder.quit_signals {
+                Some(QuitSignals::install())
+            } else {
+                None
+            },
+            suspend_signal: if builder.suspend_signal {
+                Some(SuspendSignal::install())
+            } else {
+                None
+            },
+            sources: Vec::new(),
+            recorder: None,
+            replay: None,
+            replayed: VecDeque::new(),
+            crash_report: None,
+            idle: IdleState::new(),
+            backend,
+        })
+    }
+
+    /// Registers the event given for a curses key code, replacing any
+    /// existing mapping.
+    ///
+    /// Use this to fix keys that are not recognised on a terminal, the key
+    /// codes can be found with
+    /// [`diagnostics::input_probe`](../diagnostics/fn.input_probe.html).
+    pub fn register_keycode(&mut self, code: i32, event: Event) {
+        self.backend.decoder.register_keycode(code, event);
+    }
+
+    /// Gets the keymap decoding curses key codes without a standard meaning,
+    /// such as keys with modifiers.
+    pub fn keymap(&self) -> &Keymap {
+        self.backend.decoder.keymap()
+    }
+
+    /// Gets the keymap decoding curses key codes without a standard meaning,
+    /// to add the codes a terminal sends or remap them.
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        self.backend.decoder.keymap_mut()
+    }
+
+    /// Registers key code mappings from a file, giving the number of mappings
+    /// loaded.
+    ///
+    /// Each line maps a terminfo key name, or a numeric key code, to a key with
+    /// optional modifiers.
+    ///
+    /// ```text
+    /// # rxvt sends these for ctrl and the arrow keys
+    /// kUP5 = ctrl+up
+    /// 565 = ctrl+shift+right
+    /// ```
+    ///
+    /// *Invalid lines are logged and skipped.*
+    pub fn load_keycodes<P: AsRef<Path>>(&mut self, path: P) -> io::Result<usize> {
+        let mappings = keymap::load(path.as_ref())?;
+        let count = mappings.len();
+        for (code, event) in mappings {
+            self.backend.decoder.register_keycode(code, event);
+        }
+        Ok(count)
+    }
+
+    /// Runs a closure with the bottom rows of the window handed over to normal
+    /// output, so whatever it writes to stdout and stderr, such as the output
+    /// of a build, scrolls within them instead of over the interface.
+    ///
+    /// *The rows are cleared first and keep the output until they are drawn
+    /// again, at the next refresh. Headless and inline windows just run the
+    /// closure.*
+    pub fn with_suspended_region<T, F: FnOnce() -> T>(&self, rows: usize, f: F) -> T {
+        self.refresh();
+        let region = self.backend.lend_rows(rows);
+        let result = f();
+        if let Some(region) = region {
+            self.backend.reclaim_rows(region);
+            // The output scrolled whatever was in the rows away.
+            self.viewport.set(self.viewport.get() + 1);
+        }
+        result
+    }
+
+    /// Shuts down the window, restoring the terminal to its original state.
+    ///
+    /// Gives an error if the terminal could not be restored. Any modes that
+    /// were still active are logged as they are repaired.
+    ///
+    /// *Dropping the window does the same, but can only log errors.*
+    pub fn shutdown(mut self) -> Result<()> {
+        self.backend.restore_terminal()
+    }
+
+    /// Checks whether the window is headless, rendering nothing because no
+    /// terminal is attached.
+    pub fn is_headless(&self) -> bool {
+        self.backend.headless
+    }
+
+    /// Gets the soft label key row, if it was enabled with
+    /// [`Builder::soft_labels`](struct.Builder.html#method.soft_labels).
+    pub fn soft_labels(&mut self) -> Option<SoftLabels<'_>> {
+        self.backend.soft_labels.map(SoftLabels::new)
+    }
+
+    /// Gets a line reserved with
+    /// [`Builder::reserve_top`](struct.Builder.html#method.reserve_top) or
+    /// [`Builder::reserve_bottom`](struct.Builder.html#method.reserve_bottom).
+    ///
+    /// *Lines are indexed from the edge of the screen inwards.*
+    pub fn reserved_line(&self, edge: Edge, index: usize) -> Option<ReservedLine<'_>> {
+        self.backend
+            .reserved
+            .iter()
+            .filter(|&&(e, _)| e == edge)
+            .nth(index)
+            .map(|&(_, win)| ReservedLine::new(win))
+    }
+
+    /// Redefines the palette entry at `index` to the given color.
+    ///
+    /// Uses `init_color` when curses can change colors, otherwise falls back
+    /// to OSC 4 on 256-color terminals if the profile allows it. The original
+    /// palette is restored when the window is dropped.
+    pub fn define_color(&self, index: u8, rgb: Rgb) -> Result<()> {
+        let colors = self.backend.capabilities.colors;
+        if !colors.has_colors {
+            return Err(Error::Unsupported("colors"));
+        }
+        if usize::from(index) >= colors.colors {
+            return Err(Error::OutOfRange("color index"));
+        }
+
+        if colors.can_change_color {
+            let (r, g, b) = curses::color_content(i16::from(index));
+            let (new_r, new_g, new_b) = rgb.to_curses();
+            if curses::init_color(i16::from(index), new_r, new_g, new_b) == curses::ERR {
+                return Err(Error::Curses("init_color"));
+            }
+            self.backend
+                .palette
+                .borrow_mut()
+                .entry(index)
+                .or_insert(SavedColor::Curses(r, g, b));
+        } else if colors.colors >= 256 && self.supports(Features::Palette) {
+            self.write_escape(&format!(
+                "\x1B]4;{};rgb:{:02x}/{:02x}/{:02x}\x1B\\",
+                index, rgb.r, rgb.g, rgb.b
+            ));
+            self.backend
+                .palette
+                .borrow_mut()
+                .entry(index)
+                .or_insert(SavedColor::Osc);
+        } else {
+            return Err(Error::Unsupported("palette redefinition"));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the title of the terminal window, as shown in its title bar or
+    /// tab.
+    ///
+    /// The title the terminal had is saved the first time, and restored when
+    /// the window is dropped.
+    ///
+    /// *Control characters in the title are left out. Fails when headless, or
+    /// when the profile doesn't allow it.*
+    pub fn set_title(&self, title: &str) -> Result<()> {
+        if self.is_headless() || !self.supports(Features::Title) {
+            return Err(Error::Unsupported("window title"));
+        }
+
+        let title: String = title.chars().filter(|ch| !ch.is_control()).collect();
+        let mut escape = String::new();
+        if !self.backend.title_saved.replace(true) {
+            escape.push_str("\x1B[22;0t");
+        }
+        escape.push_str(&format!("\x1B]2;{}\x1B\\", title));
+        self.write_escape(&escape);
+        Ok(())
+    }
+
+    /// Copies text to the system clipboard through the terminal with OSC 52,
+    /// which also works over SSH.
+    ///
+    /// *Fails when headless, or when the profile doesn't allow it. Terminals
+    /// that don't support it, or refuse clipboard access, ignore it.*
+    pub fn copy_to_clipboard(&self, text: &str) -> Result<()> {
+        if self.is_headless() || !self.supports(Features::Osc52) {
+            return Err(Error::Unsupported("clipboard"));
+        }
+
+        self.write_escape(&format!("\x1B]52;c;{}\x1B\\", base64(text.as_bytes())));
+        Ok(())
+    }
+
+    /// Rings the bell, which some terminals show as a flash instead.
+    pub fn bell(&self) {
+        if !self.defer_alert(false) {
+            curses::beep();
+        }
+    }
+
+    /// Flashes the screen as a visual bell, or rings the bell on terminals
+    /// that can't flash.
+    pub fn flash(&self) {
+        if !self.defer_alert(true) {
+            curses::flash();
+        }
+    }
+
+    pub(crate) fn input(&self) -> &InputWindow {
+        &self.backend.input
+    }
+}
+
+impl<B: Backend> Window<B> {
+    /// Creates a window drawing through the given backend.
+    ///
+    /// The window assumes a UTF-8 terminal with narrow ambiguous-width
+    /// characters, and is otherwise configured like a default
+    /// [`Builder`](struct.Builder.html).
+    pub fn with_backend(mut backend: B) -> Window<B> {
+        backend.set_ambiguous_width(AmbiguousWidth::Narrow);
+        Window {
+            event_queue: EventQueue::new(),
+            coalesce_mouse: true,
+            repeat: None,
+            normalize: Normalize::None,
+            cursor_request: Cell::new(None),
+            batch: Cell::default(),
+            held_escapes: RefCell::new(String::new()),
+            cells_sent: Cell::new(0),
+            viewport: Cell::new(0),
+            unsaved: RefCell::new(Vec::new()),
+            quit_key: Cell::new(Some((Key::Char('c'), Modifier::Ctrl))),
+            cursor_visibility: Cell::new(CursorVisibility::Hidden),
+            announcer: RefCell::new(None),
+            localizer: Box::new(English),
+            encoding: Encoding::Utf8,
+            ascii: false,
+            ambiguous_width: AmbiguousWidth::Narrow,
+            style: Cell::new(Style::default()),
+            monochrome: false,
+            buffer: RefCell::new(Buffer::new(0, 0)),
+            front: RefCell::new(Buffer::new(0, 0)),
+            arena: RefCell::new(Arena::new()),
+            shared: Arc::default(),
+            quit_signals: None,
+            suspend_signal: None,
+            sources: Vec::new(),
+            recorder: None,
+            replay: None,
+            replayed: VecDeque::new(),
+            crash_report: None,
+            idle: IdleState::new(),
+            backend,
+        }
+    }
+
+    /// Gets the backend the window draws through.
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Gets the backend the window draws through, mutably.
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    /// Polls the window for an event.
+    ///
+    /// *Handles key press modifiers and mouse events.*
+    ///
+    /// Bursts of mouse drag events that are already waiting are coalesced into
+    /// the latest position, unless disabled with
+    /// [`Builder::coalesce_mouse`](struct.Builder.html#method.coalesce_mouse).
+    ///
+    /// Resize and input events are delivered before queued background events,
+    /// which are only returned when no terminal input is waiting.
+    ///
+    /// Held navigation keys repeat at a steady rate when enabled with
+    /// [`Builder::auto_repeat`](struct.Builder.html#method.auto_repeat), the
+    /// repeats given while waiting for events.
+    ///
+    /// Key aliases are merged as selected with
+    /// [`Builder::normalize_keys`](struct.Builder.html#method.normalize_keys).
+    ///
+    /// While [replaying](#method.replay), the replayed events are given
+    /// instead, and while [recording](#method.start_recording) each event
+    /// given is recorded.
+    ///
+    /// Once no input has been given for the
+    /// [idle timeout](#method.set_idle_timeout), an
+    /// [`Event::Idle`](../event/enum.Event.html#variant.Idle) is given when no
+    /// other events are waiting.
+    pub fn poll_event(&mut self) -> Option<Event> {
+        let ev = match self.next_event() {
+            Some(ev) => {
+                self.idle.process(&ev);
+                Some(ev)
+            }
+            // Replayed recordings have their own idle events.
+            None if self.replay.is_none() => self.idle.poll(),
+            None => None,
+        };
+        if let (Some(ev), Some(recorder)) = (ev.as_ref(), self.recorder.as_mut()) {
+            if let Err(err) = recorder.record(ev) {
+                warn!("stopping recording: {}", err);
+                self.recorder = None;
+            }
+        }
+        if let (Some(ev), Some(_)) = (ev.as_ref(), self.crash_report.as_ref()) {
+            crash::record_event(ev);
+        }
+        ev
+    }
+
+    /// Waits for an event like [`poll_event`](#method.poll_event), for at
+    /// most the timeout if one is given.
+    ///
+    /// *Events sent through an [`EventSender`](struct.EventSender.html) or
+    /// [`WindowHandle`](struct.WindowHandle.html) wake the window straight
+    /// away. Other sources are polled regularly while waiting.*
+    pub fn wait_event(&mut self, timeout: Option<Duration>) -> Option<Event> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let wakeup = self.wakeup();
+        let mut idle = false;
+        loop {
+            if let Some(ev) = self.poll_event() {
+                return Some(ev);
+            }
+            let mut wait = if idle { MIN_WAIT } else { WAIT_INTERVAL };
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+                if now >= deadline {
+                    return None;
+                }
+                wait = wait.min(deadline - now);
+            }
+            if let Some(repeat) = self.repeat.as_ref().and_then(RepeatState::deadline) {
+                wait = wait.min(repeat.saturating_duration_since(Instant::now()));
+            }
+            match wakeup {
+                // Stdin stays readable at its end, without giving events.
+                Some(ref wakeup) if idle => {
+                    wakeup.wait(false, wait);
+                    idle = false;
+                }
+                Some(ref wakeup) => idle = wakeup.wait(true, wait),
+                None => thread::sleep(MIN_WAIT.min(wait)),
+            }
+        }
+    }
+
+    /// Gets the pipe waking the window, creating it the first time.
+    fn wakeup(&self) -> Option<Arc<Wakeup>> {
+        let mut shared = handle::lock(&self.shared);
+        if shared.wakeup.is_none() {
+            shared.wakeup = Wakeup::new().map(Arc::new);
+        }
+        shared.wakeup.clone()
+    }
+
+    /// Starts recording the events given by
+    /// [`poll_event`](#method.poll_event) to the writer, with the time they
+    /// were given, replacing any recording already started.
+    ///
+    /// *See [`Recorder`](../event/struct.Recorder.html) for the format.
+    /// Recording stops if the writer fails.*
+    pub fn start_recording<W: Write + 'static>(&mut self, writer: W) -> io::Result<()> {
+        self.recorder = Some(Recorder::new(writer)?);
+        Ok(())
+    }
+
+    /// Stops recording events.
+    pub fn stop_recording(&mut self) {
+        self.recorder = None;
+    }
+
+    /// Checks whether events are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Gets how long the window waits without input before it is idle.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle.timeout()
+    }
+
+    /// Sets how long the window waits without input, meaning keys, mouse
+    /// events and pastes, before it is idle and gives an
+    /// [`Event::Idle`](../event/enum.Event.html#variant.Idle), or `None` to
+    /// never be idle, as by default.
+    pub fn set_idle_timeout(&mut self, timeout: Option<Duration>) {
+        self.idle.set_timeout(timeout);
+    }
+
+    /// Gets the frame rate [`run`](#method.run) drops to while idle.
+    pub fn idle_fps(&self) -> Option<u32> {
+        self.idle.fps()
+    }
+
+    /// Sets the frame rate [`run`](#method.run) drops to while idle, so a
+    /// dashboard nobody is watching does not keep drawing at the full rate,
+    /// or `None` to keep the full rate.
+    ///
+    /// *The full rate is restored as soon as there is input, without waiting
+    /// for the next slow frame.*
+    pub fn set_idle_fps(&mut self, fps: Option<u32>) {
+        self.idle.set_fps(fps);
+    }
+
+    /// Checks whether the idle timeout has passed since the last input.
+    pub fn is_idle(&self) -> bool {
+        self.idle.is_idle()
+    }
+
+    /// Gets the time since the last input.
+    pub fn idle_time(&self) -> Duration {
+        self.idle.elapsed()
+    }
+
+    /// Keeps what is needed to report a crash, written to the path by the
+    /// [panic hook](#method.install_panic_hook) should the application
+    /// panic, so a user can attach it to a bug report.
+    ///
+    /// The report gives the panic message, the terminal size and
+    /// capabilities, the text of the last frame presented and up to the
+    /// given number of the latest events. The events come last, as a
+    /// [recording](../event/struct.Recorder.html) to play back from its
+    /// header line on.
+    ///
+    /// *Nothing is written unless the hook is installed. The report stops
+    /// being kept when the window is dropped, or another window keeps one.*
+    pub fn set_crash_report<P: Into<PathBuf>>(&mut self, path: P, events: usize) {
+        let capabilities = self.capabilities().clone();
+        self.crash_report = Some(crash::start(path.into(), events, capabilities));
+        self.record_frame();
+    }
+
+    /// Stops keeping a crash report.
+    pub fn clear_crash_report(&mut self) {
+        self.crash_report = None;
+    }
+
+    /// Keeps the frame last presented for the crash report.
+    fn record_frame(&self) {
+        if self.crash_report.is_some() {
+            crash::record_frame(self.get_size(), &self.front.borrow());
+        }
+    }
+
+    /// Plays back a recording of events, giving them from
+    /// [`poll_event`](#method.poll_event) at the pace they were recorded.
+    ///
+    /// Terminal input, events from handles and other sources are held back
+    /// until the recording ends, so the session plays out the same way.
+    pub fn replay<R: Read + 'static>(&mut self, reader: R) {
+        self.replay_source(ReplaySource::new(reader));
+    }
+
+    /// Plays back events from a source like [`replay`](#method.replay),
+    /// until it closes.
+    pub fn replay_source<S: EventSource + 'static>(&mut self, source: S) {
+        self.replay = Some(Box::new(source));
+        self.replayed.clear();
+    }
+
+    /// Checks whether a recording is being played back.
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Gives the next event, before it is recorded.
+    fn next_event(&mut self) -> Option<Event> {
+        if let Some(ref mut replay) = self.replay {
+            if self.replayed.is_empty() {
+                self.replayed.extend(replay.poll());
+            }
+            let ev = self.replayed.pop_front();
+            if ev.is_none() && replay.is_closed() {
+                self.replay = None;
+            }
+            return ev;
+        }
+
+        self.sync_shared();
+
+        let ev = self.event_queue.pop(Priority::Input);
+        if ev.is_some() {
+            return ev;
+        }
+
+        loop {
+            let ev = match self.read_event() {
+                Some(ev) if self.coalesce_mouse => self.coalesce_holds(ev),
+                Some(ev) => ev,
+                None => {
+                    // Repeats of a held key come once the input is read, in
+                    // case it held the release.
+                    let now = Instant::now();
+                    if let Some(ev) = self.repeat.as_mut().and_then(|repeat| repeat.poll(now)) {
+                        return Some(ev);
+                    }
+                    return self.event_queue.pop(Priority::Background);
+                }
+            };
+
+            match self.repeat {
+                Some(ref mut repeat) => {
+                    if let Some(ev) = repeat.process(ev, Instant::now()) {
+                        return Some(ev);
+                    }
+                }
+                None => return Some(ev),
+            }
+        }
+    }
+
+    /// Gives the events of the window as an asynchronous stream, so terminal
+    /// input can be awaited alongside other sources.
+    ///
+    /// *Requires the `async` feature.*
+    #[cfg(feature = "async")]
+    pub fn event_stream(&mut self) -> EventStream<'_, B> {
+        EventStream::new(self)
+    }
+
+    /// Polls for an event like [`poll_event`](#method.poll_event), resolving
+    /// key events through the bindings into their actions.
+    pub fn poll_action<A: Clone>(&mut self, bindings: &Bindings<A>) -> Option<Resolved<A>> {
+        self.poll_event().map(|ev| bindings.resolve(ev))
+    }
+
+    /// Coalesces any waiting mouse drag or move events following `ev` into the
+    /// latest position.
+    fn coalesce_holds(&mut self, mut ev: Event) -> Event {
+        while let Some(moving) = motion(&ev) {
+            match self.read_event() {
+                Some(next) if motion(&next) == Some(moving) => ev = next,
+                Some(next) => {
+                    self.event_queue.push_front(next);
+                    break;
+                }
+                None => break,
+            }
+        }
+        ev
+    }
+
+    /// Reads the next input event from the backend, normalizing its keys.
+    pub(crate) fn read_event(&mut self) -> Option<Event> {
+        let span = Span::enter(Phase::Decode);
+        let normalize = self.normalize;
+        let ev = self.backend.read_event().map(|ev| ev.normalize(normalize));
+        span.record("events", ev.is_some() as usize);
+        ev
+    }
+
+    /// Pushes an event onto the event queue, to be returned by a later
+    /// [`poll_event`](#method.poll_event).
+    ///
+    /// *Events are delivered in order of priority, then in the order they were
+    /// pushed.*
+    pub fn push_event(&mut self, event: Event) {
+        self.event_queue.push_back(event);
+    }
+
+    /// Adds a source of events, polled alongside terminal input until it
+    /// closes.
+    pub fn add_source<S: EventSource + 'static>(&mut self, source: S) {
+        self.sources.push(Box::new(source));
+    }
+
+    /// Creates a handle that can be sent to other threads to interact with the
+    /// window.
+    pub fn handle(&self) -> WindowHandle {
+        let size = self.get_size();
+        handle::lock(&self.shared).size = size;
+        WindowHandle::new(self.shared.clone())
+    }
+
+    /// Creates a sender that other threads can use to send events to the
+    /// window, waking it if it is [waiting](#method.wait_event).
+    pub fn event_sender(&self) -> EventSender {
+        EventSender::new(self.shared.clone())
+    }
+
+    /// Gets the number of events waiting in the event queue.
+    pub fn pending_events(&self) -> usize {
+        self.event_queue.len()
+    }
+
+    /// Clears the window, without refreshing.
+    ///
+    /// *Only the window's buffer is cleared, so redrawing the same content
+    /// afterwards sends nothing to the terminal.*
+    pub fn erase(&self) {
+        self.buffer().clear(self.resolve(Style::default()));
+    }
+
+    /// Sends the cells drawn since the last flush to the backend, without
+    /// presenting them.
+    ///
+    /// Drawing goes into a buffer, which is compared with the previously
+    /// flushed frame so only the changed cells are sent.
+    pub fn flush(&self) {
+        if self.defer_refresh(false) {
+            return;
+        }
+        let (rows, cols) = self.get_size();
+        self.flush_area(Rect::new(0, 0, cols, rows));
+    }
+
+    /// Flushes and presents the window.
+    ///
+    /// *Applies the cursor requested with
+    /// [`request_cursor`](#method.request_cursor) since the last refresh, or
+    /// else the [cursor visibility](#method.set_cursor_visibility).*
+    pub fn refresh(&self) {
+        if self.defer_refresh(true) {
+            return;
+        }
+        self.flush();
+        self.present();
+        self.cursor_request.set(None);
+        self.record_frame();
+    }
+
+    /// Requests the cursor to show after the next refresh.
+    ///
+    /// The request only lasts for a single frame, so the focused widget should
+    /// request its cursor every time it is rendered. If several requests are
+    /// made during a frame, the last one wins.
+    pub fn request_cursor(&self, style: CursorStyle) {
+        self.cursor_request.set(Some(style));
+    }
+
+    /// Gets how the cursor is shown in frames without a requested cursor.
+    pub fn cursor_visibility(&self) -> CursorVisibility {
+        self.cursor_visibility.get()
+    }
+
+    /// Sets how the cursor is shown in frames without a requested cursor, at
+    /// the position of the drawing cursor.
+    ///
+    /// *The cursor is hidden by default. It is shown as set again after a
+    /// suspend, and restored to the terminal's usual cursor on drop.*
+    pub fn set_cursor_visibility(&self, visibility: CursorVisibility) {
+        self.cursor_visibility.set(visibility);
+    }
+
+    /// Moves the drawing cursor, where text is printed by
+    /// [`print_here`](#method.print_here) and a visible cursor is shown.
+    pub fn move_cursor(&self, row: usize, col: usize) {
+        self.buffer().move_cursor(row, col);
+    }
+
+    /// Gets the number of changed cells sent to the backend so far.
+    pub(crate) fn cells_sent(&self) -> usize {
+        self.cells_sent.get()
+    }
+
+    /// Gets a count that changes whenever what was on the terminal was lost
+    /// or moved, so graphics placed on it must be placed again.
+    pub(crate) fn viewport(&self) -> usize {
+        // The next flush redraws everything if the front buffer was forgotten,
+        // which counts already so graphics placed before it aren't placed twice.
+        let redraw = self.front.borrow().size() != self.backend.size();
+        self.viewport.get() + usize::from(redraw)
+    }
+
+    /// Presents the flushed frame with the cursor to show.
+    pub(crate) fn present(&self) {
+        let _span = Span::enter(Phase::Flush);
+        self.backend.present(self.shown_cursor());
+    }
+
+    /// Gets the cursor to show for the frame.
+    pub(crate) fn shown_cursor(&self) -> CursorStyle {
+        self.cursor_request.get().unwrap_or_else(|| {
+            let (row, col) = self.buffer().cursor();
+            self.cursor_visibility.get().at(row, col)
+        })
+    }
+
+    /// Gets the size of the window in rows and columns.
+    pub fn get_size(&self) -> (usize, usize) {
+        self.backend.size()
+    }
+
+    /// Prints a message to window at the given position.
+    pub fn print<S: AsRef<str>>(&self, row: usize, col: usize, msg: S) {
+        self.print_styled(row, col, msg, Style::default());
+    }
+
+    /// Prints a character to window at the given position.
+    ///
+    /// *A zero width character, such as a combining mark, is joined to the
+    /// character before the position, and control characters are ignored. A
+    /// wide character that doesn't fit before the edge of the window isn't
+    /// drawn.*
+    pub fn printch(&self, row: usize, col: usize, ch: char) {
+        let mut buf = [0; 4];
+        let ch = self.encode_char(ch).encode_utf8(&mut buf);
+        self.buffer().put(
+            row,
+            col,
+            ch,
+            self.resolve(Style::default()),
+            self.ambiguous_width,
+        );
+    }
+
+    /// Inserts a character at the given position, shifting the rest of the row
+    /// right.
+    ///
+    /// *The last character of the row is lost.*
+    pub fn insert_char(&self, row: usize, col: usize, ch: char) {
+        self.buffer().insert_char(
+            row,
+            col,
+            self.encode_char(ch),
+            self.resolve(Style::default()),
+            self.ambiguous_width,
+        );
+    }
+
+    /// Deletes the character at the given position, shifting the rest of the
+    /// row left.
+    pub fn delete_char(&self, row: usize, col: usize) {
+        self.buffer().delete_char(row, col);
+    }
+
+    /// Translates the position of a mouse event into coordinates local to the
+    /// given rect.
+    ///
+    /// *Gives `None` for non-mouse events and positions outside of the rect.*
+    pub fn translate_mouse(&self, event: &Event, rect: Rect) -> Option<(usize, usize)> {
+        match *event {
+            Event::Mouse { pos, .. } => rect.to_local(pos),
+            _ => None,
+        }
+    }
+
+    /// Sets the announcer used to mirror the interface to a screen reader,
+    /// `None` disables announcements.
+    pub fn set_announcer(&mut self, announcer: Option<Announcer>) {
+        self.announcer = RefCell::new(announcer);
+    }
+
+    /// Announces a message, if an announcer is set.
+    pub fn announce(&self, msg: &str) {
+        if let Some(ref mut announcer) = *self.announcer.borrow_mut() {
+            announcer.announce(msg);
+        }
+    }
+
+    /// Reports that the focus moved to the named element, if an announcer is
+    /// set.
+    pub fn announce_focus(&self, name: &str) {
+        if let Some(ref mut announcer) = *self.announcer.borrow_mut() {
+            announcer.focus_changed(name);
+        }
+    }
+
+    /// Reports that the selection changed to the described item, if an
+    /// announcer is set.
+    pub fn announce_selection(&self, desc: &str) {
+        if let Some(ref mut announcer) = *self.announcer.borrow_mut() {
+            announcer.selection_changed(desc);
+        }
+    }
+
+    /// Gets the character encoding detected from the locale.
+    pub fn encoding(&self) -> &Encoding {
+        &self.encoding
+    }
+
+    /// Checks whether the ASCII fallback mode is enabled.
+    ///
+    /// In ASCII fallback mode non-ASCII characters are printed as `?`, apart
+    /// from the single line box characters the backend can draw. It is
+    /// enabled automatically when the locale encoding isn't UTF-8.
+    pub fn ascii_mode(&self) -> bool {
+        self.ascii
+    }
+
+    /// Sets whether the ASCII fallback mode is enabled.
+    pub fn set_ascii_mode(&mut self, ascii: bool) {
+        self.ascii = ascii;
+    }
+
+    /// Sets the key aliases merged before events are delivered, overriding
+    /// [`Builder::normalize_keys`](struct.Builder.html#method.normalize_keys).
+    pub fn set_normalize_keys(&mut self, policy: Normalize) {
+        self.normalize = policy;
+    }
+
+    /// Gets how East Asian ambiguous-width characters are measured.
+    pub fn ambiguous_width(&self) -> AmbiguousWidth {
+        self.ambiguous_width
+    }
+
+    /// Sets how East Asian ambiguous-width characters are measured, overriding
+    /// the setting detected at init.
+    ///
+    /// *Changing it redraws the whole window on the next refresh, as the text
+    /// already on the terminal was laid out the old way.*
+    pub fn set_ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        if ambiguous != self.ambiguous_width {
+            // Forgetting the front buffer makes the next flush redraw it all.
+            *self.front.get_mut() = Buffer::new(0, 0);
+        }
+        self.ambiguous_width = ambiguous;
+        self.backend.set_ambiguous_width(ambiguous);
+    }
+
+    /// Gets the style everything is currently drawn within, patched with the
+    /// styles of any enclosing [`with_style`](#method.with_style) calls.
+    pub fn style(&self) -> Style {
+        self.style.get()
+    }
+
+    /// Sets the style everything is drawn within, such as the colors of a
+    /// theme.
+    ///
+    /// *Erasing the window fills it with the style.*
+    pub fn set_style(&mut self, style: Style) {
+        self.style.set(style);
+    }
+
+    /// Checks whether colors are drawn as attributes instead.
+    pub fn is_monochrome(&self) -> bool {
+        self.monochrome
+    }
+
+    /// Sets whether colors are drawn as attributes instead, for monochrome
+    /// terminals and users who turn color off.
+    ///
+    /// Every style drawn is mapped by [`Style::monochrome`], so widgets and
+    /// themes need no changes.
+    ///
+    /// *On by default on terminals without colors. Only what is drawn
+    /// afterwards is mapped.*
+    ///
+    /// [`Style::monochrome`]: ../style/struct.Style.html#method.monochrome
+    pub fn set_monochrome(&mut self, monochrome: bool) {
+        self.monochrome = monochrome;
+    }
+
+    /// Draws within a style, patched over the current style for the duration
+    /// of the closure.
+    ///
+    /// Containers use this so the widgets rendered in them inherit their
+    /// style, and the styles of the widgets patch over it in turn.
+    pub fn with_style<T, F: FnOnce(&Self) -> T>(&self, style: Style, f: F) -> T {
+        let outer = self.style.replace(self.style.get().patch(style));
+        let result = f(self);
+        self.style.set(outer);
+        result
+    }
+
+    /// Gets the number of columns a string occupies, measured with the
+    /// window's ambiguous width setting.
+    pub fn display_width(&self, s: &str) -> usize {
+        text::str_width(s, self.ambiguous_width)
+    }
+
+    /// Sets the localizer used for strings rendered by the crate.
+    pub fn set_localizer<L: Localizer + 'static>(&mut self, localizer: L) {
+        self.localizer = Box::new(localizer);
+    }
+
+    /// Gets the localized text of a message.
+    pub fn localize(&self, message: Message) -> Cow<'static, str> {
+        self.localizer
+            .localize(message)
+            .unwrap_or_else(|| Cow::Borrowed(message.english()))
+    }
+
+    /// Gets the position of the cursor in rows and columns.
+    pub fn get_cursor(&self) -> (usize, usize) {
+        self.buffer().cursor()
+    }
+
+    /// Prints a message to the window at the cursor position, advancing the
+    /// cursor past it.
+    pub fn print_here<S: AsRef<str>>(&self, msg: S) {
+        self.print_here_styled(msg, Style::default());
+    }
+
+    /// Prints a string to the window at the given position, in the given
+    /// style.
+    ///
+    /// *Colors are approximated on terminals with fewer colors, and ignored
+    /// on terminals without colors.*
+    pub fn print_styled<S: AsRef<str>>(&self, row: usize, col: usize, msg: S, style: Style) {
+        self.put(row, col, msg.as_ref(), style);
+    }
+
+    /// Prints a message to the window at the given position, cut down to fit
+    /// in at most `max_width` columns, giving the number of columns used.
+    ///
+    /// *Widths are measured with the window's ambiguous width setting, so a
+    /// wide character that would straddle the limit is left out.*
+    pub fn print_clipped<S: AsRef<str>>(
+        &self,
+        row: usize,
+        col: usize,
+        max_width: usize,
+        msg: S,
+    ) -> usize {
+        let msg = self.encode(msg.as_ref());
+        let msg = text::truncate(&msg, max_width, self.ambiguous_width);
+        self.put(row, col, msg, Style::default());
+        self.display_width(msg)
+    }
+
+    /// Prints a message to the window at the given position as a hyperlink to
+    /// the URL, giving the number of columns used.
+    ///
+    /// *The link is added with OSC 8 when the profile allows it, otherwise
+    /// only the message is printed. The message is cut off at the edge of the
+    /// window.*
+    pub fn print_hyperlink<S: AsRef<str>>(
+        &self,
+        row: usize,
+        col: usize,
+        msg: S,
+        url: &str,
+    ) -> usize {
+        let (_, cols) = self.get_size();
+        let msg = self.encode(msg.as_ref());
+        let msg = text::truncate(&msg, cols.saturating_sub(col), self.ambiguous_width);
+        self.put(row, col, msg, Style::default());
+
+        if self.supports(Features::Hyperlinks) && !msg.is_empty() {
+            let url: String = url.chars().filter(|ch| !ch.is_control()).collect();
+            let style = self.resolve(Style::default());
+            let payload = format!(
+                "{}\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\\x1B[0m",
+                style.sgr(self.color_caps()),
+                url,
+                msg
+            );
+            self.place_graphic(row, col, payload.into());
+        }
+        self.display_width(msg)
+    }
+
+    /// Prints a message to the window at the cursor position in the given
+    /// style, advancing the cursor past it.
+    pub fn print_here_styled<S: AsRef<str>>(&self, msg: S, style: Style) {
+        let (row, col) = self.buffer().cursor();
+        self.put(row, col, msg.as_ref(), style);
+    }
+
+    /// Moves the cursor forward by the given number of columns, wrapping onto
+    /// following rows.
+    ///
+    /// *The cursor stops at the end of the last row.*
+    pub fn advance(&self, cols: usize) {
+        let (rows, width) = self.get_size();
+        let (row, col) = self.get_cursor();
+        if rows == 0 || width == 0 {
+            return;
+        }
+
+        let pos = (row * width + col)
+            .saturating_add(cols)
+            .min(rows * width - 1);
+        self.buffer().move_cursor(pos / width, pos % width);
+    }
+
+    /// Moves the cursor to the start of the next row.
+    ///
+    /// *The cursor stays on the last row if it is already there.*
+    pub fn newline(&self) {
+        let (rows, _) = self.get_size();
+        let (row, _) = self.get_cursor();
+
+        let row = (row + 1).min(rows.saturating_sub(1));
+        self.buffer().move_cursor(row, 0);
+    }
+
+    /// Creates a pane covering the given region of the window.
+    ///
+    /// Gives an error if the region is empty or does not fit in the window.
+    pub fn subwindow(&self, rect: Rect) -> Result<Pane<'_, B>> {
+        let (rows, cols) = self.get_size();
+        if rect.width == 0 || rect.height == 0 || rect.right() > cols || rect.bottom() > rows {
+            return Err(Error::OutOfRange("subwindow"));
+        }
+        Ok(Pane::new(self, rect))
+    }
+
+    /// Gets the color capabilities of the terminal.
+    pub fn color_caps(&self) -> ColorCaps {
+        self.backend.capabilities().colors
+    }
+
+    /// Gets the size of a cell in pixels as width and height, if known.
+    ///
+    /// *The size is taken from the terminal size, or from the size of a cell
+    /// or of the text area reported to the probe when
+    /// [`Builder::probe_capabilities`](struct.Builder.html#method.probe_capabilities)
+    /// is used.*
+    pub fn cell_size(&self) -> Option<(usize, usize)> {
+        self.backend.capabilities().cell_size
+    }
+
+    /// Queues an encoded graphic to be drawn at the given position after the
+    /// next refresh.
+    pub(crate) fn place_graphic(&self, row: usize, col: usize, payload: Rc<str>) {
+        self.backend.draw_graphic(row, col, payload);
+    }
+
+    /// Gets the capabilities of the terminal.
+    pub fn capabilities(&self) -> &Capabilities {
+        self.backend.capabilities()
+    }
+
+    /// Checks whether an optional terminal feature is allowed by the
+    /// degradation profile.
+    pub fn supports(&self, feature: Features) -> bool {
+        self.backend.capabilities().supports(feature)
+    }
+}
+
+impl<B: Backend> Window<B> {
+    /// Moves events sent from handles and received signals into the event
+    /// queue, and publishes the current size to handles.
+    pub(crate) fn sync_shared(&mut self) {
+        let size = self.get_size();
+        let events = {
+            let mut shared = handle::lock(&self.shared);
+            shared.size = size;
+            shared.take_events()
+        };
+        for event in events {
+            self.event_queue.push_back(event);
+        }
+
+        for source in &mut self.sources {
+            for event in source.poll() {
+                self.event_queue.push_back(event);
+            }
+        }
+        self.sources.retain(|source| !source.is_closed());
+
+        if self.quit_signals.is_some() {
+            for reason in signal::take_pending().into_iter().rev() {
+                self.event_queue.push_front(Event::Quit(reason));
+            }
+        }
+
+        if self.suspend_signal.is_some() && signal::take_suspend() {
+            if let Err(err) = self.suspend() {
+                error!("could not suspend: {}", err);
+            }
+            if let Some(ref suspend_signal) = self.suspend_signal {
+                suspend_signal.stop();
+            }
+            if let Err(err) = self.resume() {
+                error!("could not resume: {}", err);
+            }
+            self.event_queue.push_front(Event::Refresh);
+        }
+    }
+
+    /// Leaves the terminal temporarily, restoring it as it was before the
+    /// window, such as to run an editor.
+    ///
+    /// Nothing is drawn and no input is read until the window is
+    /// [resumed](#method.resume).
+    pub fn suspend(&mut self) -> Result<()> {
+        self.backend.suspend()
+    }
+
+    /// Takes over the terminal again after a [suspend](#method.suspend),
+    /// redrawing the screen as it was and turning the mouse and paste modes
+    /// back on.
+    pub fn resume(&mut self) -> Result<()> {
+        self.viewport.set(self.viewport.get() + 1);
+        self.backend.resume()
+    }
+
+    /// Resolves the style of text drawn in the current style.
+    fn resolve(&self, style: Style) -> Style {
+        let style = Style {
+            attr_off: Attr::None,
+            ..self.style.get().patch(style)
+        };
+        if self.monochrome {
+            style.monochrome()
+        } else {
+            style
+        }
+    }
+
+    /// Gets the buffer drawn into, resized to the backend first if needed.
+    fn buffer(&self) -> RefMut<'_, Buffer> {
+        let mut buffer = self.buffer.borrow_mut();
+        let size = self.backend.size();
+        if buffer.size() != size {
+            buffer.resize(size.0, size.1);
+        }
+        buffer
+    }
+
+    /// Sends the changed cells in an area of the buffer to the backend.
+    fn flush_area(&self, area: Rect) {
+        let buffer = self.buffer();
+        let mut front = self.front.borrow_mut();
+        if front.size() != buffer.size() {
+            // The backend was resized, so nothing that was on it is known.
+            let (rows, cols) = buffer.size();
+            *front = Buffer::new(rows, cols);
+            self.backend.erase();
+            self.viewport.set(self.viewport.get() + 1);
+        }
+
+        let span = Span::enter(Phase::Draw);
+        let mut arena = self.arena.borrow_mut();
+        arena.reset();
+        let cells = buffer.diff(&front, area, &mut arena);
+        self.cells_sent.set(self.cells_sent.get() + cells);
+        span.record("cells", cells);
+        span.record("runs", arena.runs().len());
+        for &(row, col, ref text, style) in arena.runs() {
+            self.backend.print(row, col, arena.get(text.clone()), style);
+        }
+        front.copy_area(&buffer, area);
+    }
+
+    /// Draws a message into the buffer, encoded through the frame's arena.
+    fn put(&self, row: usize, col: usize, msg: &str, style: Style) {
+        let mut arena = self.arena.borrow_mut();
+        let msg = if self.ascii && !msg.is_ascii() {
+            let range = arena.alloc_chars(msg.chars().map(|ch| self.encode_char(ch)));
+            arena.get(range)
+        } else {
+            msg
+        };
+        self.buffer()
+            .put(row, col, msg, self.resolve(style), self.ambiguous_width);
+    }
+
+    pub(crate) fn encode<'a>(&self, msg: &'a str) -> Cow<'a, str> {
+        if self.ascii && !msg.is_ascii() {
+            Cow::Owned(msg.chars().map(|ch| self.encode_char(ch)).collect())
+        } else {
+            Cow::Borrowed(msg)
+        }
+    }
+
+    fn encode_char(&self, ch: char) -> char {
+        if !self.ascii || ch.is_ascii() || ch == text::SOFT_HYPHEN {
+            // Soft hyphens are left out when drawn.
+            ch
+        } else if text::is_non_breaking_space(ch) {
+            ' '
+        } else if self.backend.line_drawing() && draw::acs(ch).is_some() {
+            ch
+        } else {
+            '?'
+        }
+    }
+}
+
+/// Encodes bytes as base64, padded.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | u32::from(byte) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Gets the drag or move of a mouse event, with the modifiers held, for
+/// coalescing.
+fn motion(ev: &Event) -> Option<(MouseEvent, Modifier)> {
+    match *ev {
+        Event::Mouse {
+            event: event @ MouseEvent::Hold(_),
+            modifier,
+            ..
+        }
+        | Event::Mouse {
+            event: event @ MouseEvent::Moved,
+            modifier,
+            ..
+        } => Some((event, modifier)),
+        _ => None,
+    }
+}
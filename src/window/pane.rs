@@ -0,0 +1,127 @@
+use backend::Backend;
+use draw::BorderStyle;
+use event::Event;
+use layout::Rect;
+use style::Style;
+use text;
+use window::{CursesBackend, Window};
+
+/// A rectangular region of a window with its own drawing.
+///
+/// Positions given to a pane are relative to its top left corner, and
+/// anything drawn past its edges is clipped.
+///
+/// *Obtained through [`Window::subwindow`](struct.Window.html#method.subwindow).*
+pub struct Pane<'a, B: Backend + 'a = CursesBackend> {
+    parent: &'a Window<B>,
+    rect: Rect,
+    style: Style,
+}
+
+impl<'a, B: Backend> Pane<'a, B> {
+    pub(crate) fn new(parent: &'a Window<B>, rect: Rect) -> Pane<'a, B> {
+        Pane {
+            parent,
+            rect,
+            style: Style::default(),
+        }
+    }
+
+    /// Gets the region of the window covered by the pane.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Gets the size of the pane as rows and columns.
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.rect.height, self.rect.width)
+    }
+
+    /// Gets the style the pane draws within.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style the pane draws within, patched over the window's style.
+    ///
+    /// *Erasing the pane fills it with the style.*
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Clears the pane, without refreshing.
+    pub fn erase(&self) {
+        let blank = " ".repeat(self.rect.width);
+        for row in 0..self.rect.height {
+            self.print(row, 0, &blank);
+        }
+    }
+
+    /// Prints a message to the pane at the given position, clipped to the
+    /// edge of the pane.
+    pub fn print<S: AsRef<str>>(&self, row: usize, col: usize, msg: S) {
+        self.print_styled(row, col, msg, Style::default());
+    }
+
+    /// Prints a message to the pane at the given position in the given style,
+    /// clipped to the edge of the pane.
+    pub fn print_styled<S: AsRef<str>>(&self, row: usize, col: usize, msg: S, style: Style) {
+        if let Some(msg) = self.clip(row, col, msg.as_ref()) {
+            self.parent.with_style(self.style, |win| {
+                win.print_styled(self.rect.y + row, self.rect.x + col, msg, style)
+            });
+        }
+    }
+
+    /// Draws a border around the edge of the pane.
+    ///
+    /// *The border is drawn over the outermost cells of the pane, so content
+    /// should be kept inside [`inner`](#method.inner).*
+    pub fn draw_border(&self) {
+        self.parent.with_style(self.style, |win| {
+            win.draw_box(self.rect, BorderStyle::Single)
+        });
+    }
+
+    /// Gets the region inside the border, relative to the pane.
+    pub fn inner(&self) -> Rect {
+        Rect::new(
+            1,
+            1,
+            self.rect.width.saturating_sub(2),
+            self.rect.height.saturating_sub(2),
+        )
+    }
+
+    /// Draws the pane to the terminal immediately, without refreshing the
+    /// rest of the window.
+    ///
+    /// *Drawing to the pane also draws to the window, so a
+    /// [`Window::refresh`](struct.Window.html#method.refresh) shows it too.*
+    pub fn refresh(&self) {
+        if self.parent.defer_refresh(true) {
+            return;
+        }
+        self.parent.flush_area(self.rect);
+        self.parent.present();
+    }
+
+    /// Translates the position of a mouse event into coordinates local to the
+    /// pane.
+    ///
+    /// *Gives `None` for non-mouse events and positions outside of the pane.*
+    pub fn translate_mouse(&self, event: &Event) -> Option<(usize, usize)> {
+        self.parent.translate_mouse(event, self.rect)
+    }
+
+    /// Clips a message printed at the given position to the edge of the pane.
+    fn clip(&self, row: usize, col: usize, msg: &str) -> Option<String> {
+        if row >= self.rect.height || col >= self.rect.width {
+            return None;
+        }
+
+        let msg = self.parent.encode(msg);
+        let room = self.rect.width - col;
+        Some(text::truncate(&msg, room, self.parent.ambiguous_width()).to_owned())
+    }
+}
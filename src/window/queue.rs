@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+use event::Event;
+
+/// Represents the delivery priority of a queued event.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub(crate) enum Priority {
+    /// Terminal resizes, delivered before anything else.
+    Resize = 0,
+    /// Keyboard and mouse input.
+    Input = 1,
+    /// Events generated by the application, such as ticks and timers.
+    Background = 2,
+}
+
+impl Priority {
+    /// Gets the priority of an event.
+    pub(crate) fn of(event: &Event) -> Priority {
+        match *event {
+            Event::Resize => Priority::Resize,
+            Event::Refresh
+            | Event::Key { .. }
+            | Event::Mouse { .. }
+            | Event::Paste(_)
+            | Event::Quit(_)
+            | Event::Unknown(_) => Priority::Input,
+            Event::Tick
+            | Event::Idle(_)
+            | Event::FileChanged(_)
+            | Event::Message { .. }
+            | Event::User(_) => Priority::Background,
+        }
+    }
+}
+
+/// A small priority queue of events, FIFO within each priority level.
+#[derive(Debug, Default)]
+pub(crate) struct EventQueue {
+    levels: [VecDeque<Event>; 3],
+}
+
+impl EventQueue {
+    pub(crate) fn new() -> EventQueue {
+        EventQueue::default()
+    }
+
+    /// Pushes an event to the back of its priority level.
+    pub(crate) fn push_back(&mut self, event: Event) {
+        self.levels[Priority::of(&event) as usize].push_back(event);
+    }
+
+    /// Pushes an event to the front of its priority level.
+    pub(crate) fn push_front(&mut self, event: Event) {
+        self.levels[Priority::of(&event) as usize].push_front(event);
+    }
+
+    /// Pops the next event with at least the given priority.
+    pub(crate) fn pop(&mut self, min: Priority) -> Option<Event> {
+        self.levels[..=min as usize]
+            .iter_mut()
+            .find(|level| !level.is_empty())
+            .and_then(|level| level.pop_front())
+    }
+
+    /// Gets the number of queued events.
+    pub(crate) fn len(&self) -> usize {
+        self.levels.iter().map(|level| level.len()).sum()
+    }
+}
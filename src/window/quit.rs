@@ -0,0 +1,204 @@
+use std::cell::Cell;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use backend::Backend;
+use event::{Event, Key, Modifier, QuitReason};
+use i18n::Message;
+use layout::Rect;
+use widget::{Dialog, Widget};
+
+use super::Window;
+
+/// How long the confirm dialog waits for an event before drawing again.
+const CONFIRM_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+pub(crate) struct State {
+    name: String,
+    unsaved: Cell<bool>,
+}
+
+/// Tracks whether part of an application, such as an open document, has
+/// changes that would be lost by quitting.
+///
+/// ```no_run
+/// use termui::window::Unsaved;
+/// use termui::Window;
+///
+/// let win = Window::new();
+/// let notes = Unsaved::new("notes.txt");
+/// win.track_unsaved(&notes);
+///
+/// // Typing into the notes.
+/// notes.mark();
+/// assert!(win.has_unsaved_changes());
+///
+/// // Writing them out.
+/// notes.saved();
+/// assert!(!win.has_unsaved_changes());
+/// ```
+///
+/// Clones share the same state, so a component can keep one and mark it as
+/// it changes while the window holds another.
+///
+/// *The window stops tracking the changes once every clone is dropped, such
+/// as when the document is closed.*
+#[derive(Clone, Debug)]
+pub struct Unsaved {
+    state: Rc<State>,
+}
+
+impl Unsaved {
+    /// Creates a tracker without changes, named as shown when confirming a
+    /// quit.
+    pub fn new<S: Into<String>>(name: S) -> Unsaved {
+        Unsaved {
+            state: Rc::new(State {
+                name: name.into(),
+                unsaved: Cell::new(false),
+            }),
+        }
+    }
+
+    /// Gets the name shown when confirming a quit.
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    /// Marks that there are unsaved changes.
+    pub fn mark(&self) {
+        self.state.unsaved.set(true);
+    }
+
+    /// Marks that the changes were saved, or thrown away.
+    pub fn saved(&self) {
+        self.state.unsaved.set(false);
+    }
+
+    /// Checks whether there are unsaved changes.
+    pub fn is_unsaved(&self) -> bool {
+        self.state.unsaved.get()
+    }
+}
+
+impl<B: Backend> Window<B> {
+    /// Tracks changes that should be confirmed before quitting.
+    pub fn track_unsaved(&self, unsaved: &Unsaved) {
+        let mut tracked = self.unsaved.borrow_mut();
+        tracked.retain(|state| state.strong_count() > 0);
+        tracked.push(Rc::downgrade(&unsaved.state));
+    }
+
+    /// Gets the names of the trackers with unsaved changes, in the order they
+    /// were tracked.
+    pub fn unsaved_changes(&self) -> Vec<String> {
+        self.unsaved
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|state| state.unsaved.get())
+            .map(|state| state.name.clone())
+            .collect()
+    }
+
+    /// Checks whether any of the tracked changes are unsaved.
+    pub fn has_unsaved_changes(&self) -> bool {
+        !self.unsaved_changes().is_empty()
+    }
+
+    /// Gets the key asking to quit, confirmed by
+    /// [`run`](#method.run) like a quit event.
+    pub fn quit_key(&self) -> Option<(Key, Modifier)> {
+        self.quit_key.get()
+    }
+
+    /// Sets the key asking to quit, `Ctrl-C` by default, or `None` to only
+    /// confirm quit events.
+    pub fn set_quit_key(&self, key: Option<(Key, Modifier)>) {
+        self.quit_key.set(key);
+    }
+
+    /// Asks whether to quit if there are unsaved changes, giving whether to
+    /// go ahead.
+    ///
+    /// ```no_run
+    /// # use termui::event::Event;
+    /// # use termui::Window;
+    /// # let mut win = Window::new();
+    /// while let Some(ev) = win.poll_event() {
+    ///     if let Event::Quit(_) = ev {
+    ///         if win.confirm_quit() {
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// A dialog naming what is unsaved is drawn over the window until
+    /// answered, taking every event. Another quit event while it is open
+    /// or press of the quit key quits straight away, so a second `Ctrl-C` or
+    /// `SIGTERM` isn't ignored.
+    ///
+    /// *Gives `true` without asking when nothing is unsaved.
+    /// [`run`](#method.run) asks before handing quit events and the
+    /// [quit key](#method.set_quit_key) to the application, dropping them if
+    /// answered no, except for hangups, since the terminal is gone.*
+    pub fn confirm_quit(&mut self) -> bool {
+        let unsaved = self.unsaved_changes();
+        if unsaved.is_empty() {
+            return true;
+        }
+
+        let message = format!(
+            "{} {}",
+            self.localize(Message::UnsavedChanges),
+            unsaved.join(", ")
+        );
+        let mut dialog = Dialog::new(self.localize(Message::Quit), message);
+        dialog.set_buttons(vec![
+            self.localize(Message::Quit),
+            self.localize(Message::Cancel),
+        ]);
+        dialog.open();
+
+        while dialog.is_open() {
+            let (rows, cols) = self.get_size();
+            dialog.render(Rect::new(0, 0, cols, rows), self);
+            self.refresh();
+            if let Some(ev) = self.wait_event(Some(CONFIRM_POLL)) {
+                if self.asks_to_quit(&ev) {
+                    dialog.close();
+                    return true;
+                }
+                Widget::<B>::handle_event(&mut dialog, &ev);
+            }
+        }
+        // Puts back what was under the dialog.
+        let (rows, cols) = self.get_size();
+        dialog.render(Rect::new(0, 0, cols, rows), self);
+        self.refresh();
+        dialog.is_confirmed()
+    }
+
+    /// Checks whether an event asks to quit, and if so whether quitting was
+    /// confirmed, giving whether to hand the event on.
+    pub(crate) fn intercept_quit(&mut self, ev: &Event) -> bool {
+        match *ev {
+            // The terminal is gone, so there's no asking.
+            Event::Quit(QuitReason::Hangup) => true,
+            _ if self.asks_to_quit(ev) => self.confirm_quit(),
+            _ => true,
+        }
+    }
+
+    fn asks_to_quit(&self, ev: &Event) -> bool {
+        match *ev {
+            Event::Quit(_) => true,
+            Event::Key { key, modifier, .. } if !ev.is_key_release() => {
+                self.quit_key.get() == Some((key, modifier))
+            }
+            _ => false,
+        }
+    }
+}
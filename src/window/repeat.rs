@@ -0,0 +1,294 @@
+use std::time::{Duration, Instant};
+
+use event::{Event, Key, KeyEventKind, Modifier};
+
+/// The longest gap between presses of a key for it to still be taken as
+/// held, when the terminal doesn't report releases.
+const HOLD_TIMEOUT: Duration = Duration::from_millis(150);
+/// The longest a terminal is taken to wait before repeating a held key.
+const FIRST_REPEAT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Configures software auto-repeat for navigation keys.
+///
+/// Once a navigation key has been held for `delay`, repeats of it are given
+/// every `rate` until it is released, with an increasing `repeat_count`,
+/// whatever rate the terminal itself repeats at.
+///
+/// With the progressive keyboard protocol, a key is held until the terminal
+/// reports its release. Otherwise it is taken as held while the terminal
+/// keeps repeating it, and presses before `delay` are given as they come, as
+/// they may be separate taps.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AutoRepeat {
+    /// How long a key is held before it starts repeating.
+    pub delay: Duration,
+    /// The interval between repeats.
+    pub rate: Duration,
+}
+
+impl Default for AutoRepeat {
+    fn default() -> AutoRepeat {
+        AutoRepeat {
+            delay: Duration::from_millis(500),
+            rate: Duration::from_millis(30),
+        }
+    }
+}
+
+/// Tracks the currently held key, giving its repeats.
+#[derive(Debug)]
+pub(crate) struct RepeatState {
+    config: AutoRepeat,
+    held: Option<Held>,
+    /// Whether the terminal reports key releases.
+    releases: bool,
+}
+
+#[derive(Debug)]
+struct Held {
+    key: Key,
+    modifier: Modifier,
+    pressed: Instant,
+    /// When the terminal last sent the key, pressed or repeated.
+    last_seen: Instant,
+    /// The gap between the last two repeats sent by the terminal.
+    interval: Option<Duration>,
+    /// Whether the terminal repeated the key since it was pressed.
+    repeated: bool,
+    /// When the next repeat is due.
+    next: Instant,
+    count: u32,
+}
+
+impl Held {
+    /// Checks whether the key is still held, going by the repeats sent by
+    /// the terminal.
+    fn is_held(&self, now: Instant) -> bool {
+        // A single repeat could be a second tap, so it takes two to go by.
+        self.interval.is_some_and(|interval| {
+            now.duration_since(self.last_seen) <= (interval * 2).min(HOLD_TIMEOUT)
+        })
+    }
+}
+
+impl RepeatState {
+    pub(crate) fn new(config: AutoRepeat) -> RepeatState {
+        RepeatState {
+            config,
+            held: None,
+            releases: false,
+        }
+    }
+
+    /// Processes an event, giving `None` if it should be dropped.
+    pub(crate) fn process(&mut self, event: Event, now: Instant) -> Option<Event> {
+        let (key, modifier, kind) = match event {
+            Event::Key { key, .. } if event.is_key_release() => {
+                self.releases = true;
+                if self.held.as_ref().is_some_and(|held| held.key == key) {
+                    self.held = None;
+                }
+                return Some(event);
+            }
+            Event::Key {
+                key,
+                modifier,
+                kind,
+                ..
+            } if key.is_navigation() => (key, modifier, kind),
+            Event::Key { .. } => {
+                self.held = None;
+                return Some(event);
+            }
+            _ => return Some(event),
+        };
+
+        let delay = self.config.delay;
+        if let Some(ref mut held) = self.held {
+            let since = now.duration_since(held.last_seen);
+            let timeout = if held.repeated {
+                HOLD_TIMEOUT
+            } else {
+                FIRST_REPEAT_TIMEOUT
+            };
+            if held.key == key
+                && held.modifier == modifier
+                && (kind == KeyEventKind::Repeat || since <= timeout)
+            {
+                if held.repeated {
+                    held.interval = Some(since);
+                }
+                held.repeated = true;
+                held.last_seen = now;
+                if now >= held.next {
+                    return Some(self.repeat(now));
+                }
+                // Until the delay, the presses may be taps of the key.
+                if kind == KeyEventKind::Press && now.duration_since(held.pressed) < delay {
+                    return Some(event);
+                }
+                return None;
+            }
+        }
+
+        self.held = Some(Held {
+            key,
+            modifier,
+            pressed: now,
+            last_seen: now,
+            interval: None,
+            repeated: false,
+            next: now + delay,
+            count: 0,
+        });
+        Some(event)
+    }
+
+    /// Gives a repeat of the held key if one is due.
+    pub(crate) fn poll(&mut self, now: Instant) -> Option<Event> {
+        let releases = self.releases;
+        match self.held {
+            Some(ref held) if now < held.next => None,
+            Some(ref held) if releases || held.is_held(now) => Some(self.repeat(now)),
+            // The terminal stopped repeating it, so it was released.
+            Some(ref held) if held.interval.is_some() => {
+                self.held = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets when the next repeat is due, if a key may be held.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.held
+            .as_ref()
+            .filter(|held| self.releases || held.interval.is_some())
+            .map(|held| held.next)
+    }
+
+    /// Gives the next repeat of the held key.
+    fn repeat(&mut self, now: Instant) -> Event {
+        let rate = self.config.rate;
+        let held = self.held.as_mut().expect("a key is held");
+        held.count += 1;
+        // Repeats keep their pace, unless they fell behind.
+        held.next += rate;
+        if held.next <= now {
+            held.next = now + rate;
+        }
+        Event::Key {
+            key: held.key,
+            modifier: held.modifier,
+            repeat_count: held.count,
+            kind: KeyEventKind::Repeat,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{AutoRepeat, RepeatState};
+    use event::{Event, Key, KeyEventKind, Modifier};
+
+    fn key(kind: KeyEventKind) -> Event {
+        Event::Key {
+            key: Key::Down,
+            modifier: Modifier::None,
+            repeat_count: 0,
+            kind,
+        }
+    }
+
+    fn repeat_count(event: Option<Event>) -> Option<u32> {
+        match event {
+            Some(Event::Key {
+                repeat_count,
+                kind: KeyEventKind::Repeat,
+                ..
+            }) => Some(repeat_count),
+            _ => None,
+        }
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn repeats_until_released() {
+        let mut state = RepeatState::new(AutoRepeat::default());
+        let start = Instant::now();
+        let press = key(KeyEventKind::Press);
+        assert_eq!(state.process(press.clone(), start), Some(press));
+        assert_eq!(state.poll(start + ms(100)), None);
+        // Held keys repeat without the terminal sending anything more, as
+        // releases are reported.
+        state.releases = true;
+        assert_eq!(state.deadline(), Some(start + ms(500)));
+        assert_eq!(repeat_count(state.poll(start + ms(500))), Some(1));
+        assert_eq!(state.poll(start + ms(510)), None);
+        assert_eq!(repeat_count(state.poll(start + ms(530))), Some(2));
+
+        let release = key(KeyEventKind::Release);
+        assert_eq!(
+            state.process(release.clone(), start + ms(540)),
+            Some(release)
+        );
+        assert_eq!(state.poll(start + ms(600)), None);
+        assert_eq!(state.deadline(), None);
+    }
+
+    #[test]
+    fn follows_terminal_repeats() {
+        let mut state = RepeatState::new(AutoRepeat::default());
+        let start = Instant::now();
+        let press = key(KeyEventKind::Press);
+        state.process(press.clone(), start);
+        // Without releases, a key only held once pressed is left alone.
+        assert_eq!(state.deadline(), None);
+        assert_eq!(state.poll(start + ms(500)), None);
+
+        // The terminal repeats every 40ms after its own delay of 300ms,
+        // given as taps until the delay.
+        for t in (300..500).step_by(40) {
+            assert_eq!(
+                state.process(press.clone(), start + ms(t)),
+                Some(press.clone())
+            );
+        }
+        assert_eq!(
+            repeat_count(state.process(press.clone(), start + ms(500))),
+            Some(1)
+        );
+        assert_eq!(state.process(press.clone(), start + ms(520)), None);
+        assert_eq!(repeat_count(state.poll(start + ms(530))), Some(2));
+
+        // Once the terminal stops repeating, the key was released.
+        assert_eq!(state.poll(start + ms(700)), None);
+        assert_eq!(state.deadline(), None);
+    }
+
+    #[test]
+    fn taps_dont_repeat() {
+        let mut state = RepeatState::new(AutoRepeat::default());
+        let start = Instant::now();
+        let press = key(KeyEventKind::Press);
+        state.process(press.clone(), start);
+        assert!(state.process(press.clone(), start + ms(700)).is_some());
+        assert_eq!(state.poll(start + ms(730)), None);
+        assert_eq!(state.deadline(), None);
+    }
+
+    #[test]
+    fn other_keys_stop_repeats() {
+        let mut state = RepeatState::new(AutoRepeat::default());
+        state.releases = true;
+        let start = Instant::now();
+        state.process(key(KeyEventKind::Press), start);
+        state.process(Event::key(Key::Char('a')), start + ms(100));
+        assert_eq!(state.poll(start + ms(600)), None);
+    }
+}
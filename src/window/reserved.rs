@@ -0,0 +1,87 @@
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+
+use ffi;
+
+/// The maximum number of lines curses allows to be reserved.
+pub(crate) const MAX_RESERVED_LINES: usize = 5;
+
+/// Represents an edge of the screen.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Edge {
+    /// The top of the screen.
+    Top,
+    /// The bottom of the screen.
+    Bottom,
+}
+
+thread_local! {
+    static RESERVED: RefCell<Vec<(Edge, ffi::WindowPtr)>> = const { RefCell::new(Vec::new()) };
+}
+
+extern "C" fn reserve_top(win: ffi::WindowPtr, _cols: c_int) -> c_int {
+    RESERVED.with(|lines| lines.borrow_mut().push((Edge::Top, win)));
+    0
+}
+
+extern "C" fn reserve_bottom(win: ffi::WindowPtr, _cols: c_int) -> c_int {
+    RESERVED.with(|lines| lines.borrow_mut().push((Edge::Bottom, win)));
+    0
+}
+
+/// Requests the lines to be reserved, must be called before `initscr`.
+pub(crate) fn request(top: usize, bottom: usize) {
+    for _ in 0..top {
+        unsafe { ffi::ripoffline(1, reserve_top) };
+    }
+    for _ in 0..bottom {
+        unsafe { ffi::ripoffline(-1, reserve_bottom) };
+    }
+}
+
+/// Takes the lines reserved during `initscr`.
+pub(crate) fn take() -> Vec<(Edge, ffi::WindowPtr)> {
+    RESERVED.with(|lines| lines.borrow_mut().drain(..).collect())
+}
+
+/// A handle to a line reserved outside of the main drawing area.
+///
+/// *Obtained through [`Window::reserved_line`](struct.Window.html#method.reserved_line).*
+pub struct ReservedLine<'a> {
+    win: ffi::WindowPtr,
+    _window: PhantomData<&'a ()>,
+}
+
+impl<'a> ReservedLine<'a> {
+    pub(crate) fn new(win: ffi::WindowPtr) -> ReservedLine<'a> {
+        ReservedLine {
+            win,
+            _window: PhantomData,
+        }
+    }
+
+    /// Gets the width of the line in columns.
+    pub fn width(&self) -> usize {
+        unsafe { ffi::getmaxx(self.win).max(0) as usize }
+    }
+
+    /// Clears the line, without refreshing.
+    pub fn erase(&self) {
+        unsafe { ffi::werase(self.win) };
+    }
+
+    /// Prints a message to the line at the given column.
+    pub fn print<S: AsRef<str>>(&self, col: usize, msg: S) {
+        let msg = msg.as_ref().replace('\0', "");
+        let msg = CString::new(msg).expect("interior nul byte");
+        unsafe { ffi::mvwaddnstr(self.win, 0, col as c_int, msg.as_ptr(), -1) };
+    }
+
+    /// Marks the line to be redrawn on the next
+    /// [`Window::refresh`](struct.Window.html#method.refresh).
+    pub fn refresh(&self) {
+        unsafe { ffi::wnoutrefresh(self.win) };
+    }
+}
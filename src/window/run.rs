@@ -0,0 +1,171 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use backend::Backend;
+use event::Event;
+use layout::Rect;
+
+use super::Window;
+
+/// Whether a frame loop should keep going, returned from each call of
+/// [`Window::run`](struct.Window.html#method.run).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ControlFlow {
+    /// Keep running.
+    Continue,
+    /// Stop running, returning from the loop.
+    Break,
+}
+
+/// A frame of a frame loop, giving the window and timing of the frame.
+///
+/// *Given by [`Window::run`](struct.Window.html#method.run).*
+pub struct Frame<'a, B: Backend + 'a> {
+    window: &'a mut Window<B>,
+    count: u64,
+    delta: Duration,
+}
+
+impl<'a, B: Backend> Frame<'a, B> {
+    /// Gets the window being drawn.
+    pub fn window(&self) -> &Window<B> {
+        self.window
+    }
+
+    /// Gets the window being drawn, mutably.
+    pub fn window_mut(&mut self) -> &mut Window<B> {
+        self.window
+    }
+
+    /// Gets the area of the whole window, as it is for this frame.
+    pub fn area(&self) -> Rect {
+        let (rows, cols) = self.window.get_size();
+        Rect::new(0, 0, cols, rows)
+    }
+
+    /// Gets the number of the frame, counted from zero.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the time since the previous frame started, zero for the first.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+impl<B: Backend> Window<B> {
+    /// Runs a loop drawing the window at the given number of frames per
+    /// second, until the closure breaks.
+    ///
+    /// Each frame, the closure is given every event waiting as `Some`, then
+    /// `Some(Event::Tick)` for anything moving with time, then `None` to draw
+    /// the frame. The window is erased before drawing and refreshed after, so
+    /// only the cells that changed since the last frame are sent.
+    ///
+    /// ```no_run
+    /// use termui::event::{Event, Key};
+    /// use termui::window::ControlFlow;
+    /// use termui::Window;
+    ///
+    /// let mut win = Window::new();
+    /// let mut ticks = 0;
+    /// win.run(30, |frame, ev| {
+    ///     match ev {
+    ///         Some(Event::Key { key: Key::Char('q'), .. }) => return ControlFlow::Break,
+    ///         Some(Event::Tick) => ticks += 1,
+    ///         Some(_) => {}
+    ///         None => frame.window().print(0, 0, format!("{} ticks", ticks)),
+    ///     }
+    ///     ControlFlow::Continue
+    /// });
+    /// ```
+    ///
+    /// The rate drops to the [idle rate](#method.set_idle_fps) while the
+    /// window is [idle](#method.set_idle_timeout), if one is set.
+    ///
+    /// Quit events and the [quit key](#method.set_quit_key) are only given
+    /// once [confirmed](#method.confirm_quit) if there are
+    /// [unsaved changes](#method.track_unsaved).
+    ///
+    /// *Resizes waiting together are given as one, and the frame is drawn
+    /// straight after a resize, without waiting for the next. A rate of zero
+    /// is treated as one.*
+    pub fn run<F>(&mut self, fps: u32, mut f: F)
+    where
+        F: FnMut(&mut Frame<'_, B>, Option<Event>) -> ControlFlow,
+    {
+        let active = Duration::from_secs(1) / fps.max(1);
+        let mut count = 0;
+        let mut last: Option<Instant> = None;
+        let mut next = Instant::now();
+        let mut woken = None;
+        loop {
+            let mut resized = false;
+            while let Some(ev) = woken.take().or_else(|| self.poll_event()) {
+                if ev == Event::Resize {
+                    if resized {
+                        continue;
+                    }
+                    resized = true;
+                }
+                if !self.intercept_quit(&ev) {
+                    continue;
+                }
+                let delta = last.map_or(Duration::from_secs(0), |last| last.elapsed());
+                if self.run_frame(&mut f, count, delta, Some(ev)) == ControlFlow::Break {
+                    return;
+                }
+            }
+
+            let now = Instant::now();
+            let idle = self.idle_fps().filter(|_| self.is_idle());
+            if idle.is_none() && next > now + active {
+                // Input came while idle, so the full rate comes back at once.
+                next = now;
+            }
+            if now < next && !resized {
+                if idle.is_some() {
+                    // Waits for input too, to stop being idle.
+                    woken = self.wait_event(Some(next - now));
+                } else {
+                    thread::sleep(next - now);
+                }
+                continue;
+            }
+
+            let delta = last.map_or(Duration::from_secs(0), |last| now - last);
+            if self.run_frame(&mut f, count, delta, Some(Event::Tick)) == ControlFlow::Break {
+                return;
+            }
+            self.erase();
+            if self.run_frame(&mut f, count, delta, None) == ControlFlow::Break {
+                return;
+            }
+            self.refresh();
+
+            let interval = idle.map_or(active, |fps| Duration::from_secs(1) / fps.max(1));
+            count += 1;
+            last = Some(now);
+            next = (next + interval).max(now);
+        }
+    }
+
+    pub(crate) fn run_frame<F>(
+        &mut self,
+        f: &mut F,
+        count: u64,
+        delta: Duration,
+        ev: Option<Event>,
+    ) -> ControlFlow
+    where
+        F: FnMut(&mut Frame<'_, B>, Option<Event>) -> ControlFlow,
+    {
+        let mut frame = Frame {
+            window: self,
+            count,
+            delta,
+        };
+        f(&mut frame, ev)
+    }
+}
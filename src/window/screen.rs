@@ -0,0 +1,159 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{env, ptr};
+
+use curses;
+use error::{Error, Result};
+use ffi;
+
+/// Whether a window currently owns the curses screen.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// A guard marking the curses screen as owned by a window, released on drop.
+#[derive(Debug)]
+pub(crate) struct Instance(());
+
+impl Instance {
+    /// Claims the curses screen, or gives an error if another window already
+    /// owns it.
+    pub(crate) fn acquire() -> Result<Instance> {
+        if ACTIVE.swap(true, Ordering::SeqCst) {
+            Err(Error::AlreadyActive)
+        } else {
+            Ok(Instance(()))
+        }
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        ACTIVE.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Checks whether a window currently owns the curses screen.
+pub(crate) fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Restores the terminal from a panic hook, so the panic message is printed
+/// to the normal screen instead of being lost.
+///
+/// *The window restores the rest when dropped while unwinding.*
+pub(crate) fn restore_for_panic() {
+    if !is_active() || unsafe { ffi::isendwin() } {
+        return;
+    }
+
+    if is_interactive() {
+        curses::mousemask(0, ptr::null_mut());
+        print!("\x1B[?1016l\x1B[?1003l\x1B[?1002l\x1B[?2004l\x1B[<u");
+        let _ = io::stdout().flush();
+    }
+    curses::curs_set(1);
+    curses::endwin();
+}
+
+/// The terminal types tried when falling back to a headless screen.
+#[cfg(unix)]
+const HEADLESS_TERMS: &[&str] = &["vt100", "ansi", "xterm"];
+
+/// Checks whether the process is attached to a terminal capable of running a
+/// full screen interface.
+#[cfg(unix)]
+pub(crate) fn is_interactive() -> bool {
+    use libc;
+
+    let is_tty =
+        unsafe { libc::isatty(libc::STDIN_FILENO) == 1 && libc::isatty(libc::STDOUT_FILENO) == 1 };
+    let term = env::var("TERM").unwrap_or_default();
+
+    is_tty && !term.is_empty() && term != "dumb"
+}
+
+/// Checks whether the process is attached to a terminal capable of running a
+/// full screen interface.
+#[cfg(not(unix))]
+pub(crate) fn is_interactive() -> bool {
+    env::var("TERM").map(|term| term != "dumb").unwrap_or(true)
+}
+
+/// Initializes curses, giving the window to draw into and whether it is
+/// headless.
+///
+/// When not attached to a terminal, curses is initialized on `/dev/null` so
+/// that drawing still works but nothing is rendered.
+#[cfg(unix)]
+pub(crate) fn init() -> Result<(curses::Window, bool)> {
+    use libc;
+
+    if is_interactive() {
+        let screen = unsafe {
+            let output = libc::fdopen(libc::STDOUT_FILENO, b"w\0".as_ptr() as *const _);
+            let input = libc::fdopen(libc::STDIN_FILENO, b"r\0".as_ptr() as *const _);
+            curses::newterm(None, output, input)
+        };
+        if !screen.is_null() {
+            return Ok((curses::newwin(0, 0, 0, 0), false));
+        }
+
+        warn!("could not initialize terminal, falling back to headless mode");
+    }
+
+    let null = unsafe {
+        libc::fopen(
+            b"/dev/null\0".as_ptr() as *const _,
+            b"r+\0".as_ptr() as *const _,
+        )
+    };
+    if null.is_null() {
+        return Err(Error::Unsupported("headless mode"));
+    }
+
+    for term in HEADLESS_TERMS {
+        let screen = curses::newterm(Some(term), null, null);
+        if !screen.is_null() {
+            return Ok((curses::newwin(0, 0, 0, 0), true));
+        }
+    }
+
+    Err(Error::Unsupported("headless mode"))
+}
+
+/// Initializes curses, giving the window to draw into and whether it is
+/// headless.
+#[cfg(not(unix))]
+pub(crate) fn init() -> Result<(curses::Window, bool)> {
+    Ok((curses::initscr(), false))
+}
+
+/// Renders the text content of a window as plain lines, dropping trailing
+/// whitespace and blank lines.
+pub(crate) fn transcript(window: &curses::Window) -> String {
+    let (rows, cols) = window.get_max_yx();
+
+    let mut lines = Vec::new();
+    for row in 0..rows {
+        let line: String = (0..cols)
+            .map(|col| {
+                // The character text is in the low byte, `A_CHARTEXT` is not a
+                // mask on every platform.
+                let ch = window.mvinch(row, col) as u8;
+                if ch.is_ascii_graphic() {
+                    ch as char
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        lines.push(line.trim_end().to_owned());
+    }
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
@@ -0,0 +1,163 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use event::QuitReason;
+
+/// The signals received but not yet delivered, as a bitmask of reasons.
+static PENDING: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether a suspend signal was received but not yet handled.
+static SUSPEND: AtomicBool = AtomicBool::new(false);
+
+const REASONS: [QuitReason; 3] = [
+    QuitReason::Interrupt,
+    QuitReason::Terminate,
+    QuitReason::Hangup,
+];
+
+fn bit(reason: QuitReason) -> usize {
+    1 << reason as usize
+}
+
+/// The signal handlers installed by a window, restored on drop.
+#[derive(Debug)]
+pub(crate) struct QuitSignals {
+    #[cfg(unix)]
+    previous: Vec<(i32, usize)>,
+}
+
+#[cfg(unix)]
+impl QuitSignals {
+    /// Installs handlers turning quit signals into events.
+    pub(crate) fn install() -> QuitSignals {
+        use libc;
+
+        extern "C" fn on_signal(signal: libc::c_int) {
+            if let Some(reason) = reason_of(signal) {
+                PENDING.fetch_or(bit(reason), Ordering::SeqCst);
+            }
+        }
+
+        PENDING.store(0, Ordering::SeqCst);
+
+        let previous = REASONS
+            .iter()
+            .map(|&reason| {
+                let signal = signal_of(reason);
+                let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                (signal, unsafe { libc::signal(signal, handler) })
+            })
+            .collect();
+
+        QuitSignals { previous }
+    }
+}
+
+#[cfg(not(unix))]
+impl QuitSignals {
+    /// Installs handlers turning quit signals into events.
+    ///
+    /// *Signals are only supported on unix, elsewhere this does nothing.*
+    pub(crate) fn install() -> QuitSignals {
+        QuitSignals {}
+    }
+}
+
+#[cfg(unix)]
+impl Drop for QuitSignals {
+    fn drop(&mut self) {
+        use libc;
+
+        for &(signal, handler) in &self.previous {
+            unsafe { libc::signal(signal, handler) };
+        }
+    }
+}
+
+/// The suspend signal handler installed by a window, restored on drop.
+#[derive(Debug)]
+pub(crate) struct SuspendSignal {
+    #[cfg(unix)]
+    previous: usize,
+}
+
+#[cfg(unix)]
+impl SuspendSignal {
+    /// Installs a handler deferring suspend signals until taken.
+    pub(crate) fn install() -> SuspendSignal {
+        use libc;
+
+        extern "C" fn on_signal(_: libc::c_int) {
+            SUSPEND.store(true, Ordering::SeqCst);
+        }
+
+        SUSPEND.store(false, Ordering::SeqCst);
+
+        let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        SuspendSignal {
+            previous: unsafe { libc::signal(libc::SIGTSTP, handler) },
+        }
+    }
+
+    /// Stops the process until it is continued.
+    pub(crate) fn stop(&self) {
+        use libc;
+
+        unsafe { libc::raise(libc::SIGSTOP) };
+    }
+}
+
+#[cfg(not(unix))]
+impl SuspendSignal {
+    /// Installs a handler deferring suspend signals until taken.
+    ///
+    /// *Signals are only supported on unix, elsewhere this does nothing.*
+    pub(crate) fn install() -> SuspendSignal {
+        SuspendSignal {}
+    }
+
+    /// Stops the process until it is continued.
+    pub(crate) fn stop(&self) {}
+}
+
+#[cfg(unix)]
+impl Drop for SuspendSignal {
+    fn drop(&mut self) {
+        use libc;
+
+        unsafe { libc::signal(libc::SIGTSTP, self.previous) };
+    }
+}
+
+#[cfg(unix)]
+fn signal_of(reason: QuitReason) -> i32 {
+    use libc;
+
+    match reason {
+        QuitReason::Interrupt => libc::SIGINT,
+        QuitReason::Terminate => libc::SIGTERM,
+        QuitReason::Hangup => libc::SIGHUP,
+    }
+}
+
+#[cfg(unix)]
+fn reason_of(signal: i32) -> Option<QuitReason> {
+    REASONS
+        .iter()
+        .cloned()
+        .find(|&reason| signal_of(reason) == signal)
+}
+
+/// Takes the quit signals received since the last call.
+pub(crate) fn take_pending() -> Vec<QuitReason> {
+    let pending = PENDING.swap(0, Ordering::SeqCst);
+    REASONS
+        .iter()
+        .cloned()
+        .filter(|&reason| pending & bit(reason) != 0)
+        .collect()
+}
+
+/// Takes whether a suspend signal was received since the last call.
+pub(crate) fn take_suspend() -> bool {
+    SUSPEND.swap(false, Ordering::SeqCst)
+}
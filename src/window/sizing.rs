@@ -0,0 +1,71 @@
+use backend::Backend;
+use layout::Rect;
+
+use super::Window;
+
+/// The size of a cell in pixels assumed when the terminal doesn't report it,
+/// as width and height.
+const DEFAULT_CELL_SIZE: (usize, usize) = (8, 16);
+
+impl<B: Backend> Window<B> {
+    /// Gets the size of a cell in pixels as width and height, or a typical
+    /// 8 by 16 if the terminal doesn't report it.
+    pub fn cell_size_or_default(&self) -> (usize, usize) {
+        self.cell_size().unwrap_or(DEFAULT_CELL_SIZE)
+    }
+
+    /// Gets how many times taller a cell is than it is wide, 2 if the size of
+    /// a cell isn't known.
+    pub fn cell_aspect(&self) -> f64 {
+        let (width, height) = self.cell_size_or_default();
+        height as f64 / width as f64
+    }
+
+    /// Gets the number of columns and rows covering the given size in pixels,
+    /// as width and height.
+    pub fn pixels_to_cells(&self, width: usize, height: usize) -> (usize, usize) {
+        let (cell_width, cell_height) = self.cell_size_or_default();
+        (width.div_ceil(cell_width), height.div_ceil(cell_height))
+    }
+
+    /// Gets the size in pixels of the given number of columns and rows, as
+    /// width and height.
+    pub fn cells_to_pixels(&self, cols: usize, rows: usize) -> (usize, usize) {
+        let (cell_width, cell_height) = self.cell_size_or_default();
+        (cols * cell_width, rows * cell_height)
+    }
+
+    /// Gets the largest area inside the given one whose shape on screen has
+    /// the given ratio of width to height, centered in it.
+    ///
+    /// ```no_run
+    /// # use termui::layout::Rect;
+    /// # use termui::Window;
+    /// # let win = Window::new();
+    /// // A square canvas, drawn square whatever the font.
+    /// let canvas = win.fit_aspect(Rect::new(0, 0, 80, 24), 1.0);
+    /// ```
+    ///
+    /// *Used by canvases and images that would look stretched if drawn into
+    /// cells as though they were square.*
+    pub fn fit_aspect(&self, area: Rect, ratio: f64) -> Rect {
+        if area.width == 0 || area.height == 0 || ratio <= 0.0 {
+            return Rect::new(area.x, area.y, 0, 0);
+        }
+
+        // In cells, the shape is wider by how much taller a cell is.
+        let ratio = ratio * self.cell_aspect();
+        let (width, height) = if area.width as f64 > area.height as f64 * ratio {
+            ((area.height as f64 * ratio).round() as usize, area.height)
+        } else {
+            (area.width, (area.width as f64 / ratio).round() as usize)
+        };
+        let (width, height) = (width.clamp(1, area.width), height.clamp(1, area.height));
+        Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        )
+    }
+}
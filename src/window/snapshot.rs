@@ -0,0 +1,71 @@
+use backend::{Backend, Buffer};
+use layout::Rect;
+
+use super::Window;
+
+/// The cells drawn in an area of a window, saved so they can be put back,
+/// such as after an overlay drawn over them is dismissed.
+///
+/// ```no_run
+/// # use termui::layout::Rect;
+/// # use termui::Window;
+/// # let win = Window::new();
+/// let popup = Rect::new(10, 5, 30, 6);
+/// let under = win.snapshot(popup);
+/// win.draw_box(popup, Default::default());
+/// win.refresh();
+///
+/// // Once dismissed, only the cells under the popup are sent again.
+/// win.restore(&under);
+/// win.refresh();
+/// ```
+///
+/// *Taken with [`Window::snapshot`](struct.Window.html#method.snapshot). Only
+/// the cells in the area are kept, so a snapshot of a popup stays small
+/// however large the window is.*
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    area: Rect,
+    size: (usize, usize),
+    cells: Buffer,
+}
+
+impl Snapshot {
+    /// Gets the area saved.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+}
+
+impl<B: Backend> Window<B> {
+    /// Saves the cells drawn in an area, without refreshing.
+    pub fn snapshot(&self, area: Rect) -> Snapshot {
+        let buffer = self.buffer();
+        Snapshot {
+            area,
+            size: buffer.size(),
+            cells: buffer.crop(area),
+        }
+    }
+
+    /// Puts back the cells saved in a snapshot, to be shown at the next
+    /// refresh.
+    ///
+    /// *Nothing is put back if the window was resized since.*
+    pub fn restore(&self, snapshot: &Snapshot) {
+        let mut buffer = self.buffer();
+        if buffer.size() == snapshot.size {
+            buffer.paste(&snapshot.cells, snapshot.area.y, snapshot.area.x);
+        }
+    }
+
+    /// Checks whether the cells saved in a snapshot are still the ones drawn,
+    /// meaning nothing was drawn over the area since.
+    ///
+    /// *Useful before [restoring](#method.restore) what was under a popup, in
+    /// case the background was redrawn in the meantime.*
+    pub fn is_unchanged(&self, snapshot: &Snapshot) -> bool {
+        let buffer = self.buffer();
+        buffer.size() == snapshot.size && buffer.crop(snapshot.area) == snapshot.cells
+    }
+}
@@ -0,0 +1,131 @@
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+
+use error::{Error, Result};
+use ffi;
+
+/// Represents the layout of the soft label key row.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SoftLabelFormat {
+    /// Eight labels, arranged 3-2-3.
+    ThreeTwoThree,
+    /// Eight labels, arranged 4-4.
+    FourFour,
+    /// Twelve labels, arranged 4-4-4.
+    FourFourFour,
+    /// Twelve labels, arranged 4-4-4, with an index line.
+    FourFourFourIndexed,
+}
+
+impl SoftLabelFormat {
+    pub(crate) fn code(self) -> i32 {
+        match self {
+            SoftLabelFormat::ThreeTwoThree => 0,
+            SoftLabelFormat::FourFour => 1,
+            SoftLabelFormat::FourFourFour => 2,
+            SoftLabelFormat::FourFourFourIndexed => 3,
+        }
+    }
+
+    /// Gets the number of labels in the layout.
+    pub fn count(self) -> usize {
+        match self {
+            SoftLabelFormat::ThreeTwoThree | SoftLabelFormat::FourFour => 8,
+            SoftLabelFormat::FourFourFour | SoftLabelFormat::FourFourFourIndexed => 12,
+        }
+    }
+}
+
+/// Represents the justification of a soft label.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SoftLabelAlign {
+    /// Left justified.
+    Left,
+    /// Centered.
+    Center,
+    /// Right justified.
+    Right,
+}
+
+/// A handle to the soft label key row of a window.
+///
+/// *Obtained through [`Window::soft_labels`](struct.Window.html#method.soft_labels).*
+pub struct SoftLabels<'a> {
+    format: SoftLabelFormat,
+    _window: PhantomData<&'a mut ()>,
+}
+
+impl<'a> SoftLabels<'a> {
+    pub(crate) fn new(format: SoftLabelFormat) -> SoftLabels<'a> {
+        SoftLabels {
+            format,
+            _window: PhantomData,
+        }
+    }
+
+    /// Gets the layout of the soft label key row.
+    pub fn format(&self) -> SoftLabelFormat {
+        self.format
+    }
+
+    /// Sets the label at the given index, starting from 1.
+    pub fn set(&mut self, index: usize, label: &str, align: SoftLabelAlign) -> Result<()> {
+        self.check_index(index)?;
+
+        let label = CString::new(label).map_err(|_| Error::OutOfRange("soft label"))?;
+        let align = match align {
+            SoftLabelAlign::Left => 0,
+            SoftLabelAlign::Center => 1,
+            SoftLabelAlign::Right => 2,
+        };
+
+        match unsafe { ffi::slk_set(index as i32, label.as_ptr(), align) } {
+            0 => Ok(()),
+            _ => Err(Error::Curses("slk_set")),
+        }
+    }
+
+    /// Gets the label at the given index, starting from 1.
+    pub fn get(&self, index: usize) -> Option<String> {
+        if self.check_index(index).is_err() {
+            return None;
+        }
+
+        unsafe {
+            let label = ffi::slk_label(index as i32);
+            if label.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(label).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    /// Hides the soft label key row.
+    pub fn clear(&mut self) {
+        unsafe { ffi::slk_clear() };
+    }
+
+    /// Shows the soft label key row after it was hidden.
+    pub fn restore(&mut self) {
+        unsafe { ffi::slk_restore() };
+    }
+
+    /// Forces the soft label key row to be redrawn on the next refresh.
+    pub fn touch(&mut self) {
+        unsafe { ffi::slk_touch() };
+    }
+
+    /// Refreshes the soft label key row.
+    pub fn refresh(&mut self) {
+        unsafe { ffi::slk_refresh() };
+    }
+
+    fn check_index(&self, index: usize) -> Result<()> {
+        if index == 0 || index > self.format.count() {
+            Err(Error::OutOfRange("soft label index"))
+        } else {
+            Ok(())
+        }
+    }
+}
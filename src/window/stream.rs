@@ -0,0 +1,164 @@
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+
+use backend::Backend;
+use event::Event;
+
+use super::handle::{self, Shared};
+use super::Window;
+
+/// How long the watcher waits for input before waking the stream anyway, so
+/// resizes and escape sequence timeouts are still noticed.
+const WAKE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The shortest time between wakes, should stdin stay readable without
+/// giving events, such as at the end of a file.
+const MIN_INTERVAL: Duration = Duration::from_millis(10);
+
+/// An asynchronous stream of the events of a [`Window`](struct.Window.html).
+///
+/// Created by [`Window::event_stream`](struct.Window.html#method.event_stream).
+/// Events are read on the task polling the stream, since the window must stay
+/// on its own thread; a watcher thread only waits for input to arrive and
+/// wakes the task.
+///
+/// *The stream never ends. The window can be drawn to through
+/// [`get_mut`](#method.get_mut) between events.*
+pub struct EventStream<'a, B: Backend + 'a> {
+    window: &'a mut Window<B>,
+    shared: Arc<Mutex<Shared>>,
+    watcher: Arc<Watcher>,
+}
+
+/// The waker of the task last polling the stream, woken by the watcher
+/// thread once input arrives.
+#[derive(Debug, Default)]
+struct Watch {
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+#[derive(Debug, Default)]
+struct Watcher {
+    watch: Mutex<Watch>,
+    polled: Condvar,
+}
+
+impl Watcher {
+    fn lock(&self) -> MutexGuard<'_, Watch> {
+        self.watch.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Wakes the latest waker whenever input arrives, until the stream is
+    /// dropped.
+    fn run(&self) {
+        loop {
+            {
+                let mut watch = self.lock();
+                while watch.waker.is_none() && !watch.closed {
+                    watch = self
+                        .polled
+                        .wait(watch)
+                        .unwrap_or_else(|err| err.into_inner());
+                }
+                if watch.closed {
+                    return;
+                }
+            }
+
+            let start = Instant::now();
+            wait_for_input(WAKE_INTERVAL);
+            if let Some(rest) = MIN_INTERVAL.checked_sub(start.elapsed()) {
+                thread::sleep(rest);
+            }
+            // The task may have been polled again since, with another waker.
+            if let Some(waker) = self.lock().waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<'a, B: Backend> EventStream<'a, B> {
+    pub(super) fn new(window: &'a mut Window<B>) -> EventStream<'a, B> {
+        let shared = window.shared.clone();
+        let watcher = Arc::new(Watcher::default());
+        let thread_watcher = watcher.clone();
+        thread::spawn(move || thread_watcher.run());
+
+        EventStream {
+            window,
+            shared,
+            watcher,
+        }
+    }
+
+    /// Gets the window the events are read from.
+    pub fn get_ref(&self) -> &Window<B> {
+        self.window
+    }
+
+    /// Gets the window the events are read from, mutably.
+    pub fn get_mut(&mut self) -> &mut Window<B> {
+        self.window
+    }
+}
+
+impl<'a, B: Backend> Stream for EventStream<'a, B> {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let this = self.get_mut();
+        if let Some(event) = this.window.poll_event() {
+            return Poll::Ready(Some(event));
+        }
+
+        {
+            let mut shared = handle::lock(&this.shared);
+            shared.waker = Some(cx.waker().clone());
+            // A handle may have sent an event since the window last looked.
+            if !shared.events.is_empty() || shared.redraw {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+        let mut watch = this.watcher.lock();
+        match watch.waker {
+            Some(ref waker) if waker.will_wake(cx.waker()) => {}
+            _ => watch.waker = Some(cx.waker().clone()),
+        }
+        this.watcher.polled.notify_one();
+        Poll::Pending
+    }
+}
+
+impl<'a, B: Backend> Drop for EventStream<'a, B> {
+    fn drop(&mut self) {
+        self.watcher.lock().closed = true;
+        self.watcher.polled.notify_one();
+    }
+}
+
+/// Waits until input is waiting on stdin, or the timeout passes.
+#[cfg(unix)]
+fn wait_for_input(timeout: Duration) {
+    use libc;
+
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut fd, 1, timeout.as_millis() as libc::c_int) };
+}
+
+/// Waits until input is waiting on stdin, or the timeout passes.
+#[cfg(not(unix))]
+fn wait_for_input(timeout: Duration) {
+    thread::sleep(timeout);
+}
@@ -0,0 +1,87 @@
+#[cfg(not(unix))]
+use std::thread;
+use std::time::Duration;
+
+#[cfg(unix)]
+use libc;
+
+/// Wakes a window blocked waiting for events, through a pipe it waits on
+/// alongside the terminal's input.
+#[derive(Debug)]
+pub(crate) struct Wakeup {
+    #[cfg(unix)]
+    fds: [libc::c_int; 2],
+}
+
+#[cfg(unix)]
+impl Wakeup {
+    pub(crate) fn new() -> Option<Wakeup> {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            warn!("could not create wakeup pipe");
+            return None;
+        }
+        for &fd in &fds {
+            unsafe {
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+                libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+                libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            }
+        }
+        Some(Wakeup { fds })
+    }
+
+    /// Wakes the waiting window, or the next one to wait.
+    pub(crate) fn wake(&self) {
+        // A full pipe is already waking the window.
+        unsafe { libc::write(self.fds[1], b"\0".as_ptr() as *const _, 1) };
+    }
+
+    /// Waits until woken, input is waiting on stdin if watching it, or the
+    /// timeout passes, giving whether stdin is readable.
+    pub(crate) fn wait(&self, stdin: bool, timeout: Duration) -> bool {
+        let mut fds = [
+            libc::pollfd {
+                fd: self.fds[0],
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        let count = if stdin { 2 } else { 1 };
+        let millis = timeout.as_millis().min(i32::MAX as u128) as libc::c_int;
+        unsafe { libc::poll(fds.as_mut_ptr(), count, millis) };
+
+        let mut buf = [0u8; 64];
+        while unsafe { libc::read(self.fds[0], buf.as_mut_ptr() as *mut _, buf.len()) } > 0 {}
+        stdin && fds[1].revents != 0
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Wakeup {
+    fn drop(&mut self) {
+        for &fd in &self.fds {
+            unsafe { libc::close(fd) };
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl Wakeup {
+    pub(crate) fn new() -> Option<Wakeup> {
+        Some(Wakeup {})
+    }
+
+    pub(crate) fn wake(&self) {}
+
+    /// Sleeps for the timeout, since there is nothing to wait on.
+    pub(crate) fn wait(&self, _stdin: bool, timeout: Duration) -> bool {
+        thread::sleep(timeout);
+        false
+    }
+}